@@ -0,0 +1,68 @@
+use super::App;
+use crate::model::novel::Annotation;
+
+impl App {
+    /// 获取当前小说的批注列表
+    pub fn get_current_annotations(&self) -> Option<&Vec<Annotation>> {
+        self.current_novel
+            .as_ref()
+            .map(|novel| &novel.progress.annotations)
+    }
+
+    /// 开始编辑指定行的批注：该行已有批注时把内容填入输入框
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - 要批注的行号
+    pub fn start_annotation_edit(&mut self, line: usize) {
+        let note = self
+            .get_current_annotations()
+            .and_then(|annotations| annotations.iter().find(|a| a.line == line))
+            .map(|a| a.note.clone())
+            .unwrap_or_default();
+        self.annotation.editing_line = line;
+        self.annotation.input = Some(note);
+    }
+
+    /// 保存正在编辑的批注并结束编辑，内容为空时删除该行的批注
+    ///
+    /// # Returns
+    ///
+    /// 批注所在的行号；不在编辑或当前无小说时返回 `None`。
+    pub fn submit_annotation_input(&mut self) -> Option<usize> {
+        let note = self.annotation.input.take()?;
+        let line = self.annotation.editing_line;
+        self.current_novel
+            .as_mut()?
+            .progress
+            .set_annotation(line, note.trim().to_string());
+        self.save_current_progress();
+        Some(line)
+    }
+
+    /// 删除当前小说的指定批注
+    ///
+    /// # Returns
+    ///
+    /// 如果删除成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
+    pub fn remove_annotation(&mut self, index: usize) -> Option<()> {
+        let annotations = &mut self.current_novel.as_mut()?.progress.annotations;
+        if index >= annotations.len() {
+            return None;
+        }
+        annotations.remove(index);
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 跳转到指定批注所在的行
+    ///
+    /// # Returns
+    ///
+    /// 如果跳转成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
+    pub fn jump_to_annotation(&mut self, index: usize) -> Option<()> {
+        let line = self.get_current_annotations()?.get(index)?.line;
+        self.jump_to(line);
+        Some(())
+    }
+}
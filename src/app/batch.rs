@@ -0,0 +1,148 @@
+//! 书架多选：用空格标记多本小说后批量删除、添加标签或归档
+
+use anyhow::Result;
+use std::path::PathBuf;
+
+use super::App;
+use crate::i18n::trf;
+use crate::model::chapter_cache;
+
+/// 归档的小说移到小说目录下的这个子文件夹，在书架上显示为同名分类
+pub const ARCHIVE_CATEGORY: &str = "归档";
+
+impl App {
+    /// 标记/取消标记书架上的小说
+    ///
+    /// # Returns
+    ///
+    /// 操作后是否处于标记状态。
+    pub fn toggle_bookshelf_mark(&mut self, index: usize) -> bool {
+        let Some(novel) = self.novels.get(index) else {
+            return false;
+        };
+        if self.bookshelf.marked.remove(&novel.path) {
+            false
+        } else {
+            self.bookshelf.marked.insert(novel.path.clone());
+            true
+        }
+    }
+
+    /// 书架上的小说是否被标记
+    pub fn bookshelf_is_marked(&self, index: usize) -> bool {
+        self.novels
+            .get(index)
+            .is_some_and(|novel| self.bookshelf.marked.contains(&novel.path))
+    }
+
+    /// 批量操作的对象：已标记的小说（按书架顺序），没有标记时为选中的小说
+    pub fn batch_targets(&self) -> Vec<usize> {
+        if self.bookshelf.marked.is_empty() {
+            return self.selected_novel_index.into_iter().collect();
+        }
+        (0..self.novels.len())
+            .filter(|&index| self.bookshelf_is_marked(index))
+            .collect()
+    }
+
+    /// 给批量操作的对象添加标签，然后清除标记
+    ///
+    /// # Returns
+    ///
+    /// 新增了标签的小说数量。
+    pub fn tag_batch(&mut self, input: &str) -> usize {
+        let tagged = self
+            .batch_targets()
+            .into_iter()
+            .filter(|&index| self.add_novel_tags(index, input) > 0)
+            .count();
+        self.bookshelf.marked.clear();
+        tagged
+    }
+
+    /// 把批量操作的对象移到回收站，然后清除标记并刷新书架
+    ///
+    /// # Returns
+    ///
+    /// 删除的数量。
+    ///
+    /// # Errors
+    ///
+    /// 某本小说移动失败时停止，已删除的不会恢复。
+    pub fn delete_batch(&mut self) -> Result<usize> {
+        let paths = self.batch_paths();
+        self.bookshelf.marked.clear();
+        let mut deleted = 0;
+        let result = paths.iter().try_for_each(|path| {
+            if let Some(index) = self.novels.iter().position(|n| &n.path == path) {
+                self.delete_novel(index)?;
+                deleted += 1;
+            }
+            Ok(())
+        });
+        self.reload_novels();
+        self.refresh_bookshelf_selection();
+        result.map(|()| deleted)
+    }
+
+    /// 把批量操作的对象移到所在小说目录的 “归档” 子文件夹，阅读进度和书签随之迁移
+    ///
+    /// 已在归档中的小说跳过；归档中已有同名文件时不覆盖，计为失败。
+    ///
+    /// # Returns
+    ///
+    /// 归档成功的数量和失败的数量。
+    pub fn archive_batch(&mut self) -> (usize, usize) {
+        let targets: Vec<(PathBuf, PathBuf)> = self
+            .batch_targets()
+            .into_iter()
+            .filter(|&index| self.novels[index].category != ARCHIVE_CATEGORY)
+            .filter_map(|index| {
+                let novel = &self.novels[index];
+                let dir = novel.root.join(ARCHIVE_CATEGORY);
+                Some((novel.path.clone(), dir.join(novel.path.file_name()?)))
+            })
+            .collect();
+        self.bookshelf.marked.clear();
+
+        let (mut archived, mut failed) = (0, 0);
+        for (old_path, new_path) in targets {
+            let moved = !new_path.exists()
+                && new_path
+                    .parent()
+                    .is_some_and(|dir| std::fs::create_dir_all(dir).is_ok())
+                && std::fs::rename(&old_path, &new_path).is_ok();
+            if !moved {
+                failed += 1;
+                continue;
+            }
+            chapter_cache::rename(&old_path, &new_path);
+            self.library.rename_novel(&old_path, &new_path);
+            if let Some(novel) = &mut self.current_novel
+                && novel.path == old_path
+            {
+                novel.path = new_path;
+            }
+            archived += 1;
+        }
+        if archived > 0 {
+            if let Err(e) = self.library.save() {
+                self.set_error(trf!(
+                    "保存书库失败：{}",
+                    "Failed to save the library: {}",
+                    e
+                ));
+            }
+            self.reload_novels();
+            self.refresh_bookshelf_selection();
+        }
+        (archived, failed)
+    }
+
+    fn batch_paths(&self) -> Vec<PathBuf> {
+        self.batch_targets()
+            .into_iter()
+            .map(|index| self.novels[index].path.clone())
+            .collect()
+    }
+}
@@ -1,4 +1,46 @@
-use super::App;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use super::search::{exports_dir, sanitize_file_name};
+use super::{App, BookmarkSort, DeletedBookmark, MAX_BOOKMARK_UNDO};
+use crate::i18n::{tr, trf};
+use crate::model::novel::Bookmark;
+use crate::state::AppState;
+
+/// 全部书签浏览中的一条书签
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShelfBookmark {
+    /// 所属小说在 novels 列表中的索引
+    pub novel_index: usize,
+    /// 书签
+    pub bookmark: Bookmark,
+}
+
+/// 书签导出的 JSON 文件内容
+#[derive(Serialize, Deserialize)]
+struct BookmarkExport {
+    /// 小说标题
+    title: String,
+    /// 书签（含备注）
+    bookmarks: Vec<Bookmark>,
+}
+
+/// 系统剪贴板，创建后一直保留：X11 等平台上复制的内容由持有剪贴板的进程提供，
+/// 过早释放会导致粘贴不到
+static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+/// 把文本复制到系统剪贴板
+fn copy_to_clipboard(text: String) -> anyhow::Result<()> {
+    let mut clipboard = CLIPBOARD.lock().unwrap_or_else(|e| e.into_inner());
+    if clipboard.is_none() {
+        *clipboard = Some(arboard::Clipboard::new()?);
+    }
+    if let Some(clipboard) = clipboard.as_mut() {
+        clipboard.set_text(text)?;
+    }
+    Ok(())
+}
 
 impl App {
     /// 在当前小说的阅读位置添加书签
@@ -6,53 +48,176 @@ impl App {
     /// # Arguments
     ///
     /// * `name` - 书签名称
-    pub fn add_bookmark(&mut self, name: String) {
+    /// * `note` - 书签备注，可为空
+    /// * `tag` - 书签标签，可为空
+    pub fn add_bookmark(&mut self, name: String, note: String, tag: String) {
         if let Some(novel) = &mut self.current_novel {
             let position = novel.progress.scroll_offset;
-            novel.progress.add_bookmark(name, position);
+            let bookmark = novel.progress.add_bookmark(name, position);
+            bookmark.note = note;
+            bookmark.tag = tag;
             self.save_current_progress();
         }
     }
 
-    /// 删除当前小说的指定书签
+    /// 修改当前小说指定书签的名称、备注和标签
+    ///
+    /// # Returns
+    ///
+    /// 如果修改成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
+    pub fn update_bookmark(
+        &mut self,
+        index: usize,
+        name: String,
+        note: String,
+        tag: String,
+    ) -> Option<()> {
+        let bookmark = self
+            .current_novel
+            .as_mut()?
+            .progress
+            .bookmarks
+            .get_mut(index)?;
+        bookmark.name = name;
+        bookmark.note = note;
+        bookmark.tag = tag;
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 把指定书签移动到当前阅读位置，名称、备注和创建时间保持不变
+    ///
+    /// # Returns
+    ///
+    /// 移动后书签的新索引（书签按位置排序）；索引无效或当前无小说时返回 `None`。
+    pub fn move_bookmark_to_current(&mut self, index: usize) -> Option<usize> {
+        let progress = &mut self.current_novel.as_mut()?.progress;
+        let mut bookmark = progress.remove_bookmark(index)?;
+        bookmark.position = progress.scroll_offset;
+        let new_index = progress
+            .bookmarks
+            .partition_point(|b| b.position <= bookmark.position);
+        progress.bookmarks.insert(new_index, bookmark);
+        self.save_current_progress();
+        Some(new_index)
+    }
+
+    /// 开始编辑指定书签：把名称、备注和标签填入输入框
+    ///
+    /// # Returns
+    ///
+    /// 如果索引无效或当前无小说则返回 `None`。
+    pub fn start_bookmark_edit(&mut self, index: usize) -> Option<()> {
+        let bookmark = self.get_current_bookmarks()?.get(index)?.clone();
+        self.bookmark.clear_input();
+        self.bookmark.input = bookmark.name;
+        self.bookmark.note_input = bookmark.note;
+        self.bookmark.tag_input = bookmark.tag;
+        self.bookmark.editing_index = Some(index);
+        Some(())
+    }
+
+    /// 保存书签输入：编辑模式下修改原书签，否则在阅读位置添加新书签
+    ///
+    /// # Returns
+    ///
+    /// 名称为空时不保存并返回 `None`。
+    pub fn submit_bookmark_input(&mut self) -> Option<()> {
+        let name = self.bookmark.input.trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        let note = self.bookmark.note_input.trim_end().to_string();
+        let tag = self.bookmark.tag_input.clone();
+        match self.bookmark.editing_index {
+            Some(index) => self.update_bookmark(index, name, note, tag)?,
+            None => self.add_bookmark(name, note, tag),
+        }
+        self.clear_bookmark_inputs();
+        Some(())
+    }
+
+    /// 删除当前小说的指定书签或自动记录
+    ///
+    /// 删除的条目放入撤销缓冲区，可用 [`App::undo_bookmark_removal`] 恢复。
     ///
     /// # Arguments
     ///
-    /// * `index` - 要删除的书签索引
+    /// * `index` - 书签列表中的索引，见 [`App::bookmark_entry`]
     ///
     /// # Returns
     ///
     /// 如果删除成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
     pub fn remove_bookmark(&mut self, index: usize) -> Option<()> {
-        if let Some(novel) = &mut self.current_novel
-            && novel.progress.remove_bookmark(index).is_some()
-        {
-            self.save_current_progress();
-            Some(())
+        let novel = self.current_novel.as_mut()?;
+        let progress = &mut novel.progress;
+        let auto = index >= progress.bookmarks.len();
+        let bookmark = if auto {
+            let auto_index = index - progress.bookmarks.len();
+            if auto_index >= progress.auto_positions.len() {
+                return None;
+            }
+            progress.auto_positions.remove(auto_index)
         } else {
-            None
+            progress.remove_bookmark(index)?
+        };
+        let deleted = &mut self.bookmark.deleted;
+        deleted.push(DeletedBookmark {
+            path: novel.path.clone(),
+            bookmark,
+            auto,
+        });
+        if deleted.len() > MAX_BOOKMARK_UNDO {
+            deleted.remove(0);
         }
+        self.save_current_progress();
+        Some(())
     }
 
-    /// 跳转到指定书签位置
+    /// 恢复当前小说最近删除的一个书签或自动记录
+    ///
+    /// 书签按位置插回原处，自动记录按时间插回原处。
+    ///
+    /// # Returns
+    ///
+    /// 恢复后条目在书签列表中的索引（见 [`App::bookmark_entry`]）；
+    /// 当前小说没有可撤销的删除时返回 `None`。
+    pub fn undo_bookmark_removal(&mut self) -> Option<usize> {
+        let novel = self.current_novel.as_mut()?;
+        let deleted = &mut self.bookmark.deleted;
+        let position = deleted.iter().rposition(|entry| entry.path == novel.path)?;
+        let DeletedBookmark { bookmark, auto, .. } = deleted.remove(position);
+        let progress = &mut novel.progress;
+        let index = if auto {
+            let auto_index = progress
+                .auto_positions
+                .partition_point(|entry| entry.timestamp >= bookmark.timestamp);
+            progress.auto_positions.insert(auto_index, bookmark);
+            progress.bookmarks.len() + auto_index
+        } else {
+            let index = progress
+                .bookmarks
+                .partition_point(|b| b.position <= bookmark.position);
+            progress.bookmarks.insert(index, bookmark);
+            index
+        };
+        self.save_current_progress();
+        Some(index)
+    }
+
+    /// 跳转到指定书签或自动记录的位置
     ///
     /// # Arguments
     ///
-    /// * `index` - 要跳转的书签索引
+    /// * `index` - 书签列表中的索引，见 [`App::bookmark_entry`]
     ///
     /// # Returns
     ///
     /// 如果跳转成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
     pub fn jump_to_bookmark(&mut self, index: usize) -> Option<()> {
-        if let Some(novel) = &mut self.current_novel
-            && let Some(bookmark) = novel.progress.bookmarks.get(index)
-        {
-            novel.progress.scroll_offset = bookmark.position;
-            self.save_current_progress();
-            Some(())
-        } else {
-            None
-        }
+        let position = self.bookmark_entry(index)?.position;
+        self.jump_to(position);
+        Some(())
     }
 
     /// 获取当前小说的书签列表
@@ -60,14 +225,330 @@ impl App {
     /// # Returns
     ///
     /// 如果当前有打开的小说则返回其书签列表的引用，否则返回 `None`。
-    pub fn get_current_bookmarks(&self) -> Option<&Vec<crate::model::novel::Bookmark>> {
+    pub fn get_current_bookmarks(&self) -> Option<&Vec<Bookmark>> {
         self.current_novel
             .as_ref()
             .map(|novel| &novel.progress.bookmarks)
     }
 
+    /// 书签列表中指定索引的条目
+    ///
+    /// 书签列表先列出手动添加的书签，再列出自动记录的位置：
+    /// 索引小于书签数时指向书签，否则指向第 `index - 书签数` 条自动记录。
+    pub fn bookmark_entry(&self, index: usize) -> Option<&Bookmark> {
+        let progress = &self.current_novel.as_ref()?.progress;
+        progress.bookmarks.get(index).or_else(|| {
+            index
+                .checked_sub(progress.bookmarks.len())
+                .and_then(|auto_index| progress.auto_positions.get(auto_index))
+        })
+    }
+
+    /// 书签列表中的条目是否为自动记录的位置
+    pub fn is_auto_bookmark(&self, index: usize) -> bool {
+        self.get_current_bookmarks()
+            .is_some_and(|bookmarks| index >= bookmarks.len())
+    }
+
+    /// 书签列表的显示顺序：按当前排序方式排列的书签，之后是自动记录（最近的在前）
+    ///
+    /// 按标签筛选时只显示带该标签的书签，不显示自动记录；
+    /// 有筛选关键词时只显示名称或备注包含关键词的条目。
+    pub fn bookmark_display_order(&self) -> Vec<usize> {
+        let Some(novel) = &self.current_novel else {
+            return Vec::new();
+        };
+        let bookmarks = &novel.progress.bookmarks;
+        let tag_filter = &self.bookmark.tag_filter;
+        let keyword = self.bookmark.filter.to_lowercase();
+        let matches = |bookmark: &Bookmark| {
+            keyword.is_empty()
+                || bookmark.name.to_lowercase().contains(&keyword)
+                || bookmark.note.to_lowercase().contains(&keyword)
+        };
+        let mut order: Vec<usize> = (0..bookmarks.len())
+            .filter(|&i| {
+                tag_filter
+                    .as_ref()
+                    .is_none_or(|tag| &bookmarks[i].tag == tag)
+            })
+            .filter(|&i| matches(&bookmarks[i]))
+            .collect();
+        self.sort_bookmark_order(&mut order);
+        if tag_filter.is_none() {
+            let auto_positions = novel.progress.auto_positions.iter().enumerate();
+            order.extend(
+                auto_positions
+                    .filter(|(_, entry)| matches(entry))
+                    .map(|(i, _)| bookmarks.len() + i),
+            );
+        }
+        order
+    }
+
+    /// 筛选条件变化后修正选中项
+    ///
+    /// 原选中的书签仍显示时保持不变，否则选中显示顺序中的第一个；没有可显示的书签时取消选中。
+    pub fn refresh_bookmark_filter(&mut self) {
+        let order = self.bookmark_display_order();
+        if !self
+            .bookmark
+            .selected_index
+            .is_some_and(|index| order.contains(&index))
+        {
+            self.bookmark.selected_index = order.first().copied();
+        }
+    }
+
+    /// 按当前排序方式排列书签索引
+    fn sort_bookmark_order(&self, order: &mut [usize]) {
+        let Some(bookmarks) = self.get_current_bookmarks() else {
+            return;
+        };
+        match self.bookmark.sort {
+            BookmarkSort::Position => {}
+            BookmarkSort::Created => {
+                order.sort_by_key(|&i| std::cmp::Reverse(bookmarks[i].timestamp));
+            }
+            BookmarkSort::Name => order.sort_by(|&a, &b| bookmarks[a].name.cmp(&bookmarks[b].name)),
+        }
+    }
+
+    /// 书签或自动记录所在位置的正文，用于复制
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 书签列表中的索引，见 [`App::bookmark_entry`]
+    /// * `paragraph` - 为 `true` 时取所在的整段（以空行分隔），否则只取所在行
+    ///
+    /// # Returns
+    ///
+    /// 去掉首尾空白、按行连接的正文；索引无效、当前无小说或内容为空时返回 `None`。
+    pub fn bookmark_passage(&self, index: usize, paragraph: bool) -> Option<String> {
+        let position = self.bookmark_entry(index)?.position;
+        let novel = self.current_novel.as_ref()?;
+        let range = if paragraph {
+            novel.paragraph_range(position)
+        } else {
+            position..position + 1
+        };
+        let text = novel
+            .lines()
+            .get(range)?
+            .iter()
+            .map(|line| line.trim())
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!text.is_empty()).then_some(text)
+    }
+
+    /// 把书签所在行或所在段落复制到系统剪贴板，结果通过状态消息提示
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 书签列表中的索引，见 [`App::bookmark_entry`]
+    /// * `paragraph` - 为 `true` 时复制所在的整段，否则只复制所在行
+    pub fn copy_bookmark_passage(&mut self, index: usize, paragraph: bool) {
+        let Some(text) = self.bookmark_passage(index, paragraph) else {
+            self.set_error(tr(
+                "书签所在位置没有可复制的内容",
+                "Nothing to copy at the bookmark",
+            ));
+            return;
+        };
+        let chars = text.chars().count();
+        match copy_to_clipboard(text) {
+            Ok(()) => {
+                let what = if paragraph {
+                    tr("段落", "paragraph")
+                } else {
+                    tr("行", "line")
+                };
+                self.set_info(trf!(
+                    "已复制书签所在{}（{} 字）",
+                    "Copied the bookmarked {} ({} characters)",
+                    what,
+                    chars
+                ));
+            }
+            Err(e) => self.set_error(trf!(
+                "复制到剪贴板失败: {}",
+                "Failed to copy to the clipboard: {}",
+                e
+            )),
+        }
+    }
+
     /// 清空书签输入框内容
     pub fn clear_bookmark_inputs(&mut self) {
         self.bookmark.clear_input();
     }
+
+    /// 书架上小说的书签 JSON 导出文件路径，导入时也从这里读取
+    fn bookmark_export_json_path(&self, index: usize) -> Option<PathBuf> {
+        let novel = self.novels.get(index)?;
+        Some(exports_dir().join(format!("{}-书签.json", sanitize_file_name(&novel.title))))
+    }
+
+    /// 把书架上一本小说的书签（含备注）导出为 Markdown 和 JSON 文件
+    ///
+    /// 文件保存在数据目录下的 `exports/` 中：Markdown 便于阅读和分享，
+    /// JSON 可用 [`App::import_bookmarks`] 重新导入。
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 小说在 novels 列表中的索引
+    ///
+    /// # Returns
+    ///
+    /// Markdown 文件的路径；索引无效、没有书签或写入失败时返回错误。
+    pub fn export_bookmarks(&mut self, index: usize) -> std::io::Result<PathBuf> {
+        // 当前打开的小说可能有尚未写入记录的修改
+        self.save_current_progress();
+        let novel = self
+            .novels
+            .get(index)
+            .ok_or_else(|| std::io::Error::other(tr("没有选中小说", "No novel selected")))?;
+        let progress = self.library.get_novel_progress(&novel.path);
+        if progress.bookmarks.is_empty() {
+            return Err(std::io::Error::other(tr(
+                "这本小说没有书签",
+                "This novel has no bookmarks",
+            )));
+        }
+        let chapters = progress.custom_chapters.as_ref().unwrap_or(&novel.chapters);
+
+        let mut text = trf!(
+            "# 《{}》书签\n\n共 {} 个\n",
+            "# Bookmarks of {}\n\n{} total\n",
+            novel.title,
+            progress.bookmarks.len()
+        );
+        for bookmark in &progress.bookmarks {
+            text.push_str(&format!("\n## {}\n\n", bookmark.name));
+            let chapter = if chapters.is_empty() {
+                String::new()
+            } else {
+                let chapter_index = Self::find_chapter_index(chapters, bookmark.position);
+                format!(" · {}", chapters[chapter_index].title.trim())
+            };
+            text.push_str(&trf!(
+                "- 位置：第 {} 行{}\n",
+                "- Position: line {}{}\n",
+                bookmark.position + 1,
+                chapter
+            ));
+            if !bookmark.tag.is_empty() {
+                text.push_str(&trf!("- 标签：{}\n", "- Tags: {}\n", bookmark.tag));
+            }
+            if bookmark.timestamp > 0
+                && let Some(time) = chrono::DateTime::from_timestamp(bookmark.timestamp as i64, 0)
+            {
+                let time = time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+                text.push_str(&trf!("- 时间：{}\n", "- Time: {}\n", time));
+            }
+            if !bookmark.note.is_empty() {
+                text.push_str(&format!("\n{}\n", bookmark.note));
+            }
+        }
+
+        let export = BookmarkExport {
+            title: novel.title.clone(),
+            bookmarks: progress.bookmarks,
+        };
+        let json = serde_json::to_string_pretty(&export).map_err(std::io::Error::other)?;
+
+        let dir = exports_dir();
+        std::fs::create_dir_all(&dir)?;
+        let markdown_path = dir.join(format!("{}-书签.md", sanitize_file_name(&novel.title)));
+        std::fs::write(&markdown_path, text)?;
+        if let Some(json_path) = self.bookmark_export_json_path(index) {
+            std::fs::write(json_path, json)?;
+        }
+        Ok(markdown_path)
+    }
+
+    /// 从 `exports/` 中的 JSON 文件导入书架上一本小说的书签
+    ///
+    /// 与已有书签合并：同一位置、同名的书签视为重复，不会再次添加。
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 小说在 novels 列表中的索引
+    ///
+    /// # Returns
+    ///
+    /// 新增的书签数量；索引无效、文件不存在或格式错误时返回错误。
+    pub fn import_bookmarks(&mut self, index: usize) -> std::io::Result<usize> {
+        let path = self
+            .bookmark_export_json_path(index)
+            .ok_or_else(|| std::io::Error::other(tr("没有选中小说", "No novel selected")))?;
+        let content = std::fs::read_to_string(&path)?;
+        let export: BookmarkExport =
+            serde_json::from_str(&content).map_err(std::io::Error::other)?;
+
+        self.save_current_progress();
+        let novel_path = self.novels[index].path.clone();
+        let progress = self.library.progress_mut(&novel_path);
+        let mut added = 0;
+        for bookmark in export.bookmarks {
+            if progress
+                .bookmarks
+                .iter()
+                .any(|b| b.position == bookmark.position && b.name == bookmark.name)
+            {
+                continue;
+            }
+            let index = progress
+                .bookmarks
+                .partition_point(|b| b.position <= bookmark.position);
+            progress.bookmarks.insert(index, bookmark);
+            added += 1;
+        }
+        let bookmarks = progress.bookmarks.clone();
+        if let Some(novel) = &mut self.current_novel
+            && novel.path == novel_path
+        {
+            novel.progress.bookmarks = bookmarks;
+        }
+        self.library.save().map_err(std::io::Error::other)?;
+        Ok(added)
+    }
+
+    /// 书架上所有小说的书签，按书架顺序分组，同一本书内按位置排列
+    ///
+    /// 当前打开的小说以内存中的进度为准。
+    pub fn all_bookmarks(&self) -> Vec<ShelfBookmark> {
+        let mut entries = Vec::new();
+        for (novel_index, novel) in self.novels.iter().enumerate() {
+            let bookmarks = match &self.current_novel {
+                Some(current) if current.path == novel.path => current.progress.bookmarks.clone(),
+                _ => self.library.get_novel_progress(&novel.path).bookmarks,
+            };
+            entries.extend(bookmarks.into_iter().map(|bookmark| ShelfBookmark {
+                novel_index,
+                bookmark,
+            }));
+        }
+        entries
+    }
+
+    /// 打开全部书签浏览中选中书签所在的小说，并跳转到书签位置
+    ///
+    /// # Returns
+    ///
+    /// 打开成功返回 `Some(())`；没有选中或小说加载失败时返回 `None`。
+    pub fn open_selected_shelf_bookmark(&mut self) -> Option<()> {
+        let entry = self
+            .all_bookmarks()
+            .into_iter()
+            .nth(self.bookmark.all_selected_index?)?;
+        self.open_novel(entry.novel_index);
+        if self.state != AppState::Reading {
+            return None;
+        }
+        let line_count = self.current_novel.as_ref()?.line_count();
+        self.jump_to(entry.bookmark.position.min(line_count.saturating_sub(1)));
+        Some(())
+    }
 }
@@ -0,0 +1,214 @@
+use crate::model::chapter_parser::is_volume_title;
+use crate::model::novel::Chapter;
+
+use super::App;
+
+/// 在阅读位置添加章节时，标题最多截取的字符数
+const MAX_ADDED_TITLE_CHARS: usize = 30;
+
+impl App {
+    /// 目录中可见的章节索引
+    ///
+    /// 有筛选关键词时显示所有标题包含关键词的章节（忽略折叠）；
+    /// 否则隐藏已折叠的卷下的章节。
+    pub fn visible_chapter_indices(&self) -> Vec<usize> {
+        let Some(novel) = &self.current_novel else {
+            return Vec::new();
+        };
+        let filter = self.chapter_list.filter.to_lowercase();
+        if !filter.is_empty() {
+            return novel
+                .chapters
+                .iter()
+                .enumerate()
+                .filter(|(_, chapter)| chapter.title.to_lowercase().contains(&filter))
+                .map(|(index, _)| index)
+                .collect();
+        }
+
+        let mut visible = Vec::new();
+        let mut hidden = false;
+        for (index, chapter) in novel.chapters.iter().enumerate() {
+            if is_volume_title(&chapter.title) {
+                hidden = self.chapter_list.collapsed.contains(&index);
+                visible.push(index);
+            } else if !hidden {
+                visible.push(index);
+            }
+        }
+        visible
+    }
+
+    /// 章节是否为卷/部级标题
+    pub fn is_volume(&self, index: usize) -> bool {
+        self.current_novel
+            .as_ref()
+            .and_then(|novel| novel.chapters.get(index))
+            .is_some_and(|chapter| is_volume_title(&chapter.title))
+    }
+
+    /// 章节所属的卷：之前最近的一个卷标题；章节本身是卷或前面没有卷时返回 `None`
+    pub fn parent_volume(&self, index: usize) -> Option<usize> {
+        if self.is_volume(index) {
+            return None;
+        }
+        (0..index).rev().find(|&i| self.is_volume(i))
+    }
+
+    /// 折叠选中的卷；选中的是卷内章节时折叠其所属的卷并选中该卷
+    pub fn collapse_selected_volume(&mut self) {
+        let Some(index) = self.selected_chapter_index else {
+            return;
+        };
+        let volume = if self.is_volume(index) {
+            index
+        } else if let Some(volume) = self.parent_volume(index) {
+            volume
+        } else {
+            return;
+        };
+        self.chapter_list.collapsed.insert(volume);
+        self.selected_chapter_index = Some(volume);
+    }
+
+    /// 展开选中的卷
+    pub fn expand_selected_volume(&mut self) {
+        if let Some(index) = self.selected_chapter_index {
+            self.chapter_list.collapsed.remove(&index);
+        }
+    }
+
+    /// 筛选关键词变化后修正选中项
+    ///
+    /// 原选中章节仍可见时保持不变，否则选中第一个可见章节；没有可见章节时取消选中。
+    pub fn refresh_chapter_filter(&mut self) {
+        let visible = self.visible_chapter_indices();
+        if !self
+            .selected_chapter_index
+            .is_some_and(|index| visible.contains(&index))
+        {
+            self.selected_chapter_index = visible.first().copied();
+        }
+    }
+
+    /// 删除目录中选中的章节（至少保留一章）
+    ///
+    /// # Returns
+    ///
+    /// 删除成功返回 `Some(())`，没有选中、索引无效或只剩一章时返回 `None`。
+    pub fn delete_selected_chapter(&mut self) -> Option<()> {
+        let index = self.selected_chapter_index?;
+        let novel = self.current_novel.as_mut()?;
+        if novel.chapters.len() <= 1 || index >= novel.chapters.len() {
+            return None;
+        }
+        novel.chapters.remove(index);
+        self.selected_chapter_index = Some(index.min(novel.chapters.len() - 1));
+        self.save_chapter_edits();
+        Some(())
+    }
+
+    /// 重命名目录中选中的章节
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - 新标题，去掉首尾空白后为空时不修改
+    pub fn rename_selected_chapter(&mut self, title: &str) -> Option<()> {
+        let title = title.trim();
+        if title.is_empty() {
+            return None;
+        }
+        let index = self.selected_chapter_index?;
+        let chapter = self.current_novel.as_mut()?.chapters.get_mut(index)?;
+        chapter.title = title.to_string();
+        self.save_chapter_edits();
+        Some(())
+    }
+
+    /// 以当前阅读位置的首行作为新章节的开头
+    ///
+    /// 标题取该行内容（过长时截断，空行时为“新章节”），添加后选中新章节。
+    ///
+    /// # Returns
+    ///
+    /// 添加成功返回 `Some(())`；该行已是某章开头或没有打开小说时返回 `None`。
+    pub fn add_chapter_at_current_line(&mut self) -> Option<()> {
+        let novel = self.current_novel.as_mut()?;
+        let line = novel.progress.scroll_offset;
+        let index = novel.chapters.partition_point(|c| c.start_line < line);
+        if novel
+            .chapters
+            .get(index)
+            .is_some_and(|c| c.start_line == line)
+        {
+            return None;
+        }
+
+        let text = novel.lines().get(line).map_or("", |l| l.trim());
+        let title = if text.is_empty() {
+            "新章节".to_string()
+        } else {
+            text.chars().take(MAX_ADDED_TITLE_CHARS).collect()
+        };
+        novel.chapters.insert(
+            index,
+            Chapter {
+                title,
+                start_line: line,
+            },
+        );
+        self.selected_chapter_index = Some(index);
+        self.save_chapter_edits();
+        Some(())
+    }
+
+    /// 放弃手动编辑，恢复自动识别的章节目录
+    pub fn reset_chapter_edits(&mut self) {
+        let Some(novel) = &mut self.current_novel else {
+            return;
+        };
+        let settings = self.library.chapter_settings(&novel.path);
+        let patterns = self
+            .preferences
+            .chapter_regexes(&settings.patterns)
+            .unwrap_or_default();
+        novel.progress.custom_chapters = None;
+        self.chapter_list.collapsed.clear();
+        novel.chapters = crate::model::chapter_cache::load_or_parse(
+            &novel.path,
+            novel.lines(),
+            &patterns,
+            settings.auto_detect,
+        );
+        self.selected_chapter_index = self.find_current_chapter_index();
+        self.save_current_progress();
+    }
+
+    /// 切换当前小说是否使用内置规则自动识别章节，并重新生成目录
+    ///
+    /// 手动编辑过的目录会被放弃。
+    ///
+    /// # Returns
+    ///
+    /// 切换后是否开启自动识别；没有打开小说时返回 `None`。
+    pub fn toggle_auto_chapters(&mut self) -> Option<bool> {
+        let path = self.current_novel.as_ref()?.path.clone();
+        let settings = self.library.chapter_settings_mut(&path);
+        settings.auto_detect = !settings.auto_detect;
+        let enabled = settings.auto_detect;
+        // 重新生成目录时会保存进度，设置随之写入
+        self.reset_chapter_edits();
+        Some(enabled)
+    }
+
+    /// 把当前目录记为手动编辑结果并保存
+    ///
+    /// 章节索引可能变化，因此同时展开所有卷。
+    fn save_chapter_edits(&mut self) {
+        self.chapter_list.collapsed.clear();
+        if let Some(novel) = &mut self.current_novel {
+            novel.progress.custom_chapters = Some(novel.chapters.clone());
+            self.save_current_progress();
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! 命令行模式：按 `:` 输入 vim 风格的命令，如 `:goto 1200`、`:chapter 57`、`:set width=90`
+
+use super::{App, ReaderOption};
+use crate::i18n::{tr, trf};
+use crate::model::chapter_parser::is_volume_title;
+use crate::state::AppState;
+
+/// 解析后的命令
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    /// 跳转到指定行（从 1 开始）
+    Goto(usize),
+    /// 跳转到全文的指定百分比位置
+    GotoPercent(usize),
+    /// 跳转到第几章（从 1 开始，不计卷标题）
+    Chapter(usize),
+    /// 上传到 WebDAV
+    SyncUp,
+    /// 从 WebDAV 下载（需要确认）
+    SyncDown,
+    /// 设置阅读选项，`None` 表示只显示当前值
+    Set(ReaderOption, Option<usize>),
+    /// 退出程序
+    Quit,
+}
+
+/// 可用命令的简要说明，命令无法识别时提示
+fn usage() -> &'static str {
+    tr(
+        "可用命令：goto、chapter、sync、set、q",
+        "Commands: goto, chapter, sync, set, q",
+    )
+}
+
+/// 解析正整数参数
+fn parse_number(arg: Option<&str>) -> Result<usize, String> {
+    let arg = arg.ok_or_else(|| tr("缺少数字参数", "A number is required").to_string())?;
+    arg.parse()
+        .map_err(|_| trf!("不是有效的数字：{}", "Not a valid number: {}", arg))
+}
+
+/// 解析 `goto` 的参数：行号或以 `%` 结尾的百分比
+fn parse_goto(arg: Option<&str>) -> Result<Command, String> {
+    match arg.and_then(|arg| arg.strip_suffix('%')) {
+        Some(percent) => match parse_number(Some(percent))? {
+            percent @ 0..=100 => Ok(Command::GotoPercent(percent)),
+            _ => Err(tr(
+                "百分比应在 0-100 之间",
+                "The percentage must be between 0 and 100",
+            )
+            .into()),
+        },
+        None => parse_number(arg).map(Command::Goto),
+    }
+}
+
+/// 解析 `set` 的参数：`名称=值`、`名称 值`，或只有名称（显示当前值）
+fn parse_set(args: &[&str]) -> Result<Command, String> {
+    let joined = args.join(" ");
+    let (name, value) = match joined.split_once(['=', ' ']) {
+        Some((name, value)) => (
+            name.trim(),
+            Some(value.trim().trim_start_matches('=').trim()),
+        ),
+        None => (joined.trim(), None),
+    };
+    let name = name.trim_end_matches('?');
+    if name.is_empty() {
+        return Err(tr("缺少选项名", "An option name is required").into());
+    }
+    let option = ReaderOption::from_name(name).ok_or_else(|| {
+        let names: Vec<_> = ReaderOption::ALL
+            .iter()
+            .map(|option| option.name())
+            .collect();
+        trf!(
+            "未知选项：{}（可用：{}）",
+            "Unknown option: {} (available: {})",
+            name,
+            names.join(", ")
+        )
+    })?;
+    let value = value.map(|value| parse_number(Some(value))).transpose()?;
+    Ok(Command::Set(option, value))
+}
+
+impl Command {
+    /// 解析一行命令（不含开头的 `:`）
+    ///
+    /// 单独的数字等同于 `goto`，与 vim 的 `:1200` 相同。
+    ///
+    /// # Errors
+    ///
+    /// 命令或参数无法识别时返回错误提示。
+    fn parse(input: &str) -> Result<Self, String> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let Some((&name, args)) = words.split_first() else {
+            return Err(usage().into());
+        };
+        match name {
+            "goto" | "go" | "g" => parse_goto(args.first().copied()),
+            "chapter" | "ch" => parse_number(args.first().copied()).map(Command::Chapter),
+            "sync" => match args.first().copied() {
+                Some("up") => Ok(Command::SyncUp),
+                Some("down") => Ok(Command::SyncDown),
+                _ => Err(tr("用法：sync up 或 sync down", "Usage: sync up or sync down").into()),
+            },
+            "set" | "se" => parse_set(args),
+            "q" | "quit" => Ok(Command::Quit),
+            _ if name.ends_with('%') || name.chars().all(|c| c.is_ascii_digit()) => {
+                parse_goto(Some(name))
+            }
+            _ => Err(trf!(
+                "未知命令：{}。{}",
+                "Unknown command: {}. {}",
+                name,
+                usage()
+            )),
+        }
+    }
+}
+
+impl App {
+    /// 执行命令行中输入的命令，失败时在状态栏显示错误
+    ///
+    /// 跳转类命令只在阅读界面及其目录、书签、搜索等子界面中可用，执行后回到阅读界面。
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - 输入的命令（不含开头的 `:`），为空时不做任何事
+    pub fn run_command(&mut self, input: &str) {
+        if input.trim().is_empty() {
+            return;
+        }
+        if let Err(e) = Command::parse(input).and_then(|command| self.execute_command(command)) {
+            self.set_error(e);
+        }
+    }
+
+    fn execute_command(&mut self, command: Command) -> Result<(), String> {
+        match command {
+            Command::Goto(line) => {
+                let line_count = self.command_novel_lines()?;
+                self.jump_to(line.saturating_sub(1).min(line_count.saturating_sub(1)));
+                self.state = AppState::Reading;
+            }
+            Command::GotoPercent(percent) => {
+                let line_count = self.command_novel_lines()?;
+                self.jump_to((line_count * percent / 100).min(line_count.saturating_sub(1)));
+                self.state = AppState::Reading;
+            }
+            Command::Chapter(number) => {
+                self.command_novel_lines()?;
+                let chapters: Vec<usize> = self
+                    .current_novel
+                    .iter()
+                    .flat_map(|novel| &novel.chapters)
+                    .filter(|chapter| !is_volume_title(&chapter.title))
+                    .map(|chapter| chapter.start_line)
+                    .collect();
+                let Some(&line) = number.checked_sub(1).and_then(|i| chapters.get(i)) else {
+                    return Err(match chapters.len() {
+                        0 => tr(
+                            "这本小说没有识别到章节",
+                            "No chapters were found in this novel",
+                        )
+                        .to_string(),
+                        total => trf!(
+                            "章节序号应在 1-{} 之间",
+                            "The chapter number must be between 1 and {}",
+                            total
+                        ),
+                    });
+                };
+                self.jump_to(line);
+                self.state = AppState::Reading;
+            }
+            Command::SyncUp => self.trigger_sync(),
+            Command::SyncDown => self.request_download(),
+            Command::Set(option, value) => {
+                if let Some(value) = value {
+                    self.set_reader_option(option, value)?;
+                }
+                self.set_info(trf!(
+                    "{}：{}",
+                    "{}: {}",
+                    option.label(),
+                    self.reader_option_value(option)
+                ));
+            }
+            Command::Quit => self.request_quit(),
+        }
+        Ok(())
+    }
+
+    /// 跳转类命令的前提：正在阅读一本小说；返回这本小说的行数
+    fn command_novel_lines(&self) -> Result<usize, String> {
+        match (&self.state, &self.current_novel) {
+            (
+                AppState::Reading
+                | AppState::Searching
+                | AppState::ChapterList
+                | AppState::BookmarkList
+                | AppState::HighlightList
+                | AppState::AnnotationList,
+                Some(novel),
+            ) => Ok(novel.line_count()),
+            _ => Err(tr("请先打开一本小说再跳转", "Open a novel before jumping").into()),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! 配置热重载：监视配置文件和偏好设置文件，在外部修改后立即生效，不必重启
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use super::App;
+use crate::config::{self, AppConfig};
+use crate::i18n::{tr, trf};
+use crate::model::preferences::Preferences;
+
+/// 最后一次文件变化后等待这么久再重新读取，避免编辑器分几次写入时读到半个文件
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 配置文件和偏好设置文件的监视器
+pub struct ConfigWatcher {
+    /// 监视器本身，丢弃后停止监视
+    _watcher: RecommendedWatcher,
+    /// 文件系统事件接收端
+    rx: Receiver<notify::Result<notify::Event>>,
+    /// 配置文件是否有待重新读取的变化
+    config_changed: bool,
+    /// 偏好设置文件是否有待重新读取的变化
+    preferences_changed: bool,
+    /// 最近一次相关变化的时间，`None` 表示没有待处理的变化
+    pending_since: Option<Instant>,
+}
+
+/// 事件是否修改了指定文件
+fn touches(event: &notify::Event, file: &Path) -> bool {
+    matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_))
+        && event
+            .paths
+            .iter()
+            .any(|path| path.file_name() == file.file_name())
+}
+
+impl App {
+    /// 开始监视配置文件和偏好设置文件所在的目录
+    ///
+    /// 监视器创建失败时只是不自动重新加载，不影响其他功能。
+    pub fn watch_config_files(&mut self) {
+        self.config_watcher = None;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let Ok(mut watcher) = notify::recommended_watcher(tx) else {
+            return;
+        };
+        // 只监视文件所在的目录：编辑器保存时常常先写临时文件再改名，直接监视文件会丢失后续变化
+        let mut dirs: Vec<PathBuf> = [AppConfig::path(), Preferences::preferences_path()]
+            .iter()
+            .filter_map(|path| path.parent().map(Path::to_path_buf))
+            .collect();
+        dirs.dedup();
+        for dir in dirs {
+            let _ = watcher.watch(&dir, RecursiveMode::NonRecursive);
+        }
+        self.config_watcher = Some(ConfigWatcher {
+            _watcher: watcher,
+            rx,
+            config_changed: false,
+            preferences_changed: false,
+            pending_since: None,
+        });
+    }
+
+    /// 处理配置文件监视事件（主循环中调用）
+    ///
+    /// 文件变化稳定一段时间后重新读取：配置变化时提示已重新加载，
+    /// 无法解析时提示错误并保留当前设置。程序自己保存的内容与当前设置相同，不会提示。
+    pub fn poll_config_watcher(&mut self) {
+        let Some(watcher) = &mut self.config_watcher else {
+            return;
+        };
+        let config_path = AppConfig::path();
+        let preferences_path = Preferences::preferences_path();
+        loop {
+            match watcher.rx.try_recv() {
+                Ok(Ok(event)) => {
+                    if touches(&event, &config_path) {
+                        watcher.config_changed = true;
+                        watcher.pending_since = Some(Instant::now());
+                    }
+                    if touches(&event, &preferences_path) {
+                        watcher.preferences_changed = true;
+                        watcher.pending_since = Some(Instant::now());
+                    }
+                }
+                Ok(Err(_)) => {}
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.config_watcher = None;
+                    return;
+                }
+            }
+        }
+        if watcher
+            .pending_since
+            .is_none_or(|since| since.elapsed() < RELOAD_DEBOUNCE)
+        {
+            return;
+        }
+        watcher.pending_since = None;
+        let config_changed = std::mem::take(&mut watcher.config_changed);
+        let preferences_changed = std::mem::take(&mut watcher.preferences_changed);
+
+        if config_changed {
+            self.reload_config_file();
+        }
+        if preferences_changed {
+            self.reload_preferences_file();
+        }
+    }
+
+    /// 重新读取配置文件，有变化或错误时提示
+    fn reload_config_file(&mut self) {
+        let (changed, errors) = config::reload();
+        if !errors.is_empty() {
+            self.set_error(errors.join("；"));
+        } else if changed {
+            self.set_info(tr("已重新加载 config.toml", "Reloaded config.toml"));
+        }
+    }
+
+    /// 重新读取偏好设置文件，有变化时替换当前的偏好设置并刷新书架
+    fn reload_preferences_file(&mut self) {
+        let preferences = match Preferences::reload() {
+            Ok(preferences) => preferences,
+            Err(e) => {
+                self.set_error(trf!(
+                    "{}，已保留当前设置",
+                    "{}, keeping the current settings",
+                    e
+                ));
+                return;
+            }
+        };
+        if preferences == self.preferences {
+            return;
+        }
+        let dirs_changed = preferences.library_dirs != self.preferences.library_dirs;
+        self.preferences = preferences;
+        crate::i18n::set_language(self.preferences.language);
+        if dirs_changed {
+            self.reload_novels();
+            self.watch_novel_dirs();
+        } else {
+            self.sort_novels();
+        }
+        self.set_info(tr("已重新加载偏好设置", "Reloaded preferences"));
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use super::App;
+use crate::config::config;
+use crate::i18n::{tr, trf};
+use crate::model::preferences::QuitKey;
+use crate::state::AppState;
+
+/// 需要用户确认后才执行的操作
+///
+/// 对话框显示期间书架可能被自动重新扫描，小说和孤立记录按路径而不是索引指定。
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfirmAction {
+    /// 打开书架中指定路径的小说
+    OpenNovel(PathBuf),
+    /// 保存进度并退出程序
+    Quit,
+    /// 把书架中指定路径的小说移到回收站
+    DeleteNovel(PathBuf),
+    /// 删除指定路径的孤立记录
+    DeleteOrphaned(PathBuf),
+    /// 彻底删除回收站中指定索引的小说
+    PurgeTrashed(usize),
+    /// 从 WebDAV 下载，覆盖本地数据
+    SyncDown,
+    /// 合并内容重复的两本小说（保留的路径，移到回收站的路径）
+    MergeDuplicate(PathBuf, PathBuf),
+    /// 把书架上标记的小说（没有标记时为选中的小说）移到回收站
+    DeleteBatch,
+    /// 从导出的设置文件导入，覆盖本机设置
+    ImportSettings,
+    /// 用 `settings.backups` 中指定索引的备份替换阅读进度
+    RestoreBackup(usize),
+}
+
+/// 确认对话框（y/n）
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfirmDialog {
+    /// 提示内容
+    pub message: String,
+    /// 确认后执行的操作
+    pub action: ConfirmAction,
+}
+
+impl App {
+    /// 弹出确认对话框
+    ///
+    /// 对话框显示期间所有按键都交给对话框处理，确认后才会执行 `action`。
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - 提示内容
+    /// * `action` - 确认后执行的操作
+    pub fn request_confirm(&mut self, message: impl Into<String>, action: ConfirmAction) {
+        self.confirm = Some(ConfirmDialog {
+            message: message.into(),
+            action,
+        });
+    }
+
+    /// 确认时按路径找到小说在书架中的位置；小说已被移走或删除时提示错误
+    fn confirmed_novel_index(&mut self, path: &Path) -> Option<usize> {
+        let index = self.novels.iter().position(|n| n.path == path);
+        if index.is_none() {
+            self.set_error(tr(
+                "这本小说已不在书架上",
+                "This novel is no longer on the bookshelf",
+            ));
+        }
+        index
+    }
+
+    /// 关闭确认对话框，`accepted` 为真时执行对应操作
+    pub fn resolve_confirm(&mut self, accepted: bool) {
+        let Some(dialog) = self.confirm.take() else {
+            return;
+        };
+        if !accepted {
+            return;
+        }
+        match dialog.action {
+            ConfirmAction::OpenNovel(path) => {
+                if let Some(index) = self.confirmed_novel_index(&path) {
+                    self.open_novel(index);
+                }
+            }
+            ConfirmAction::Quit => self.quit(),
+            ConfirmAction::DeleteNovel(path) => {
+                let Some(index) = self.confirmed_novel_index(&path) else {
+                    return;
+                };
+                match self.delete_novel(index) {
+                    Ok(()) => {
+                        if self.state == AppState::BookDetail {
+                            // 从详情页删除时回到书架，选中原位置的下一本
+                            self.state = AppState::Bookshelf;
+                            self.selected_novel_index =
+                                Some(index.min(self.novels.len().saturating_sub(1)));
+                            self.reload_novels();
+                            self.refresh_bookshelf_selection();
+                        }
+                        self.set_info(trf!(
+                            "已移到回收站，{} 天内可在设置 → 回收站中恢复", "Moved to the trash; restore it within {} days from Settings → Trash",
+                            config().trash_retention_days
+                        ));
+                    }
+                    Err(e) => self.set_error(format!("Failed to delete novel: {}", e)),
+                }
+            }
+            ConfirmAction::DeleteOrphaned(path) => {
+                match self.settings.orphaned_novels.iter().position(|n| n.path == path) {
+                    Some(index) => self.delete_orphaned_novel(index),
+                    None => self.set_error(tr(
+                        "这条孤立记录已不存在",
+                        "This orphaned record no longer exists",
+                    )),
+                }
+            }
+            ConfirmAction::PurgeTrashed(index) => match self.purge_trashed_novel(index) {
+                Ok(()) => self.set_info(tr("已彻底删除", "Deleted permanently")),
+                Err(e) => self.set_error(trf!("删除失败：{}", "Delete failed: {}", e)),
+            },
+            ConfirmAction::SyncDown => self.trigger_download(),
+            ConfirmAction::DeleteBatch => match self.delete_batch() {
+                Ok(count) => self.set_info(trf!(
+                    "已把 {} 本小说移到回收站，{} 天内可在设置 → 回收站中恢复", "Moved {} novels to the trash; restore them within {} days from Settings → Trash",
+                    count,
+                    config().trash_retention_days
+                )),
+                Err(e) => self.set_error(format!("Failed to delete novel: {}", e)),
+            },
+            ConfirmAction::ImportSettings => match self.import_settings() {
+                Ok(()) => self.set_info(tr("已导入设置", "Settings imported")),
+                Err(e) => self.set_error(trf!("导入失败：{}", "Import failed: {}", e)),
+            },
+            ConfirmAction::RestoreBackup(index) => match self.restore_progress_backup(index) {
+                Ok(()) => self.set_info(tr("已恢复，恢复前的进度已另存为最新的备份", "Restored; the previous progress was saved as the newest backup")),
+                Err(e) => self.set_error(trf!("恢复失败：{}", "Restore failed: {}", e)),
+            },
+            ConfirmAction::MergeDuplicate(keep, drop) => {
+                let (Some(keep), Some(drop)) = (
+                    self.confirmed_novel_index(&keep),
+                    self.confirmed_novel_index(&drop),
+                ) else {
+                    return;
+                };
+                match self.merge_duplicate_novels(keep, drop) {
+                    Ok(()) => self.set_info(tr("已合并，重复的文件已移到回收站", "Merged; the duplicate files were moved to the trash")),
+                    Err(e) => self.set_error(trf!("合并失败：{}", "Merge failed: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// 请求退出程序
+    ///
+    /// 开启了 `confirm_quit` 偏好时先弹出确认对话框，否则直接退出。
+    pub fn request_quit(&mut self) {
+        if self.preferences.confirm_quit {
+            let message = match self.preferences.quit_key {
+                QuitKey::Disabled => tr("确定退出？", "Quit?").to_string(),
+                key => trf!(
+                    "确定退出？（再按 {} 也可确认）",
+                    "Quit? (press {} again to confirm)",
+                    key.hint()
+                ),
+            };
+            self.request_confirm(message, ConfirmAction::Quit);
+        } else {
+            self.quit();
+        }
+    }
+
+    /// 保存当前进度并设置退出标志
+    fn quit(&mut self) {
+        self.record_exit_position();
+        self.save_current_progress();
+        self.should_quit = true;
+    }
+}
@@ -0,0 +1,128 @@
+//! 重复小说检测：内容相同（忽略空白和换行差异）但文件名不同的小说
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::App;
+use crate::model::trash::Trash;
+
+/// 文件大小相差在这个比例以内才比较内容，避免读取所有文件
+const SIZE_TOLERANCE: f64 = 0.05;
+
+/// 计算小说内容的指纹：去掉所有空白字符后的 CRC32
+///
+/// 换行方式、缩进、空行不同的同一本书得到相同的指纹。
+fn content_fingerprint(path: &Path) -> Option<u32> {
+    let bytes = std::fs::read(path).ok()?;
+    let text = String::from_utf8_lossy(&bytes);
+    let mut hasher = crc32fast::Hasher::new();
+    let mut buf = [0u8; 4];
+    for c in text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '\u{feff}')
+    {
+        hasher.update(c.encode_utf8(&mut buf).as_bytes());
+    }
+    Some(hasher.finalize())
+}
+
+impl App {
+    /// 检测书架上内容重复的小说，结果保存在 `bookshelf.duplicate_groups`
+    ///
+    /// 只比较大小相近的文件；指纹按文件大小缓存，文件未变化时不重新读取。
+    pub fn detect_duplicates(&mut self) {
+        let mut by_size: Vec<(u64, usize)> = self
+            .novels
+            .iter()
+            .enumerate()
+            .filter(|(_, novel)| novel.file_size > 0)
+            .map(|(index, novel)| (novel.file_size, index))
+            .collect();
+        by_size.sort_unstable();
+
+        let mut groups: HashMap<u32, Vec<PathBuf>> = HashMap::new();
+        for (position, &(size, index)) in by_size.iter().enumerate() {
+            let near = |&(other, _): &(u64, usize)| {
+                size.abs_diff(other) as f64 <= size.max(other) as f64 * SIZE_TOLERANCE
+            };
+            let has_neighbor = position
+                .checked_sub(1)
+                .and_then(|prev| by_size.get(prev))
+                .is_some_and(near)
+                || by_size.get(position + 1).is_some_and(near);
+            if !has_neighbor {
+                continue;
+            }
+
+            let path = &self.novels[index].path;
+            let cached = self
+                .bookshelf
+                .fingerprints
+                .get(path)
+                .filter(|(cached_size, _)| *cached_size == size)
+                .map(|&(_, fingerprint)| fingerprint);
+            let Some(fingerprint) = cached.or_else(|| content_fingerprint(path)) else {
+                continue;
+            };
+            self.bookshelf
+                .fingerprints
+                .insert(path.clone(), (size, fingerprint));
+            groups.entry(fingerprint).or_default().push(path.clone());
+        }
+
+        self.bookshelf.duplicate_groups = groups
+            .into_values()
+            .filter(|paths| paths.len() > 1)
+            .collect();
+    }
+
+    /// 与书架上指定小说内容重复的其他小说
+    ///
+    /// # Returns
+    ///
+    /// 其他小说在 `novels` 中的索引。
+    pub fn bookshelf_duplicates(&self, index: usize) -> Vec<usize> {
+        let Some(path) = self.novels.get(index).map(|n| &n.path) else {
+            return Vec::new();
+        };
+        let Some(group) = self
+            .bookshelf
+            .duplicate_groups
+            .iter()
+            .find(|paths| paths.contains(path))
+        else {
+            return Vec::new();
+        };
+        group
+            .iter()
+            .filter(|p| *p != path)
+            .filter_map(|p| self.novels.iter().position(|n| &n.path == p))
+            .collect()
+    }
+
+    /// 合并两本内容重复的小说：保留 `keep`，把 `drop` 的进度、书签、标签等并入，然后把 `drop` 移到回收站
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - 保留的小说在 `novels` 中的索引
+    /// * `drop` - 并入后移到回收站的小说索引
+    pub fn merge_duplicate_novels(&mut self, keep: usize, drop: usize) -> std::io::Result<()> {
+        let (Some(keep_novel), Some(drop_novel)) = (self.novels.get(keep), self.novels.get(drop))
+        else {
+            return Ok(());
+        };
+        if keep == drop {
+            return Ok(());
+        }
+        let keep_path = keep_novel.path.clone();
+        let drop_path = drop_novel.path.clone();
+
+        Trash::load().move_in(&drop_path, None)?;
+        self.library.merge_novels(&keep_path, &drop_path);
+        self.library.save()?;
+        self.reload_novels();
+        self.selected_novel_index = self.novels.iter().position(|n| n.path == keep_path);
+        self.selected_category = None;
+        Ok(())
+    }
+}
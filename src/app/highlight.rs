@@ -0,0 +1,70 @@
+use super::App;
+use crate::model::novel::Highlight;
+
+impl App {
+    /// 在指定行开始选择要高亮的行
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - 选择的起点，也是光标的初始位置
+    pub fn start_visual_selection(&mut self, line: usize) {
+        self.reader.visual_anchor = Some(line);
+        self.reader.visual_cursor = line;
+    }
+
+    /// 当前选中的行范围（闭区间，起点在前）；不在选择模式时返回 `None`
+    pub fn visual_range(&self) -> Option<(usize, usize)> {
+        let anchor = self.reader.visual_anchor?;
+        let cursor = self.reader.visual_cursor;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// 把选中的行保存为高亮并退出选择模式
+    ///
+    /// # Returns
+    ///
+    /// 保存成功返回 `Some(())`；不在选择模式或没有打开小说时返回 `None`。
+    pub fn confirm_visual_selection(&mut self) -> Option<()> {
+        let (start, end) = self.visual_range()?;
+        self.reader.visual_anchor = None;
+        self.current_novel
+            .as_mut()?
+            .progress
+            .add_highlight(start, end);
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 获取当前小说的高亮列表
+    pub fn get_current_highlights(&self) -> Option<&Vec<Highlight>> {
+        self.current_novel
+            .as_ref()
+            .map(|novel| &novel.progress.highlights)
+    }
+
+    /// 删除当前小说的指定高亮
+    ///
+    /// # Returns
+    ///
+    /// 如果删除成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
+    pub fn remove_highlight(&mut self, index: usize) -> Option<()> {
+        let highlights = &mut self.current_novel.as_mut()?.progress.highlights;
+        if index >= highlights.len() {
+            return None;
+        }
+        highlights.remove(index);
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 跳转到指定高亮的起始行
+    ///
+    /// # Returns
+    ///
+    /// 如果跳转成功返回 `Some(())`，如果索引无效或当前无小说则返回 `None`。
+    pub fn jump_to_highlight(&mut self, index: usize) -> Option<()> {
+        let start = self.get_current_highlights()?.get(index)?.start;
+        self.jump_to(start);
+        Some(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use super::App;
+
+/// 跳转历史最多保留的条目数
+const MAX_JUMP_HISTORY: usize = 100;
+
+/// 跳转距离达到该行数时，自动记录跳转前的位置
+const BIG_JUMP_LINES: usize = 100;
+
+/// 单本小说的会话内导航状态
+///
+/// 跳转历史与浏览器的前进/后退相同：新的跳转会清空前进栈。
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NovelNavigation {
+    /// 可以后退到的位置（末尾为最近一次）
+    pub back: Vec<usize>,
+    /// 后退后可以再前进到的位置（末尾为最近一次）
+    pub forward: Vec<usize>,
+    /// vim 风格的标记（字母 → 行号），区别于需要命名的持久书签
+    pub marks: HashMap<char, usize>,
+}
+
+impl App {
+    /// 跳转到当前小说的指定行，并记录跳转前的位置
+    ///
+    /// 搜索、目录、书签、`gg`/`G` 等所有"跳转"都应通过此函数，
+    /// 这样才能用 `Ctrl+o`/`Tab` 在跳转历史中后退和前进。
+    /// 跨度较大的跳转还会把跳转前的位置记入书签列表的自动记录。
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - 目标行号
+    pub fn jump_to(&mut self, line: usize) {
+        let Some(novel) = &mut self.current_novel else {
+            return;
+        };
+        let from = novel.progress.scroll_offset;
+        // 跳转总是落在目标行开头，即使目标就是首行（页首在长段落中间时）
+        novel.progress.char_offset = None;
+        if line == from {
+            novel.sync_char_offset();
+            return;
+        }
+
+        let history = self.navigation.entry(novel.path.clone()).or_default();
+        history.back.push(from);
+        if history.back.len() > MAX_JUMP_HISTORY {
+            history.back.remove(0);
+        }
+        history.forward.clear();
+
+        if line.abs_diff(from) >= BIG_JUMP_LINES {
+            novel.progress.record_auto_position("跳转前", from);
+        }
+        novel.last_position = Some(from);
+        novel.progress.scroll_offset = line;
+        self.save_current_progress();
+    }
+
+    /// 在当前位置与最近一次跳转前的位置之间来回切换
+    ///
+    /// 与跳转历史不同，这里只记住一个位置，连续使用会在两处之间往返。
+    ///
+    /// # Returns
+    ///
+    /// 如果存在可返回的位置返回 `Some(())`，否则返回 `None`。
+    pub fn return_to_last_position(&mut self) -> Option<()> {
+        let novel = self.current_novel.as_mut()?;
+        let target = novel.last_position?;
+        novel.last_position = Some(novel.progress.scroll_offset);
+        novel.progress.scroll_offset = target;
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 把当前阅读位置记入自动记录（离开阅读界面、退出或切换小说时调用）
+    ///
+    /// 只修改内存中的进度，由随后的保存一并写入。
+    pub fn record_exit_position(&mut self) {
+        if let Some(novel) = &mut self.current_novel {
+            let line = novel.progress.scroll_offset;
+            novel.progress.record_auto_position("退出时", line);
+        }
+    }
+
+    /// 回到上一次跳转前的位置
+    ///
+    /// # Returns
+    ///
+    /// 如果存在可后退的位置返回 `Some(())`，否则返回 `None`。
+    pub fn jump_back(&mut self) -> Option<()> {
+        let novel = self.current_novel.as_mut()?;
+        let history = self.navigation.get_mut(&novel.path)?;
+        let target = history.back.pop()?;
+        history.forward.push(novel.progress.scroll_offset);
+        novel.progress.scroll_offset = target;
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 撤销一次后退，回到后退前的位置
+    ///
+    /// # Returns
+    ///
+    /// 如果存在可前进的位置返回 `Some(())`，否则返回 `None`。
+    pub fn jump_forward(&mut self) -> Option<()> {
+        let novel = self.current_novel.as_mut()?;
+        let history = self.navigation.get_mut(&novel.path)?;
+        let target = history.forward.pop()?;
+        history.back.push(novel.progress.scroll_offset);
+        novel.progress.scroll_offset = target;
+        self.save_current_progress();
+        Some(())
+    }
+
+    /// 在当前位置设置标记
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 标记名（单个字母），同名标记会被覆盖
+    pub fn set_mark(&mut self, name: char) {
+        if let Some(novel) = &self.current_novel {
+            self.navigation
+                .entry(novel.path.clone())
+                .or_default()
+                .marks
+                .insert(name, novel.progress.scroll_offset);
+        }
+    }
+
+    /// 跳转到标记位置（计入跳转历史）
+    ///
+    /// # Returns
+    ///
+    /// 如果标记存在返回 `Some(())`，否则返回 `None`。
+    pub fn jump_to_mark(&mut self, name: char) -> Option<()> {
+        let path = &self.current_novel.as_ref()?.path;
+        let line = *self.navigation.get(path)?.marks.get(&name)?;
+        self.jump_to(line);
+        Some(())
+    }
+
+    /// 把当前位置保存到编号快捷书签（随阅读进度持久保存）
+    ///
+    /// # Arguments
+    ///
+    /// * `slot` - 编号 1–9，已有的同号书签会被覆盖
+    pub fn set_quick_slot(&mut self, slot: u8) {
+        if let Some(novel) = &mut self.current_novel {
+            let line = novel.progress.scroll_offset;
+            novel.progress.quick_slots.insert(slot, line);
+            self.save_current_progress();
+        }
+    }
+
+    /// 跳转到编号快捷书签（计入跳转历史）
+    ///
+    /// # Returns
+    ///
+    /// 如果该编号已设置返回 `Some(())`，否则返回 `None`。
+    pub fn jump_to_quick_slot(&mut self, slot: u8) -> Option<()> {
+        let novel = self.current_novel.as_ref()?;
+        let line = *novel.progress.quick_slots.get(&slot)?;
+        let line = line.min(novel.lines().len().saturating_sub(1));
+        self.jump_to(line);
+        Some(())
+    }
+}
@@ -0,0 +1,103 @@
+//! 组合键：先按 `,`，再按一个键执行操作；等待第二个键时弹出当前界面可用的组合键
+
+use super::App;
+use crate::i18n::tr;
+use crate::state::AppState;
+
+/// 组合键执行的操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderAction {
+    /// 书签列表（阅读时为当前小说，书架上为所有小说）
+    Bookmarks,
+    /// 章节目录
+    ChapterList,
+    /// 高亮列表
+    Highlights,
+    /// 批注列表
+    Annotations,
+    /// 全文搜索
+    Search,
+    /// 上传到 WebDAV
+    SyncUp,
+    /// 从 WebDAV 下载
+    SyncDown,
+    /// 设置
+    Settings,
+    /// 切换界面语言
+    ToggleLanguage,
+    /// 开启/关闭鼠标捕获
+    ToggleMouseCapture,
+    /// 退出程序
+    Quit,
+}
+
+/// 所有组合键：`,` 之后按的键和对应的操作，按提示中的显示顺序
+const LEADER_BINDINGS: [(char, LeaderAction); 11] = [
+    ('b', LeaderAction::Bookmarks),
+    ('t', LeaderAction::ChapterList),
+    ('h', LeaderAction::Highlights),
+    ('a', LeaderAction::Annotations),
+    ('/', LeaderAction::Search),
+    ('s', LeaderAction::SyncUp),
+    ('d', LeaderAction::SyncDown),
+    ('o', LeaderAction::Settings),
+    ('l', LeaderAction::ToggleLanguage),
+    ('m', LeaderAction::ToggleMouseCapture),
+    ('q', LeaderAction::Quit),
+];
+
+impl LeaderAction {
+    /// 提示中显示的说明
+    pub fn label(self) -> &'static str {
+        match self {
+            LeaderAction::Bookmarks => tr("书签", "Bookmarks"),
+            LeaderAction::ChapterList => tr("目录", "Chapters"),
+            LeaderAction::Highlights => tr("高亮", "Highlights"),
+            LeaderAction::Annotations => tr("批注", "Annotations"),
+            LeaderAction::Search => tr("搜索", "Search"),
+            LeaderAction::SyncUp => tr("上传同步", "Sync up"),
+            LeaderAction::SyncDown => tr("下载同步", "Sync down"),
+            LeaderAction::Settings => tr("设置", "Settings"),
+            LeaderAction::ToggleLanguage => tr("切换语言", "Switch language"),
+            LeaderAction::ToggleMouseCapture => tr("鼠标捕获", "Mouse capture"),
+            LeaderAction::Quit => tr("退出", "Quit"),
+        }
+    }
+
+    /// 在指定界面中是否可用
+    fn available_in(self, state: &AppState) -> bool {
+        match self {
+            LeaderAction::Bookmarks => {
+                matches!(state, AppState::Reading | AppState::Bookshelf)
+            }
+            LeaderAction::ChapterList
+            | LeaderAction::Highlights
+            | LeaderAction::Annotations
+            | LeaderAction::Search => *state == AppState::Reading,
+            LeaderAction::Settings => *state == AppState::Bookshelf,
+            LeaderAction::SyncUp
+            | LeaderAction::SyncDown
+            | LeaderAction::ToggleLanguage
+            | LeaderAction::ToggleMouseCapture
+            | LeaderAction::Quit => true,
+        }
+    }
+}
+
+impl App {
+    /// 当前界面可用的组合键：`,` 之后按的键和对应的操作
+    pub fn leader_bindings(&self) -> Vec<(char, LeaderAction)> {
+        LEADER_BINDINGS
+            .into_iter()
+            .filter(|(_, action)| action.available_in(&self.state))
+            .collect()
+    }
+
+    /// 当前界面中 `,` 之后按下 `key` 对应的操作
+    pub fn leader_action(&self, key: char) -> Option<LeaderAction> {
+        self.leader_bindings()
+            .into_iter()
+            .find(|&(binding, _)| binding == key)
+            .map(|(_, action)| action)
+    }
+}
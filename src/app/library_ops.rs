@@ -1,8 +1,749 @@
 use anyhow::Result;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::TryRecvError;
+use std::time::{Duration, Instant};
 
-use super::App;
+use super::queue::QUEUE_CATEGORY;
+use super::{App, BookshelfRow, ConfirmAction};
+use crate::config::{self, config, expand_home};
+use crate::i18n::{tr, trf};
+use crate::model::chapter_cache;
+use crate::model::file_stats::FileStats;
+use crate::model::library::{Library, NovelInfo, ReadingStatus};
+use crate::model::matcher;
+use crate::model::novel::{Chapter, Novel};
+use crate::model::preferences::BookshelfSort;
+use crate::model::series;
+use crate::model::trash::Trash;
+use crate::state::AppState;
+
+/// 自然顺序比较标题：连续数字按数值比较，使 "第2部" 排在 "第10部" 之前
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let take_number = |chars: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut digits = String::new();
+                    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+                        digits.push(c);
+                    }
+                    digits.trim_start_matches('0').to_string()
+                };
+                let x_num = take_number(&mut a_chars);
+                let y_num = take_number(&mut b_chars);
+                let ordering = x_num.len().cmp(&y_num.len()).then(x_num.cmp(&y_num));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(&y);
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}
+
+/// 书架上的分区，按声明顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    /// 待读队列，值为队列中的位置
+    Queue(usize),
+    /// 收藏
+    Favorite,
+    /// 其余小说，按分类排列
+    Category,
+}
+
+/// 把目录中的所有文件和子文件夹移到另一个目录
+///
+/// 移动前先检查重名，有重名时不移动任何文件。无法直接重命名时（如跨磁盘）先复制再删除。
+///
+/// # Returns
+///
+/// 移动的文件和子文件夹数。
+fn move_dir_contents(from: &Path, to: &Path) -> std::io::Result<usize> {
+    let entries: Vec<PathBuf> = std::fs::read_dir(from)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    if let Some(name) = entries
+        .iter()
+        .filter_map(|path| path.file_name())
+        .find(|name| to.join(name).exists())
+    {
+        return Err(std::io::Error::other(trf!(
+            "新目录中已有 {}",
+            "{} already exists in the new directory",
+            name.to_string_lossy()
+        )));
+    }
+    for source in &entries {
+        if let Some(name) = source.file_name() {
+            move_path(source, &to.join(name))?;
+        }
+    }
+    Ok(entries.len())
+}
+
+/// 移动文件或文件夹，无法直接重命名时递归复制后删除原路径
+fn move_path(source: &Path, target: &Path) -> std::io::Result<()> {
+    if std::fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+    if source.is_dir() {
+        std::fs::create_dir_all(target)?;
+        for entry in std::fs::read_dir(source)? {
+            let path = entry?.path();
+            if let Some(name) = path.file_name() {
+                move_path(&path, &target.join(name))?;
+            }
+        }
+        std::fs::remove_dir(source)
+    } else {
+        std::fs::copy(source, target)?;
+        std::fs::remove_file(source)
+    }
+}
+
+/// 书架默认的标题顺序：忽略大小写比较标题，再按原标题和路径区分
+pub(super) fn compare_titles(a: &Novel, b: &Novel) -> Ordering {
+    a.title
+        .to_lowercase()
+        .cmp(&b.title.to_lowercase())
+        .then_with(|| a.title.cmp(&b.title))
+        .then_with(|| a.path.cmp(&b.path))
+}
 
 impl App {
+    /// 按偏好设置中的排序方式排列书架
+    ///
+    /// 待读队列中的小说按队列顺序排在最前面，收藏的小说紧随其后（不分分类）；其余小说同一分类的排在一起（根目录下的小说在前），
+    /// 分类内按排序方式排列，相同排序键的小说按标题排列。同一系列的各卷按卷号排在一起，
+    /// 位置取排得最靠前的一卷。排序后保持选中同一本小说。
+    pub fn sort_novels(&mut self) {
+        self.detect_series();
+        let selected_path = self
+            .selected_novel_index
+            .and_then(|index| self.novels.get(index))
+            .map(|novel| novel.path.clone());
+
+        // 各排序方式的排序键越大越靠前
+        let keys: Vec<u64> = (0..self.novels.len())
+            .map(|index| match self.preferences.bookshelf_sort {
+                BookshelfSort::Title => 0,
+                BookshelfSort::RecentlyRead => self.bookshelf_last_read(index).unwrap_or(0),
+                BookshelfSort::Progress => {
+                    self.bookshelf_progress_percent(index).unwrap_or(0) as u64
+                }
+                BookshelfSort::FileSize => self.novels[index].file_size,
+            })
+            .collect();
+        // 待读队列（按队列顺序）在最前，其次是收藏，最后按分类排列
+        let sections: Vec<Section> = (0..self.novels.len())
+            .map(|index| match self.bookshelf_queue_position(index) {
+                Some(position) => Section::Queue(position),
+                None if self.bookshelf_is_favorite(index) => Section::Favorite,
+                None => Section::Category,
+            })
+            .collect();
+        let mut keyed: Vec<((Section, u64), Novel)> = sections
+            .into_iter()
+            .zip(keys)
+            .zip(std::mem::take(&mut self.novels))
+            .collect();
+        keyed.sort_by(|((section_a, key_a), a), ((section_b, key_b), b)| {
+            section_a
+                .cmp(section_b)
+                .then_with(|| {
+                    if *section_a == Section::Category {
+                        natural_cmp(&a.category, &b.category)
+                    } else {
+                        Ordering::Equal
+                    }
+                })
+                .then_with(|| key_b.cmp(key_a))
+                .then_with(|| compare_titles(a, b))
+        });
+        let novels: Vec<(Section, Novel)> = keyed
+            .into_iter()
+            .map(|((section, _), novel)| (section, novel))
+            .collect();
+
+        // 同一系列（同为收藏或同一分类）的各卷跟在排得最靠前的一卷后面，按卷号排列；
+        // 待读队列保持用户排好的顺序
+        let mut anchors: HashMap<(bool, String, String), usize> = HashMap::new();
+        let mut order: Vec<(usize, u32, usize)> = Vec::with_capacity(novels.len());
+        for (rank, (section, novel)) in novels.iter().enumerate() {
+            let favorite = *section == Section::Favorite;
+            match self.bookshelf.series.get(&novel.path).cloned() {
+                Some((name, volume)) if !matches!(section, Section::Queue(_)) => {
+                    let category = if favorite { "" } else { &novel.category };
+                    let anchor = *anchors
+                        .entry((favorite, category.to_string(), name))
+                        .or_insert(rank);
+                    order.push((anchor, volume, rank));
+                }
+                _ => order.push((rank, 0, rank)),
+            }
+        }
+        order.sort_unstable();
+        let mut novels: Vec<Option<Novel>> = novels.into_iter().map(|(_, n)| Some(n)).collect();
+        self.novels = order
+            .into_iter()
+            .filter_map(|(_, _, rank)| novels[rank].take())
+            .collect();
+
+        if let Some(path) = selected_path {
+            self.selected_novel_index = self.novels.iter().position(|n| n.path == path);
+        }
+        self.reveal_bookshelf_selection();
+    }
+
+    /// 书架上的小说是否符合当前的筛选条件
+    pub fn bookshelf_matches(&self, index: usize) -> bool {
+        let tag_matches = match &self.bookshelf.tag_filter {
+            Some(tag) => self.bookshelf_tags(index).contains(tag),
+            None => true,
+        };
+        let status_matches = self
+            .bookshelf
+            .status_filter
+            .is_none_or(|status| self.bookshelf_status(index) == status);
+        tag_matches && status_matches && self.bookshelf_title_matches(index)
+    }
+
+    /// 小说标题是否包含筛选关键词（不区分大小写，也可用拼音首字母或全拼匹配）
+    fn bookshelf_title_matches(&self, index: usize) -> bool {
+        let filter = self.bookshelf.filter.trim();
+        if filter.is_empty() {
+            return true;
+        }
+        let title = &self.novels[index].title;
+        title.to_lowercase().contains(&filter.to_lowercase())
+            || matcher::pinyin_contains(title, filter)
+    }
+
+    /// 书架上要显示的行：每个分类前有一行标题，折叠的分类只显示标题
+    ///
+    /// 只包含符合筛选条件的小说，没有这类小说的分类不显示。按标题筛选时展开所有分类。
+    /// 依赖 [`App::sort_novels`] 把同一分类的小说排在一起。
+    pub fn bookshelf_rows(&self) -> Vec<BookshelfRow> {
+        let mut rows = Vec::new();
+        let mut current_category: Option<&str> = None;
+        for index in 0..self.novels.len() {
+            if !self.bookshelf_matches(index) {
+                continue;
+            }
+            let category = self.bookshelf_category(index);
+            if category.is_empty() {
+                rows.push(BookshelfRow::Novel(index));
+                continue;
+            }
+            let collapsed = self.bookshelf.filter.trim().is_empty()
+                && self.preferences.collapsed_categories.contains(category);
+            if current_category != Some(category) {
+                current_category = Some(category);
+                let count = (index..self.novels.len())
+                    .take_while(|&i| self.bookshelf_category(i) == category)
+                    .filter(|&i| self.bookshelf_matches(i))
+                    .count();
+                rows.push(BookshelfRow::Category {
+                    name: category.to_string(),
+                    count,
+                    collapsed,
+                });
+            }
+            if !collapsed {
+                rows.push(BookshelfRow::Novel(index));
+            }
+        }
+        rows
+    }
+
+    /// 书架光标所在的行号
+    pub fn bookshelf_selected_row(&self, rows: &[BookshelfRow]) -> Option<usize> {
+        rows.iter().position(|row| match row {
+            BookshelfRow::Category { name, .. } => self.selected_category.as_ref() == Some(name),
+            BookshelfRow::Novel(index) => {
+                self.selected_category.is_none() && self.selected_novel_index == Some(*index)
+            }
+        })
+    }
+
+    /// 把书架光标移到指定的行，`None` 表示不选中任何行
+    pub fn select_bookshelf_row(&mut self, row: Option<&BookshelfRow>) {
+        match row {
+            Some(BookshelfRow::Category { name, .. }) => {
+                self.selected_category = Some(name.clone());
+                self.selected_novel_index = None;
+            }
+            Some(BookshelfRow::Novel(index)) => {
+                self.selected_category = None;
+                self.selected_novel_index = Some(*index);
+            }
+            None => {
+                self.selected_category = None;
+                self.selected_novel_index = None;
+            }
+        }
+    }
+
+    /// 筛选条件变化后，光标所在的行不再显示时移到第一行
+    pub fn refresh_bookshelf_selection(&mut self) {
+        let rows = self.bookshelf_rows();
+        if self.bookshelf_selected_row(&rows).is_none() {
+            // 优先落在第一本小说上，筛选后可以直接回车打开
+            let first = rows
+                .iter()
+                .find(|row| matches!(row, BookshelfRow::Novel(_)))
+                .or(rows.first());
+            self.select_bookshelf_row(first);
+        }
+    }
+
+    /// 书架光标所在的分类：光标在分类标题上，或在某个分类中的小说上
+    pub fn bookshelf_cursor_category(&self) -> Option<String> {
+        self.selected_category.clone().or_else(|| {
+            self.selected_novel_index
+                .filter(|&index| index < self.novels.len())
+                .map(|index| self.bookshelf_category(index).to_string())
+                .filter(|category| !category.is_empty())
+        })
+    }
+
+    /// 折叠或展开书架上的分类，并保存到偏好设置
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - 分类名（子文件夹路径）
+    /// * `collapsed` - 是否折叠
+    pub fn set_bookshelf_category_collapsed(&mut self, name: &str, collapsed: bool) {
+        let changed = if collapsed {
+            self.preferences
+                .collapsed_categories
+                .insert(name.to_string())
+        } else {
+            self.preferences.collapsed_categories.remove(name)
+        };
+        if changed {
+            self.reveal_bookshelf_selection();
+            self.save_preferences();
+        }
+    }
+
+    /// 选中的小说所在分类被折叠时，把光标移到该分类的标题上
+    fn reveal_bookshelf_selection(&mut self) {
+        if let Some(index) = self
+            .selected_novel_index
+            .filter(|&index| index < self.novels.len())
+        {
+            let category = self.bookshelf_category(index);
+            if self.preferences.collapsed_categories.contains(category) {
+                self.selected_category = Some(category.to_string());
+                self.selected_novel_index = None;
+            }
+        }
+    }
+
+    /// 书架上小说显示在哪个分类下：待读队列中的小说显示在最上方的队列分类中，
+    /// 收藏的小说固定在队列之后，不属于任何分类
+    pub fn bookshelf_category(&self, index: usize) -> &str {
+        if self.bookshelf_queue_position(index).is_some() {
+            QUEUE_CATEGORY
+        } else if self.bookshelf_is_favorite(index) {
+            ""
+        } else {
+            &self.novels[index].category
+        }
+    }
+
+    /// 书架上的小说是否已收藏
+    pub fn bookshelf_is_favorite(&self, index: usize) -> bool {
+        self.bookshelf_novel_info(index)
+            .is_some_and(|info| info.favorite)
+    }
+
+    /// 书架上小说的阅读状态
+    pub fn bookshelf_status(&self, index: usize) -> ReadingStatus {
+        self.bookshelf_novel_info(index)
+            .map_or(ReadingStatus::Unread, NovelInfo::reading_status)
+    }
+
+    /// 把书架上小说的阅读状态切换到下一种并保存
+    ///
+    /// # Returns
+    ///
+    /// 切换后的状态；索引无效时返回 `None`。
+    pub fn cycle_bookshelf_status(&mut self, index: usize) -> Option<ReadingStatus> {
+        let path = self.novels.get(index)?.path.clone();
+        let status = self.bookshelf_status(index).next();
+        self.library.set_status(&path, status);
+        if let Err(e) = self.library.save() {
+            self.set_error(format!("Failed to save: {}", e));
+        }
+        // 标记为读完的小说会移出待读队列
+        self.sort_novels();
+        self.refresh_bookshelf_selection();
+        Some(status)
+    }
+
+    /// 切换书架的阅读状态筛选：全部 → 未读 → 在读 → 读完 → 全部
+    pub fn cycle_bookshelf_status_filter(&mut self) {
+        self.bookshelf.status_filter = match self.bookshelf.status_filter {
+            None => Some(ReadingStatus::Unread),
+            Some(ReadingStatus::Finished) => None,
+            Some(status) => Some(status.next()),
+        };
+        self.refresh_bookshelf_selection();
+    }
+
+    /// 读到结尾时把当前小说标记为读完
+    pub fn mark_current_novel_finished(&mut self) {
+        let Some(novel) = &self.current_novel else {
+            return;
+        };
+        let already_finished = self
+            .library
+            .novels
+            .iter()
+            .find(|info| Library::same_novel_path(&info.path, &novel.path))
+            .is_some_and(|info| info.status == Some(ReadingStatus::Finished));
+        if already_finished {
+            return;
+        }
+        self.library
+            .set_status(&novel.path, ReadingStatus::Finished);
+        if let Err(e) = self.library.save() {
+            self.set_error(format!("Failed to save: {}", e));
+        }
+    }
+
+    /// 切换书架上小说的收藏状态，保存后重新排列书架
+    ///
+    /// # Returns
+    ///
+    /// 切换后是否为收藏；索引无效时返回 `None`。
+    pub fn toggle_bookshelf_favorite(&mut self, index: usize) -> Option<bool> {
+        let path = self.novels.get(index)?.path.clone();
+        let favorite = self.library.toggle_favorite(&path);
+        if let Err(e) = self.library.save() {
+            self.set_error(format!("Failed to save: {}", e));
+        }
+        self.sort_novels();
+        Some(favorite)
+    }
+
+    /// 重新扫描所有小说目录并刷新书架，保持选中同一本小说
+    pub fn reload_novels(&mut self) {
+        let novels = match Self::load_all_novels(&self.preferences.library_dirs) {
+            Ok(novels) => novels,
+            Err(e) => {
+                self.set_error(trf!(
+                    "扫描小说目录失败：{}",
+                    "Failed to scan the novel directories: {}",
+                    e
+                ));
+                return;
+            }
+        };
+        let selected_path = self
+            .selected_novel_index
+            .and_then(|index| self.novels.get(index))
+            .map(|novel| novel.path.clone());
+
+        self.novels = novels;
+        self.selected_category = None;
+        self.selected_novel_index = selected_path
+            .and_then(|path| self.novels.iter().position(|n| n.path == path))
+            .or_else(|| Self::first_index_if_any(self.novels.len()));
+        self.sort_novels();
+        self.detect_duplicates();
+    }
+
+    /// 把外部的小说文件导入默认小说目录，登记到书库并刷新书架
+    ///
+    /// 导入后书架选中该小说。移动文件时若无法直接重命名（如跨磁盘），先复制再删除原文件。
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - 用户输入的文件路径，支持以 `~` 开头，可带拖放时加上的引号
+    /// * `move_file` - 是否移动文件（否则复制）
+    ///
+    /// # Returns
+    ///
+    /// 导入后的文件路径；文件不存在、格式不支持、已有同名文件或复制失败时返回错误。
+    pub fn import_novel(&mut self, input: &str, move_file: bool) -> std::io::Result<PathBuf> {
+        let input = input.trim().trim_matches(['"', '\'']);
+        if input.is_empty() {
+            return Err(std::io::Error::other(tr(
+                "路径不能为空",
+                "The path must not be empty",
+            )));
+        }
+        let source = expand_home(input);
+        if !source.is_file() {
+            return Err(std::io::Error::other(trf!(
+                "{} 不是有效的文件",
+                "{} is not a valid file",
+                source.display()
+            )));
+        }
+        let supported = source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| config().is_supported_extension(ext));
+        let Some(file_name) = source.file_name().filter(|_| supported) else {
+            return Err(std::io::Error::other(trf!(
+                "不支持的文件格式，仅支持 {}",
+                "Unsupported file format; supported formats: {}",
+                config().supported_extensions.join(" / ")
+            )));
+        };
+        let target = Self::get_novels_dir().join(file_name);
+        if target.exists() {
+            return Err(std::io::Error::other(trf!(
+                "小说目录中已有 {}",
+                "{} already exists in the novel directory",
+                file_name.to_string_lossy()
+            )));
+        }
+
+        if move_file {
+            if std::fs::rename(&source, &target).is_err() {
+                std::fs::copy(&source, &target)?;
+                std::fs::remove_file(&source)?;
+            }
+        } else {
+            std::fs::copy(&source, &target)?;
+        }
+
+        self.library.register_novel(&target);
+        self.library.save()?;
+        self.reload_novels();
+        self.selected_novel_index = self.novels.iter().position(|n| n.path == target);
+        self.selected_category = None;
+        Ok(target)
+    }
+
+    /// 重命名书架上的小说文件，保留扩展名，并把阅读进度、书签和标签迁移到新文件名
+    ///
+    /// 同步清单按本地文件生成，下次上传时会以新文件名上传并删除远程的旧文件。
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 小说在 `novels` 列表中的索引
+    /// * `new_title` - 新书名（不含扩展名）
+    ///
+    /// # Returns
+    ///
+    /// 改名后的文件路径；书名为空、包含路径分隔符、已有同名文件或改名失败时返回错误。
+    pub fn rename_novel(&mut self, index: usize, new_title: &str) -> std::io::Result<PathBuf> {
+        let Some(old_path) = self.novels.get(index).map(|novel| novel.path.clone()) else {
+            return Err(std::io::Error::other(tr(
+                "没有选中小说",
+                "No novel selected",
+            )));
+        };
+        let new_title = new_title.trim();
+        if new_title.is_empty() {
+            return Err(std::io::Error::other(tr(
+                "书名不能为空",
+                "The title must not be empty",
+            )));
+        }
+        if new_title.contains(['/', '\\']) {
+            return Err(std::io::Error::other(tr(
+                "书名不能包含路径分隔符",
+                "The title must not contain path separators",
+            )));
+        }
+        let file_name = match old_path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) => format!("{}.{}", new_title, ext),
+            None => new_title.to_string(),
+        };
+        let new_path = old_path.with_file_name(file_name);
+        if new_path == old_path {
+            return Ok(new_path);
+        }
+        if new_path.exists() {
+            return Err(std::io::Error::other(trf!(
+                "已有同名文件 {}",
+                "A file named {} already exists",
+                new_title
+            )));
+        }
+
+        std::fs::rename(&old_path, &new_path)?;
+        chapter_cache::rename(&old_path, &new_path);
+        self.library.rename_novel(&old_path, &new_path);
+        if let Some(novel) = &mut self.current_novel
+            && novel.path == old_path
+        {
+            novel.path = new_path.clone();
+            novel.title = new_title.to_string();
+        }
+        self.library.save()?;
+        self.reload_novels();
+        self.selected_novel_index = self.novels.iter().position(|n| n.path == new_path);
+        self.selected_category = None;
+        Ok(new_path)
+    }
+
+    /// 添加额外的小说目录，保存到偏好设置并重新扫描书架
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - 用户输入的目录，支持以 `~` 开头表示用户主目录
+    ///
+    /// # Errors
+    ///
+    /// 目录不存在、不是目录或已在列表中时返回错误说明。
+    pub fn add_library_dir(&mut self, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(tr("目录不能为空", "The directory must not be empty").to_string());
+        }
+        let dir = expand_home(input);
+        if !dir.is_dir() {
+            return Err(trf!(
+                "{} 不是有效的目录",
+                "{} is not a valid directory",
+                dir.display()
+            ));
+        }
+        if dir == Self::get_novels_dir() || self.preferences.library_dirs.contains(&dir) {
+            return Err(trf!(
+                "{} 已在小说目录中",
+                "{} is already a novel directory",
+                dir.display()
+            ));
+        }
+
+        self.preferences.library_dirs.push(dir);
+        self.save_preferences();
+        self.reload_novels();
+        self.watch_novel_dirs();
+        Ok(())
+    }
+
+    /// 更换默认小说目录并写入配置文件，可选把原目录中的文件移到新目录
+    ///
+    /// 阅读记录随目录迁移：不移动文件时，原目录中的小说不再出现在书架上，
+    /// 新目录中相同位置的小说沿用原来的阅读进度和书签。
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - 新目录，支持以 `~` 开头，不存在时自动创建
+    /// * `move_files` - 是否把原目录中的文件和子文件夹（分类）移到新目录
+    ///
+    /// # Returns
+    ///
+    /// 移动的文件和子文件夹数。
+    ///
+    /// # Errors
+    ///
+    /// 目录无效、与现有目录重复、新目录中已有同名文件或移动失败时返回错误。
+    pub fn set_novels_dir(&mut self, input: &str, move_files: bool) -> std::io::Result<usize> {
+        let input = input.trim().trim_matches(['"', '\'']);
+        if input.is_empty() {
+            return Err(std::io::Error::other(tr(
+                "目录不能为空",
+                "The directory must not be empty",
+            )));
+        }
+        let new_dir = expand_home(input);
+        if !new_dir.is_absolute() {
+            return Err(std::io::Error::other(tr(
+                "请输入绝对路径或以 ~ 开头的路径",
+                "Enter an absolute path or one starting with ~",
+            )));
+        }
+        let old_dir = Self::get_novels_dir();
+        if new_dir == old_dir {
+            return Err(std::io::Error::other(tr(
+                "已是当前的小说目录",
+                "This is already the novel directory",
+            )));
+        }
+        if self.preferences.library_dirs.contains(&new_dir) {
+            return Err(std::io::Error::other(trf!(
+                "{} 已是额外的小说目录，请先移除",
+                "{} is already an extra novel directory; remove it first",
+                new_dir.display()
+            )));
+        }
+        if move_files && new_dir.starts_with(&old_dir) {
+            return Err(std::io::Error::other(tr(
+                "新目录不能位于原目录中",
+                "The new directory must not be inside the old one",
+            )));
+        }
+        if new_dir.exists() && !new_dir.is_dir() {
+            return Err(std::io::Error::other(trf!(
+                "{} 不是目录",
+                "{} is not a directory",
+                new_dir.display()
+            )));
+        }
+        std::fs::create_dir_all(&new_dir)?;
+
+        let moved = if move_files {
+            move_dir_contents(&old_dir, &new_dir)?
+        } else {
+            0
+        };
+
+        let mut updated = (*config()).clone();
+        updated.novels_dir = new_dir.to_string_lossy().into_owned();
+        config::save(updated)?;
+
+        self.library.relocate_novels(&old_dir, &new_dir);
+        if let Some(novel) = &mut self.current_novel
+            && let Ok(rel) = novel.path.strip_prefix(&old_dir)
+        {
+            novel.path = new_dir.join(rel);
+        }
+        self.library.save()?;
+        self.reload_novels();
+        self.watch_novel_dirs();
+        self.detect_orphaned_novels();
+        Ok(moved)
+    }
+
+    /// 移除额外的小说目录（不删除目录中的文件），保存到偏好设置并重新扫描书架
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 目录在 `preferences.library_dirs` 中的索引
+    pub fn remove_library_dir(&mut self, index: usize) {
+        if index >= self.preferences.library_dirs.len() {
+            return;
+        }
+        self.preferences.library_dirs.remove(index);
+        self.save_preferences();
+        self.reload_novels();
+        self.watch_novel_dirs();
+    }
+
+    /// 切换书架排序方式并保存到偏好设置
+    pub fn cycle_bookshelf_sort(&mut self) {
+        self.preferences.bookshelf_sort = self.preferences.bookshelf_sort.next();
+        self.sort_novels();
+        if let Err(e) = self.preferences.save() {
+            self.set_error(format!("Failed to save preferences: {}", e));
+        }
+    }
+
     /// 检测孤立的小说记录
     ///
     /// 扫描 library 中所有小说记录，找出 JSON 中存在但文件已被删除的记录。
@@ -18,10 +759,119 @@ impl App {
         self.settings.selected_orphaned_index = None;
     }
 
-    /// 删除指定索引的小说
+    /// 删除指定索引的孤立记录并保存，然后重新检测孤立记录
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 记录在 `settings.orphaned_novels` 中的索引
+    pub fn delete_orphaned_novel(&mut self, index: usize) {
+        let Some(orphaned_novel) = self.settings.orphaned_novels.get(index) else {
+            return;
+        };
+        let path = orphaned_novel.path.clone();
+        self.library.novels.retain(|n| n.path != path);
+        chapter_cache::remove(&path);
+        if let Err(e) = self.library.save() {
+            self.set_error(format!("Failed to save: {}", e));
+        }
+        self.detect_orphaned_novels();
+
+        if !self.settings.orphaned_novels.is_empty() {
+            let new_index = index.min(self.settings.orphaned_novels.len() - 1);
+            self.settings.selected_orphaned_index = Some(new_index);
+        }
+    }
+
+    /// 猜测孤立记录对应的文件被移到了哪里：书架上文件名相同且没有阅读记录的小说
+    ///
+    /// # Returns
+    ///
+    /// 找到时返回该小说的路径，否则返回记录原来的路径，供用户修改。
+    pub fn relocate_candidate(&self, index: usize) -> Option<PathBuf> {
+        let orphaned = self.settings.orphaned_novels.get(index)?;
+        let candidate = orphaned.path.file_name().and_then(|file_name| {
+            self.novels
+                .iter()
+                .enumerate()
+                .find(|(i, novel)| {
+                    novel.path.file_name() == Some(file_name)
+                        && self
+                            .bookshelf_novel_info(*i)
+                            .is_none_or(|info| info.last_read == 0)
+                })
+                .map(|(_, novel)| novel.path.clone())
+        });
+        Some(candidate.unwrap_or_else(|| orphaned.path.clone()))
+    }
+
+    /// 把孤立记录指向新的文件路径，阅读进度、书签和标签都保留，然后刷新书架并重新检测孤立记录
+    ///
+    /// 新文件已有书库记录时（如移动后打开过）两条记录合并，与合并重复小说的规则相同。
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 记录在 `settings.orphaned_novels` 中的索引
+    /// * `input` - 用户输入的新文件路径，支持以 `~` 开头，可带引号
+    ///
+    /// # Returns
+    ///
+    /// 新文件路径；文件不存在时返回错误，记录保持不变。
+    pub fn relocate_orphaned_novel(
+        &mut self,
+        index: usize,
+        input: &str,
+    ) -> std::io::Result<PathBuf> {
+        let Some(old_path) = self
+            .settings
+            .orphaned_novels
+            .get(index)
+            .map(|info| info.path.clone())
+        else {
+            return Err(std::io::Error::other(tr(
+                "没有选中记录",
+                "No record selected",
+            )));
+        };
+        let input = input.trim().trim_matches(['"', '\'']);
+        if input.is_empty() {
+            return Err(std::io::Error::other(tr(
+                "路径不能为空",
+                "The path must not be empty",
+            )));
+        }
+        let new_path = expand_home(input);
+        if !new_path.is_file() {
+            return Err(std::io::Error::other(trf!(
+                "{} 不是有效的文件",
+                "{} is not a valid file",
+                new_path.display()
+            )));
+        }
+        let new_path = std::path::absolute(&new_path)?;
+
+        let has_record = self
+            .library
+            .novels
+            .iter()
+            .any(|info| Library::same_novel_path(&info.path, &new_path));
+        chapter_cache::remove(&old_path);
+        if has_record {
+            self.library.merge_novels(&new_path, &old_path);
+        } else {
+            self.library.rename_novel(&old_path, &new_path);
+        }
+        self.library.save()?;
+        self.reload_novels();
+        self.detect_orphaned_novels();
+        let count = self.settings.orphaned_novels.len();
+        self.settings.selected_orphaned_index = (count > 0).then(|| index.min(count - 1));
+        Ok(new_path)
+    }
+
+    /// 删除指定索引的小说（移到回收站）
     ///
     /// 执行以下操作：
-    /// 1. 删除物理文件
+    /// 1. 把文件连同书库记录移到回收站，可在设置 → 回收站中恢复
     /// 2. 从 novels 列表中移除
     /// 3. 从 library 中移除进度记录
     /// 4. 保存 library 更改
@@ -38,7 +888,13 @@ impl App {
             let novel = &self.novels[index];
 
             if novel.path.exists() {
-                std::fs::remove_file(&novel.path)?;
+                let info = self
+                    .library
+                    .novels
+                    .iter()
+                    .find(|n| n.path == novel.path)
+                    .cloned();
+                Trash::load().move_in(&novel.path, info)?;
             }
 
             self.library.novels.retain(|n| n.path != novel.path);
@@ -57,16 +913,488 @@ impl App {
         Ok(())
     }
 
+    /// 从回收站恢复小说：文件放回原处，阅读进度、书签等记录一并还原，然后刷新书架
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 条目在 `settings.trash` 中的索引
+    ///
+    /// # Returns
+    ///
+    /// 恢复后的文件路径；原位置已有同名文件或移动失败时返回错误。
+    pub fn restore_trashed_novel(&mut self, index: usize) -> std::io::Result<PathBuf> {
+        let entry = self.settings.trash.restore(index)?;
+        if let Some(mut info) = entry.info
+            && !self
+                .library
+                .novels
+                .iter()
+                .any(|n| Library::same_novel_path(&n.path, &entry.original_path))
+        {
+            info.path = entry.original_path.clone();
+            self.library.novels.push(info);
+            self.library.save()?;
+        }
+        self.reload_novels();
+        self.clamp_trash_selection();
+        Ok(entry.original_path)
+    }
+
+    /// 彻底删除回收站中的小说
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 条目在 `settings.trash` 中的索引
+    pub fn purge_trashed_novel(&mut self, index: usize) -> std::io::Result<()> {
+        self.settings.trash.remove(index)?;
+        self.clamp_trash_selection();
+        Ok(())
+    }
+
+    /// 回收站条目减少后保持选中位置
+    fn clamp_trash_selection(&mut self) {
+        let count = self.settings.trash.entries.len();
+        self.settings.selected_trash_index = self
+            .settings
+            .selected_trash_index
+            .map(|index| index.min(count.saturating_sub(1)))
+            .filter(|_| count > 0);
+    }
+
+    /// 用进度备份替换当前的阅读进度、书签等记录，然后刷新书架
+    ///
+    /// 替换前的记录另存为一个新备份；打开中的小说同步换成备份中的进度。
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 备份在 `settings.backups` 中的索引
+    pub fn restore_progress_backup(&mut self, index: usize) -> std::io::Result<()> {
+        let Some(backup) = self.settings.backups.get(index) else {
+            return Ok(());
+        };
+        self.library = Library::restore_backup(&backup.path)?;
+        if let Some(novel) = &mut self.current_novel {
+            novel.progress = self.library.get_novel_progress(&novel.path);
+        }
+        self.reload_novels();
+        self.settings.backups = Library::list_backups();
+        self.settings.selected_backup_index = Self::first_index_if_any(self.settings.backups.len());
+        Ok(())
+    }
+
+    /// 清除回收站中超过保留天数的小说
+    pub fn purge_expired_trash(&mut self) {
+        if let Err(e) = Trash::load().purge_expired(config().trash_retention_days) {
+            self.set_error(format!("Failed to purge trash: {}", e));
+        }
+    }
+
     /// 保存当前小说的阅读进度
     ///
     /// 更新并保存当前小说的进度。如果保存失败，会设置错误消息。
     pub fn save_current_progress(&mut self) {
-        if let Some(novel) = &self.current_novel {
+        if let Some(novel) = &mut self.current_novel {
+            novel.record_furthest_line();
+            novel.sync_char_offset();
             self.library
                 .update_novel_progress(&novel.path, novel.progress.clone());
+            self.library
+                .set_total_lines(&novel.path, novel.line_count());
             if let Err(e) = self.library.save() {
                 self.set_error(format!("Failed to save progress: {}", e));
             }
         }
     }
+
+    /// 当前小说的阅读进度是否有尚未写入书库的变化
+    pub fn has_unsaved_progress(&self) -> bool {
+        self.current_novel
+            .as_ref()
+            .is_some_and(|novel| self.library.get_novel_progress(&novel.path) != novel.progress)
+    }
+
+    /// 定期自动保存阅读进度（主循环中调用）
+    ///
+    /// 每隔配置文件中的 `autosave_secs` 检查一次，进度有变化时才写入文件，
+    /// 避免程序崩溃或终端被关闭时丢失这段时间的阅读位置。
+    pub fn autosave_progress(&mut self) {
+        let interval = Duration::from_secs(config().autosave_secs);
+        if interval.is_zero() {
+            return;
+        }
+        let Some(checked_at) = self.reader.autosave_checked_at else {
+            self.reader.autosave_checked_at = Some(Instant::now());
+            return;
+        };
+        if checked_at.elapsed() < interval {
+            return;
+        }
+        self.reader.autosave_checked_at = Some(Instant::now());
+        if self.has_unsaved_progress() {
+            self.save_current_progress();
+        }
+    }
+
+    /// 打开书架中指定索引的小说并进入阅读界面
+    ///
+    /// 如果已有打开的小说，会先保存其进度。加载失败时设置错误消息并停留在原界面。
+    /// 手动编辑过的目录直接使用；否则章节目录在后台线程中按偏好设置中的章节标题正则
+    /// 与内置规则合并解析（或从缓存读取），完成前目录为空，见 [`App::poll_chapter_parse`]。
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 小说在 novels 列表中的索引
+    pub fn open_novel(&mut self, index: usize) {
+        let Some(mut novel) = self.novels.get(index).cloned() else {
+            return;
+        };
+
+        if novel.is_empty()
+            && let Err(e) = novel.load_content()
+        {
+            self.set_error(format!("Failed to load novel: {}", e));
+            return;
+        }
+
+        self.record_exit_position();
+        self.save_current_progress();
+        novel.progress = self.library.get_novel_progress(&novel.path);
+        novel.restore_char_offset();
+        self.chapter_parse_rx = None;
+        if let Some(chapters) = &novel.progress.custom_chapters {
+            novel.chapters = chapters.clone();
+        } else {
+            self.start_chapter_parse(&novel);
+        }
+
+        self.selected_novel_index = Some(index);
+        self.selected_category = None;
+        self.current_novel = Some(novel);
+        self.reader.session_start = Some(Instant::now());
+        self.reader.next_novel_offered = false;
+        self.state = AppState::Reading;
+    }
+
+    /// 在后台线程中加载小说的章节目录
+    fn start_chapter_parse(&mut self, novel: &Novel) {
+        let settings = self.library.chapter_settings(&novel.path);
+        let patterns = match self.preferences.chapter_regexes(&settings.patterns) {
+            Ok(patterns) => patterns,
+            Err(e) => {
+                self.set_error(e);
+                Vec::new()
+            }
+        };
+        let auto_detect = settings.auto_detect;
+        let path = novel.path.clone();
+        let lines = novel.shared_lines();
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.chapter_parse_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let chapters = chapter_cache::load_or_parse(&path, &lines, &patterns, auto_detect);
+            tx.send((path, chapters)).ok();
+        });
+    }
+
+    /// 轮询后台章节解析（主循环中调用）
+    ///
+    /// 解析完成且当前小说未切换时填入章节目录；正在查看目录且没有选中项时选中当前章节。
+    pub fn poll_chapter_parse(&mut self) {
+        let Some(rx) = &self.chapter_parse_rx else {
+            return;
+        };
+        let (path, chapters) = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.chapter_parse_rx = None;
+                return;
+            }
+        };
+        self.chapter_parse_rx = None;
+
+        let Some(novel) = &mut self.current_novel else {
+            return;
+        };
+        if novel.path != path {
+            return;
+        }
+        novel.chapters = chapters.clone();
+        if let Some(shelf_novel) = self.novels.iter_mut().find(|n| n.path == path) {
+            shelf_novel.chapters = chapters;
+        }
+        if self.state == AppState::ChapterList && self.selected_chapter_index.is_none() {
+            self.selected_chapter_index = self.find_current_chapter_index();
+        }
+    }
+
+    /// 章节目录是否正在后台解析
+    pub fn is_parsing_chapters(&self) -> bool {
+        self.chapter_parse_rx.is_some()
+    }
+
+    /// 把旧版本保存在偏好设置中、按标题区分的章节设置迁移到各书的记录中
+    ///
+    /// 只迁移书架上能按标题找到的小说，迁移后保存进度和偏好设置。
+    pub fn migrate_legacy_chapter_settings(&mut self) {
+        let patterns = std::mem::take(&mut self.preferences.legacy_novel_chapter_patterns);
+        let disabled = std::mem::take(&mut self.preferences.legacy_auto_chapters_disabled);
+        if patterns.is_empty() && disabled.is_empty() {
+            return;
+        }
+
+        for novel in &self.novels {
+            let novel_patterns = patterns.get(&novel.title);
+            let novel_disabled = disabled.contains(&novel.title);
+            if novel_patterns.is_none() && !novel_disabled {
+                continue;
+            }
+            let settings = self.library.chapter_settings_mut(&novel.path);
+            settings
+                .patterns
+                .extend(novel_patterns.into_iter().flatten().cloned());
+            if novel_disabled {
+                settings.auto_detect = false;
+            }
+        }
+
+        if let Err(e) = self.library.save() {
+            eprintln!("Failed to save migrated chapter settings: {}", e);
+        }
+        if let Err(e) = self.preferences.save() {
+            eprintln!("Failed to save preferences: {}", e);
+        }
+    }
+
+    /// 书架上小说的阅读位置所在章节
+    ///
+    /// 根据保存的阅读进度和章节目录（手动编辑过的目录优先，否则为缓存的目录）计算。
+    ///
+    /// # Returns
+    ///
+    /// 如 “第3章”；没有阅读记录或没有章节目录时返回 `None`。
+    pub fn bookshelf_chapter_label(&self, index: usize) -> Option<String> {
+        let info = self.bookshelf_novel_info(index)?;
+        let chapters = self.bookshelf_chapters(index);
+        if chapters.is_empty() {
+            return None;
+        }
+        let chapter = Self::find_chapter_index(chapters, info.progress.scroll_offset);
+        Some(trf!("第{}章", "Chapter {}", chapter + 1))
+    }
+
+    /// 书架上小说的阅读进度百分比（0-100）
+    ///
+    /// 根据保存的阅读位置和最近一次打开时记录的总行数计算；
+    /// 没有阅读记录或从未打开过时返回 `None`。
+    pub fn bookshelf_progress_percent(&self, index: usize) -> Option<usize> {
+        let info = self.bookshelf_novel_info(index)?;
+        if info.total_lines == 0 {
+            return None;
+        }
+        let read = (info.progress.scroll_offset + 1).min(info.total_lines);
+        Some(read * 100 / info.total_lines)
+    }
+
+    /// 书架上小说最近一次阅读的时间（Unix 秒），没有阅读记录时返回 `None`
+    pub fn bookshelf_last_read(&self, index: usize) -> Option<u64> {
+        self.bookshelf_novel_info(index)
+            .map(|info| info.last_read)
+            .filter(|&last_read| last_read > 0)
+    }
+
+    /// 书架上小说的书签数
+    pub fn bookshelf_bookmark_count(&self, index: usize) -> usize {
+        self.bookshelf_novel_info(index)
+            .map_or(0, |info| info.progress.bookmarks.len())
+    }
+
+    /// 打开书架上小说的详情页，读取文件的编码和行数、字数
+    pub fn open_book_detail(&mut self, index: usize) {
+        let Some(novel) = self.novels.get(index) else {
+            return;
+        };
+        self.bookshelf.detail_stats = FileStats::read(&novel.path).ok();
+        self.bookshelf.detail_tag_index = (!self.bookshelf_tags(index).is_empty()).then_some(0);
+        self.bookshelf.tag_input = None;
+        self.bookshelf.rename_input = None;
+        self.bookshelf.note_input = None;
+        self.selected_novel_index = Some(index);
+        self.state = AppState::BookDetail;
+    }
+
+    /// 书架上小说的章节目录：手动编辑过的目录优先，否则为缓存的目录（可能为空）
+    pub fn bookshelf_chapters(&self, index: usize) -> &[Chapter] {
+        let Some(novel) = self.novels.get(index) else {
+            return &[];
+        };
+        self.bookshelf_novel_info(index)
+            .and_then(|info| info.progress.custom_chapters.as_deref())
+            .unwrap_or(&novel.chapters)
+    }
+
+    /// 书架上小说在 Library 中的记录
+    pub(super) fn bookshelf_novel_info(&self, index: usize) -> Option<&NovelInfo> {
+        let novel = self.novels.get(index)?;
+        self.library
+            .novels
+            .iter()
+            .find(|info| Library::same_novel_path(&info.path, &novel.path))
+    }
+
+    /// 由标题识别书架上各小说所属的系列，保存在 `bookshelf.series`
+    ///
+    /// 标题没有卷号但与某个系列同名的小说（如 “三体2” 旁边的 “三体”）视为第一卷。
+    fn detect_series(&mut self) {
+        let mut detected: HashMap<PathBuf, (String, u32)> = self
+            .novels
+            .iter()
+            .filter_map(|novel| Some((novel.path.clone(), series::parse_volume(&novel.title)?)))
+            .collect();
+        let names: std::collections::HashSet<String> =
+            detected.values().map(|(name, _)| name.clone()).collect();
+        for novel in &self.novels {
+            let title = novel.title.trim().to_lowercase();
+            if !detected.contains_key(&novel.path) && names.contains(&title) {
+                detected.insert(novel.path.clone(), (title, 1));
+            }
+        }
+        self.bookshelf.series = detected;
+    }
+
+    /// 书架上的小说所属系列的名称（小写）和卷号
+    pub fn bookshelf_series(&self, index: usize) -> Option<&(String, u32)> {
+        self.bookshelf.series.get(&self.novels.get(index)?.path)
+    }
+
+    /// 查找当前小说的下一本
+    ///
+    /// 当前小说属于书架上的某个系列时取同一分类中卷号更大的下一卷，系列已读到最后一卷时没有下一本；
+    /// 否则取按标题自然顺序排在当前小说之后的一本。
+    ///
+    /// # Returns
+    ///
+    /// 下一本小说在 novels 列表中的索引；没有打开的小说或已是最后一本时返回 `None`。
+    pub fn next_novel_index(&self) -> Option<usize> {
+        let current = self.current_novel.as_ref()?;
+        if let Some((name, volume)) = self.bookshelf.series.get(&current.path) {
+            let volumes: Vec<(usize, u32)> = self
+                .novels
+                .iter()
+                .enumerate()
+                .filter(|(_, novel)| {
+                    novel.path != current.path && novel.category == current.category
+                })
+                .filter_map(|(index, novel)| {
+                    let (other_name, other_volume) = self.bookshelf.series.get(&novel.path)?;
+                    (other_name == name).then_some((index, *other_volume))
+                })
+                .collect();
+            if !volumes.is_empty() {
+                return volumes
+                    .into_iter()
+                    .filter(|&(_, other_volume)| other_volume > *volume)
+                    .min_by_key(|&(_, other_volume)| other_volume)
+                    .map(|(index, _)| index);
+            }
+        }
+        self.novels
+            .iter()
+            .enumerate()
+            .filter(|(_, novel)| {
+                novel.path != current.path
+                    && natural_cmp(&novel.title, &current.title) == Ordering::Greater
+            })
+            .min_by(|(_, a), (_, b)| natural_cmp(&a.title, &b.title))
+            .map(|(index, _)| index)
+    }
+
+    /// 读到结尾时询问是否打开下一本
+    ///
+    /// # Returns
+    ///
+    /// 如果存在下一本并已弹出确认对话框返回 `true`。
+    pub fn offer_next_novel(&mut self) -> bool {
+        let Some(index) = self.next_novel_index() else {
+            return false;
+        };
+        let current = self
+            .current_novel
+            .as_ref()
+            .map(|novel| novel.title.as_str())
+            .unwrap_or_default();
+        let message = trf!(
+            "已读完《{}》，打开下一本《{}》？",
+            "Finished {}. Open the next one, {}?",
+            current,
+            self.novels[index].title
+        );
+        let path = self.novels[index].path.clone();
+        self.request_confirm(message, ConfirmAction::OpenNovel(path));
+        true
+    }
+
+    /// 继续阅读最近读过的小说
+    ///
+    /// 根据 `last_read` 找到最近阅读的小说并在保存的位置打开。
+    /// 没有阅读记录或该小说已不在书架上时设置错误消息。
+    pub fn continue_reading(&mut self) {
+        let Some(recent) = self.library.most_recent_novel() else {
+            self.set_error(tr("还没有阅读记录", "Nothing has been read yet"));
+            return;
+        };
+        match self
+            .novels
+            .iter()
+            .position(|n| Library::same_novel_path(&n.path, &recent.path))
+        {
+            Some(index) => self.open_novel(index),
+            None => {
+                let message = trf!(
+                    "最近阅读的《{}》已不在书架上",
+                    "The last read novel {} is no longer on the bookshelf",
+                    recent.title
+                );
+                self.set_error(message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_natural_cmp_orders_numbers_by_value() {
+        assert_eq!(natural_cmp("Book 2", "Book 10"), Ordering::Less);
+        assert_eq!(natural_cmp("第10部", "第9部"), Ordering::Greater);
+        assert_eq!(natural_cmp("Book 02", "Book 2"), Ordering::Equal);
+        assert_eq!(natural_cmp("Book 1", "Book 1 番外"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_move_dir_contents_moves_files_and_categories() {
+        let from = tempdir().unwrap();
+        let to = tempdir().unwrap();
+        std::fs::write(from.path().join("a.txt"), "甲").unwrap();
+        std::fs::create_dir(from.path().join("玄幻")).unwrap();
+        std::fs::write(from.path().join("玄幻").join("b.txt"), "乙").unwrap();
+        std::fs::write(to.path().join("a.txt"), "已有").unwrap();
+
+        // 有重名时不移动任何文件
+        assert!(move_dir_contents(from.path(), to.path()).is_err());
+        assert!(from.path().join("玄幻").join("b.txt").exists());
+
+        std::fs::remove_file(to.path().join("a.txt")).unwrap();
+        assert_eq!(move_dir_contents(from.path(), to.path()).unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(to.path().join("a.txt")).unwrap(),
+            "甲"
+        );
+        assert!(to.path().join("玄幻").join("b.txt").exists());
+        assert_eq!(std::fs::read_dir(from.path()).unwrap().count(), 0);
+    }
 }
@@ -1,20 +1,91 @@
 use anyhow::Result;
 use ratatui::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Receiver;
-
-use crate::config::CONFIG;
-use crate::model::library::{Library, NovelInfo};
-use crate::model::novel::Novel;
+use std::time::{Duration, Instant};
+
+use crate::config::config;
+use crate::i18n::{self, tr};
+use crate::model::chapter_cache;
+use crate::model::file_stats::FileStats;
+use crate::model::library::{BackupInfo, Library, NovelInfo, ReadingStatus};
+use crate::model::matcher::SearchQuery;
+use crate::model::novel::{Bookmark, Chapter, Novel};
+use crate::model::preferences::{Preferences, ThemeName};
+use crate::model::trash::Trash;
 use crate::state::{AppState, SettingsMode};
 use crate::sync::config::WebDavConfig;
 use crate::sync::sync_engine::SyncMessage;
 use crate::ui::sync_status::SyncStatus;
+use crate::ui::theme::Theme;
 
+mod annotation;
+mod batch;
 mod bookmark;
+mod chapter_list;
+mod command;
+mod config_watcher;
+mod confirm;
+mod duplicates;
+mod highlight;
+mod jump;
+mod leader;
 mod library_ops;
+mod performance;
+mod queue;
+mod reader_options;
 mod search;
+mod settings_transfer;
+mod setup;
 mod sync_ops;
+mod tags;
+mod watcher;
+
+use config_watcher::ConfigWatcher;
+pub use confirm::{ConfirmAction, ConfirmDialog};
+pub use jump::NovelNavigation;
+pub use leader::LeaderAction;
+pub use performance::PerformanceOption;
+pub use queue::QUEUE_CATEGORY;
+pub use reader_options::ReaderOption;
+pub use search::SearchMessage;
+pub use setup::{SetupState, SetupStep};
+use watcher::NovelWatcher;
+
+/// 搜索范围
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchScope {
+    /// 全书
+    #[default]
+    Book,
+    /// 进入搜索时所在的章节
+    Chapter,
+    /// 输入开头 `起始-结束` 指定的章节范围（从 1 开始，包含两端）
+    ChapterRange,
+}
+
+impl SearchScope {
+    /// 切换到下一种范围
+    pub fn next(self) -> Self {
+        match self {
+            SearchScope::Book => SearchScope::Chapter,
+            SearchScope::Chapter => SearchScope::ChapterRange,
+            SearchScope::ChapterRange => SearchScope::Book,
+        }
+    }
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchScope::Book => tr("全书", "Whole book"),
+            SearchScope::Chapter => tr("本章", "This chapter"),
+            SearchScope::ChapterRange => tr("章节范围", "Chapter range"),
+        }
+    }
+}
 
 /// 搜索相关状态
 #[derive(Default)]
@@ -25,32 +96,250 @@ pub struct SearchState {
     pub results: Vec<(usize, String)>,
     /// 当前选中的搜索结果索引
     pub selected_index: Option<usize>,
+    /// 模糊模式：支持拼音和少量错字（不随 `clear` 重置）
+    pub fuzzy: bool,
+    /// 搜索范围（不随 `clear` 重置）
+    pub scope: SearchScope,
+    /// 正在输入的结果序号（`Ctrl+g` 后），`None` 表示不在输入序号
+    pub goto_input: Option<String>,
+    /// 后台搜索线程的结果通道（仅大文件使用）
+    pub rx: Option<Receiver<SearchMessage>>,
+    /// 当前后台搜索的取消标志
+    pub cancel: Option<Arc<AtomicBool>>,
+    /// 等待防抖的搜索请求的提交时间
+    pub pending_since: Option<Instant>,
 }
 
 impl SearchState {
     /// 清空搜索状态
     ///
-    /// 重置输入框、搜索结果和选中索引。
+    /// 重置输入框、搜索结果和选中索引，并取消正在进行的后台搜索。
     pub fn clear(&mut self) {
+        self.cancel();
         self.input.clear();
         self.results.clear();
         self.selected_index = None;
+        self.goto_input = None;
+    }
+
+    /// 取消正在等待或进行中的后台搜索
+    pub fn cancel(&mut self) {
+        if let Some(flag) = self.cancel.take() {
+            flag.store(true, Ordering::Relaxed);
+        }
+        self.rx = None;
+        self.pending_since = None;
+    }
+
+    /// 是否有尚未完成的后台搜索
+    pub fn is_searching(&self) -> bool {
+        self.rx.is_some() || self.pending_since.is_some()
+    }
+
+    /// 章节范围模式下从输入开头解析出的章节范围
+    ///
+    /// # Returns
+    ///
+    /// 输入形如 `12-30 关键词` 时返回 `Some((12, 30))`（从 1 开始，包含两端），
+    /// 其他范围模式或格式不符时返回 `None`。
+    pub fn chapter_range(&self) -> Option<(usize, usize)> {
+        if self.scope != SearchScope::ChapterRange {
+            return None;
+        }
+        let (range, _) = self.input.split_once(' ')?;
+        let (start, end) = range.split_once('-')?;
+        let start: usize = start.trim().parse().ok()?;
+        let end: usize = end.trim().parse().ok()?;
+        (start >= 1 && start <= end).then_some((start, end))
+    }
+
+    /// 用于高亮的关键词，布尔查询时取第一个正向词
+    pub fn highlight_term(&self) -> String {
+        SearchQuery::parse(self.term()).highlight_term().to_string()
+    }
+
+    /// 实际要搜索的关键词
+    ///
+    /// 章节范围模式下去掉输入开头的范围部分，其他模式即为整个输入。
+    pub fn term(&self) -> &str {
+        if self.chapter_range().is_some() {
+            self.input
+                .split_once(' ')
+                .map_or("", |(_, term)| term.trim_start())
+        } else {
+            &self.input
+        }
+    }
+}
+
+/// 书签列表的排序方式（只影响显示，书签始终按位置保存）
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BookmarkSort {
+    /// 按位置
+    #[default]
+    Position,
+    /// 按创建时间，最新的在前
+    Created,
+    /// 按名称
+    Name,
+}
+
+impl BookmarkSort {
+    /// 切换到下一种排序方式
+    pub fn next(self) -> Self {
+        match self {
+            BookmarkSort::Position => BookmarkSort::Created,
+            BookmarkSort::Created => BookmarkSort::Name,
+            BookmarkSort::Name => BookmarkSort::Position,
+        }
+    }
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            BookmarkSort::Position => tr("按位置", "By position"),
+            BookmarkSort::Created => tr("按时间", "By time"),
+            BookmarkSort::Name => tr("按名称", "By name"),
+        }
     }
 }
 
+/// 撤销缓冲区中最多保留的已删除书签数
+pub const MAX_BOOKMARK_UNDO: usize = 20;
+
+/// 已删除、可以撤销恢复的书签
+#[derive(Debug, Clone)]
+pub struct DeletedBookmark {
+    /// 所属小说的路径
+    pub path: PathBuf,
+    /// 被删除的书签
+    pub bookmark: Bookmark,
+    /// 是否为自动记录的位置
+    pub auto: bool,
+}
+
 /// 书签相关状态
 #[derive(Default)]
 pub struct BookmarkState {
-    /// 当前选中的书签索引
+    /// 当前选中的书签索引（在书签列表中的索引，与显示顺序无关）
     pub selected_index: Option<usize>,
-    /// 添加书签时的输入内容
+    /// 书签列表的排序方式
+    pub sort: BookmarkSort,
+    /// 只显示带该标签的书签，`None` 表示不筛选
+    pub tag_filter: Option<String>,
+    /// 按名称和备注筛选书签的关键词（不区分大小写），为空时不筛选
+    pub filter: String,
+    /// 是否正在输入筛选关键词
+    pub filter_input: bool,
+    /// 添加/编辑书签时输入的名称
     pub input: String,
+    /// 添加/编辑书签时输入的备注（可多行）
+    pub note_input: String,
+    /// 添加/编辑书签时选择的标签，空字符串表示没有标签
+    pub tag_input: String,
+    /// 输入焦点是否在备注上
+    pub note_focused: bool,
+    /// 正在编辑的书签索引，`None` 表示添加新书签
+    pub editing_index: Option<usize>,
+    /// 全部书签浏览中选中的条目索引（在 [`App::all_bookmarks`] 中的索引）
+    pub all_selected_index: Option<usize>,
+    /// 最近删除的书签（最近的在最后），按 `u` 撤销删除
+    pub deleted: Vec<DeletedBookmark>,
 }
 
 impl BookmarkState {
-    /// 清空输入框内容
+    /// 清空输入框内容并回到添加模式
     pub fn clear_input(&mut self) {
         self.input.clear();
+        self.note_input.clear();
+        self.tag_input.clear();
+        self.note_focused = false;
+        self.editing_index = None;
+    }
+}
+
+/// 章节目录界面状态
+#[derive(Default)]
+pub struct ChapterListState {
+    /// 是否处于编辑模式（`d` 删除、`r` 重命名、`a` 在阅读位置添加章节）
+    pub editing: bool,
+    /// 重命名时的输入内容，`None` 表示未在重命名
+    pub rename_input: Option<String>,
+    /// 按标题筛选章节的关键词（不区分大小写），为空时显示全部章节
+    pub filter: String,
+    /// 是否正在输入筛选关键词
+    pub filter_input: bool,
+    /// 已折叠的卷（章节索引）
+    pub collapsed: HashSet<usize>,
+}
+
+/// 高亮列表界面状态
+#[derive(Debug, Default)]
+pub struct HighlightListState {
+    /// 当前选中的高亮索引
+    pub selected_index: Option<usize>,
+}
+
+/// 批注编辑和批注列表的界面状态
+#[derive(Debug, Default)]
+pub struct AnnotationState {
+    /// 批注列表中选中的批注索引
+    pub selected_index: Option<usize>,
+    /// 正在输入的批注内容，`None` 表示未在编辑
+    pub input: Option<String>,
+    /// 正在编辑批注的行号
+    pub editing_line: usize,
+}
+
+/// 阅读界面的会话状态（不持久化）
+#[derive(Default)]
+pub struct ReaderState {
+    /// 专注模式：隐藏边框、帮助栏等界面元素，只显示正文
+    pub zen_mode: bool,
+    /// 夜间模式：所有界面文字以暗灰色显示，降低屏幕亮度和存在感
+    pub night_mode: bool,
+    /// 需要在正文中高亮的关键词（从搜索跳转时记录）
+    pub highlight_term: Option<String>,
+    /// 高亮关键词来自模糊搜索，没有普通匹配时高亮拼音或容错匹配到的部分
+    pub highlight_fuzzy: bool,
+    /// 高亮关键词的所有匹配行（升序），用于显示“第几处/共几处”和 `n`/`N` 跳转
+    pub match_lines: Vec<usize>,
+    /// 阅读引导线：高亮视口中的锚点行，避免视线离开后找不到位置
+    pub reading_guide: bool,
+    /// 引导线锚点（相对视口顶部的逻辑行序号）
+    pub guide_anchor: usize,
+    /// 伪装模式：正文前附加伪日志前缀，远看像程序输出
+    pub camouflage: bool,
+    /// 竖排模式：传统从右到左竖排
+    pub vertical_layout: bool,
+    /// 到达最后一页后已询问过是否打开下一本，离开最后一页时清除
+    pub next_novel_offered: bool,
+    /// 等待后续按键的前缀键（如 `gg` 的第一个 `g`）
+    pub pending_key: Option<char>,
+    /// 正在输入的数字前缀（如 `25j` 中的 25）
+    pub pending_count: Option<usize>,
+    /// 阅读界面内查找的输入内容（`Some` 表示正在输入）
+    pub find_input: Option<String>,
+    /// 开始查找时的阅读位置，取消查找时恢复
+    pub find_origin: usize,
+    /// 本次阅读会话的开始时间（打开小说时记录）
+    pub session_start: Option<Instant>,
+    /// 选择模式的起点行（`Some` 表示正在选择要高亮的行）
+    pub visual_anchor: Option<usize>,
+    /// 选择模式的光标行，与起点之间（含两端）的行为选中范围
+    pub visual_cursor: usize,
+    /// 自动滚动开启时为上次滚动的时间，`None` 表示未开启
+    pub auto_scroll: Option<Instant>,
+    /// 上次检查是否需要自动保存进度的时间
+    pub autosave_checked_at: Option<Instant>,
+}
+
+impl ReaderState {
+    /// 本次阅读会话已持续的时间
+    pub fn session_elapsed(&self) -> Duration {
+        self.session_start
+            .map(|start| start.elapsed())
+            .unwrap_or_default()
     }
 }
 
@@ -67,6 +356,34 @@ pub struct SettingsState {
     pub orphaned_novels: Vec<NovelInfo>,
     /// 设置页面中选中的孤立小说索引
     pub selected_orphaned_index: Option<usize>,
+    /// 正在输入的孤立记录新文件路径，`None` 表示未在输入
+    pub relocate_input: Option<String>,
+    /// 书签导入导出模式下选中的小说索引
+    pub selected_bookmark_novel_index: Option<usize>,
+    /// 小说目录管理模式下选中的目录索引（0 为默认目录）
+    pub selected_library_dir_index: Option<usize>,
+    /// 正在输入的新小说目录，`None` 表示未在输入
+    pub library_dir_input: Option<String>,
+    /// 正在输入的新默认小说目录，`None` 表示未在输入
+    pub novels_dir_input: Option<String>,
+    /// 更换默认小说目录时是否把原目录中的文件移过去
+    pub novels_dir_move: bool,
+    /// 回收站（进入回收站页面时读取）
+    pub trash: Trash,
+    /// 回收站页面中选中的条目索引
+    pub selected_trash_index: Option<usize>,
+    /// 外观页面中选中的主题索引（[`ThemeName::ALL`] 中的位置）
+    pub selected_theme_index: Option<usize>,
+    /// 进入外观页面前的主题，取消预览时恢复
+    pub saved_theme: ThemeName,
+    /// 阅读选项页面中选中的选项索引（[`ReaderOption::ALL`] 中的位置）
+    pub selected_reader_option: Option<usize>,
+    /// 性能页面中选中的选项索引（[`PerformanceOption::ALL`] 中的位置）
+    pub selected_performance_option: Option<usize>,
+    /// 进度文件的备份，最新的在前（进入进度备份页面时读取）
+    pub backups: Vec<BackupInfo>,
+    /// 进度备份页面中选中的备份索引
+    pub selected_backup_index: Option<usize>,
     /// WebDAV配置编辑状态
     pub webdav_config_state: WebDavConfigState,
 }
@@ -74,7 +391,7 @@ pub struct SettingsState {
 /// WebDAV配置编辑状态
 #[derive(Default)]
 pub struct WebDavConfigState {
-    /// 当前选中的字段索引 (0=enabled, 1=url, 2=username, 3=password, 4=remote_path)
+    /// 当前选中的字段索引 (0=enabled, 1=url, 2=username, 3=password, 4=remote_path, 5=bookmark_merge)
     pub selected_field: usize,
     /// 临时配置（编辑中）
     pub temp_config: WebDavConfig,
@@ -95,6 +412,55 @@ impl SettingsState {
     }
 }
 
+/// 书架列表中的一行
+#[derive(Debug, Clone, PartialEq)]
+pub enum BookshelfRow {
+    /// 分类（子文件夹）标题
+    Category {
+        name: String,
+        /// 分类中的小说数
+        count: usize,
+        collapsed: bool,
+    },
+    /// 小说，值为在 `App::novels` 中的索引
+    Novel(usize),
+}
+
+/// 书架界面状态
+#[derive(Debug, Default)]
+pub struct BookshelfState {
+    /// 正在输入的导入文件路径，`None` 表示未在输入
+    pub import_input: Option<String>,
+    /// 导入时移动文件而不是复制
+    pub import_move: bool,
+    /// 正在输入的新书名，`None` 表示未在重命名
+    pub rename_input: Option<String>,
+    /// 只显示带有该标签的小说
+    pub tag_filter: Option<String>,
+    /// 只显示该阅读状态的小说
+    pub status_filter: Option<ReadingStatus>,
+    /// 标题筛选关键词，支持拼音首字母
+    pub filter: String,
+    /// 是否正在输入标题筛选关键词
+    pub filter_input: bool,
+    /// 书籍详情中选中的标签索引
+    pub detail_tag_index: Option<usize>,
+    /// 书籍详情中或给标记的小说批量添加时正在输入的新标签，`None` 表示未在输入
+    pub tag_input: Option<String>,
+    /// 书籍详情中正在编辑的备注，`None` 表示未在编辑
+    pub note_input: Option<String>,
+    /// 用空格标记的小说路径，用于批量操作
+    pub marked: HashSet<PathBuf>,
+    /// 书籍详情中小说文件的编码和行数、字数，进入详情时读取
+    pub detail_stats: Option<FileStats>,
+    /// 由标题识别的系列（小说路径 → 系列名（小写）、卷号），排序书架时更新
+    pub series: HashMap<PathBuf, (String, u32)>,
+    /// 内容重复的小说分组（每组至少两本）
+    pub duplicate_groups: Vec<Vec<PathBuf>>,
+    /// 小说内容指纹缓存（路径 → 文件大小、指纹），文件大小变化时重新计算
+    pub fingerprints: HashMap<PathBuf, (u64, u32)>,
+}
+
 pub struct App {
     /// 当前应用状态（书架/阅读/搜索/章节目录模式）
     pub state: AppState,
@@ -104,6 +470,12 @@ pub struct App {
     pub novels: Vec<Novel>,
     /// 书架选中的小说索引
     pub selected_novel_index: Option<usize>,
+    /// 书架光标所在的分类标题，为 `Some` 时 `selected_novel_index` 为 `None`
+    pub selected_category: Option<String>,
+    /// 书架界面状态
+    pub bookshelf: BookshelfState,
+    /// 首次启动向导的状态
+    pub setup: SetupState,
     /// 当前正在阅读的小说
     pub current_novel: Option<Novel>,
     /// 退出标志位
@@ -112,6 +484,10 @@ pub struct App {
     pub terminal_size: Rect,
     /// 当前选中的章节索引
     pub selected_chapter_index: Option<usize>,
+    /// 目录编辑状态
+    pub chapter_list: ChapterListState,
+    /// 后台章节解析结果接收端（小说路径，章节列表）
+    pub chapter_parse_rx: Option<Receiver<(PathBuf, Vec<Chapter>)>>,
     /// 上一个状态（用于从搜索/章节目录返回）
     pub previous_state: AppState,
 
@@ -119,10 +495,28 @@ pub struct App {
     pub search: SearchState,
     /// 书签状态
     pub bookmark: BookmarkState,
+    /// 高亮列表状态
+    pub highlight_list: HighlightListState,
+    /// 批注状态
+    pub annotation: AnnotationState,
+    /// 阅读界面状态
+    pub reader: ReaderState,
+    /// 每本小说的跳转历史和标记（按路径区分，不持久化）
+    pub navigation: HashMap<PathBuf, NovelNavigation>,
     /// 设置状态
     pub settings: SettingsState,
+    /// 用户偏好设置
+    pub preferences: Preferences,
     /// 错误消息（用于在状态栏显示错误提示）
     pub error_message: Option<String>,
+    /// 提示消息（操作成功等，与错误消息显示在同一位置）
+    pub info_message: Option<String>,
+    /// 当前显示的确认对话框
+    pub confirm: Option<ConfirmDialog>,
+    /// 命令行模式的输入内容（按 `:` 进入），`None` 表示不在命令行模式
+    pub command_input: Option<String>,
+    /// 是否已按下 `,`，正在等待组合键的第二个键
+    pub leader_pending: bool,
 
     /// WebDAV 配置
     pub webdav_config: WebDavConfig,
@@ -130,6 +524,12 @@ pub struct App {
     pub sync_rx: Option<Receiver<SyncMessage>>,
     /// 同步状态显示
     pub sync_status: SyncStatus,
+    /// 小说目录的文件监视器，`None` 表示未在监视
+    pub novel_watcher: Option<NovelWatcher>,
+    /// 配置文件和偏好设置文件的监视器，`None` 表示未在监视
+    pub config_watcher: Option<ConfigWatcher>,
+    /// 是否捕获鼠标事件：捕获时支持滚轮和点击翻页，不捕获时可以用终端原生的选择和复制
+    pub mouse_capture: bool,
 }
 
 impl App {
@@ -144,7 +544,7 @@ impl App {
             .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
         path.push(format!(
             "{}_test_{}_{}",
-            CONFIG.dir_name,
+            config().dir_name,
             std::process::id(),
             thread_id
         ));
@@ -153,14 +553,15 @@ impl App {
 
     /// 初始化应用程序
     /// # 流程
-    /// 1. 加载历史进度 2. 扫描小说目录（懒加载，不加载内容）
+    /// 1. 加载历史进度 2. 扫描所有小说目录（懒加载，不加载内容）
     pub fn new() -> Result<Self> {
         let library = Library::load();
 
-        let novels_dir = Self::get_novels_dir();
-        let novels = Self::load_novels_from_dir(&novels_dir)?;
-
         let webdav_config = WebDavConfig::load();
+        let preferences = Preferences::load();
+        i18n::set_language(preferences.language);
+
+        let novels = Self::load_all_novels(&preferences.library_dirs)?;
 
         let selected_novel_index = Self::first_index_if_any(novels.len());
 
@@ -169,21 +570,47 @@ impl App {
             library,
             novels,
             selected_novel_index,
+            selected_category: None,
+            bookshelf: BookshelfState::default(),
+            setup: SetupState::default(),
             current_novel: None,
             should_quit: false,
             terminal_size: Rect::default(),
             selected_chapter_index: None,
+            chapter_list: ChapterListState::default(),
+            chapter_parse_rx: None,
             previous_state: AppState::Bookshelf,
             search: SearchState::default(),
             bookmark: BookmarkState::default(),
+            highlight_list: HighlightListState::default(),
+            annotation: AnnotationState::default(),
+            reader: ReaderState::default(),
+            navigation: HashMap::new(),
             settings: SettingsState::default(),
+            preferences,
             error_message: None,
+            info_message: None,
+            confirm: None,
+            command_input: None,
+            leader_pending: false,
             webdav_config,
             sync_rx: None,
             sync_status: SyncStatus::Idle,
+            novel_watcher: None,
+            config_watcher: None,
+            mouse_capture: config().mouse_capture,
         };
 
+        app.purge_expired_trash();
+        app.watch_novel_dirs();
+        app.watch_config_files();
         app.detect_orphaned_novels();
+        app.migrate_legacy_chapter_settings();
+        app.sort_novels();
+        app.detect_duplicates();
+        if app.needs_setup() {
+            app.start_setup();
+        }
 
         Ok(app)
     }
@@ -192,21 +619,20 @@ impl App {
     ///
     /// # Returns
     ///
-    /// 小说目录的完整路径。在测试环境下返回临时目录，否则返回用户主目录下的 `.fish_reader/novels`。
+    /// 小说目录的完整路径。在测试环境下返回临时目录，否则返回配置文件中的 `novels_dir`，
+    /// 未配置时为用户主目录下的 `.fish_reader/novels`。
     pub fn get_novels_dir() -> PathBuf {
         #[cfg(test)]
         {
             let mut path = Self::get_test_data_dir();
             path.push("novels");
             let _ = std::fs::create_dir_all(&path);
-            return path;
+            path
         }
 
         #[cfg(not(test))]
         {
-            let mut path = home::home_dir().unwrap_or_else(|| PathBuf::from("."));
-            path.push(CONFIG.dir_name);
-            path.push("novels");
+            let path = config().novels_dir();
 
             if !path.exists()
                 && let Err(e) = std::fs::create_dir_all(&path)
@@ -220,6 +646,7 @@ impl App {
 
     /// 从指定目录扫描并加载小说列表
     ///
+    /// 递归扫描子文件夹，子文件夹的相对路径作为小说的分类；跳过隐藏文件夹，不跟随指向目录的符号链接。
     /// 仅扫描支持的文件扩展名，采用懒加载策略（不加载文件内容）。
     ///
     /// # Arguments
@@ -240,27 +667,85 @@ impl App {
             return Ok(novels);
         }
 
+        Self::scan_novels(dir, dir, &mut novels)?;
+        novels.sort_by(library_ops::compare_titles);
+
+        Ok(novels)
+    }
+
+    /// 扫描默认小说目录和偏好设置中的额外小说目录
+    ///
+    /// 额外目录中的小说以该目录名作为分类（子文件夹继续细分），同一文件只保留一次。
+    ///
+    /// # Arguments
+    ///
+    /// * `extra_dirs` - 额外的小说目录
+    ///
+    /// # Errors
+    ///
+    /// 默认目录读取失败时返回错误；额外目录不存在或读取失败时跳过。
+    fn load_all_novels(extra_dirs: &[PathBuf]) -> Result<Vec<Novel>> {
+        let mut novels = Self::load_novels_from_dir(&Self::get_novels_dir())?;
+
+        for dir in extra_dirs {
+            let Ok(found) = Self::load_novels_from_dir(dir) else {
+                continue;
+            };
+            let name = dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| dir.display().to_string());
+            for mut novel in found {
+                if novels.iter().any(|n| n.path == novel.path) {
+                    continue;
+                }
+                novel.category = if novel.category.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}/{}", name, novel.category)
+                };
+                novels.push(novel);
+            }
+        }
+
+        Ok(novels)
+    }
+
+    /// 递归扫描 `dir` 中的小说，分类为 `dir` 相对 `root` 的路径
+    fn scan_novels(root: &Path, dir: &Path, novels: &mut Vec<Novel>) -> Result<()> {
+        let category = dir
+            .strip_prefix(root)
+            .map(|relative| {
+                relative
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .unwrap_or_default();
+
         for entry in std::fs::read_dir(dir)? {
             let entry = entry?;
             let path = entry.path();
-            if path.is_file()
+            if entry.file_type()?.is_dir() {
+                if !entry.file_name().to_string_lossy().starts_with('.') {
+                    Self::scan_novels(root, &path, novels)?;
+                }
+            } else if path.is_file()
                 && let Some(ext) = path.extension().and_then(|s| s.to_str())
-                && CONFIG.supported_extensions.contains(&ext)
+                && config().is_supported_extension(ext)
             {
-                let novel = Novel::new(path);
+                let mut novel = Novel::new(path);
+                novel.file_size = entry.metadata().map_or(0, |meta| meta.len());
+                novel.category = category.clone();
+                novel.root = root.to_path_buf();
+                // 书架显示阅读位置所在章节，只使用已有的缓存
+                novel.chapters = chapter_cache::load_cached(&novel.path).unwrap_or_default();
                 novels.push(novel);
             }
         }
 
-        novels.sort_by(|a, b| {
-            a.title
-                .to_lowercase()
-                .cmp(&b.title.to_lowercase())
-                .then_with(|| a.title.cmp(&b.title))
-                .then_with(|| a.path.cmp(&b.path))
-        });
-
-        Ok(novels)
+        Ok(())
     }
 
     /// 设置错误消息
@@ -274,6 +759,63 @@ impl App {
         self.error_message = Some(msg.into());
     }
 
+    /// 设置提示消息
+    ///
+    /// 与错误消息一样在下一次按键时清除。
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - 提示消息内容
+    pub fn set_info(&mut self, msg: impl Into<String>) {
+        self.info_message = Some(msg.into());
+    }
+
+    /// 是否有需要持续刷新界面的后台活动：同步、搜索、章节解析或自动滚动
+    ///
+    /// 主循环据此决定空闲时是否按每次循环重绘，见 `performance.idle_redraw_ms`。
+    pub fn has_background_activity(&self) -> bool {
+        self.sync_rx.is_some()
+            || self.search.rx.is_some()
+            || self.search.pending_since.is_some()
+            || self.is_parsing_chapters()
+            || self.reader.auto_scroll.is_some()
+    }
+
+    /// 当前界面主题的配色
+    pub fn theme(&self) -> &'static Theme {
+        Theme::of(self.preferences.theme)
+    }
+
+    /// 保存用户偏好设置
+    pub fn save_preferences(&mut self) {
+        if let Err(e) = self.preferences.save() {
+            self.set_error(format!("Failed to save preferences: {}", e));
+        }
+    }
+
+    /// 切换界面语言并保存到偏好设置
+    pub fn toggle_language(&mut self) {
+        self.preferences.language = self.preferences.language.next();
+        i18n::set_language(self.preferences.language);
+        self.save_preferences();
+    }
+
+    /// 开启或关闭鼠标捕获（主循环随后同步到终端）
+    pub fn toggle_mouse_capture(&mut self) {
+        self.mouse_capture = !self.mouse_capture;
+        if self.mouse_capture {
+            self.set_info(tr(
+                "已开启鼠标捕获，可用滚轮滚动和点击翻页；按 F2 关闭",
+                "Mouse capture on: scroll and click to turn pages; press F2 to turn it off",
+            ));
+        } else {
+            self.set_info(tr(
+                "已关闭鼠标捕获，可用鼠标选择和复制文字；按 F2 重新开启",
+                "Mouse capture off: select and copy text with the mouse; press F2 to turn it back on",
+            ));
+        }
+    }
+
     /// Save WebDAV configuration
     pub fn save_webdav_config(&mut self) {
         self.webdav_config = self.settings.webdav_config_state.temp_config.clone();
@@ -287,7 +829,9 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::library::ChapterSettings;
     use crate::model::novel::{Chapter, ReadingProgress};
+    use crate::model::preferences::BookshelfSort;
     use std::path::PathBuf;
     use std::sync::mpsc;
     use tempfile::tempdir;
@@ -298,18 +842,35 @@ mod tests {
             library: Library::default(),
             novels: Vec::new(),
             selected_novel_index: None,
+            selected_category: None,
+            bookshelf: BookshelfState::default(),
+            setup: SetupState::default(),
             current_novel: None,
             should_quit: false,
             terminal_size: Rect::default(),
             selected_chapter_index: None,
+            chapter_list: ChapterListState::default(),
+            chapter_parse_rx: None,
             previous_state: AppState::Bookshelf,
             search: SearchState::default(),
             bookmark: BookmarkState::default(),
+            highlight_list: HighlightListState::default(),
+            annotation: AnnotationState::default(),
+            reader: ReaderState::default(),
+            navigation: HashMap::new(),
             settings: SettingsState::default(),
+            preferences: Preferences::default(),
             error_message: None,
+            info_message: None,
+            confirm: None,
+            command_input: None,
+            leader_pending: false,
             webdav_config: WebDavConfig::default(),
             sync_rx: None,
             sync_status: SyncStatus::Idle,
+            novel_watcher: None,
+            config_watcher: None,
+            mouse_capture: true,
         }
     }
 
@@ -332,6 +893,72 @@ mod tests {
         assert!(app.search.results.is_empty());
     }
 
+    #[test]
+    fn test_perform_search_large_novel_runs_in_background() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("big.txt"));
+        let content: Vec<String> = (0..search::THREADED_SEARCH_MIN_LINES + 10)
+            .map(|i| {
+                if i % 10_000 == 5 {
+                    "needle".to_string()
+                } else {
+                    format!("line {}", i)
+                }
+            })
+            .collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+
+        app.search.input = "needle".to_string();
+        app.perform_search();
+        assert!(app.search.is_searching());
+        assert!(app.search.results.is_empty());
+
+        app.start_background_search();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.search.is_searching() && Instant::now() < deadline {
+            app.poll_search();
+        }
+        assert!(!app.search.is_searching());
+        let lines: Vec<usize> = app.search.results.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![5, 10_005, 20_005]);
+        assert_eq!(app.search.selected_index, Some(0));
+
+        app.search.clear();
+        assert!(!app.search.is_searching());
+        assert!(app.search.results.is_empty());
+    }
+
+    #[test]
+    fn test_perform_search_respects_scope() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("第一章\n张三出场\n第二章\n张三吃饭\n第三章\n张三睡觉".to_string());
+        novel.parse_chapters();
+        novel.progress.scroll_offset = 3;
+        app.current_novel = Some(novel);
+        app.search.input = "张三".to_string();
+
+        app.perform_search();
+        assert_eq!(app.search.results.len(), 3);
+
+        app.search.scope = SearchScope::Chapter;
+        app.perform_search();
+        assert_eq!(app.search.results, vec![(3, "张三吃饭".to_string())]);
+
+        // 章节范围模式下没有范围前缀时不搜索
+        app.search.scope = SearchScope::ChapterRange;
+        app.perform_search();
+        assert!(app.search.results.is_empty());
+
+        app.search.input = "2-9 张三".to_string();
+        assert_eq!(app.search.chapter_range(), Some((2, 9)));
+        assert_eq!(app.search.term(), "张三");
+        app.perform_search();
+        let lines: Vec<usize> = app.search.results.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![3, 5]);
+    }
+
     #[test]
     fn test_find_current_chapter_index() {
         let mut app = create_test_app();
@@ -356,6 +983,13 @@ mod tests {
             scroll_offset: 5,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
         assert_eq!(app.find_current_chapter_index(), Some(0));
 
@@ -363,6 +997,13 @@ mod tests {
             scroll_offset: 15,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
         assert_eq!(app.find_current_chapter_index(), Some(1));
 
@@ -370,6 +1011,13 @@ mod tests {
             scroll_offset: 25,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
         assert_eq!(app.find_current_chapter_index(), Some(2));
     }
@@ -381,7 +1029,7 @@ mod tests {
         novel.progress.scroll_offset = 50;
         app.current_novel = Some(novel);
 
-        app.add_bookmark("My Bookmark".to_string());
+        app.add_bookmark("My Bookmark".to_string(), String::new(), String::new());
 
         let bookmarks = app.get_current_bookmarks().unwrap();
         assert_eq!(bookmarks.len(), 1);
@@ -401,6 +1049,77 @@ mod tests {
         assert!(app.get_current_bookmarks().unwrap().is_empty());
     }
 
+    #[test]
+    fn test_undo_bookmark_removal_restores_in_reverse_order() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.progress.add_bookmark("First".to_string(), 10);
+        novel.progress.add_bookmark("Second".to_string(), 20);
+        novel.progress.record_auto_position("退出时", 30);
+        app.current_novel = Some(novel);
+
+        app.remove_bookmark(2).unwrap();
+        app.remove_bookmark(0).unwrap();
+        app.remove_bookmark(0).unwrap();
+        assert!(app.get_current_bookmarks().unwrap().is_empty());
+
+        assert_eq!(app.undo_bookmark_removal(), Some(0));
+        assert_eq!(app.get_current_bookmarks().unwrap()[0].name, "Second");
+        assert_eq!(app.undo_bookmark_removal(), Some(0));
+        let names: Vec<&str> = app
+            .get_current_bookmarks()
+            .unwrap()
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["First", "Second"]);
+        // 自动记录恢复到自动记录中
+        assert_eq!(app.undo_bookmark_removal(), Some(2));
+        assert!(app.is_auto_bookmark(2));
+        assert!(app.undo_bookmark_removal().is_none());
+
+        // 其他小说删除的书签不会恢复到当前小说
+        app.remove_bookmark(0).unwrap();
+        app.current_novel = Some(Novel::new(PathBuf::from("other.txt")));
+        assert!(app.undo_bookmark_removal().is_none());
+    }
+
+    #[test]
+    fn test_bookmark_passage_line_and_paragraph() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("开头\n\n\u{3000}\u{3000}第一句\n第二句\n\n结尾".to_string());
+        novel.progress.add_bookmark("段落".to_string(), 3);
+        novel.progress.add_bookmark("空行".to_string(), 1);
+        app.current_novel = Some(novel);
+
+        assert_eq!(app.bookmark_passage(1, false).as_deref(), Some("第二句"));
+        assert_eq!(
+            app.bookmark_passage(1, true).as_deref(),
+            Some("第一句\n第二句")
+        );
+        assert!(app.bookmark_passage(0, true).is_none());
+        assert!(app.bookmark_passage(5, false).is_none());
+    }
+
+    #[test]
+    fn test_move_bookmark_to_current() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.progress.add_bookmark("First".to_string(), 10);
+        novel.progress.add_bookmark("Second".to_string(), 20).note = "note".to_string();
+        novel.progress.scroll_offset = 5;
+        app.current_novel = Some(novel);
+
+        assert_eq!(app.move_bookmark_to_current(1), Some(0));
+        let bookmarks = app.get_current_bookmarks().unwrap();
+        assert_eq!(bookmarks[0].name, "Second");
+        assert_eq!(bookmarks[0].position, 5);
+        assert_eq!(bookmarks[0].note, "note");
+        assert_eq!(bookmarks.len(), 2);
+        assert!(app.move_bookmark_to_current(9).is_none());
+    }
+
     #[test]
     fn test_remove_bookmark_no_novel() {
         let mut app = create_test_app();
@@ -449,23 +1168,29 @@ mod tests {
 
     #[test]
     fn test_search_state_clear() {
-        let mut search = SearchState::default();
-        search.input = "query".to_string();
-        search.results = vec![(1, "result".to_string())];
-        search.selected_index = Some(0);
+        let mut search = SearchState {
+            input: "query".to_string(),
+            results: vec![(1, "result".to_string())],
+            selected_index: Some(0),
+            fuzzy: true,
+            ..Default::default()
+        };
 
         search.clear();
 
         assert!(search.input.is_empty());
         assert!(search.results.is_empty());
         assert!(search.selected_index.is_none());
+        assert!(search.fuzzy);
     }
 
     #[test]
     fn test_settings_state_reset() {
-        let mut settings = SettingsState::default();
-        settings.mode = SettingsMode::DeleteNovel;
-        settings.selected_option = Some(5);
+        let mut settings = SettingsState {
+            mode: SettingsMode::DeleteNovel,
+            selected_option: Some(5),
+            ..Default::default()
+        };
 
         settings.reset();
 
@@ -498,17 +1223,471 @@ mod tests {
         let dir = tempdir().unwrap();
         let txt_path = dir.path().join("book_a.txt");
         let md_path = dir.path().join("note.md");
-        let sub_dir = dir.path().join("nested");
         std::fs::write(&txt_path, "hello").unwrap();
         std::fs::write(&md_path, "ignore").unwrap();
-        std::fs::create_dir_all(&sub_dir).unwrap();
-        std::fs::write(sub_dir.join("book_b.txt"), "nested").unwrap();
 
         let novels = App::load_novels_from_dir(dir.path()).unwrap();
 
         assert_eq!(novels.len(), 1);
         assert_eq!(novels[0].title, "book_a");
         assert_eq!(novels[0].path, txt_path);
+        assert_eq!(novels[0].file_size, 5);
+        assert_eq!(novels[0].category, "");
+    }
+
+    #[test]
+    fn test_load_novels_from_dir_scans_subfolders_as_categories() {
+        let dir = tempdir().unwrap();
+        let deep_dir = dir.path().join("武侠").join("金庸");
+        let hidden_dir = dir.path().join(".stversions");
+        std::fs::create_dir_all(&deep_dir).unwrap();
+        std::fs::create_dir_all(&hidden_dir).unwrap();
+        std::fs::write(dir.path().join("root.txt"), "r").unwrap();
+        std::fs::write(dir.path().join("武侠").join("book_b.txt"), "b").unwrap();
+        std::fs::write(deep_dir.join("book_c.txt"), "c").unwrap();
+        std::fs::write(deep_dir.join("note.md"), "ignore").unwrap();
+        std::fs::write(hidden_dir.join("old.txt"), "ignore").unwrap();
+
+        let novels = App::load_novels_from_dir(dir.path()).unwrap();
+
+        let found: Vec<(&str, &str)> = novels
+            .iter()
+            .map(|n| (n.title.as_str(), n.category.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("book_b", "武侠"), ("book_c", "武侠/金庸"), ("root", "")]
+        );
+    }
+
+    #[test]
+    fn test_import_novel_copies_or_moves_into_novels_dir() {
+        let mut app = create_test_app();
+        let outside = tempdir().unwrap();
+        let source = outside.path().join("导入测试.txt");
+        std::fs::write(&source, "第一章\n正文").unwrap();
+        std::fs::write(outside.path().join("cover.jpg"), "x").unwrap();
+
+        let quoted = format!("'{}'", source.display());
+        let target = app.import_novel(&quoted, false).unwrap();
+        assert_eq!(target, App::get_novels_dir().join("导入测试.txt"));
+        assert!(source.exists());
+        let index = app.selected_novel_index.unwrap();
+        assert_eq!(app.novels[index].path, target);
+        assert!(app.library.novels.iter().any(|n| n.path == target));
+        assert_eq!(app.library.get_novel_progress(&target).scroll_offset, 0);
+
+        // 同名文件不会覆盖
+        assert!(app.import_novel(&source.to_string_lossy(), true).is_err());
+        assert!(source.exists());
+        std::fs::remove_file(&target).unwrap();
+
+        app.import_novel(&source.to_string_lossy(), true).unwrap();
+        assert!(!source.exists());
+        assert!(target.exists());
+
+        let unsupported = outside.path().join("cover.jpg");
+        assert!(
+            app.import_novel(&unsupported.to_string_lossy(), false)
+                .is_err()
+        );
+        assert!(app.import_novel("", false).is_err());
+        std::fs::remove_file(&target).unwrap();
+    }
+
+    #[test]
+    fn test_rename_novel_keeps_progress_and_bookmarks() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let old_path = dir.join("改名前.txt");
+        std::fs::write(&old_path, "第一章\n正文").unwrap();
+        std::fs::write(dir.join("已存在.txt"), "x").unwrap();
+        app.library.progress_mut(&old_path).scroll_offset = 42;
+        app.library
+            .progress_mut(&old_path)
+            .bookmarks
+            .push(Bookmark::new("书签".to_string(), 1));
+        app.library.tags_mut(&old_path).push("玄幻".to_string());
+        app.reload_novels();
+        let index = app.novels.iter().position(|n| n.path == old_path).unwrap();
+
+        assert!(app.rename_novel(index, "  ").is_err());
+        assert!(app.rename_novel(index, "a/b").is_err());
+        assert!(app.rename_novel(index, "已存在").is_err());
+        assert!(old_path.exists());
+
+        let new_path = app.rename_novel(index, "改名后").unwrap();
+        assert_eq!(new_path, dir.join("改名后.txt"));
+        assert!(!old_path.exists());
+        let index = app.selected_novel_index.unwrap();
+        assert_eq!(app.novels[index].path, new_path);
+        assert_eq!(app.novels[index].title, "改名后");
+
+        let progress = app.library.get_novel_progress(&new_path);
+        assert_eq!(progress.scroll_offset, 42);
+        assert_eq!(progress.bookmarks.len(), 1);
+        let info = app
+            .library
+            .novels
+            .iter()
+            .find(|n| n.path == new_path)
+            .unwrap();
+        assert_eq!(info.title, "改名后");
+        assert_eq!(info.tags, vec!["玄幻".to_string()]);
+        assert!(!app.library.novels.iter().any(|n| n.path == old_path));
+    }
+
+    #[test]
+    fn test_config_watcher_reloads_preferences_and_rejects_broken_config() {
+        let mut app = create_test_app();
+        app.watch_config_files();
+        let poll_until = |app: &mut App, done: &dyn Fn(&App) -> bool| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !done(app) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+                app.poll_config_watcher();
+            }
+        };
+
+        let edited = Preferences {
+            theme: ThemeName::Sepia,
+            ..app.preferences.clone()
+        };
+        edited.save().unwrap();
+        poll_until(&mut app, &|app| app.preferences.theme == ThemeName::Sepia);
+        assert_eq!(app.preferences.theme, ThemeName::Sepia);
+        assert_eq!(app.info_message.as_deref(), Some("已重新加载偏好设置"));
+
+        // 无法解析的配置文件不影响当前配置
+        let before = config();
+        std::fs::write(crate::config::AppConfig::path(), "[reader\nmax_width = 80").unwrap();
+        poll_until(&mut app, &|app| app.error_message.is_some());
+        assert!(
+            app.error_message
+                .as_ref()
+                .unwrap()
+                .contains("已保留当前配置")
+        );
+        assert_eq!(config().reader, before.reader);
+    }
+
+    #[test]
+    fn test_novel_watcher_refreshes_bookshelf_on_external_changes() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        app.reload_novels();
+        app.watch_novel_dirs();
+        let path = dir.join("外部放入.txt");
+        std::fs::write(&path, "正文").unwrap();
+
+        let poll_until = |app: &mut App, done: &dyn Fn(&App) -> bool| {
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while !done(app) && Instant::now() < deadline {
+                std::thread::sleep(Duration::from_millis(50));
+                app.poll_novel_watcher();
+            }
+        };
+        poll_until(&mut app, &|app| app.novels.iter().any(|n| n.path == path));
+        assert!(app.novels.iter().any(|n| n.path == path));
+
+        app.library.register_novel(&path);
+        std::fs::remove_file(&path).unwrap();
+        poll_until(&mut app, &|app| app.novels.is_empty());
+        assert!(app.novels.iter().all(|n| n.path != path));
+        assert!(app.settings.orphaned_novels.iter().any(|n| n.path == path));
+    }
+
+    #[test]
+    fn test_duplicate_novels_are_detected_and_merged() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let keep = dir.join("斗破苍穹.txt");
+        let copy = dir.join("斗破苍穹(1).txt");
+        let other = dir.join("另一本.txt");
+        std::fs::write(&keep, "第一章\n  正文内容\n\n第二章\n结尾\n").unwrap();
+        // 换行方式和缩进不同，内容相同
+        std::fs::write(&copy, "第一章\r\n正文内容\r\n第二章\r\n结尾").unwrap();
+        std::fs::write(&other, "第一章\n别的正文内容\n第二章\n").unwrap();
+
+        let progress = app.library.progress_mut(&keep);
+        progress.scroll_offset = 1;
+        progress.add_bookmark("甲".to_string(), 1);
+        let progress = app.library.progress_mut(&copy);
+        progress.scroll_offset = 3;
+        progress.add_bookmark("乙".to_string(), 3);
+        progress.add_bookmark("甲".to_string(), 1);
+        app.library.tags_mut(&copy).push("玄幻".to_string());
+        for info in app.library.novels.iter_mut().filter(|n| n.path == copy) {
+            info.last_read = 100;
+        }
+        app.reload_novels();
+
+        let index = |app: &App, path: &Path| app.novels.iter().position(|n| n.path == path);
+        let keep_index = index(&app, &keep).unwrap();
+        let copy_index = index(&app, &copy).unwrap();
+        assert_eq!(app.bookshelf_duplicates(keep_index), vec![copy_index]);
+        assert!(
+            app.bookshelf_duplicates(index(&app, &other).unwrap())
+                .is_empty()
+        );
+
+        app.merge_duplicate_novels(keep_index, copy_index).unwrap();
+        assert!(!copy.exists());
+        assert!(index(&app, &copy).is_none());
+        assert!(app.bookshelf.duplicate_groups.is_empty());
+        assert_eq!(app.selected_novel_index, index(&app, &keep));
+
+        let progress = app.library.get_novel_progress(&keep);
+        let names: Vec<&str> = progress.bookmarks.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["甲", "乙"]);
+        // 副本最近读过，阅读位置取副本的
+        assert_eq!(progress.scroll_offset, 3);
+        let info = app.library.novels.iter().find(|n| n.path == keep).unwrap();
+        assert_eq!(info.tags, vec!["玄幻".to_string()]);
+        assert!(!app.library.novels.iter().any(|n| n.path == copy));
+    }
+
+    #[test]
+    fn test_add_and_remove_library_dir_rescans_bookshelf() {
+        let mut app = create_test_app();
+        let extra = tempdir().unwrap();
+        std::fs::create_dir_all(extra.path().join("科幻")).unwrap();
+        std::fs::write(extra.path().join("a.txt"), "a").unwrap();
+        std::fs::write(extra.path().join("科幻").join("b.txt"), "b").unwrap();
+        let name = extra
+            .path()
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+
+        assert!(app.add_library_dir("/definitely/missing/dir").is_err());
+        app.add_library_dir(&extra.path().to_string_lossy())
+            .unwrap();
+        assert!(
+            app.add_library_dir(&extra.path().to_string_lossy())
+                .is_err()
+        );
+
+        let found: Vec<(String, PathBuf)> = app
+            .novels
+            .iter()
+            .filter(|n| n.root == extra.path())
+            .map(|n| (n.category.clone(), n.path.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (name.clone(), extra.path().join("a.txt")),
+                (
+                    format!("{}/科幻", name),
+                    extra.path().join("科幻").join("b.txt")
+                ),
+            ]
+        );
+
+        app.remove_library_dir(0);
+        assert!(app.preferences.library_dirs.is_empty());
+        assert!(app.novels.iter().all(|n| n.root != extra.path()));
+        // 文件未被删除
+        assert!(extra.path().join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_reading_status_cycle_and_filter() {
+        let mut app = create_test_app();
+        for title in ["a", "b"] {
+            app.novels
+                .push(Novel::new(PathBuf::from(format!("{}.txt", title))));
+        }
+        app.library.update_novel_progress(
+            &PathBuf::from("b.txt"),
+            ReadingProgress {
+                scroll_offset: 3,
+                ..Default::default()
+            },
+        );
+        assert_eq!(app.bookshelf_status(0), ReadingStatus::Unread);
+        assert_eq!(app.bookshelf_status(1), ReadingStatus::Reading);
+
+        app.selected_novel_index = Some(0);
+        app.cycle_bookshelf_status_filter();
+        assert_eq!(app.bookshelf.status_filter, Some(ReadingStatus::Unread));
+        assert_eq!(app.bookshelf_rows(), vec![BookshelfRow::Novel(0)]);
+
+        // 手动标记后不再符合筛选条件，光标移到剩下的第一行（没有则不选中）
+        assert_eq!(app.cycle_bookshelf_status(0), Some(ReadingStatus::Reading));
+        assert!(app.bookshelf_rows().is_empty());
+        assert_eq!(app.selected_novel_index, None);
+
+        app.cycle_bookshelf_status_filter();
+        assert_eq!(app.bookshelf_rows().len(), 2);
+        app.cycle_bookshelf_status_filter();
+        app.cycle_bookshelf_status_filter();
+        assert_eq!(app.bookshelf.status_filter, None);
+    }
+
+    #[test]
+    fn test_series_volumes_are_grouped_and_read_in_order() {
+        let mut app = create_test_app();
+        for (title, last_read) in [
+            ("三体外传", 0),
+            ("三体3", 300),
+            ("围城", 200),
+            ("三体", 0),
+            ("三体 第二部", 100),
+        ] {
+            let path = PathBuf::from(format!("{}.txt", title));
+            app.library.register_novel(&path);
+            app.library.novels.last_mut().unwrap().last_read = last_read;
+            app.novels.push(Novel::new(path));
+        }
+        app.preferences.bookshelf_sort = BookshelfSort::RecentlyRead;
+        app.sort_novels();
+
+        // 系列排在最近读过的一卷的位置，各卷按卷号排列
+        let titles: Vec<&str> = app.novels.iter().map(|n| n.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["三体", "三体 第二部", "三体3", "围城", "三体外传"]
+        );
+        assert_eq!(app.bookshelf_series(0), Some(&("三体".to_string(), 1)));
+        assert_eq!(app.bookshelf_series(4), None);
+
+        app.current_novel = Some(app.novels[0].clone());
+        assert_eq!(app.next_novel_index(), Some(1));
+        app.current_novel = Some(app.novels[1].clone());
+        assert_eq!(app.next_novel_index(), Some(2));
+        // 系列的最后一卷读完后不推荐系列之外的小说
+        app.current_novel = Some(app.novels[2].clone());
+        assert_eq!(app.next_novel_index(), None);
+        app.current_novel = Some(app.novels[3].clone());
+        assert_eq!(app.next_novel_index(), None);
+    }
+
+    #[test]
+    fn test_favorite_novels_are_pinned_above_categories() {
+        let mut app = create_test_app();
+        for (title, category, size) in [("a", "", 10), ("b", "", 30), ("z", "武侠", 20)] {
+            let mut novel = Novel::new(PathBuf::from(format!("{}.txt", title)));
+            novel.category = category.to_string();
+            novel.file_size = size;
+            app.novels.push(novel);
+        }
+        app.sort_novels();
+        app.selected_novel_index = Some(2);
+
+        assert_eq!(app.toggle_bookshelf_favorite(2), Some(true));
+        let titles =
+            |app: &App| -> Vec<String> { app.novels.iter().map(|n| n.title.clone()).collect() };
+        assert_eq!(titles(&app), vec!["z", "a", "b"]);
+        assert_eq!(app.selected_novel_index, Some(0));
+        assert!(app.bookshelf_is_favorite(0));
+        // 收藏的小说不显示在分类下，也不计入最近阅读
+        assert_eq!(app.bookshelf_rows().len(), 3);
+        assert_eq!(app.bookshelf_last_read(0), None);
+
+        app.preferences.bookshelf_sort = BookshelfSort::FileSize;
+        app.sort_novels();
+        assert_eq!(titles(&app), vec!["z", "b", "a"]);
+
+        assert_eq!(app.toggle_bookshelf_favorite(0), Some(false));
+        assert_eq!(titles(&app), vec!["b", "a", "z"]);
+        assert!(matches!(
+            app.bookshelf_rows()[2],
+            BookshelfRow::Category { .. }
+        ));
+    }
+
+    #[test]
+    fn test_novel_tags_and_bookshelf_tag_filter() {
+        let mut app = create_test_app();
+        for title in ["a", "b", "c"] {
+            let mut novel = Novel::new(PathBuf::from(format!("{}.txt", title)));
+            novel.category = if title == "c" { "武侠" } else { "" }.to_string();
+            app.novels.push(novel);
+        }
+        app.sort_novels();
+
+        assert_eq!(app.add_novel_tags(0, "玄幻，已完结, 玄幻 ,"), 2);
+        assert_eq!(app.bookshelf_tags(0), ["玄幻", "已完结"]);
+        assert_eq!(app.add_novel_tags(2, "玄幻"), 1);
+        assert_eq!(app.library.all_tags(), vec!["已完结", "玄幻"]);
+        // 标签不计入最近阅读
+        assert_eq!(app.bookshelf_last_read(0), None);
+
+        app.selected_novel_index = Some(1);
+        app.cycle_bookshelf_tag_filter();
+        assert_eq!(app.bookshelf.tag_filter.as_deref(), Some("已完结"));
+        assert_eq!(app.bookshelf_rows(), vec![BookshelfRow::Novel(0)]);
+        // 光标所在的小说被筛掉后移到第一行
+        assert_eq!(app.selected_novel_index, Some(0));
+
+        app.cycle_bookshelf_tag_filter();
+        assert_eq!(
+            app.bookshelf_rows(),
+            vec![
+                BookshelfRow::Novel(0),
+                BookshelfRow::Category {
+                    name: "武侠".to_string(),
+                    count: 1,
+                    collapsed: false,
+                },
+                BookshelfRow::Novel(2),
+            ]
+        );
+        app.cycle_bookshelf_tag_filter();
+        assert_eq!(app.bookshelf.tag_filter, None);
+        assert_eq!(app.bookshelf_rows().len(), 4);
+
+        app.remove_novel_tag(0, 0);
+        assert_eq!(app.bookshelf_tags(0), ["已完结"]);
+    }
+
+    #[test]
+    fn test_bookshelf_rows_group_and_collapse_categories() {
+        let mut app = create_test_app();
+        for (title, category) in [("b", "武侠"), ("a", ""), ("c", "武侠"), ("d", "科幻")] {
+            let mut novel = Novel::new(PathBuf::from(format!("{}.txt", title)));
+            novel.category = category.to_string();
+            app.novels.push(novel);
+        }
+        app.sort_novels();
+        let category = |name: &str, count, collapsed| BookshelfRow::Category {
+            name: name.to_string(),
+            count,
+            collapsed,
+        };
+        assert_eq!(
+            app.bookshelf_rows(),
+            vec![
+                BookshelfRow::Novel(0),
+                category("武侠", 2, false),
+                BookshelfRow::Novel(1),
+                BookshelfRow::Novel(2),
+                category("科幻", 1, false),
+                BookshelfRow::Novel(3),
+            ]
+        );
+
+        // 折叠光标所在的分类后，光标移到分类标题上
+        app.selected_novel_index = Some(2);
+        let name = app.bookshelf_cursor_category().unwrap();
+        assert_eq!(name, "武侠");
+        app.set_bookshelf_category_collapsed(&name, true);
+        assert_eq!(app.selected_novel_index, None);
+        assert_eq!(app.selected_category.as_deref(), Some("武侠"));
+        let rows = app.bookshelf_rows();
+        assert_eq!(rows[1], category("武侠", 2, true));
+        assert_eq!(rows.len(), 4);
+        assert_eq!(app.bookshelf_selected_row(&rows), Some(1));
+
+        app.select_bookshelf_row(rows.get(3));
+        assert_eq!(app.selected_category, None);
+        assert_eq!(app.selected_novel_index, Some(3));
     }
 
     #[test]
@@ -529,6 +1708,91 @@ mod tests {
         assert_eq!(novels[2].path, z_path);
     }
 
+    #[test]
+    fn test_sort_novels_by_each_mode_keeps_selection() {
+        let mut app = create_test_app();
+        // (标题, 文件大小, 最近阅读时间, 阅读位置, 总行数)
+        let books = [
+            ("a", 30, 100, 90, 100),
+            ("b", 10, 300, 10, 100),
+            ("c", 20, 200, 50, 100),
+        ];
+        for (title, size, last_read, offset, total_lines) in books {
+            let path = PathBuf::from(format!("{}.txt", title));
+            let mut novel = Novel::new(path.clone());
+            novel.file_size = size;
+            app.novels.push(novel);
+            app.library.novels.push(NovelInfo {
+                title: title.to_string(),
+                path,
+                progress: ReadingProgress {
+                    scroll_offset: offset,
+                    ..Default::default()
+                },
+                last_read,
+                total_lines,
+                chapter_settings: Default::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
+            });
+        }
+        app.selected_novel_index = Some(2);
+        let titles =
+            |app: &App| -> Vec<String> { app.novels.iter().map(|n| n.title.clone()).collect() };
+
+        app.preferences.bookshelf_sort = BookshelfSort::RecentlyRead;
+        app.sort_novels();
+        assert_eq!(titles(&app), vec!["b", "c", "a"]);
+        // 仍选中同一本
+        assert_eq!(app.selected_novel_index, Some(1));
+
+        app.preferences.bookshelf_sort = BookshelfSort::Progress;
+        app.sort_novels();
+        assert_eq!(titles(&app), vec!["a", "c", "b"]);
+
+        app.preferences.bookshelf_sort = BookshelfSort::FileSize;
+        app.sort_novels();
+        assert_eq!(titles(&app), vec!["a", "c", "b"]);
+        app.library.novels.clear();
+        app.cycle_bookshelf_sort();
+        assert_eq!(app.preferences.bookshelf_sort, BookshelfSort::Title);
+        assert_eq!(titles(&app), vec!["a", "b", "c"]);
+        assert_eq!(app.selected_novel_index, Some(2));
+    }
+
+    #[test]
+    fn test_bookshelf_metadata_from_library() {
+        let mut app = create_test_app();
+        let path = PathBuf::from("a.txt");
+        app.novels.push(Novel::new(path.clone()));
+        assert_eq!(app.bookshelf_progress_percent(0), None);
+        assert_eq!(app.bookshelf_last_read(0), None);
+
+        app.library.novels.push(NovelInfo {
+            title: "a".to_string(),
+            path,
+            progress: ReadingProgress {
+                scroll_offset: 49,
+                ..Default::default()
+            },
+            last_read: 1_700_000_000,
+            total_lines: 200,
+            chapter_settings: Default::default(),
+            tags: Vec::new(),
+            favorite: false,
+            status: None,
+            note: String::new(),
+            queue: None,
+        });
+        assert_eq!(app.bookshelf_progress_percent(0), Some(25));
+        assert_eq!(app.bookshelf_last_read(0), Some(1_700_000_000));
+        assert!(app.bookshelf_chapters(0).is_empty());
+        assert!(app.bookshelf_chapters(1).is_empty());
+    }
+
     #[test]
     fn test_detect_orphaned_novels_collects_missing_and_resets_index() {
         let dir = tempdir().unwrap();
@@ -543,11 +1807,27 @@ mod tests {
                 title: "exists".to_string(),
                 path: existing,
                 progress: ReadingProgress::default(),
+                last_read: 0,
+                total_lines: 0,
+                chapter_settings: ChapterSettings::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
             },
             NovelInfo {
                 title: "missing".to_string(),
                 path: missing.clone(),
                 progress: ReadingProgress::default(),
+                last_read: 0,
+                total_lines: 0,
+                chapter_settings: ChapterSettings::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
             },
         ];
 
@@ -559,7 +1839,8 @@ mod tests {
     }
 
     #[test]
-    fn test_delete_novel_removes_file_and_updates_selection() {
+    fn test_delete_novel_moves_file_to_trash_and_restores() {
+        let _ = std::fs::remove_dir_all(Trash::dir());
         let dir = tempdir().unwrap();
         let first = dir.path().join("first.txt");
         let second = dir.path().join("second.txt");
@@ -572,12 +1853,31 @@ mod tests {
             NovelInfo {
                 title: "first".to_string(),
                 path: first.clone(),
-                progress: ReadingProgress::default(),
+                progress: ReadingProgress {
+                    scroll_offset: 7,
+                    ..ReadingProgress::default()
+                },
+                last_read: 0,
+                total_lines: 0,
+                chapter_settings: ChapterSettings::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
             },
             NovelInfo {
                 title: "second".to_string(),
                 path: second.clone(),
                 progress: ReadingProgress::default(),
+                last_read: 0,
+                total_lines: 0,
+                chapter_settings: ChapterSettings::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
             },
         ];
         app.settings.selected_delete_novel_index = Some(0);
@@ -589,6 +1889,79 @@ mod tests {
         assert_eq!(app.novels[0].path, second);
         assert_eq!(app.library.novels.len(), 1);
         assert_eq!(app.settings.selected_delete_novel_index, Some(0));
+
+        app.settings.trash = Trash::load();
+        assert_eq!(app.settings.trash.entries.len(), 1);
+        assert_eq!(app.settings.trash.entries[0].title(), "first");
+        assert!(app.settings.trash.entries[0].trashed_path().exists());
+
+        assert_eq!(app.restore_trashed_novel(0).unwrap(), first);
+        assert!(first.exists());
+        assert!(app.settings.trash.entries.is_empty());
+        let restored = app.library.novels.iter().find(|n| n.path == first);
+        assert_eq!(restored.unwrap().progress.scroll_offset, 7);
+    }
+
+    #[test]
+    fn test_bookshelf_chapter_label_uses_saved_position() {
+        let mut app = create_test_app();
+        let path = PathBuf::from("shelf.txt");
+        let mut novel = Novel::new(path.clone());
+        novel.chapters = vec![
+            Chapter {
+                title: "第一章".to_string(),
+                start_line: 0,
+            },
+            Chapter {
+                title: "第二章".to_string(),
+                start_line: 50,
+            },
+        ];
+        app.novels = vec![novel];
+        assert_eq!(app.bookshelf_chapter_label(0), None);
+
+        app.library.novels = vec![NovelInfo {
+            title: "shelf".to_string(),
+            path,
+            progress: ReadingProgress {
+                scroll_offset: 60,
+                ..ReadingProgress::default()
+            },
+            last_read: 0,
+            total_lines: 0,
+            chapter_settings: ChapterSettings::default(),
+            tags: Vec::new(),
+            favorite: false,
+            status: None,
+            note: String::new(),
+            queue: None,
+        }];
+        assert_eq!(app.bookshelf_chapter_label(0), Some("第2章".to_string()));
+    }
+
+    #[test]
+    fn test_migrate_legacy_chapter_settings_moves_to_library() {
+        let mut app = create_test_app();
+        let path = PathBuf::from("特殊.txt");
+        app.novels = vec![
+            Novel::new(path.clone()),
+            Novel::new(PathBuf::from("普通.txt")),
+        ];
+        app.preferences
+            .legacy_novel_chapter_patterns
+            .insert("特殊".to_string(), vec!["^【.+】$".to_string()]);
+        app.preferences
+            .legacy_auto_chapters_disabled
+            .insert("特殊".to_string());
+
+        app.migrate_legacy_chapter_settings();
+
+        let settings = app.library.chapter_settings(&path);
+        assert_eq!(settings.patterns, vec!["^【.+】$".to_string()]);
+        assert!(!settings.auto_detect);
+        assert_eq!(app.library.novels.len(), 1);
+        assert!(app.preferences.legacy_novel_chapter_patterns.is_empty());
+        assert!(app.preferences.legacy_auto_chapters_disabled.is_empty());
     }
 
     #[test]
@@ -674,6 +2047,14 @@ mod tests {
             title: "first".to_string(),
             path: PathBuf::from("first.txt"),
             progress: ReadingProgress::default(),
+            last_read: 0,
+            total_lines: 0,
+            chapter_settings: ChapterSettings::default(),
+            tags: Vec::new(),
+            favorite: false,
+            status: None,
+            note: String::new(),
+            queue: None,
         }];
         app.settings.selected_delete_novel_index = Some(0);
 
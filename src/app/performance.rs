@@ -0,0 +1,93 @@
+//! 设置中的性能选项：主循环间隔和空闲时的重绘间隔，立即保存到配置文件
+
+use super::App;
+use crate::config::{self, config};
+use crate::i18n::{tr, trf};
+
+/// 主循环间隔的可选值（毫秒）
+const TICK_CHOICES_MS: [u64; 6] = [20, 50, 100, 200, 500, 1000];
+
+/// 空闲重绘间隔的可选值（毫秒），0 表示每次循环都重绘
+const IDLE_REDRAW_CHOICES_MS: [u64; 7] = [0, 250, 500, 1000, 2000, 5000, 10_000];
+
+/// 性能页面中的一项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerformanceOption {
+    /// 主循环间隔（配置文件 `performance.tick_ms`）
+    TickRate,
+    /// 空闲重绘间隔（配置文件 `performance.idle_redraw_ms`）
+    IdleRedraw,
+}
+
+impl PerformanceOption {
+    /// 所有选项，按页面中的显示顺序
+    pub const ALL: [PerformanceOption; 2] =
+        [PerformanceOption::TickRate, PerformanceOption::IdleRedraw];
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            PerformanceOption::TickRate => tr("刷新间隔", "Tick rate"),
+            PerformanceOption::IdleRedraw => tr("空闲重绘", "Idle redraw"),
+        }
+    }
+}
+
+impl App {
+    /// 性能选项当前值的显示文本
+    pub fn performance_option_value(&self, option: PerformanceOption) -> String {
+        let performance = &config().performance;
+        match option {
+            PerformanceOption::TickRate => trf!("{} 毫秒", "{} ms", performance.tick_ms),
+            PerformanceOption::IdleRedraw if performance.idle_redraw_ms == 0 => {
+                tr("每次刷新都重绘", "Every tick").to_string()
+            }
+            PerformanceOption::IdleRedraw => {
+                trf!("每 {} 毫秒", "Every {} ms", performance.idle_redraw_ms)
+            }
+        }
+    }
+
+    /// 把一项性能选项调到上一档或下一档（`increase`）并写回配置文件
+    ///
+    /// # Returns
+    ///
+    /// 是否调整了（已到上下限时返回 `false`）。
+    pub fn adjust_performance_option(&mut self, option: PerformanceOption, increase: bool) -> bool {
+        let mut updated = (*config()).clone();
+        let performance = &mut updated.performance;
+        let changed = match option {
+            PerformanceOption::TickRate => {
+                step_choice(performance.tick_ms, &TICK_CHOICES_MS, increase)
+                    .map(|ms| performance.tick_ms = ms)
+            }
+            PerformanceOption::IdleRedraw => step_choice(
+                performance.idle_redraw_ms,
+                &IDLE_REDRAW_CHOICES_MS,
+                increase,
+            )
+            .map(|ms| performance.idle_redraw_ms = ms),
+        }
+        .is_some();
+        if changed && let Err(e) = config::save(updated) {
+            self.set_error(trf!(
+                "保存配置失败：{}",
+                "Failed to save the configuration: {}",
+                e
+            ));
+            return false;
+        }
+        changed
+    }
+}
+
+/// 可选值中比 `value` 大（`increase`）或小的最近一档
+///
+/// 配置文件中手动填写的值不在可选值中时，也能调到相邻的一档。
+fn step_choice(value: u64, choices: &[u64], increase: bool) -> Option<u64> {
+    if increase {
+        choices.iter().copied().find(|&choice| choice > value)
+    } else {
+        choices.iter().copied().rev().find(|&choice| choice < value)
+    }
+}
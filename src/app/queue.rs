@@ -0,0 +1,48 @@
+//! 待读队列：排好接下来要读的小说，在书架最上方单独显示
+
+use super::App;
+
+/// 待读队列在书架上显示为这个分类，排在收藏和其他分类前面
+pub const QUEUE_CATEGORY: &str = "待读队列";
+
+impl App {
+    /// 书架上的小说在待读队列中的位置（从 0 开始），不在队列中时返回 `None`
+    pub fn bookshelf_queue_position(&self, index: usize) -> Option<usize> {
+        self.bookshelf_novel_info(index)?.queue
+    }
+
+    /// 把书架上的小说加入待读队列末尾或移出队列，保存后重新排列书架
+    ///
+    /// # Returns
+    ///
+    /// 操作后是否在队列中；索引无效时返回 `None`。
+    pub fn toggle_bookshelf_queued(&mut self, index: usize) -> Option<bool> {
+        let path = self.novels.get(index)?.path.clone();
+        let queued = self.library.toggle_queued(&path);
+        self.save_queue();
+        Some(queued)
+    }
+
+    /// 在待读队列中把书架上的小说前移（`up`）或后移一位
+    ///
+    /// # Returns
+    ///
+    /// 是否移动了。
+    pub fn move_bookshelf_queued(&mut self, index: usize, up: bool) -> bool {
+        let Some(path) = self.novels.get(index).map(|n| n.path.clone()) else {
+            return false;
+        };
+        let moved = self.library.move_in_queue(&path, up);
+        if moved {
+            self.save_queue();
+        }
+        moved
+    }
+
+    fn save_queue(&mut self) {
+        if let Err(e) = self.library.save() {
+            self.set_error(format!("Failed to save: {}", e));
+        }
+        self.sort_novels();
+    }
+}
@@ -0,0 +1,284 @@
+//! 设置中的阅读选项：逐项调整，立即保存并作用于当前打开的小说
+
+use super::App;
+use crate::config::{self, AUTO_SCROLL_STEP_MS, MAX_AUTO_SCROLL_MS, MAX_INDENT, config};
+use crate::i18n::{tr, trf};
+use crate::model::novel::MAX_LINE_SPACING;
+use crate::model::preferences::MAX_PAGE_OVERLAP;
+
+/// 正文最大宽度的调整步长和范围（列），超过上限为不限制
+const MAX_WIDTH_STEP: u16 = 10;
+const MIN_MAX_WIDTH: u16 = 40;
+const MAX_MAX_WIDTH: u16 = 200;
+
+/// 阅读选项页面中的一项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReaderOption {
+    /// 当前小说的行间距（按书保存）
+    LineSpacing,
+    /// 段首缩进（配置文件 `reader.indent`）
+    Indent,
+    /// 正文最大宽度（配置文件 `reader.max_width`）
+    MaxWidth,
+    /// 翻页重叠行数（偏好设置 `page_overlap`）
+    PageOverlap,
+    /// 自动滚动间隔（配置文件 `reader.auto_scroll_ms`）
+    AutoScroll,
+}
+
+impl ReaderOption {
+    /// 所有选项，按页面中的显示顺序
+    pub const ALL: [ReaderOption; 5] = [
+        ReaderOption::LineSpacing,
+        ReaderOption::Indent,
+        ReaderOption::MaxWidth,
+        ReaderOption::PageOverlap,
+        ReaderOption::AutoScroll,
+    ];
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            ReaderOption::LineSpacing => tr("行间距", "Line spacing"),
+            ReaderOption::Indent => tr("段首缩进", "Indent"),
+            ReaderOption::MaxWidth => tr("最大宽度", "Max width"),
+            ReaderOption::PageOverlap => tr("翻页重叠", "Page overlap"),
+            ReaderOption::AutoScroll => tr("自动滚动", "Auto-scroll"),
+        }
+    }
+
+    /// 在命令行 `:set` 中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            ReaderOption::LineSpacing => "spacing",
+            ReaderOption::Indent => "indent",
+            ReaderOption::MaxWidth => "width",
+            ReaderOption::PageOverlap => "overlap",
+            ReaderOption::AutoScroll => "autoscroll",
+        }
+    }
+
+    /// 按命令行中的名称查找选项
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|option| option.name() == name)
+    }
+
+    /// 可以设置的取值范围，用于提示
+    fn range_hint(self) -> String {
+        match self {
+            ReaderOption::LineSpacing => format!("0-{}", MAX_LINE_SPACING),
+            ReaderOption::Indent => format!("0-{}", MAX_INDENT),
+            ReaderOption::MaxWidth => trf!(
+                "0（不限制）或 {}-{}",
+                "0 (unlimited) or {}-{}",
+                MIN_MAX_WIDTH,
+                MAX_MAX_WIDTH
+            ),
+            ReaderOption::PageOverlap => format!("0-{}", MAX_PAGE_OVERLAP),
+            ReaderOption::AutoScroll => {
+                trf!(
+                    "{}-{} 毫秒",
+                    "{}-{} ms",
+                    AUTO_SCROLL_STEP_MS,
+                    MAX_AUTO_SCROLL_MS
+                )
+            }
+        }
+    }
+}
+
+impl App {
+    /// 阅读选项当前值的显示文本
+    pub fn reader_option_value(&self, option: ReaderOption) -> String {
+        let reader = &config().reader;
+        match option {
+            ReaderOption::LineSpacing => match &self.current_novel {
+                Some(novel) => trf!(
+                    "{} 行（《{}》）",
+                    "{} lines ({})",
+                    novel.progress.line_spacing,
+                    novel.title
+                ),
+                None => tr("未打开小说", "No novel open").to_string(),
+            },
+            ReaderOption::Indent if reader.indent == 0 => tr("不缩进", "None").to_string(),
+            ReaderOption::Indent => trf!("{} 字", "{} chars", reader.indent),
+            ReaderOption::MaxWidth if reader.max_width == 0 => {
+                tr("不限制", "Unlimited").to_string()
+            }
+            ReaderOption::MaxWidth => trf!("{} 列", "{} columns", reader.max_width),
+            ReaderOption::PageOverlap => trf!("{} 行", "{} lines", self.preferences.page_overlap),
+            ReaderOption::AutoScroll => {
+                trf!(
+                    "每 {} 秒一行",
+                    "One line every {} s",
+                    reader.auto_scroll_ms as f64 / 1000.0
+                )
+            }
+        }
+    }
+
+    /// 增大（`increase`）或减小一项阅读选项并立即保存
+    ///
+    /// 行间距作用于当前打开的小说，没有打开小说时不调整；其他选项对所有小说生效。
+    ///
+    /// # Returns
+    ///
+    /// 是否调整了（已到上下限时返回 `false`）。
+    pub fn adjust_reader_option(&mut self, option: ReaderOption, increase: bool) -> bool {
+        match option {
+            ReaderOption::LineSpacing => {
+                let Some(novel) = &mut self.current_novel else {
+                    self.set_error(tr(
+                        "没有打开的小说，行间距按书保存",
+                        "No novel is open; line spacing is saved per book",
+                    ));
+                    return false;
+                };
+                let Some(spacing) = step(novel.progress.line_spacing, increase)
+                    .filter(|&spacing| spacing <= MAX_LINE_SPACING)
+                else {
+                    return false;
+                };
+                novel.progress.line_spacing = spacing;
+                self.save_current_progress();
+                true
+            }
+            ReaderOption::PageOverlap => {
+                let Some(overlap) = step(self.preferences.page_overlap, increase)
+                    .filter(|&overlap| overlap <= MAX_PAGE_OVERLAP)
+                else {
+                    return false;
+                };
+                self.preferences.page_overlap = overlap;
+                self.save_preferences();
+                true
+            }
+            ReaderOption::Indent | ReaderOption::MaxWidth | ReaderOption::AutoScroll => {
+                let mut updated = (*config()).clone();
+                let reader = &mut updated.reader;
+                let changed = match option {
+                    ReaderOption::Indent => step(reader.indent, increase)
+                        .filter(|&indent| indent <= MAX_INDENT)
+                        .map(|indent| reader.indent = indent),
+                    ReaderOption::MaxWidth => step_max_width(reader.max_width, increase)
+                        .map(|max_width| reader.max_width = max_width),
+                    _ => step_auto_scroll(reader.auto_scroll_ms, increase)
+                        .map(|ms| reader.auto_scroll_ms = ms),
+                }
+                .is_some();
+                if changed && let Err(e) = config::save(updated) {
+                    self.set_error(trf!(
+                        "保存配置失败：{}",
+                        "Failed to save the configuration: {}",
+                        e
+                    ));
+                    return false;
+                }
+                changed
+            }
+        }
+    }
+
+    /// 把一项阅读选项直接设为指定值并立即保存（命令行 `:set` 使用）
+    ///
+    /// 与 [`App::adjust_reader_option`] 相同，行间距作用于当前打开的小说，其他选项对所有小说生效。
+    ///
+    /// # Errors
+    ///
+    /// 值超出范围、行间距没有打开的小说或保存配置失败时返回错误提示。
+    pub fn set_reader_option(&mut self, option: ReaderOption, value: usize) -> Result<(), String> {
+        let out_of_range = || {
+            trf!(
+                "{} 的取值范围是 {}",
+                "{} must be {}",
+                option.name(),
+                option.range_hint()
+            )
+        };
+        match option {
+            ReaderOption::LineSpacing => {
+                let Some(novel) = &mut self.current_novel else {
+                    return Err(tr(
+                        "没有打开的小说，行间距按书保存",
+                        "No novel is open; line spacing is saved per book",
+                    )
+                    .to_string());
+                };
+                if value > MAX_LINE_SPACING {
+                    return Err(out_of_range());
+                }
+                novel.progress.line_spacing = value;
+                self.save_current_progress();
+            }
+            ReaderOption::PageOverlap => {
+                if value > MAX_PAGE_OVERLAP {
+                    return Err(out_of_range());
+                }
+                self.preferences.page_overlap = value;
+                self.save_preferences();
+            }
+            ReaderOption::Indent | ReaderOption::MaxWidth | ReaderOption::AutoScroll => {
+                let mut updated = (*config()).clone();
+                let reader = &mut updated.reader;
+                match option {
+                    ReaderOption::Indent if value <= MAX_INDENT => reader.indent = value,
+                    ReaderOption::MaxWidth
+                        if value == 0
+                            || (MIN_MAX_WIDTH as usize..=MAX_MAX_WIDTH as usize)
+                                .contains(&value) =>
+                    {
+                        reader.max_width = value as u16
+                    }
+                    ReaderOption::AutoScroll
+                        if (AUTO_SCROLL_STEP_MS..=MAX_AUTO_SCROLL_MS).contains(&(value as u64)) =>
+                    {
+                        reader.auto_scroll_ms = value as u64
+                    }
+                    _ => return Err(out_of_range()),
+                }
+                config::save(updated).map_err(|e| {
+                    trf!(
+                        "保存配置失败：{}",
+                        "Failed to save the configuration: {}",
+                        e
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 加一或减一，减到负数时返回 `None`
+fn step(value: usize, increase: bool) -> Option<usize> {
+    if increase {
+        value.checked_add(1)
+    } else {
+        value.checked_sub(1)
+    }
+}
+
+/// 最大宽度的下一档：按步长在上下限之间调整，超过上限为不限制（0）
+fn step_max_width(width: u16, increase: bool) -> Option<u16> {
+    match (width, increase) {
+        (0, true) => None,
+        (0, false) => Some(MAX_MAX_WIDTH),
+        (width, true) if width >= MAX_MAX_WIDTH => Some(0),
+        (width, true) => Some(width + MAX_WIDTH_STEP),
+        (width, false) if width <= MIN_MAX_WIDTH => None,
+        (width, false) => Some((width - MAX_WIDTH_STEP).max(MIN_MAX_WIDTH)),
+    }
+}
+
+/// 自动滚动间隔的下一档，超出范围时返回 `None`
+fn step_auto_scroll(ms: u64, increase: bool) -> Option<u64> {
+    let ms = if increase {
+        ms + AUTO_SCROLL_STEP_MS
+    } else {
+        ms.saturating_sub(AUTO_SCROLL_STEP_MS)
+    };
+    (AUTO_SCROLL_STEP_MS..=MAX_AUTO_SCROLL_MS)
+        .contains(&ms)
+        .then_some(ms)
+}
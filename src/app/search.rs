@@ -1,42 +1,329 @@
-use super::App;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use super::{App, SearchScope};
+use crate::i18n::{tr, trf};
+use crate::model::library::Library;
+use crate::model::matcher::SearchQuery;
+
+/// 达到此行数的小说改为在后台线程中搜索
+pub const THREADED_SEARCH_MIN_LINES: usize = 20_000;
+
+/// 后台搜索每处理多少行检查一次取消标志并发送一批结果
+const SEARCH_CHUNK_LINES: usize = 4096;
+
+/// 输入停止多久后才开始后台搜索
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// 后台搜索线程发回的消息
+pub enum SearchMessage {
+    /// 一批匹配结果（行号，内容），按行号递增
+    Matches(Vec<(usize, String)>),
+    /// 全文搜索完毕
+    Done,
+}
+
+/// 导出文件所在的目录（数据目录下的 `exports/`）
+pub(super) fn exports_dir() -> PathBuf {
+    Library::get_progress_path().with_file_name("exports")
+}
+
+/// 把路径中不允许的字符替换为下划线
+pub(super) fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect()
+}
 
 impl App {
     /// 在当前小说内容中搜索关键词
     ///
-    /// 执行不区分大小写的搜索，更新搜索结果列表。
+    /// 执行不区分大小写的搜索，更新搜索结果列表。关键词按 [`SearchQuery`] 解析，
+    /// 支持 `+词`/`-词` 组合；开启模糊模式时每个词都可以用拼音或带错字的写法。
     ///
     /// # Note
     ///
-    /// 搜索输入为空时会清空结果列表。
+    /// 只搜索 [`App::search_line_range`] 给出的行区间；关键词为空或范围无效时会清空结果列表。
+    /// 区间行数超过 [`THREADED_SEARCH_MIN_LINES`] 时
+    /// 不会立即搜索，而是记录请求，由 [`App::poll_search`] 防抖后交给后台线程。
     pub fn perform_search(&mut self) {
-        if let Some(novel) = &self.current_novel {
-            if !self.search.input.is_empty() {
-                self.search.results.clear();
+        let Some(novel) = &self.current_novel else {
+            return;
+        };
+        self.search.cancel();
 
-                let search_term = self.search.input.to_lowercase();
+        let range = self.search_line_range();
+        let query = SearchQuery::parse(self.search.term());
+        let Some(range) = range.filter(|_| !query.is_empty()) else {
+            self.search.results.clear();
+            self.search.selected_index = None;
+            return;
+        };
 
-                for (line_num, line) in novel.lines().iter().enumerate() {
-                    if line.to_lowercase().contains(&search_term) {
-                        self.search.results.push((line_num, line.clone()));
-                    }
+        if range.len() >= THREADED_SEARCH_MIN_LINES {
+            self.search.pending_since = Some(Instant::now());
+            return;
+        }
+
+        let fuzzy = self.search.fuzzy;
+        self.search.results = novel.lines()[range.clone()]
+            .iter()
+            .zip(range)
+            .filter(|(line, _)| query.matches(line, fuzzy))
+            .map(|(line, line_num)| (line_num, line.clone()))
+            .collect();
+        self.reset_search_selection();
+    }
+
+    /// 根据搜索范围计算要搜索的行区间
+    ///
+    /// # Returns
+    ///
+    /// 没有打开小说，或章节范围模式下输入中没有有效的范围时返回 `None`。
+    /// 小说没有章节时，“本章”即为全书。
+    pub fn search_line_range(&self) -> Option<Range<usize>> {
+        let novel = self.current_novel.as_ref()?;
+        let len = novel.line_count();
+        let chapters = &novel.chapters;
+        let chapter_lines = |first: usize, last: usize| {
+            let start = chapters[first].start_line.min(len);
+            let end = chapters
+                .get(last + 1)
+                .map_or(len, |next| next.start_line.min(len));
+            start..end.max(start)
+        };
+
+        match self.search.scope {
+            SearchScope::Book => Some(0..len),
+            SearchScope::Chapter if chapters.is_empty() => Some(0..len),
+            SearchScope::Chapter => {
+                let current = self.find_current_chapter_index().unwrap_or(0);
+                Some(chapter_lines(current, current))
+            }
+            SearchScope::ChapterRange => {
+                let (start, end) = self.search.chapter_range()?;
+                if start > chapters.len() {
+                    return None;
                 }
+                Some(chapter_lines(start - 1, end.min(chapters.len()) - 1))
+            }
+        }
+    }
 
-                if !self.search.results.is_empty() {
-                    let should_reset = match self.search.selected_index {
-                        None => true,
-                        Some(idx) => idx >= self.search.results.len(),
-                    };
-                    if should_reset {
-                        self.search.selected_index = Some(0);
-                    }
-                } else {
-                    self.search.selected_index = None;
+    /// 启动后台搜索线程，结果分批通过通道返回
+    ///
+    /// 之前的后台搜索会被取消，结果列表被清空。
+    pub(crate) fn start_background_search(&mut self) {
+        self.search.cancel();
+        let Some(range) = self.search_line_range() else {
+            return;
+        };
+        let Some(novel) = &self.current_novel else {
+            return;
+        };
+
+        let lines = novel.shared_lines();
+        let query = SearchQuery::parse(self.search.term());
+        let fuzzy = self.search.fuzzy;
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.search.rx = Some(rx);
+        self.search.cancel = Some(Arc::clone(&cancel));
+        self.search.results.clear();
+        self.search.selected_index = None;
+
+        std::thread::spawn(move || {
+            let first_line = range.start;
+            for (chunk_index, chunk) in lines[range].chunks(SEARCH_CHUNK_LINES).enumerate() {
+                if cancel.load(Ordering::Relaxed) {
+                    return;
+                }
+                let base = first_line + chunk_index * SEARCH_CHUNK_LINES;
+                let matches: Vec<(usize, String)> = chunk
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, line)| query.matches(line, fuzzy))
+                    .map(|(offset, line)| (base + offset, line.clone()))
+                    .collect();
+                if !matches.is_empty() && tx.send(SearchMessage::Matches(matches)).is_err() {
+                    return;
+                }
+            }
+            tx.send(SearchMessage::Done).ok();
+        });
+    }
+
+    /// 轮询后台搜索（主循环中调用）
+    ///
+    /// 防抖时间到达后启动后台搜索，并把已返回的结果追加到结果列表。
+    pub fn poll_search(&mut self) {
+        if let Some(since) = self.search.pending_since
+            && since.elapsed() >= SEARCH_DEBOUNCE
+        {
+            self.start_background_search();
+        }
+
+        let Some(rx) = &self.search.rx else { return };
+        let mut finished = false;
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                SearchMessage::Matches(matches) => self.search.results.extend(matches),
+                SearchMessage::Done => {
+                    finished = true;
+                    break;
                 }
-            } else {
-                self.search.results.clear();
-                self.search.selected_index = None;
             }
         }
+        if finished {
+            self.search.rx = None;
+            self.search.cancel = None;
+        }
+        self.reset_search_selection();
+    }
+
+    /// 结果变化后修正选中项：越界或未选中时选中第一条，没有结果时取消选中
+    fn reset_search_selection(&mut self) {
+        if self.search.results.is_empty() {
+            self.search.selected_index = None;
+        } else if self
+            .search
+            .selected_index
+            .is_none_or(|idx| idx >= self.search.results.len())
+        {
+            self.search.selected_index = Some(0);
+        }
+    }
+
+    /// 把当前搜索结果导出为 Markdown 文件
+    ///
+    /// 文件保存在数据目录下的 `exports/` 中，每条结果包含行号、所在章节和正文。
+    ///
+    /// # Returns
+    ///
+    /// 导出文件的路径；没有打开小说或写入失败时返回错误。
+    pub fn export_search_results(&self) -> std::io::Result<PathBuf> {
+        let novel = self
+            .current_novel
+            .as_ref()
+            .ok_or_else(|| std::io::Error::other(tr("没有打开的小说", "No novel is open")))?;
+
+        let mut text = trf!(
+            "# 《{}》搜索结果：{}\n\n范围：{} · 共 {} 条\n\n",
+            "# Search results in {}: {}\n\nScope: {} · {} results\n\n",
+            novel.title,
+            self.search.term(),
+            self.search.scope.label(),
+            self.search.results.len()
+        );
+        for (line_num, content) in &self.search.results {
+            let chapter = if novel.chapters.is_empty() {
+                String::new()
+            } else {
+                let index = Self::find_chapter_index(&novel.chapters, *line_num);
+                format!(" · {}", novel.chapters[index].title.trim())
+            };
+            text.push_str(&trf!(
+                "- 第 {} 行{}：{}\n",
+                "- Line {}{}: {}\n",
+                line_num + 1,
+                chapter,
+                content.trim()
+            ));
+        }
+
+        let dir = exports_dir();
+        std::fs::create_dir_all(&dir)?;
+        let file_name = format!(
+            "{}-搜索-{}.md",
+            sanitize_file_name(&novel.title),
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let path = dir.join(file_name);
+        std::fs::write(&path, text)?;
+        Ok(path)
+    }
+
+    /// 收集全文中包含关键词的所有行号（不区分大小写）
+    pub fn collect_match_lines(&self, term: &str) -> Vec<usize> {
+        let Some(novel) = &self.current_novel else {
+            return Vec::new();
+        };
+        if term.is_empty() {
+            return Vec::new();
+        }
+        let term = term.to_lowercase();
+        novel
+            .lines()
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.to_lowercase().contains(&term))
+            .map(|(line_num, _)| line_num)
+            .collect()
+    }
+
+    /// 当前阅读位置在高亮关键词匹配中的序号
+    ///
+    /// # Returns
+    ///
+    /// `(序号, 总数)`，序号从 1 开始，为不晚于当前首行的最后一处匹配；
+    /// 首行在第一处匹配之前时序号为 0。没有高亮或没有匹配行时返回 `None`。
+    pub fn match_position(&self) -> Option<(usize, usize)> {
+        self.reader.highlight_term.as_ref()?;
+        let novel = self.current_novel.as_ref()?;
+        let lines = &self.reader.match_lines;
+        if lines.is_empty() {
+            return None;
+        }
+        let top = novel.progress.scroll_offset;
+        Some((lines.partition_point(|&line| line <= top), lines.len()))
+    }
+
+    /// 从指定行开始查找下一处包含关键词的行（不区分大小写，到达首尾后循环）
+    ///
+    /// # Arguments
+    ///
+    /// * `term` - 关键词
+    /// * `from` - 起始行号
+    /// * `forward` - 是否向后查找
+    /// * `skip_current` - 是否跳过起始行本身（`n`/`N` 需要跳过，增量查找不跳过）
+    ///
+    /// # Returns
+    ///
+    /// 匹配的行号；关键词为空、没有打开小说或全文无匹配时返回 `None`。
+    pub fn find_match_line(
+        &self,
+        term: &str,
+        from: usize,
+        forward: bool,
+        skip_current: bool,
+    ) -> Option<usize> {
+        let novel = self.current_novel.as_ref()?;
+        if term.is_empty() {
+            return None;
+        }
+        let term = term.to_lowercase();
+        let lines = novel.lines();
+        let len = lines.len();
+        let from = from.min(len);
+        let is_match = |&line: &usize| lines[line].to_lowercase().contains(&term);
+
+        if forward {
+            let start = if skip_current { from + 1 } else { from };
+            (start..len).chain(0..start.min(len)).find(is_match)
+        } else {
+            let end = if skip_current {
+                from
+            } else {
+                (from + 1).min(len)
+            };
+            (0..end).rev().chain((end..len).rev()).find(is_match)
+        }
     }
 
     /// 根据当前阅读位置查找对应的章节索引
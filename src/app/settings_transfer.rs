@@ -0,0 +1,102 @@
+//! 设置导出导入：把配置文件、偏好设置和 WebDAV 配置打包成一个文件，便于迁移到另一台电脑
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::App;
+use super::search::exports_dir;
+use crate::config::{self, AppConfig, config};
+use crate::i18n::trf;
+use crate::model::preferences::Preferences;
+use crate::sync::config::WebDavConfig;
+
+/// 导出的设置文件名，位于 `exports/` 中
+const SETTINGS_EXPORT_FILENAME: &str = "fish_reader-设置.json";
+
+/// 导出的全部设置
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsBundle {
+    /// `config.toml` 的内容
+    config: AppConfig,
+    /// 偏好设置（含主题）
+    preferences: Preferences,
+    /// WebDAV 配置，不含密码
+    webdav: WebDavConfig,
+}
+
+impl App {
+    /// 设置导出文件的路径
+    pub fn settings_export_path() -> PathBuf {
+        exports_dir().join(SETTINGS_EXPORT_FILENAME)
+    }
+
+    /// 把配置文件、偏好设置和 WebDAV 配置（不含密码）导出到 `exports/` 中的一个 JSON 文件
+    ///
+    /// # Returns
+    ///
+    /// 导出文件的路径。
+    ///
+    /// # Errors
+    ///
+    /// 返回 IO 操作或序列化错误。
+    pub fn export_settings(&self) -> std::io::Result<PathBuf> {
+        let bundle = SettingsBundle {
+            config: (*config()).clone(),
+            preferences: self.preferences.clone(),
+            webdav: WebDavConfig {
+                password: String::new(),
+                ..self.webdav_config.clone()
+            },
+        };
+        let path = Self::settings_export_path();
+        std::fs::create_dir_all(exports_dir())?;
+        std::fs::write(&path, serde_json::to_string_pretty(&bundle)?)?;
+        Ok(path)
+    }
+
+    /// 从 `exports/` 中的设置文件导入全部设置并立即保存
+    ///
+    /// 数据目录名、默认小说目录和额外小说目录都与本机有关，保留本机的设置；
+    /// 导入的 WebDAV 配置沿用本机的密码。先检查整个设置文件，全部有效后才写入。
+    ///
+    /// # Errors
+    ///
+    /// 文件不存在、无法解析或含有无效配置项时返回错误，此时设置不变；保存失败时返回 IO 错误。
+    pub fn import_settings(&mut self) -> std::io::Result<()> {
+        let content = std::fs::read_to_string(Self::settings_export_path())?;
+        let SettingsBundle {
+            config: mut imported,
+            mut preferences,
+            mut webdav,
+        } = serde_json::from_str(&content)?;
+
+        let invalid = imported.reset_invalid();
+        if !invalid.is_empty() {
+            let errors: Vec<String> = invalid.iter().map(|option| option.message()).collect();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                trf!(
+                    "设置文件中有无效的配置项：{}",
+                    "The settings file has invalid options: {}",
+                    errors.join("; ")
+                ),
+            ));
+        }
+        let current = config();
+        imported.dir_name = current.dir_name.clone();
+        imported.novels_dir = current.novels_dir.clone();
+        preferences.library_dirs = self.preferences.library_dirs.clone();
+        webdav.password = self.webdav_config.password.clone();
+
+        if *current != imported {
+            config::save(imported)?;
+        }
+        preferences.save()?;
+        webdav.save()?;
+        self.preferences = preferences;
+        self.webdav_config = webdav;
+        crate::i18n::set_language(self.preferences.language);
+        self.sort_novels();
+        Ok(())
+    }
+}
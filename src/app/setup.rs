@@ -0,0 +1,133 @@
+//! 首次启动向导：书架为空时引导选择小说目录、导入第一本小说和配置 WebDAV
+
+use super::App;
+use crate::config::expand_home;
+use crate::i18n::{tr, trf};
+use crate::state::{AppState, SettingsMode};
+
+/// 首次启动向导的步骤
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SetupStep {
+    /// 选择或创建默认小说目录
+    #[default]
+    NovelsDir,
+    /// 导入小说文件（可跳过）
+    Import,
+    /// 是否配置 WebDAV 同步（可跳过）
+    WebDav,
+}
+
+impl SetupStep {
+    /// 步骤总数
+    pub const COUNT: usize = 3;
+
+    /// 步骤序号（从 1 开始）
+    pub fn number(self) -> usize {
+        match self {
+            SetupStep::NovelsDir => 1,
+            SetupStep::Import => 2,
+            SetupStep::WebDav => 3,
+        }
+    }
+
+    /// 步骤标题
+    pub fn title(self) -> &'static str {
+        match self {
+            SetupStep::NovelsDir => tr("小说目录", "Novel directory"),
+            SetupStep::Import => tr("导入小说", "Import a novel"),
+            SetupStep::WebDav => tr("WebDAV 同步", "WebDAV sync"),
+        }
+    }
+}
+
+/// 首次启动向导的状态
+#[derive(Default)]
+pub struct SetupState {
+    /// 当前步骤
+    pub step: SetupStep,
+    /// 当前步骤的输入内容（小说目录或要导入的文件路径）
+    pub input: String,
+}
+
+impl App {
+    /// 是否需要显示首次启动向导：书架为空且从未完成或跳过向导
+    pub fn needs_setup(&self) -> bool {
+        self.novels.is_empty() && !self.preferences.setup_done
+    }
+
+    /// 进入首次启动向导，从选择小说目录开始
+    pub fn start_setup(&mut self) {
+        self.setup.step = SetupStep::NovelsDir;
+        self.setup.input = Self::get_novels_dir().display().to_string();
+        self.state = AppState::Setup;
+    }
+
+    /// 确认向导中输入的小说目录：与当前目录相同时只创建目录，否则更换默认目录
+    ///
+    /// # Errors
+    ///
+    /// 目录无效或无法创建时返回错误，此时停留在这一步。
+    pub fn setup_confirm_novels_dir(&mut self) -> std::io::Result<()> {
+        let input = self.setup.input.trim().trim_matches(['"', '\'']);
+        if !input.is_empty() && expand_home(input) == Self::get_novels_dir() {
+            std::fs::create_dir_all(Self::get_novels_dir())?;
+        } else {
+            let input = input.to_string();
+            self.set_novels_dir(&input, false)?;
+        }
+        self.setup.step = SetupStep::Import;
+        self.setup.input.clear();
+        Ok(())
+    }
+
+    /// 导入向导中输入的文件，输入为空时进入下一步
+    ///
+    /// 导入成功后停留在这一步，可以继续导入其他文件。
+    ///
+    /// # Errors
+    ///
+    /// 文件无效或复制失败时返回错误。
+    pub fn setup_import(&mut self) -> std::io::Result<()> {
+        let input = std::mem::take(&mut self.setup.input);
+        if input.trim().is_empty() {
+            self.setup.step = SetupStep::WebDav;
+            return Ok(());
+        }
+        let path = self.import_novel(&input, false).inspect_err(|_| {
+            self.setup.input = input;
+        })?;
+        self.set_info(trf!(
+            "已导入 {}，可继续输入或留空进入下一步",
+            "Imported {}; enter another file or leave empty to continue",
+            path.display()
+        ));
+        Ok(())
+    }
+
+    /// 结束首次启动向导并记住，以后启动不再显示
+    ///
+    /// # Arguments
+    ///
+    /// * `open_webdav` - 是否接着打开 WebDAV 同步配置，否则回到书架
+    pub fn finish_setup(&mut self, open_webdav: bool) {
+        self.preferences.setup_done = true;
+        self.save_preferences();
+        self.setup = SetupState::default();
+        if open_webdav {
+            self.state = AppState::Settings;
+            self.open_webdav_config();
+        } else {
+            self.state = AppState::Bookshelf;
+        }
+    }
+
+    /// 打开设置中的 WebDAV 同步配置，编辑内容从当前配置开始
+    pub fn open_webdav_config(&mut self) {
+        self.settings.mode = SettingsMode::WebDavConfig;
+        let state = &mut self.settings.webdav_config_state;
+        state.temp_config = self.webdav_config.clone();
+        state.selected_field = 0;
+        state.edit_mode = false;
+        state.show_password = false;
+    }
+}
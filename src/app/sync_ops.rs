@@ -1,8 +1,9 @@
+use crate::i18n::tr;
 use crate::model::library::Library;
 use crate::sync::sync_engine::{SyncEngine, SyncMessage};
 use crate::ui::sync_status::SyncStatus;
 
-use super::App;
+use super::{App, ConfirmAction};
 
 impl App {
     /// 手动上传同步（后台线程执行）
@@ -11,14 +12,14 @@ impl App {
             return;
         }
         if !self.webdav_config.is_configured() {
-            self.set_error("请先配置 WebDAV");
+            self.set_error(tr("请先配置 WebDAV", "Configure WebDAV first"));
             return;
         }
 
         let config = self.webdav_config.clone();
         let (tx, rx) = std::sync::mpsc::channel();
         self.sync_rx = Some(rx);
-        self.sync_status = SyncStatus::InProgress("准备上传...".into());
+        self.sync_status = SyncStatus::InProgress(tr("准备上传...", "Preparing upload...").into());
 
         std::thread::spawn(move || match SyncEngine::new(&config) {
             Ok(engine) => engine.sync_up(&tx),
@@ -28,20 +29,36 @@ impl App {
         });
     }
 
+    /// 确认后从 WebDAV 下载：下载会覆盖本地的小说文件和阅读进度
+    pub fn request_download(&mut self) {
+        if self.sync_status.is_busy() {
+            return;
+        }
+        if !self.webdav_config.is_configured() {
+            self.set_error(tr("请先配置 WebDAV", "Configure WebDAV first"));
+            return;
+        }
+        self.request_confirm(
+            tr("从 WebDAV 下载会用远程数据覆盖本地小说和阅读进度，确定继续？", "Downloading from WebDAV overwrites local novels and reading progress with the remote data. Continue?"),
+            ConfirmAction::SyncDown,
+        );
+    }
+
     /// 手动下载同步（后台线程执行）
     pub fn trigger_download(&mut self) {
         if self.sync_status.is_busy() {
             return;
         }
         if !self.webdav_config.is_configured() {
-            self.set_error("请先配置 WebDAV");
+            self.set_error(tr("请先配置 WebDAV", "Configure WebDAV first"));
             return;
         }
 
         let config = self.webdav_config.clone();
         let (tx, rx) = std::sync::mpsc::channel();
         self.sync_rx = Some(rx);
-        self.sync_status = SyncStatus::InProgress("准备下载...".into());
+        self.sync_status =
+            SyncStatus::InProgress(tr("准备下载...", "Preparing download...").into());
 
         std::thread::spawn(move || match SyncEngine::new(&config) {
             Ok(engine) => engine.sync_down(&tx),
@@ -61,17 +78,16 @@ impl App {
                     self.sync_status = SyncStatus::InProgress(text);
                 }
                 SyncMessage::UploadComplete => {
-                    self.sync_status = SyncStatus::Success("上传完成".into());
+                    self.sync_status =
+                        SyncStatus::Success(tr("上传完成", "Upload complete").into());
                     self.sync_rx = None;
                     return;
                 }
                 SyncMessage::DownloadComplete => {
-                    if let Ok(novels) = Self::load_novels_from_dir(&Self::get_novels_dir()) {
-                        self.novels = novels;
-                        self.selected_novel_index = Self::first_index_if_any(self.novels.len());
-                    }
                     self.library = Library::load();
-                    self.sync_status = SyncStatus::Success("下载完成".into());
+                    self.reload_novels();
+                    self.sync_status =
+                        SyncStatus::Success(tr("下载完成", "Download complete").into());
                     self.sync_rx = None;
                     return;
                 }
@@ -0,0 +1,90 @@
+use super::App;
+
+impl App {
+    /// 书架上小说的标签，没有记录时为空
+    pub fn bookshelf_tags(&self, index: usize) -> &[String] {
+        self.bookshelf_novel_info(index)
+            .map(|info| info.tags.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// 给书架上的小说添加标签并保存
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 小说在 novels 列表中的索引
+    /// * `input` - 用户输入，可用逗号分隔多个标签
+    ///
+    /// # Returns
+    ///
+    /// 新增的标签数量，已有的标签和空标签会被忽略。
+    pub fn add_novel_tags(&mut self, index: usize, input: &str) -> usize {
+        let Some(novel) = self.novels.get(index) else {
+            return 0;
+        };
+        let tags = self.library.tags_mut(&novel.path);
+        let mut added = 0;
+        for tag in input.split([',', '，']).map(str::trim) {
+            if !tag.is_empty() && !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+                added += 1;
+            }
+        }
+        if added > 0 {
+            self.save_library();
+        }
+        added
+    }
+
+    /// 删除书架上小说的一个标签并保存
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - 小说在 novels 列表中的索引
+    /// * `tag_index` - 标签在该书标签列表中的索引
+    pub fn remove_novel_tag(&mut self, index: usize, tag_index: usize) {
+        let Some(novel) = self.novels.get(index) else {
+            return;
+        };
+        let tags = self.library.tags_mut(&novel.path);
+        if tag_index < tags.len() {
+            tags.remove(tag_index);
+            self.save_library();
+        }
+    }
+
+    /// 书架上小说的备注，没有记录时为空
+    pub fn bookshelf_note(&self, index: usize) -> &str {
+        self.bookshelf_novel_info(index)
+            .map_or("", |info| info.note.as_str())
+    }
+
+    /// 设置书架上小说的备注并保存，输入为空时清除
+    pub fn set_novel_note(&mut self, index: usize, note: &str) {
+        let Some(novel) = self.novels.get(index) else {
+            return;
+        };
+        self.library.set_note(&novel.path, note);
+        self.save_library();
+    }
+
+    /// 切换书架的标签筛选：依次筛选每个标签，最后回到显示全部
+    pub fn cycle_bookshelf_tag_filter(&mut self) {
+        let tags = self.library.all_tags();
+        let next = match &self.bookshelf.tag_filter {
+            None => tags.first(),
+            Some(current) => tags
+                .iter()
+                .position(|tag| tag == current)
+                .and_then(|position| tags.get(position + 1)),
+        };
+        self.bookshelf.tag_filter = next.cloned();
+        self.refresh_bookshelf_selection();
+    }
+
+    fn save_library(&mut self) {
+        if let Err(e) = self.library.save() {
+            self.set_error(format!("Failed to save tags: {}", e));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! 刷新书架：监视小说目录，程序运行期间在外部添加或删除小说文件时自动重新扫描，也可以手动重新扫描
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+use super::App;
+use crate::config::config;
+use crate::i18n::{tr, trf};
+
+/// 最后一次文件变化后等待这么久再刷新，避免复制大文件时反复扫描
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// 小说目录的文件监视器
+pub struct NovelWatcher {
+    /// 监视器本身，丢弃后停止监视
+    _watcher: RecommendedWatcher,
+    /// 文件系统事件接收端
+    rx: Receiver<notify::Result<notify::Event>>,
+    /// 最近一次相关变化的时间，`None` 表示没有待刷新的变化
+    pending_since: Option<Instant>,
+}
+
+/// 事件是否涉及小说文件或子文件夹（分类）的增删改名
+fn is_relevant(event: &notify::Event) -> bool {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(_)
+    ) {
+        return false;
+    }
+    event.paths.iter().any(|path| {
+        let is_novel = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| config().is_supported_extension(ext));
+        // 被删除的目录已无法判断类型，没有扩展名的路径都当作目录
+        is_novel || path.extension().is_none() && !is_hidden(path)
+    })
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+impl App {
+    /// 开始（或重新）监视默认小说目录和所有额外目录
+    ///
+    /// 监视器创建失败时（如系统不支持）只是不自动刷新，不影响其他功能。
+    pub fn watch_novel_dirs(&mut self) {
+        self.novel_watcher = None;
+        let (tx, rx) = std::sync::mpsc::channel();
+        let Ok(mut watcher) = notify::recommended_watcher(tx) else {
+            return;
+        };
+        let dirs =
+            std::iter::once(Self::get_novels_dir()).chain(self.preferences.library_dirs.clone());
+        for dir in dirs {
+            // 不存在的目录跳过，其余目录照常监视
+            let _ = watcher.watch(&dir, RecursiveMode::Recursive);
+        }
+        self.novel_watcher = Some(NovelWatcher {
+            _watcher: watcher,
+            rx,
+            pending_since: None,
+        });
+    }
+
+    /// 处理文件监视事件（主循环中调用）
+    ///
+    /// 小说目录变化稳定一段时间后重新扫描书架，并重新检测文件已被删除的孤立记录。
+    pub fn poll_novel_watcher(&mut self) {
+        let Some(watcher) = &mut self.novel_watcher else {
+            return;
+        };
+        loop {
+            match watcher.rx.try_recv() {
+                Ok(Ok(event)) if is_relevant(&event) => {
+                    watcher.pending_since = Some(Instant::now());
+                }
+                Ok(_) => {}
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.novel_watcher = None;
+                    return;
+                }
+            }
+        }
+        if watcher
+            .pending_since
+            .is_none_or(|since| since.elapsed() < WATCH_DEBOUNCE)
+        {
+            return;
+        }
+        watcher.pending_since = None;
+
+        let (added, removed) = self.rescan_novels();
+        if added > 0 || removed > 0 {
+            self.set_info(rescan_message(added, removed));
+        }
+    }
+
+    /// 手动重新扫描书架，并提示扫描结果
+    pub fn rescan_bookshelf(&mut self) {
+        let (added, removed) = self.rescan_novels();
+        let message = trf!(
+            "已重新扫描，共 {} 本：{}",
+            "Rescanned, {} novels: {}",
+            self.novels.len(),
+            rescan_message(added, removed)
+        );
+        self.set_info(message);
+    }
+
+    /// 重新扫描所有小说目录并重新检测孤立记录
+    ///
+    /// # Returns
+    ///
+    /// 新发现的小说数和已从目录中移除的小说数。
+    fn rescan_novels(&mut self) -> (usize, usize) {
+        let before: HashSet<PathBuf> = self.novels.iter().map(|n| n.path.clone()).collect();
+        self.reload_novels();
+        // 正在清理孤立记录时保留选中位置
+        let selected = self.settings.selected_orphaned_index;
+        self.detect_orphaned_novels();
+        let count = self.settings.orphaned_novels.len();
+        self.settings.selected_orphaned_index = selected
+            .map(|index| index.min(count.saturating_sub(1)))
+            .filter(|_| count > 0);
+
+        let added = self
+            .novels
+            .iter()
+            .filter(|n| !before.contains(&n.path))
+            .count();
+        let removed = before.len() + added - self.novels.len();
+        (added, removed)
+    }
+}
+
+/// 重新扫描结果的提示
+///
+/// # Arguments
+///
+/// * `added` - 新发现的小说数
+/// * `removed` - 已移除的小说数
+fn rescan_message(added: usize, removed: usize) -> String {
+    match (added, removed) {
+        (0, 0) => tr("书架没有变化", "bookshelf unchanged").to_string(),
+        (added, 0) => trf!("发现 {} 本新小说", "{} new novels found", added),
+        (0, removed) => trf!(
+            "{} 本小说已从目录中移除",
+            "{} novels removed from the directories",
+            removed
+        ),
+        (added, removed) => trf!(
+            "发现 {} 本新小说，{} 本已移除",
+            "{} new novels found, {} removed",
+            added,
+            removed
+        ),
+    }
+}
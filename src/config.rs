@@ -1,43 +1,603 @@
+//! 应用程序配置：启动时从 `~/.fish_reader/config.toml` 读取，缺省的字段使用默认值
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+use crate::i18n::{tr, trf};
+
+/// 配置文件所在的目录名，固定不变（`dir_name` 只决定数据目录）
+const CONFIG_DIR_NAME: &str = ".fish_reader";
+
+/// 配置文件名
+const CONFIG_FILENAME: &str = "config.toml";
+
+/// 进度文件名
+pub const PROGRESS_FILENAME: &str = "progress.json";
+
+/// 备份文件后缀（完整格式: {PROGRESS_FILENAME}.{BACKUP_SUFFIX}.{timestamp}）
+pub const BACKUP_SUFFIX: &str = "backup";
+
+/// 设置菜单项数量
+pub const SETTINGS_MENU_COUNT: usize = 11;
+
+/// 首行缩进的上限（全角空格数）
+pub const MAX_INDENT: usize = 4;
+
+/// 自动滚动间隔的调整步长和范围（毫秒）
+pub const AUTO_SCROLL_STEP_MS: u64 = 500;
+pub const MAX_AUTO_SCROLL_MS: u64 = 10_000;
+
+/// 主循环间隔的范围（毫秒）
+pub const MIN_TICK_MS: u64 = 10;
+pub const MAX_TICK_MS: u64 = 1000;
+
+/// 空闲重绘间隔的上限（毫秒）
+pub const MAX_IDLE_REDRAW_MS: u64 = 60_000;
+
 /// 应用程序配置
 ///
-/// 包含目录路径、文件扩展名、备份策略等配置项。
+/// 包含数据目录、文件扩展名、备份策略和阅读选项等配置项。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
-    /// 应用目录名称
-    pub dir_name: &'static str,
-    /// 支持的小说文件扩展名
-    pub supported_extensions: &'static [&'static str],
-    /// 进度文件名
-    pub progress_filename: &'static str,
-    /// 备份文件后缀（完整格式: {progress_filename}.{backup_suffix}.{timestamp}）
-    pub backup_suffix: &'static str,
+    /// 数据目录名称（位于用户主目录下），存放小说、进度和偏好设置
+    pub dir_name: String,
+    /// 默认小说目录，支持以 `~` 开头；为空时使用数据目录下的 `novels`
+    pub novels_dir: String,
+    /// 支持的小说文件扩展名（不含点）
+    pub supported_extensions: Vec<String>,
+    /// 回收站中的小说保留天数，超过后启动时自动清除
+    pub trash_retention_days: u64,
+    /// 阅读进度自动保存的间隔（秒），0 表示只在翻章、加书签和退出等操作时保存
+    pub autosave_secs: u64,
+    /// 启动时捕获鼠标事件（滚轮滚动、点击翻页）；关闭后可以用终端原生的选择和复制
+    pub mouse_capture: bool,
+    /// 进度文件的备份策略
+    pub backup: BackupConfig,
+    /// 阅读界面选项
+    pub reader: ReaderConfig,
+    /// 主循环和重绘频率
+    pub performance: PerformanceConfig,
+    /// 列表导航方式
+    pub navigation: NavigationConfig,
+}
+
+/// 进度文件的备份策略
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackupConfig {
     /// 备份文件时间戳间隔（秒），同一间隔内只保留一个备份
-    pub backup_timestamp_interval: u64,
+    pub interval_secs: u64,
     /// 备份保留天数
-    pub backup_retention_days: u64,
-    /// 设置菜单项数量
-    pub settings_menu_count: usize,
+    pub retention_days: u64,
+}
+
+/// 阅读界面选项
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReaderConfig {
+    /// 正文最大宽度（列），超出时居中显示，0 表示不限制（只对横排生效）
+    pub max_width: u16,
+    /// `↑`/`↓`（`k`/`j`）和鼠标滚轮每次滚动的行数
+    pub scroll_lines: usize,
+    /// 段首缩进的全角空格数，0 表示不缩进（只对横排生效）
+    pub indent: usize,
+    /// 自动滚动时每滚动一行的间隔（毫秒）
+    pub auto_scroll_ms: u64,
+}
+
+/// 主循环和重绘频率
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformanceConfig {
+    /// 主循环间隔（毫秒）：多久检查一次后台任务（同步、搜索、文件变化）和自动滚动
+    pub tick_ms: u64,
+    /// 没有按键和后台任务时的重绘间隔（毫秒），0 表示每次循环都重绘
+    pub idle_redraw_ms: u64,
+}
+
+/// 列表导航方式
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NavigationConfig {
+    /// 在列表末尾按 `↓` 回到开头、在开头按 `↑` 跳到末尾
+    pub wrap: bool,
+    /// `PageUp`/`PageDown` 每次移动的条目数，0 表示移动一整页
+    pub page_step: usize,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            dir_name: CONFIG_DIR_NAME.to_string(),
+            novels_dir: String::new(),
+            supported_extensions: vec!["txt".to_string()],
+            trash_retention_days: 30,
+            autosave_secs: 30,
+            mouse_capture: true,
+            backup: BackupConfig::default(),
+            reader: ReaderConfig::default(),
+            performance: PerformanceConfig::default(),
+            navigation: NavigationConfig::default(),
+        }
+    }
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 600,
+            retention_days: 3,
+        }
+    }
+}
+
+impl Default for ReaderConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 0,
+            scroll_lines: 1,
+            indent: 0,
+            auto_scroll_ms: 3000,
+        }
+    }
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: 100,
+            idle_redraw_ms: 1000,
+        }
+    }
+}
+
+impl Default for NavigationConfig {
+    fn default() -> Self {
+        Self {
+            wrap: true,
+            page_step: 0,
+        }
+    }
 }
 
 impl AppConfig {
-    /// 创建默认配置实例
+    /// 默认小说目录：配置了 `novels_dir` 时使用该目录，否则为数据目录下的 `novels`
+    pub fn novels_dir(&self) -> PathBuf {
+        if self.novels_dir.trim().is_empty() {
+            home::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(&self.dir_name)
+                .join("novels")
+        } else {
+            expand_home(self.novels_dir.trim())
+        }
+    }
+
+    /// 扩展名是否为支持的小说格式（不区分大小写）
+    pub fn is_supported_extension(&self, ext: &str) -> bool {
+        self.supported_extensions
+            .iter()
+            .any(|supported| supported.eq_ignore_ascii_case(ext))
+    }
+
+    /// 解析配置文件内容
+    ///
+    /// 无效的配置项改用默认值，不影响其他配置项。
     ///
     /// # Returns
     ///
-    /// 默认的配置常量。
-    pub const fn default() -> Self {
-        Self {
-            dir_name: ".fish_reader",
-            supported_extensions: &["txt"],
-            progress_filename: "progress.json",
-            backup_suffix: "backup",
-            backup_timestamp_interval: 600,
-            backup_retention_days: 3,
-            settings_menu_count: 3,
+    /// 配置和配置文件中的错误；整个文件无法解析时返回默认配置。
+    pub fn parse(content: &str) -> (Self, Vec<ConfigError>) {
+        match Self::try_parse(content) {
+            Ok((config, invalid)) => (
+                config,
+                invalid.into_iter().map(ConfigError::Invalid).collect(),
+            ),
+            Err(e) => (Self::default(), vec![ConfigError::Parse(e.to_string())]),
         }
     }
+
+    /// 解析配置文件内容，无效的配置项改用默认值
+    ///
+    /// # Errors
+    ///
+    /// 整个文件无法解析时返回解析错误。
+    fn try_parse(content: &str) -> Result<(Self, Vec<InvalidOption>), toml::de::Error> {
+        let mut config: Self = toml::from_str(content)?;
+        let invalid = config.reset_invalid();
+        Ok((config, invalid))
+    }
+
+    /// 把无效的配置项恢复为默认值
+    ///
+    /// # Returns
+    ///
+    /// 无效的配置项，为空表示全部有效。
+    pub fn reset_invalid(&mut self) -> Vec<InvalidOption> {
+        let default = Self::default();
+        let mut invalid = Vec::new();
+        if self.dir_name.trim().is_empty() || self.dir_name.contains(['/', '\\']) {
+            invalid.push(InvalidOption::DirName(std::mem::replace(
+                &mut self.dir_name,
+                default.dir_name,
+            )));
+        }
+        if !self.novels_dir.trim().is_empty() && !self.novels_dir().is_absolute() {
+            invalid.push(InvalidOption::NovelsDir(std::mem::replace(
+                &mut self.novels_dir,
+                default.novels_dir,
+            )));
+        }
+        self.supported_extensions = self
+            .supported_extensions
+            .iter()
+            .map(|ext| ext.trim().trim_start_matches('.').to_string())
+            .filter(|ext| !ext.is_empty())
+            .collect();
+        if self.supported_extensions.is_empty() {
+            invalid.push(InvalidOption::SupportedExtensions);
+            self.supported_extensions = default.supported_extensions;
+        }
+        if self.backup.interval_secs == 0 {
+            invalid.push(InvalidOption::BackupInterval);
+            self.backup.interval_secs = default.backup.interval_secs;
+        }
+        if self.reader.scroll_lines == 0 {
+            invalid.push(InvalidOption::ScrollLines);
+            self.reader.scroll_lines = default.reader.scroll_lines;
+        }
+        if self.reader.indent > MAX_INDENT {
+            invalid.push(InvalidOption::Indent);
+            self.reader.indent = default.reader.indent;
+        }
+        if !(AUTO_SCROLL_STEP_MS..=MAX_AUTO_SCROLL_MS).contains(&self.reader.auto_scroll_ms) {
+            invalid.push(InvalidOption::AutoScrollMs);
+            self.reader.auto_scroll_ms = default.reader.auto_scroll_ms;
+        }
+        if !(MIN_TICK_MS..=MAX_TICK_MS).contains(&self.performance.tick_ms) {
+            invalid.push(InvalidOption::TickMs);
+            self.performance.tick_ms = default.performance.tick_ms;
+        }
+        if self.performance.idle_redraw_ms > MAX_IDLE_REDRAW_MS {
+            invalid.push(InvalidOption::IdleRedrawMs);
+            self.performance.idle_redraw_ms = default.performance.idle_redraw_ms;
+        }
+        invalid
+    }
+
+    /// 配置文件路径：`~/.fish_reader/config.toml`
+    pub fn path() -> PathBuf {
+        #[cfg(test)]
+        {
+            crate::model::library::Library::get_progress_path().with_file_name(CONFIG_FILENAME)
+        }
+
+        #[cfg(not(test))]
+        {
+            home::home_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join(CONFIG_DIR_NAME)
+                .join(CONFIG_FILENAME)
+        }
+    }
+}
+
+/// 无效的配置项（附带无效的原值）
+#[derive(Debug, Clone, PartialEq)]
+pub enum InvalidOption {
+    DirName(String),
+    NovelsDir(String),
+    SupportedExtensions,
+    BackupInterval,
+    ScrollLines,
+    Indent,
+    AutoScrollMs,
+    TickMs,
+    IdleRedrawMs,
+}
+
+impl InvalidOption {
+    /// 按当前界面语言生成的说明
+    pub fn message(&self) -> String {
+        match self {
+            InvalidOption::DirName(value) => {
+                trf!("dir_name 无效：{:?}", "invalid dir_name: {:?}", value)
+            }
+            InvalidOption::NovelsDir(value) => trf!(
+                "novels_dir 必须是绝对路径：{:?}",
+                "novels_dir must be an absolute path: {:?}",
+                value
+            ),
+            InvalidOption::SupportedExtensions => tr(
+                "supported_extensions 不能为空",
+                "supported_extensions must not be empty",
+            )
+            .to_string(),
+            InvalidOption::BackupInterval => tr(
+                "backup.interval_secs 必须大于 0",
+                "backup.interval_secs must be greater than 0",
+            )
+            .to_string(),
+            InvalidOption::ScrollLines => tr(
+                "reader.scroll_lines 必须大于 0",
+                "reader.scroll_lines must be greater than 0",
+            )
+            .to_string(),
+            InvalidOption::Indent => trf!(
+                "reader.indent 不能大于 {}",
+                "reader.indent must not exceed {}",
+                MAX_INDENT
+            ),
+            InvalidOption::AutoScrollMs => trf!(
+                "reader.auto_scroll_ms 应在 {} 到 {} 之间",
+                "reader.auto_scroll_ms must be between {} and {}",
+                AUTO_SCROLL_STEP_MS,
+                MAX_AUTO_SCROLL_MS
+            ),
+            InvalidOption::TickMs => trf!(
+                "performance.tick_ms 应在 {} 到 {} 之间",
+                "performance.tick_ms must be between {} and {}",
+                MIN_TICK_MS,
+                MAX_TICK_MS
+            ),
+            InvalidOption::IdleRedrawMs => trf!(
+                "performance.idle_redraw_ms 不能大于 {}",
+                "performance.idle_redraw_ms must not exceed {}",
+                MAX_IDLE_REDRAW_MS
+            ),
+        }
+    }
+}
+
+/// 读取配置文件时的错误
+///
+/// 启动时配置先于偏好设置（含界面语言）读取，所以只记录错误，显示时再生成说明。
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// 配置文件无法读取，已使用默认配置
+    Read(String),
+    /// 整个文件无法解析，已使用默认配置
+    Parse(String),
+    /// 配置项无效，已使用默认值
+    Invalid(InvalidOption),
+}
+
+impl ConfigError {
+    /// 按当前界面语言生成的说明
+    pub fn message(&self) -> String {
+        match self {
+            ConfigError::Read(e) => {
+                trf!(
+                    "读取 {} 失败：{}",
+                    "Failed to read {}: {}",
+                    CONFIG_FILENAME,
+                    e
+                )
+            }
+            ConfigError::Parse(e) => trf!(
+                "{} 解析失败，已使用默认配置：{}",
+                "Failed to parse {}, using the default configuration: {}",
+                CONFIG_FILENAME,
+                e
+            ),
+            ConfigError::Invalid(option) => trf!(
+                "{}：{}，已使用默认值",
+                "{}: {}, using the default value",
+                CONFIG_FILENAME,
+                option.message()
+            ),
+        }
+    }
+}
+
+/// 展开用户输入路径开头的 `~`
+pub fn expand_home(input: &str) -> PathBuf {
+    match input.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            let home = home::home_dir().unwrap_or_else(|| PathBuf::from("."));
+            home.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(input),
+    }
+}
+
+/// 当前生效的配置，`None` 表示尚未读取
+static CONFIG: RwLock<Option<Arc<AppConfig>>> = RwLock::new(None);
+
+/// 获取当前配置
+///
+/// 尚未调用 [`init`] 时（如测试中）使用默认配置。
+pub fn config() -> Arc<AppConfig> {
+    if let Some(config) = CONFIG.read().unwrap_or_else(|e| e.into_inner()).as_ref() {
+        return Arc::clone(config);
+    }
+    let mut current = CONFIG.write().unwrap_or_else(|e| e.into_inner());
+    Arc::clone(current.get_or_insert_with(Default::default))
+}
+
+/// 启动时读取配置文件并生效
+///
+/// 配置文件不存在时写入一份默认配置，方便用户修改。
+///
+/// # Returns
+///
+/// 配置文件中的错误，为空表示没有错误。
+pub fn init() -> Vec<ConfigError> {
+    let path = AppConfig::path();
+    let (config, errors) = match std::fs::read_to_string(&path) {
+        Ok(content) => AppConfig::parse(&content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let config = AppConfig::default();
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(content) = toml::to_string_pretty(&config) {
+                let _ = std::fs::write(&path, content);
+            }
+            (config, Vec::new())
+        }
+        Err(e) => (AppConfig::default(), vec![ConfigError::Read(e.to_string())]),
+    };
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(config));
+    errors
 }
 
-/// 全局配置实例
+/// 程序运行期间配置文件被修改后，重新读取并生效
+///
+/// 整个文件无法解析时保留当前配置；数据目录名（`dir_name`）重启后才生效。
+///
+/// # Returns
 ///
-/// 应用程序使用此常量访问所有配置项。
-pub const CONFIG: AppConfig = AppConfig::default();
+/// 配置是否变化，以及配置文件中的错误说明。
+pub fn reload() -> (bool, Vec<String>) {
+    let content = match std::fs::read_to_string(AppConfig::path()) {
+        Ok(content) => content,
+        Err(e) => {
+            let message = trf!(
+                "读取 {} 失败：{}",
+                "Failed to read {}: {}",
+                CONFIG_FILENAME,
+                e
+            );
+            return (false, vec![message]);
+        }
+    };
+    let (mut updated, mut errors) = match AppConfig::try_parse(&content) {
+        Ok((updated, invalid)) => {
+            let errors: Vec<String> = invalid
+                .into_iter()
+                .map(|option| ConfigError::Invalid(option).message())
+                .collect();
+            (updated, errors)
+        }
+        Err(e) => {
+            let message = trf!(
+                "{} 解析失败，已保留当前配置：{}",
+                "Failed to parse {}, keeping the current configuration: {}",
+                CONFIG_FILENAME,
+                e
+            );
+            return (false, vec![message]);
+        }
+    };
+    let current = config();
+    if updated.dir_name != current.dir_name {
+        errors.push(trf!(
+            "{}：dir_name 重启后生效",
+            "{}: dir_name takes effect after a restart",
+            CONFIG_FILENAME
+        ));
+        updated.dir_name = current.dir_name.clone();
+    }
+    if updated.novels_dir != current.novels_dir {
+        errors.push(trf!(
+            "{}：novels_dir 重启后生效，也可以在设置 → 小说目录中更改",
+            "{}: novels_dir takes effect after a restart; you can also change it in Settings → Novel directories",
+            CONFIG_FILENAME
+        ));
+        updated.novels_dir = current.novels_dir.clone();
+    }
+    let changed = *current != updated;
+    if changed {
+        *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(updated));
+    }
+    (changed, errors)
+}
+
+/// 把修改后的配置写回配置文件并立即生效
+///
+/// # Errors
+///
+/// 返回 IO 操作或序列化错误，此时配置不变。
+pub fn save(config: AppConfig) -> std::io::Result<()> {
+    let content = toml::to_string_pretty(&config).map_err(std::io::Error::other)?;
+    let path = AppConfig::path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, content)?;
+    *CONFIG.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(config));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let (config, errors) = AppConfig::parse("[reader]\nmax_width = 80\n");
+        assert!(errors.is_empty());
+        assert_eq!(config.reader.max_width, 80);
+        assert_eq!(config.reader.scroll_lines, 1);
+        assert_eq!(config.backup, BackupConfig::default());
+        assert_eq!(config.navigation, NavigationConfig::default());
+
+        let (config, errors) = AppConfig::parse("[navigation]\nwrap = false\npage_step = 10\n");
+        assert!(errors.is_empty());
+        assert!(!config.navigation.wrap);
+        assert_eq!(config.navigation.page_step, 10);
+    }
+
+    #[test]
+    fn test_invalid_values_fall_back_to_defaults() {
+        let content = r#"
+dir_name = "a/b"
+supported_extensions = [".TXT", "md"]
+
+[backup]
+interval_secs = 0
+retention_days = 7
+"#;
+        let (config, errors) = AppConfig::parse(content);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(config.dir_name, ".fish_reader");
+        assert_eq!(config.backup.interval_secs, 600);
+        assert_eq!(config.backup.retention_days, 7);
+        assert!(config.is_supported_extension("txt"));
+        assert!(config.is_supported_extension("md"));
+        assert!(!config.is_supported_extension("epub"));
+
+        let (config, errors) = AppConfig::parse("[performance]\ntick_ms = 5\nidle_redraw_ms = 0\n");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(config.performance.tick_ms, 100);
+        assert_eq!(config.performance.idle_redraw_ms, 0);
+
+        let (config, errors) = AppConfig::parse("novels_dir = \"books\"\n");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(config.novels_dir, "");
+
+        let (config, errors) = AppConfig::parse("dir_name = ");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(config, AppConfig::default());
+    }
+
+    #[test]
+    fn test_errors_are_described_in_the_language_set_later() {
+        let (_, errors) = AppConfig::parse("[reader]\nscroll_lines = 0\n");
+        let (_, parse_errors) = AppConfig::parse("dir_name = ");
+
+        crate::i18n::set_language(crate::model::preferences::Language::English);
+        assert_eq!(
+            errors[0].message(),
+            "config.toml: reader.scroll_lines must be greater than 0, using the default value"
+        );
+        assert!(
+            parse_errors[0]
+                .message()
+                .starts_with("Failed to parse config.toml")
+        );
+        crate::i18n::set_language(crate::model::preferences::Language::Chinese);
+    }
+
+    #[test]
+    fn test_default_config_round_trips_through_toml() {
+        let content = toml::to_string_pretty(&AppConfig::default()).unwrap();
+        assert_eq!(
+            AppConfig::parse(&content),
+            (AppConfig::default(), Vec::new())
+        );
+    }
+}
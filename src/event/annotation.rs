@@ -0,0 +1,97 @@
+use crate::app::App;
+use crate::i18n::tr;
+use crate::state::AppState;
+use crossterm::event::KeyCode;
+
+use super::navigate_list;
+
+/// 处理批注列表模式下的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Enter`: 跳转到选中批注所在的行
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `e`: 编辑选中的批注
+/// - `d`: 删除选中的批注
+pub(super) fn handle_annotation_list_key(app: &mut App, key: KeyCode) {
+    if app.annotation.input.is_some() {
+        handle_annotation_input_key(app, key);
+        return;
+    }
+    let len = app.get_current_annotations().map_or(0, Vec::len);
+    match key {
+        KeyCode::Enter => {
+            if let Some(index) = app.annotation.selected_index
+                && app.jump_to_annotation(index).is_some()
+            {
+                app.state = AppState::Reading;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.annotation.selected_index = navigate_list(app.annotation.selected_index, len, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.annotation.selected_index =
+                navigate_list(app.annotation.selected_index, len, false);
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            let line = app
+                .annotation
+                .selected_index
+                .and_then(|index| app.get_current_annotations()?.get(index))
+                .map(|annotation| annotation.line);
+            if let Some(line) = line {
+                app.start_annotation_edit(line);
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if let Some(index) = app.annotation.selected_index
+                && app.remove_annotation(index).is_some()
+            {
+                let remaining = len - 1;
+                app.annotation.selected_index = (remaining > 0).then(|| index.min(remaining - 1));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 处理批注内容的输入（阅读界面和批注列表共用）
+///
+/// `Enter` 保存批注，内容为空时删除该行的批注；保存后批注列表选中该批注。
+pub(super) fn handle_annotation_input_key(app: &mut App, key: KeyCode) {
+    let Some(input) = app.annotation.input.as_mut() else {
+        return;
+    };
+    match key {
+        KeyCode::Enter => {
+            let Some(line) = app.submit_annotation_input() else {
+                return;
+            };
+            let annotations = app.get_current_annotations().map_or(&[][..], Vec::as_slice);
+            let index = annotations.partition_point(|a| a.line < line);
+            app.annotation.selected_index = if annotations.is_empty() {
+                None
+            } else {
+                Some(index.min(annotations.len() - 1))
+            };
+            if app.state == AppState::Reading {
+                app.set_info(tr(
+                    "已保存批注，按 A 查看批注列表",
+                    "Annotation saved; press A to list annotations",
+                ));
+            }
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+}
@@ -0,0 +1,129 @@
+use crate::app::{App, ConfirmAction};
+use crate::i18n::{tr, trf};
+use crossterm::event::KeyCode;
+
+use super::bookshelf::handle_rename_input_key;
+use super::navigate_list;
+
+/// 处理书籍详情界面的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Enter`: 打开这本小说
+/// - `Up`/`k`: 向上选择标签
+/// - `Down`/`j`: 向下选择标签
+/// - `a`: 输入新标签，可用逗号分隔多个，`Enter` 确认
+/// - `d`: 删除选中的标签
+/// - `m`: 与内容重复的小说合并，保留这本，另一本的进度和书签并入后移到回收站
+/// - `e`: 编辑备注，`Enter` 保存，清空后保存即删除备注
+/// - `n`: 重命名小说文件
+/// - `x`: 把小说移到回收站（需确认），之后回到书架
+pub(super) fn handle_book_detail_key(app: &mut App, key: KeyCode) {
+    let Some(index) = app.selected_novel_index else {
+        return;
+    };
+
+    if app.bookshelf.rename_input.is_some() {
+        handle_rename_input_key(app, key);
+        return;
+    }
+
+    if let Some(input) = &mut app.bookshelf.note_input {
+        match key {
+            KeyCode::Enter => {
+                let input = app.bookshelf.note_input.take().unwrap_or_default();
+                app.set_novel_note(index, &input);
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    if let Some(input) = &mut app.bookshelf.tag_input {
+        match key {
+            KeyCode::Enter => {
+                let input = app.bookshelf.tag_input.take().unwrap_or_default();
+                if app.add_novel_tags(index, &input) > 0 {
+                    app.bookshelf.detail_tag_index = Some(app.bookshelf_tags(index).len() - 1);
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    let len = app.bookshelf_tags(index).len();
+    match key {
+        KeyCode::Enter => app.open_novel(index),
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.bookshelf.detail_tag_index =
+                navigate_list(app.bookshelf.detail_tag_index, len, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.bookshelf.detail_tag_index =
+                navigate_list(app.bookshelf.detail_tag_index, len, false);
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            let Some(&other) = app.bookshelf_duplicates(index).first() else {
+                app.set_info(tr(
+                    "没有发现与这本内容重复的小说",
+                    "No novel with duplicate content was found",
+                ));
+                return;
+            };
+            let message = trf!(
+                "保留《{}》，把《{}》的进度和书签并入后移到回收站？",
+                "Keep {} and move {} to the trash after merging its progress and bookmarks?",
+                app.novels[index].title,
+                app.novels[other].title
+            );
+            let action = ConfirmAction::MergeDuplicate(
+                app.novels[index].path.clone(),
+                app.novels[other].path.clone(),
+            );
+            app.request_confirm(message, action);
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            app.bookshelf.note_input = Some(app.bookshelf_note(index).to_string());
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            app.bookshelf.rename_input = Some(app.novels[index].title.clone());
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') => {
+            let message = trf!(
+                "确定把《{}》移到回收站？",
+                "Move {} to the trash?",
+                app.novels[index].title
+            );
+            let path = app.novels[index].path.clone();
+            app.request_confirm(message, ConfirmAction::DeleteNovel(path));
+        }
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            app.bookshelf.tag_input = Some(String::new());
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if let Some(tag_index) = app.bookshelf.detail_tag_index
+                && tag_index < len
+            {
+                app.remove_novel_tag(index, tag_index);
+                let remaining = len - 1;
+                app.bookshelf.detail_tag_index =
+                    (remaining > 0).then(|| tag_index.min(remaining - 1));
+            }
+        }
+        _ => {}
+    }
+}
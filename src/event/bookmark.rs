@@ -1,8 +1,10 @@
 use crate::app::App;
+use crate::i18n::tr;
+use crate::model::novel::next_bookmark_tag;
 use crate::state::AppState;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 
-use super::navigate_list;
+use super::{list_page_size, navigate_list, page_list};
 
 /// 处理书签列表模式下的键盘事件
 ///
@@ -13,12 +15,23 @@ use super::navigate_list;
 ///
 /// # Behavior
 ///
-/// - `Enter`: 跳转到选中的书签
+/// - `Enter`: 跳转到选中的书签或自动记录的位置
 /// - `Up`/`k`: 向上选择
 /// - `Down`/`j`: 向下选择
-/// - `d`: 删除选中的书签
+/// - `d`: 删除选中的书签或自动记录
+/// - `u`: 撤销最近一次删除，可连续撤销
+/// - `y`/`Y`: 把书签所在行/所在段落复制到系统剪贴板
+/// - `e`: 编辑选中书签的名称和备注
+/// - `m`: 把选中的书签移动到当前阅读位置
+/// - `s`: 切换排序方式（位置/创建时间/名称）
+/// - `t`: 切换按标签筛选（全部 → 各个标签 → 全部）
+/// - `/`: 按名称和备注筛选书签，`Enter` 保留筛选结果
 /// - `a`: 进入添加书签模式
 pub(super) fn handle_bookmark_list_key(app: &mut App, key: KeyCode) {
+    if app.bookmark.filter_input {
+        handle_filter_key(app, key);
+        return;
+    }
     match key {
         KeyCode::Enter => {
             if let Some(index) = app.bookmark.selected_index
@@ -27,28 +40,77 @@ pub(super) fn handle_bookmark_list_key(app: &mut App, key: KeyCode) {
                 app.state = AppState::Reading;
             }
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if let Some(bookmarks) = app.get_current_bookmarks() {
-                app.bookmark.selected_index =
-                    navigate_list(app.bookmark.selected_index, bookmarks.len(), true);
+        KeyCode::Up | KeyCode::Char('k') => move_selection(app, true),
+        KeyCode::Down | KeyCode::Char('j') => move_selection(app, false),
+        KeyCode::Char('s') | KeyCode::Char('S') => {
+            app.bookmark.sort = app.bookmark.sort.next();
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            let current = app.bookmark.tag_filter.as_deref().unwrap_or_default();
+            let next = next_bookmark_tag(current);
+            app.bookmark.tag_filter = (!next.is_empty()).then(|| next.to_string());
+            app.refresh_bookmark_filter();
+        }
+        KeyCode::Char('/') => {
+            app.bookmark.filter_input = true;
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            let Some(index) = app.bookmark.selected_index else {
+                return;
+            };
+            // 删除后选中显示顺序中同一位置的书签
+            let position = app
+                .bookmark_display_order()
+                .iter()
+                .position(|&i| i == index);
+            if app.remove_bookmark(index).is_some() {
+                let order = app.bookmark_display_order();
+                app.bookmark.selected_index = position
+                    .and_then(|position| order.get(position.min(order.len().saturating_sub(1))))
+                    .copied();
             }
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if let Some(bookmarks) = app.get_current_bookmarks() {
-                app.bookmark.selected_index =
-                    navigate_list(app.bookmark.selected_index, bookmarks.len(), false);
+        KeyCode::Char(c @ ('y' | 'Y')) => {
+            if let Some(index) = app.bookmark.selected_index {
+                app.copy_bookmark_passage(index, c == 'Y');
             }
         }
-        KeyCode::Char('d') | KeyCode::Char('D') => {
-            if let Some(index) = app.bookmark.selected_index
-                && app.remove_bookmark(index).is_some()
-                && let Some(bookmarks) = app.get_current_bookmarks()
-            {
-                app.bookmark.selected_index = if bookmarks.is_empty() {
-                    None
-                } else {
-                    Some(index.min(bookmarks.len() - 1))
-                };
+        KeyCode::Char('u') | KeyCode::Char('U') => match app.undo_bookmark_removal() {
+            Some(index) => {
+                app.bookmark.selected_index = Some(index);
+                app.refresh_bookmark_filter();
+                app.set_info(tr("已恢复删除的书签", "Deleted bookmark restored"));
+            }
+            None => app.set_error(tr("没有可以撤销的删除", "Nothing to undo")),
+        },
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            let Some(index) = app.bookmark.selected_index else {
+                return;
+            };
+            if app.is_auto_bookmark(index) {
+                app.set_error(tr(
+                    "自动记录的位置不能编辑",
+                    "Automatic positions cannot be edited",
+                ));
+            } else if app.start_bookmark_edit(index).is_some() {
+                app.state = AppState::BookmarkAdd;
+            }
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            let Some(index) = app.bookmark.selected_index else {
+                return;
+            };
+            if app.is_auto_bookmark(index) {
+                app.set_error(tr(
+                    "自动记录的位置不能移动",
+                    "Automatic positions cannot be moved",
+                ));
+            } else if let Some(new_index) = app.move_bookmark_to_current(index) {
+                app.bookmark.selected_index = Some(new_index);
+                app.set_info(tr(
+                    "书签已移动到当前阅读位置",
+                    "Bookmark moved to the current reading position",
+                ));
             }
         }
         KeyCode::Char('a') | KeyCode::Char('A') => {
@@ -59,7 +121,7 @@ pub(super) fn handle_bookmark_list_key(app: &mut App, key: KeyCode) {
     }
 }
 
-/// 处理添加书签模式下的键盘事件
+/// 处理全部书签浏览的键盘事件
 ///
 /// # Arguments
 ///
@@ -68,22 +130,119 @@ pub(super) fn handle_bookmark_list_key(app: &mut App, key: KeyCode) {
 ///
 /// # Behavior
 ///
-/// - `Enter`: 确认添加书签
+/// - `Enter`: 打开书签所在的小说并跳转到书签位置
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `PageUp`/`PageDown`: 向上/向下翻页
+pub(super) fn handle_all_bookmarks_key(app: &mut App, key: KeyCode) {
+    let len = app.all_bookmarks().len();
+    match key {
+        KeyCode::Enter => {
+            app.open_selected_shelf_bookmark();
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.bookmark.all_selected_index =
+                navigate_list(app.bookmark.all_selected_index, len, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.bookmark.all_selected_index =
+                navigate_list(app.bookmark.all_selected_index, len, false);
+        }
+        KeyCode::PageUp | KeyCode::PageDown => {
+            let page_size = list_page_size(app, 5);
+            app.bookmark.all_selected_index = page_list(
+                app.bookmark.all_selected_index,
+                len,
+                page_size,
+                key == KeyCode::PageUp,
+            );
+        }
+        _ => {}
+    }
+}
+
+/// 处理书签筛选关键词的输入，每次输入都立即更新显示的书签
+fn handle_filter_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => app.bookmark.filter_input = false,
+        KeyCode::Backspace => {
+            app.bookmark.filter.pop();
+        }
+        KeyCode::Char(c) => app.bookmark.filter.push(c),
+        KeyCode::Up | KeyCode::Down => {
+            move_selection(app, key == KeyCode::Up);
+            return;
+        }
+        _ => return,
+    }
+    app.refresh_bookmark_filter();
+}
+
+/// 按显示顺序移动选中的书签
+fn move_selection(app: &mut App, move_up: bool) {
+    let order = app.bookmark_display_order();
+    let position = app
+        .bookmark
+        .selected_index
+        .and_then(|index| order.iter().position(|&i| i == index));
+    app.bookmark.selected_index =
+        navigate_list(position, order.len(), move_up).map(|position| order[position]);
+}
+
+/// 处理添加/编辑书签模式下的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+/// * `modifiers` - 按键修饰符
+///
+/// # Behavior
+///
+/// - `Tab`: 在名称和备注之间切换输入焦点
+/// - `Ctrl+t`: 切换标签（无标签 → 各个标签 → 无标签）
+/// - `Enter`: 在名称上确认保存；在备注上换行
+/// - `Ctrl+s`: 确认保存
 /// - `Backspace`: 删除输入的最后一个字符
-/// - 其他字符: 添加到输入框
-pub(super) fn handle_bookmark_add_key(app: &mut App, key: KeyCode) {
+/// - 其他字符: 添加到当前输入框
+pub(super) fn handle_bookmark_add_key(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    let focused = if app.bookmark.note_focused {
+        &mut app.bookmark.note_input
+    } else {
+        &mut app.bookmark.input
+    };
     match key {
-        KeyCode::Enter if !app.bookmark.input.trim().is_empty() => {
-            app.add_bookmark(app.bookmark.input.clone());
-            app.state = AppState::BookmarkList;
-            app.clear_bookmark_inputs();
+        KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => {
+            save_bookmark_input(app);
         }
+        KeyCode::Char('t') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.bookmark.tag_input = next_bookmark_tag(&app.bookmark.tag_input).to_string();
+        }
+        KeyCode::Tab => {
+            app.bookmark.note_focused = !app.bookmark.note_focused;
+        }
+        KeyCode::Enter if app.bookmark.note_focused => {
+            app.bookmark.note_input.push('\n');
+        }
+        KeyCode::Enter => save_bookmark_input(app),
         KeyCode::Backspace => {
-            app.bookmark.input.pop();
+            focused.pop();
         }
         KeyCode::Char(c) => {
-            app.bookmark.input.push(c);
+            focused.push(c);
         }
         _ => {}
     }
 }
+
+/// 保存书签并返回列表；名称为空时提示错误
+fn save_bookmark_input(app: &mut App) {
+    if app.submit_bookmark_input().is_some() {
+        app.state = AppState::BookmarkList;
+    } else {
+        app.set_error(tr(
+            "书签名称不能为空",
+            "The bookmark name must not be empty",
+        ));
+    }
+}
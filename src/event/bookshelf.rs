@@ -1,8 +1,9 @@
-use crate::app::App;
+use crate::app::{App, ConfirmAction};
+use crate::i18n::{tr, trf};
 use crate::state::AppState;
 use crossterm::event::KeyCode;
 
-use super::navigate_list;
+use super::{list_page_size, navigate_list, page_list};
 
 /// 处理书架模式下的键盘事件
 ///
@@ -13,39 +14,217 @@ use super::navigate_list;
 ///
 /// # Behavior
 ///
-/// - `Enter`: 打开选中的小说
+/// - `Enter`: 打开选中的小说；光标在分类标题上时折叠/展开该分类
+/// - `Left`/`h`: 折叠光标所在的分类
+/// - `Right`/`l`: 展开光标所在的分类
 /// - `Up`/`k`: 向上选择
 /// - `Down`/`j`: 向下选择
+/// - `PageUp`/`PageDown`: 向上/向下翻页
+/// - `b`: 浏览书架上所有小说的书签
+/// - `c`: 继续阅读最近读过的小说
+/// - `o`: 切换书架排序方式（标题/最近阅读/阅读进度/文件大小）
+/// - `i`: 输入路径导入外部小说文件
+/// - `n`: 重命名选中的小说文件
+/// - `e`: 查看选中小说的详情（路径、编码、字数、进度等），在详情中编辑标签、重命名或删除
+/// - `f`: 收藏/取消收藏选中的小说，收藏的小说固定在书架最上方
+/// - `t`: 依次按每个标签筛选书架，最后回到显示全部
+/// - `m`: 切换选中小说的阅读状态（未读/在读/读完）
+/// - `r`: 按阅读状态筛选书架（全部/未读/在读/读完）
+/// - `R`/`F5`: 重新扫描小说目录和孤立记录
+/// - `/`: 输入关键词按标题筛选，支持拼音首字母
+/// - `Space`: 标记/取消标记选中的小说并移到下一行
+/// - `a`: 给标记的小说（没有标记时为选中的小说）批量添加标签
+/// - `x`: 把标记的小说批量移到回收站（需确认）
+/// - `A`: 把标记的小说批量移到 “归档” 分类
+/// - `+`: 把选中的小说加入待读队列末尾或移出队列
+/// - `K`/`J`: 在待读队列中前移/后移选中的小说
 /// - `s`: 进入设置页面
 pub(super) fn handle_bookshelf_key(app: &mut App, key: KeyCode) {
+    if app.bookshelf.import_input.is_some() {
+        handle_import_input_key(app, key);
+        return;
+    }
+    if app.bookshelf.rename_input.is_some() {
+        handle_rename_input_key(app, key);
+        return;
+    }
+    if app.bookshelf.filter_input {
+        handle_filter_key(app, key);
+        return;
+    }
+    if app.bookshelf.tag_input.is_some() {
+        handle_batch_tag_input_key(app, key);
+        return;
+    }
+
     match key {
         KeyCode::Up | KeyCode::Char('k') => {
-            app.selected_novel_index =
-                navigate_list(app.selected_novel_index, app.novels.len(), true);
+            move_cursor(app, |current, len| navigate_list(current, len, true));
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.selected_novel_index =
-                navigate_list(app.selected_novel_index, app.novels.len(), false);
+            move_cursor(app, |current, len| navigate_list(current, len, false));
+        }
+        KeyCode::PageUp | KeyCode::PageDown => {
+            let page_size = list_page_size(app, 5);
+            move_cursor(app, |current, len| {
+                page_list(current, len, page_size, key == KeyCode::PageUp)
+            });
         }
         KeyCode::Enter => {
+            if let Some(name) = app.selected_category.clone() {
+                let collapsed = app.preferences.collapsed_categories.contains(&name);
+                app.set_bookshelf_category_collapsed(&name, !collapsed);
+            } else if let Some(index) = app.selected_novel_index {
+                app.open_novel(index);
+            }
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            if let Some(name) = app.bookshelf_cursor_category() {
+                app.set_bookshelf_category_collapsed(&name, true);
+            }
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            if let Some(name) = app.bookshelf_cursor_category() {
+                app.set_bookshelf_category_collapsed(&name, false);
+            }
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            app.continue_reading();
+        }
+        KeyCode::Char('o') | KeyCode::Char('O') => {
+            app.cycle_bookshelf_sort();
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            if let Some(index) = app.selected_novel_index {
+                app.open_book_detail(index);
+            }
+        }
+        KeyCode::Char('f') | KeyCode::Char('F') => {
             if let Some(index) = app.selected_novel_index
-                && index < app.novels.len()
+                && let Some(favorite) = app.toggle_bookshelf_favorite(index)
             {
-                let mut novel = app.novels[index].clone();
-
-                if novel.is_empty()
-                    && let Err(e) = novel.load_content()
-                {
-                    app.set_error(format!("Failed to load novel: {}", e));
-                    return;
+                app.set_info(if favorite {
+                    tr(
+                        "已收藏，固定在书架最上方",
+                        "Favorited and pinned to the top of the bookshelf",
+                    )
+                } else {
+                    tr("已取消收藏", "Removed from favorites")
+                });
+            }
+        }
+        KeyCode::Char('m') | KeyCode::Char('M') => {
+            if let Some(index) = app.selected_novel_index
+                && let Some(status) = app.cycle_bookshelf_status(index)
+            {
+                app.set_info(trf!("已标记为{}", "Marked as {}", status.label()));
+            }
+        }
+        KeyCode::Char('R') | KeyCode::F(5) => {
+            app.rescan_bookshelf();
+        }
+        KeyCode::Char('r') => {
+            app.cycle_bookshelf_status_filter();
+            match app.bookshelf.status_filter {
+                Some(status) => app.set_info(trf!(
+                    "只显示{}的小说",
+                    "Showing only {} novels",
+                    status.label()
+                )),
+                None => app.set_info(tr("显示全部小说", "Showing all novels")),
+            }
+        }
+        KeyCode::Char('/') => {
+            app.bookshelf.filter_input = true;
+        }
+        KeyCode::Char('t') | KeyCode::Char('T') => {
+            app.cycle_bookshelf_tag_filter();
+            match &app.bookshelf.tag_filter {
+                Some(tag) => app.set_info(trf!("只显示标签：{}", "Showing only tag: {}", tag)),
+                None if app.library.all_tags().is_empty() => app.set_info(tr(
+                    "还没有标签，可按 e 在书籍详情中添加",
+                    "No tags yet; press e to add them in the book details",
+                )),
+                None => app.set_info(tr("显示全部小说", "Showing all novels")),
+            }
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            app.bookshelf.import_input = Some(String::new());
+            app.bookshelf.import_move = false;
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            if let Some(index) = app.selected_novel_index
+                && let Some(novel) = app.novels.get(index)
+            {
+                app.bookshelf.rename_input = Some(novel.title.clone());
+            }
+        }
+        KeyCode::Char('+') => {
+            if let Some(index) = app.selected_novel_index
+                && let Some(queued) = app.toggle_bookshelf_queued(index)
+            {
+                app.set_info(if queued {
+                    tr(
+                        "已加入待读队列，可用 K/J 调整顺序",
+                        "Added to the reading queue; reorder with K/J",
+                    )
+                } else {
+                    tr("已移出待读队列", "Removed from the reading queue")
+                });
+            }
+        }
+        KeyCode::Char('K') | KeyCode::Char('J') => {
+            if let Some(index) = app.selected_novel_index
+                && app.bookshelf_queue_position(index).is_some()
+            {
+                app.move_bookshelf_queued(index, key == KeyCode::Char('K'));
+            }
+        }
+        KeyCode::Char(' ') => {
+            if let Some(index) = app.selected_novel_index {
+                app.toggle_bookshelf_mark(index);
+                move_cursor(app, |current, len| navigate_list(current, len, false));
+            }
+        }
+        KeyCode::Char('a') if !app.batch_targets().is_empty() => {
+            app.bookshelf.tag_input = Some(String::new());
+        }
+        KeyCode::Char('x') | KeyCode::Char('X') => {
+            let targets = app.batch_targets();
+            let message = match targets.as_slice() {
+                [] => return,
+                [index] => trf!(
+                    "确定把《{}》移到回收站？",
+                    "Move {} to the trash?",
+                    app.novels[*index].title
+                ),
+                _ => trf!(
+                    "确定把标记的 {} 本小说移到回收站？",
+                    "Move the {} marked novels to the trash?",
+                    targets.len()
+                ),
+            };
+            app.request_confirm(message, ConfirmAction::DeleteBatch);
+        }
+        KeyCode::Char('A') => {
+            let (archived, failed) = app.archive_batch();
+            match (archived, failed) {
+                (0, 0) => app.set_info(tr("已在归档中", "Already archived")),
+                (archived, 0) => {
+                    app.set_info(trf!("已归档 {} 本小说", "Archived {} novels", archived))
                 }
-
-                novel.progress = app.library.get_novel_progress(&novel.path);
-
-                app.current_novel = Some(novel);
-                app.state = AppState::Reading;
+                (archived, failed) => app.set_error(trf!(
+                    "已归档 {} 本，{} 本失败（归档中已有同名文件）",
+                    "Archived {} novels, {} failed (a file with the same name is already archived)",
+                    archived,
+                    failed
+                )),
             }
         }
+        KeyCode::Char('b') | KeyCode::Char('B') => {
+            app.bookmark.all_selected_index = navigate_list(None, app.all_bookmarks().len(), false);
+            app.state = AppState::AllBookmarks;
+        }
         KeyCode::Char('s') | KeyCode::Char('S') => {
             app.settings.reset();
             app.detect_orphaned_novels();
@@ -55,8 +234,129 @@ pub(super) fn handle_bookshelf_key(app: &mut App, key: KeyCode) {
             app.trigger_sync();
         }
         KeyCode::Char('d') | KeyCode::Char('D') => {
-            app.trigger_download();
+            app.request_download();
+        }
+        _ => {}
+    }
+}
+
+/// 在书架的行（分类标题和小说）之间移动光标
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `step` - 根据当前行号和行数计算新行号
+fn move_cursor(app: &mut App, step: impl FnOnce(Option<usize>, usize) -> Option<usize>) {
+    let rows = app.bookshelf_rows();
+    let row = step(app.bookshelf_selected_row(&rows), rows.len());
+    app.select_bookshelf_row(row.and_then(|row| rows.get(row)));
+}
+
+/// 处理批量添加标签时的输入，`Enter` 给所有标记的小说添加并清除标记
+fn handle_batch_tag_input_key(app: &mut App, key: KeyCode) {
+    let Some(input) = &mut app.bookshelf.tag_input else {
+        return;
+    };
+    match key {
+        KeyCode::Enter => {
+            let input = app.bookshelf.tag_input.take().unwrap_or_default();
+            let tagged = app.tag_batch(&input);
+            app.set_info(trf!("已给 {} 本小说添加标签", "Tagged {} novels", tagged));
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+}
+
+/// 处理标题筛选关键词的输入，每次输入都立即更新显示的小说
+fn handle_filter_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => app.bookshelf.filter_input = false,
+        KeyCode::Backspace => {
+            app.bookshelf.filter.pop();
+        }
+        KeyCode::Char(c) => app.bookshelf.filter.push(c),
+        KeyCode::Up => {
+            move_cursor(app, |current, len| navigate_list(current, len, true));
+            return;
+        }
+        KeyCode::Down => {
+            move_cursor(app, |current, len| navigate_list(current, len, false));
+            return;
+        }
+        _ => return,
+    }
+    app.refresh_bookshelf_selection();
+}
+
+/// 处理导入文件路径输入时的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Enter`: 导入输入的文件
+/// - `Tab`: 切换复制/移动文件
+/// - `Backspace`: 删除一个字符
+fn handle_import_input_key(app: &mut App, key: KeyCode) {
+    let Some(input) = &mut app.bookshelf.import_input else {
+        return;
+    };
+    match key {
+        KeyCode::Enter => {
+            let input = app.bookshelf.import_input.take().unwrap_or_default();
+            match app.import_novel(&input, app.bookshelf.import_move) {
+                Ok(path) => app.set_info(trf!("已导入 {}", "Imported {}", path.display())),
+                Err(e) => app.set_error(trf!("导入失败：{}", "Import failed: {}", e)),
+            }
+        }
+        KeyCode::Tab => {
+            app.bookshelf.import_move = !app.bookshelf.import_move;
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+}
+
+/// 处理重命名小说时的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Enter`: 按输入的书名重命名文件
+/// - `Backspace`: 删除一个字符
+pub(super) fn handle_rename_input_key(app: &mut App, key: KeyCode) {
+    let Some(input) = &mut app.bookshelf.rename_input else {
+        return;
+    };
+    match key {
+        KeyCode::Enter => {
+            let input = app.bookshelf.rename_input.take().unwrap_or_default();
+            let Some(index) = app.selected_novel_index else {
+                return;
+            };
+            match app.rename_novel(index, &input) {
+                Ok(path) => app.set_info(trf!("已重命名为 {}", "Renamed to {}", path.display())),
+                Err(e) => app.set_error(trf!("重命名失败：{}", "Rename failed: {}", e)),
+            }
+        }
+        KeyCode::Backspace => {
+            input.pop();
         }
+        KeyCode::Char(c) => input.push(c),
         _ => {}
     }
 }
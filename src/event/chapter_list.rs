@@ -1,8 +1,9 @@
 use crate::app::App;
+use crate::i18n::tr;
 use crate::state::AppState;
 use crossterm::event::KeyCode;
 
-use super::navigate_list;
+use super::{list_page_size, navigate_list, page_list};
 
 /// 处理章节目录模式下的键盘事件
 ///
@@ -16,31 +17,164 @@ use super::navigate_list;
 /// - `Enter`: 跳转到选中的章节
 /// - `Up`/`k`: 向上选择
 /// - `Down`/`j`: 向下选择
+/// - `PageUp`/`PageDown`: 向上/向下翻页
+/// - `Left`/`h`: 折叠选中的卷（选中卷内章节时折叠其所属的卷）
+/// - `Right`/`l`: 展开选中的卷
+/// - `/`: 按标题筛选章节，`Enter` 结束输入并保留筛选
+/// - `e`: 进入/退出编辑模式
+///
+/// 编辑模式下还支持：
+///
+/// - `d`: 删除选中的章节（误识别的标题）
+/// - `r`: 重命名选中的章节
+/// - `a`: 以当前阅读位置为开头添加章节
+/// - `R`: 放弃手动编辑，恢复自动识别
+/// - `A`: 开启/关闭本书的内置章节自动识别（关闭后只按自定义正则识别）
 pub(super) fn handle_chapter_list_key(app: &mut App, key: KeyCode) {
+    if app.chapter_list.rename_input.is_some() {
+        handle_rename_key(app, key);
+        return;
+    }
+    if app.chapter_list.filter_input {
+        handle_filter_key(app, key);
+        return;
+    }
+
+    if app.chapter_list.editing {
+        match key {
+            KeyCode::Char('d') => {
+                if app.delete_selected_chapter().is_none() {
+                    app.set_error(tr(
+                        "无法删除：至少保留一章",
+                        "Cannot delete: at least one chapter must remain",
+                    ));
+                }
+                return;
+            }
+            KeyCode::Char('r') => {
+                let title = app
+                    .selected_chapter_index
+                    .zip(app.current_novel.as_ref())
+                    .and_then(|(index, novel)| novel.chapters.get(index))
+                    .map(|chapter| chapter.title.clone());
+                app.chapter_list.rename_input = title;
+                return;
+            }
+            KeyCode::Char('a') => {
+                if app.add_chapter_at_current_line().is_none() {
+                    app.set_error(tr("当前位置已是章节开头", "A chapter already starts here"));
+                }
+                return;
+            }
+            KeyCode::Char('R') => {
+                app.reset_chapter_edits();
+                app.set_info(tr(
+                    "已恢复自动识别的目录",
+                    "Restored the automatically detected chapters",
+                ));
+                return;
+            }
+            KeyCode::Char('A') => {
+                match app.toggle_auto_chapters() {
+                    Some(true) => app.set_info(tr(
+                        "已开启本书的章节自动识别",
+                        "Automatic chapter detection enabled for this book",
+                    )),
+                    Some(false) => app.set_info(tr(
+                        "已关闭本书的章节自动识别",
+                        "Automatic chapter detection disabled for this book",
+                    )),
+                    None => {}
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+
     match key {
+        KeyCode::Char('e') => {
+            app.chapter_list.editing = !app.chapter_list.editing;
+        }
         KeyCode::Enter => {
             if let Some(index) = app.selected_chapter_index
-                && let Some(novel) = &mut app.current_novel
-                && index < novel.chapters.len()
+                && let Some(novel) = &app.current_novel
+                && let Some(chapter) = novel.chapters.get(index)
             {
-                let chapter = &novel.chapters[index];
-                novel.progress.scroll_offset = chapter.start_line;
-                app.save_current_progress();
+                app.jump_to(chapter.start_line);
                 app.state = AppState::Reading;
             }
         }
+        KeyCode::Char('/') => {
+            app.chapter_list.filter_input = true;
+        }
+        KeyCode::Left | KeyCode::Char('h') => app.collapse_selected_volume(),
+        KeyCode::Right | KeyCode::Char('l') => app.expand_selected_volume(),
+        KeyCode::PageUp | KeyCode::PageDown => {
+            let page_size = list_page_size(app, 5);
+            move_selection(app, |position, len| {
+                page_list(position, len, page_size, key == KeyCode::PageUp)
+            });
+        }
         KeyCode::Up | KeyCode::Char('k') => {
-            if let Some(novel) = &app.current_novel {
-                app.selected_chapter_index =
-                    navigate_list(app.selected_chapter_index, novel.chapters.len(), true);
-            }
+            move_selection(app, |position, len| navigate_list(position, len, true));
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            if let Some(novel) = &app.current_novel {
-                app.selected_chapter_index =
-                    navigate_list(app.selected_chapter_index, novel.chapters.len(), false);
+            move_selection(app, |position, len| navigate_list(position, len, false));
+        }
+        _ => {}
+    }
+}
+
+/// 处理章节重命名的输入
+fn handle_rename_key(app: &mut App, key: KeyCode) {
+    let Some(input) = app.chapter_list.rename_input.as_mut() else {
+        return;
+    };
+    match key {
+        KeyCode::Enter => {
+            let title = app.chapter_list.rename_input.take().unwrap_or_default();
+            if app.rename_selected_chapter(&title).is_none() {
+                app.set_error(tr(
+                    "章节标题不能为空",
+                    "The chapter title must not be empty",
+                ));
             }
         }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
         _ => {}
     }
 }
+
+/// 在可见（筛选后）的章节中移动选中项
+///
+/// `step` 接收选中项在可见列表中的位置和可见章节数，返回新位置。
+fn move_selection(app: &mut App, step: impl Fn(Option<usize>, usize) -> Option<usize>) {
+    let visible = app.visible_chapter_indices();
+    let position = app
+        .selected_chapter_index
+        .and_then(|index| visible.iter().position(|&i| i == index));
+    app.selected_chapter_index = step(position, visible.len()).map(|position| visible[position]);
+}
+
+/// 处理章节筛选关键词的输入，每次输入都立即更新可见章节
+fn handle_filter_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => app.chapter_list.filter_input = false,
+        KeyCode::Backspace => {
+            app.chapter_list.filter.pop();
+        }
+        KeyCode::Char(c) => app.chapter_list.filter.push(c),
+        KeyCode::Up | KeyCode::Down => {
+            move_selection(app, |position, len| {
+                navigate_list(position, len, key == KeyCode::Up)
+            });
+            return;
+        }
+        _ => return,
+    }
+    app.refresh_chapter_filter();
+}
@@ -0,0 +1,31 @@
+use crate::app::App;
+use crossterm::event::KeyCode;
+
+/// 处理命令行模式的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - 字符: 输入命令
+/// - `Backspace`: 删除一个字符，输入为空时退出命令行
+/// - `Enter`: 执行命令
+/// - `Esc`: 取消
+pub(super) fn handle_command_key(app: &mut App, key: KeyCode) {
+    let Some(input) = app.command_input.as_mut() else {
+        return;
+    };
+    match key {
+        KeyCode::Esc => app.command_input = None,
+        KeyCode::Backspace if input.pop().is_none() => app.command_input = None,
+        KeyCode::Enter => {
+            let input = app.command_input.take().unwrap_or_default();
+            app.run_command(&input);
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => {}
+    }
+}
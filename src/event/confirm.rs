@@ -0,0 +1,38 @@
+use crate::app::{App, ConfirmAction};
+use crate::model::preferences::QuitKey;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// 处理确认对话框的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+/// * `modifiers` - 同时按下的修饰键
+///
+/// # Behavior
+///
+/// - `y`/`Enter`: 确认并执行操作（退出确认时再按一次退出键也视为确认：
+///   `quit_key` 为 `q` 时按 `q`，为 `ctrl_q` 时按 `Ctrl+q`，为 `disabled` 时没有）
+/// - `n`/`Esc`: 取消
+/// - 其他按键: 忽略，对话框保持显示
+pub(super) fn handle_confirm_key(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    match key {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => app.resolve_confirm(true),
+        KeyCode::Char('q') | KeyCode::Char('Q')
+            if app
+                .confirm
+                .as_ref()
+                .is_some_and(|dialog| dialog.action == ConfirmAction::Quit)
+                && match app.preferences.quit_key {
+                    QuitKey::Q => true,
+                    QuitKey::CtrlQ => modifiers.contains(KeyModifiers::CONTROL),
+                    QuitKey::Disabled => false,
+                } =>
+        {
+            app.resolve_confirm(true)
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => app.resolve_confirm(false),
+        _ => {}
+    }
+}
@@ -0,0 +1,49 @@
+use crate::app::App;
+use crate::state::AppState;
+use crossterm::event::KeyCode;
+
+use super::navigate_list;
+
+/// 处理高亮列表模式下的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Enter`: 跳转到选中高亮的起始行
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `d`: 删除选中的高亮
+pub(super) fn handle_highlight_list_key(app: &mut App, key: KeyCode) {
+    let len = app.get_current_highlights().map_or(0, Vec::len);
+    match key {
+        KeyCode::Enter => {
+            if let Some(index) = app.highlight_list.selected_index
+                && app.jump_to_highlight(index).is_some()
+            {
+                app.state = AppState::Reading;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.highlight_list.selected_index =
+                navigate_list(app.highlight_list.selected_index, len, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.highlight_list.selected_index =
+                navigate_list(app.highlight_list.selected_index, len, false);
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            if let Some(index) = app.highlight_list.selected_index
+                && app.remove_highlight(index).is_some()
+            {
+                let remaining = len - 1;
+                app.highlight_list.selected_index =
+                    (remaining > 0).then(|| index.min(remaining - 1));
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,52 @@
+use crate::app::{App, LeaderAction};
+use crate::i18n::trf;
+use crate::state::AppState;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use super::{bookshelf, reader};
+
+/// 处理组合键的第二个键（已按下 `,`）
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - 当前界面可用的组合键: 执行对应操作，与单键操作相同
+/// - `Esc`: 取消
+/// - 其他键: 取消并提示没有这个组合键
+pub(super) fn handle_leader_key(app: &mut App, key: KeyCode) {
+    app.leader_pending = false;
+    let KeyCode::Char(c) = key else {
+        return;
+    };
+    let Some(action) = app.leader_action(c) else {
+        app.set_error(trf!("没有组合键 ,{}", "No chord ,{}", c));
+        return;
+    };
+    match action {
+        LeaderAction::Bookmarks if app.state == AppState::Bookshelf => {
+            bookshelf::handle_bookshelf_key(app, KeyCode::Char('b'))
+        }
+        LeaderAction::Bookmarks => reader_key(app, 'b'),
+        LeaderAction::ChapterList => reader_key(app, 't'),
+        LeaderAction::Highlights => reader_key(app, 'H'),
+        LeaderAction::Annotations => reader_key(app, 'A'),
+        LeaderAction::Search => reader_key(app, '/'),
+        LeaderAction::Settings => bookshelf::handle_bookshelf_key(app, KeyCode::Char('s')),
+        LeaderAction::SyncUp => app.trigger_sync(),
+        LeaderAction::SyncDown => app.request_download(),
+        LeaderAction::ToggleLanguage => app.toggle_language(),
+        LeaderAction::ToggleMouseCapture => app.toggle_mouse_capture(),
+        LeaderAction::Quit => app.request_quit(),
+    }
+}
+
+/// 按阅读界面中的单键执行同样的操作
+fn reader_key(app: &mut App, key: char) {
+    // 先退出选择模式，否则这个键会被当作选择模式中的操作
+    app.reader.visual_anchor = None;
+    reader::handle_reader_key(app, KeyCode::Char(key), KeyModifiers::NONE);
+}
@@ -1,23 +1,55 @@
 use crate::app::App;
+use crate::config::config;
+use crate::i18n::{tr, trf};
+use crate::model::preferences::QuitKey;
 use crate::state::AppState;
 use crate::ui::sync_status::SyncStatus;
-use crossterm::event::{KeyCode, MouseEvent, MouseEventKind};
-use unicode_width::UnicodeWidthStr;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Position;
 
+mod annotation;
+mod book_detail;
 mod bookmark;
 mod bookshelf;
 mod chapter_list;
+mod command;
+mod confirm;
+mod highlight;
+mod leader;
 mod reader;
 mod search;
 mod settings;
+mod setup;
 
 fn is_text_input_mode(app: &App) -> bool {
     match app.state {
+        AppState::Bookshelf => {
+            app.bookshelf.import_input.is_some()
+                || app.bookshelf.rename_input.is_some()
+                || app.bookshelf.tag_input.is_some()
+                || app.bookshelf.filter_input
+        }
+        AppState::BookDetail => {
+            app.bookshelf.tag_input.is_some()
+                || app.bookshelf.rename_input.is_some()
+                || app.bookshelf.note_input.is_some()
+        }
         AppState::Searching | AppState::BookmarkAdd => true,
-        AppState::Settings => {
-            app.settings.mode == crate::state::SettingsMode::WebDavConfig
-                && app.settings.webdav_config_state.edit_mode
+        AppState::Setup => app.setup.step != crate::app::SetupStep::WebDav,
+        AppState::Reading => app.reader.find_input.is_some() || app.annotation.input.is_some(),
+        AppState::AnnotationList => app.annotation.input.is_some(),
+        AppState::BookmarkList => app.bookmark.filter_input,
+        AppState::ChapterList => {
+            app.chapter_list.rename_input.is_some() || app.chapter_list.filter_input
         }
+        AppState::Settings => match app.settings.mode {
+            crate::state::SettingsMode::WebDavConfig => app.settings.webdav_config_state.edit_mode,
+            crate::state::SettingsMode::LibraryDirs => {
+                app.settings.library_dir_input.is_some() || app.settings.novels_dir_input.is_some()
+            }
+            crate::state::SettingsMode::DeleteOrphaned => app.settings.relocate_input.is_some(),
+            _ => false,
+        },
         _ => false,
     }
 }
@@ -25,34 +57,146 @@ fn is_text_input_mode(app: &App) -> bool {
 fn handle_back(app: &mut App) {
     match app.state {
         AppState::Bookshelf => {
-            app.should_quit = true;
+            if app.bookshelf.import_input.take().is_some()
+                || app.bookshelf.rename_input.take().is_some()
+                || app.bookshelf.tag_input.take().is_some()
+            {
+                return;
+            }
+            if !app.bookshelf.marked.is_empty() {
+                app.bookshelf.marked.clear();
+                return;
+            }
+            if app.bookshelf.filter_input || !app.bookshelf.filter.is_empty() {
+                // 取消筛选，保留当前选中的小说
+                app.bookshelf.filter_input = false;
+                app.bookshelf.filter.clear();
+                return;
+            }
+            if app.preferences.quit_key == QuitKey::Q {
+                app.request_quit();
+            } else {
+                app.set_info(trf!(
+                    "按 {} 退出",
+                    "Press {} to quit",
+                    app.preferences.quit_key.hint()
+                ));
+            }
         }
         AppState::Reading => {
+            if app.reader.find_input.take().is_some() {
+                // 取消阅读界面内的查找，回到查找前的位置
+                if let Some(novel) = &mut app.current_novel {
+                    novel.progress.scroll_offset = app.reader.find_origin;
+                }
+                app.reader.highlight_term = None;
+                return;
+            }
+            if app.reader.visual_anchor.take().is_some() {
+                // 取消选择
+                return;
+            }
+            if app.annotation.input.take().is_some() {
+                // 取消编辑批注
+                return;
+            }
+            app.record_exit_position();
             app.save_current_progress();
+            // 最近阅读时间和进度已变化
+            app.sort_novels();
             app.state = AppState::Bookshelf;
         }
         AppState::Searching => {
-            app.state = AppState::Reading;
+            if app.search.goto_input.take().is_none() {
+                app.state = AppState::Reading;
+            }
         }
         AppState::ChapterList => {
+            if app.chapter_list.rename_input.take().is_some() {
+                return;
+            }
+            if app.chapter_list.filter_input || !app.chapter_list.filter.is_empty() {
+                // 取消筛选，保留当前选中的章节
+                app.chapter_list.filter_input = false;
+                app.chapter_list.filter.clear();
+                return;
+            }
+            if app.chapter_list.editing {
+                app.chapter_list.editing = false;
+                return;
+            }
             app.state = AppState::Reading;
         }
         AppState::BookmarkList => {
+            if app.bookmark.filter_input || !app.bookmark.filter.is_empty() {
+                // 取消筛选，保留当前选中的书签
+                app.bookmark.filter_input = false;
+                app.bookmark.filter.clear();
+                return;
+            }
             app.state = AppState::Reading;
         }
         AppState::BookmarkAdd => {
             app.clear_bookmark_inputs();
             app.state = AppState::BookmarkList;
         }
+        AppState::AllBookmarks => {
+            app.state = AppState::Bookshelf;
+        }
+        AppState::BookDetail => {
+            if app.bookshelf.tag_input.take().is_none()
+                && app.bookshelf.rename_input.take().is_none()
+                && app.bookshelf.note_input.take().is_none()
+            {
+                app.state = AppState::Bookshelf;
+                // 删除标签后这本书可能不再符合标签筛选
+                app.refresh_bookshelf_selection();
+            }
+        }
+        AppState::HighlightList => {
+            app.state = AppState::Reading;
+        }
+        AppState::Setup => {
+            app.finish_setup(false);
+            app.set_info(tr(
+                "已跳过首次设置，可随时在设置中修改",
+                "Setup skipped; you can change everything later in Settings",
+            ));
+        }
+        AppState::AnnotationList => {
+            if app.annotation.input.take().is_none() {
+                app.state = AppState::Reading;
+            }
+        }
         AppState::Settings => match app.settings.mode {
             crate::state::SettingsMode::MainMenu => {
                 app.state = AppState::Bookshelf;
                 app.settings.reset();
             }
+            crate::state::SettingsMode::DeleteOrphaned => {
+                if app.settings.relocate_input.take().is_none() {
+                    app.settings.mode = crate::state::SettingsMode::MainMenu;
+                }
+            }
+            crate::state::SettingsMode::Appearance => {
+                app.preferences.theme = app.settings.saved_theme;
+                app.settings.mode = crate::state::SettingsMode::MainMenu;
+            }
             crate::state::SettingsMode::DeleteNovel
-            | crate::state::SettingsMode::DeleteOrphaned => {
+            | crate::state::SettingsMode::ReaderOptions
+            | crate::state::SettingsMode::Backups
+            | crate::state::SettingsMode::Performance
+            | crate::state::SettingsMode::BookmarkTransfer
+            | crate::state::SettingsMode::Trash => {
                 app.settings.mode = crate::state::SettingsMode::MainMenu;
             }
+            crate::state::SettingsMode::LibraryDirs => {
+                if app.settings.library_dir_input.take().is_none()
+                    && app.settings.novels_dir_input.take().is_none()
+                {
+                    app.settings.mode = crate::state::SettingsMode::MainMenu;
+                }
+            }
             crate::state::SettingsMode::WebDavConfig => {
                 if app.settings.webdav_config_state.edit_mode {
                     app.settings.webdav_config_state.edit_mode = false;
@@ -64,55 +208,106 @@ fn handle_back(app: &mut App) {
     }
 }
 
-/// 计算字符串在指定宽度下占用的物理行数
+/// 通用列表导航函数
+///
+/// 根据移动方向计算新的选中索引，是否在首尾循环由配置文件的 `navigation.wrap` 决定。
 ///
 /// # Arguments
 ///
-/// * `line` - 要计算的字符串
-/// * `width` - 可用宽度（字符数）
+/// * `current` - 当前选中索引
+/// * `len` - 列表长度
+/// * `move_up` - 是否向上移动（`true` 为向上，`false` 为向下）
 ///
 /// # Returns
 ///
-/// 占用的物理行数。空字符串或零宽度返回 1。
-pub(super) fn count_physical_lines(line: &str, width: usize) -> usize {
-    if line.is_empty() {
-        return 1;
-    }
-    if width == 0 {
-        return 1;
+/// 新的选中索引。如果列表为空则返回 `None`。
+pub(super) fn navigate_list(current: Option<usize>, len: usize, move_up: bool) -> Option<usize> {
+    step_list(current, len, move_up, config().navigation.wrap)
+}
+
+/// 把选中索引移动一项
+///
+/// # Arguments
+///
+/// * `current` - 当前选中索引
+/// * `len` - 列表长度
+/// * `move_up` - 是否向上移动（`true` 为向上，`false` 为向下）
+/// * `wrap` - 到达首尾时是否循环到另一端，否则停在首尾
+///
+/// # Returns
+///
+/// 新的选中索引。如果列表为空则返回 `None`。
+fn step_list(current: Option<usize>, len: usize, move_up: bool, wrap: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
     }
-    line.width().div_ceil(width)
+
+    let new_idx = match (current, move_up) {
+        (None, true) => len - 1,
+        (None, false) => 0,
+        (Some(0), true) if wrap => len - 1,
+        (Some(idx), true) => idx.saturating_sub(1),
+        (Some(idx), false) if idx + 1 >= len && wrap => 0,
+        (Some(idx), false) => (idx + 1).min(len - 1),
+    };
+
+    Some(new_idx)
 }
 
-/// 通用列表导航函数
+/// 列表翻页函数
 ///
-/// 根据移动方向计算新的选中索引，支持循环导航。
+/// 将选中索引移动一整页，到达列表首尾时停住而不是循环。
 ///
 /// # Arguments
 ///
 /// * `current` - 当前选中索引
 /// * `len` - 列表长度
-/// * `move_up` - 是否向上移动（`true` 为向上，`false` 为向下）
+/// * `page_size` - 每页可见的条目数
+/// * `move_up` - 是否向上翻页（`true` 为向上，`false` 为向下）
 ///
 /// # Returns
 ///
 /// 新的选中索引。如果列表为空则返回 `None`。
-pub(super) fn navigate_list(current: Option<usize>, len: usize, move_up: bool) -> Option<usize> {
+pub(super) fn page_list(
+    current: Option<usize>,
+    len: usize,
+    page_size: usize,
+    move_up: bool,
+) -> Option<usize> {
     if len == 0 {
         return None;
     }
 
+    let current = current.unwrap_or(0);
+    let page_size = page_size.max(1);
     let new_idx = if move_up {
-        current
-            .map(|idx| if idx == 0 { len - 1 } else { idx - 1 })
-            .unwrap_or(len - 1)
+        current.saturating_sub(page_size)
     } else {
-        current.map(|idx| (idx + 1) % len).unwrap_or(0)
+        (current + page_size).min(len - 1)
     };
 
     Some(new_idx)
 }
 
+/// 计算列表翻页一次移动的条目数
+///
+/// 配置文件设置了 `navigation.page_step` 时使用该值，否则为一页可见的条目数。
+///
+/// # Arguments
+///
+/// * `app` - 应用实例
+/// * `reserved_rows` - 标题、边框、帮助栏等非列表内容占用的行数
+pub(super) fn list_page_size(app: &App, reserved_rows: u16) -> usize {
+    match config().navigation.page_step {
+        0 => app
+            .terminal_size
+            .height
+            .saturating_sub(reserved_rows)
+            .max(1) as usize,
+        step => step,
+    }
+}
+
 /// 处理键盘事件
 ///
 /// 根据当前应用状态将键盘事件分发到对应的处理函数。
@@ -121,8 +316,10 @@ pub(super) fn navigate_list(current: Option<usize>, len: usize, move_up: bool) -
 ///
 /// * `app` - 应用实例的可变引用
 /// * `key` - 按下的键位代码
-pub fn handle_key(app: &mut App, key: KeyCode) {
+/// * `modifiers` - 同时按下的修饰键（阅读界面和 `Ctrl+q` 退出键使用）
+pub fn handle_key(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
     app.error_message = None;
+    app.info_message = None;
     if matches!(
         app.sync_status,
         SyncStatus::Success(_) | SyncStatus::Error(_)
@@ -130,56 +327,149 @@ pub fn handle_key(app: &mut App, key: KeyCode) {
         app.sync_status = SyncStatus::Idle;
     }
 
+    // 在按键可能移动阅读位置之前记录，往回跳转不会丢失读到过的最远位置
+    if let Some(novel) = &mut app.current_novel {
+        novel.record_furthest_line();
+    }
+
+    if app.confirm.is_some() {
+        confirm::handle_confirm_key(app, key, modifiers);
+        return;
+    }
+
+    if app.preferences.quit_key == QuitKey::CtrlQ
+        && matches!(key, KeyCode::Char('q') | KeyCode::Char('Q'))
+        && modifiers.contains(KeyModifiers::CONTROL)
+    {
+        app.request_quit();
+        return;
+    }
+
+    if app.command_input.is_some() {
+        command::handle_command_key(app, key);
+        return;
+    }
+
+    if app.leader_pending {
+        leader::handle_leader_key(app, key);
+        return;
+    }
+
     if matches!(key, KeyCode::Esc) {
         handle_back(app);
         return;
     }
 
-    if matches!(key, KeyCode::Char('q') | KeyCode::Char('Q')) && !is_text_input_mode(app) {
-        app.save_current_progress();
-        app.should_quit = true;
+    if key == KeyCode::F(2) {
+        app.toggle_mouse_capture();
+        return;
+    }
+
+    if key == KeyCode::Char(':') && !is_text_input_mode(app) {
+        // 进入命令行模式，放弃阅读界面中输入到一半的数字前缀和组合键
+        app.reader.pending_count = None;
+        app.reader.pending_key = None;
+        app.command_input = Some(String::new());
+        return;
+    }
+
+    if key == KeyCode::Char(',') && !is_text_input_mode(app) && app.state != AppState::Setup {
+        app.reader.pending_count = None;
+        app.reader.pending_key = None;
+        app.leader_pending = true;
+        return;
+    }
+
+    if app.preferences.quit_key == QuitKey::Q
+        && matches!(key, KeyCode::Char('q') | KeyCode::Char('Q'))
+        && !is_text_input_mode(app)
+    {
+        app.request_quit();
         return;
     }
 
     match app.state {
         AppState::Bookshelf => bookshelf::handle_bookshelf_key(app, key),
-        AppState::Reading => reader::handle_reader_key(app, key),
-        AppState::Searching => search::handle_search_key(app, key),
+        AppState::Reading => reader::handle_reader_key(app, key, modifiers),
+        AppState::Searching => search::handle_search_key(app, key, modifiers),
         AppState::ChapterList => chapter_list::handle_chapter_list_key(app, key),
         AppState::Settings => settings::handle_settings_key(app, key),
         AppState::BookmarkList => bookmark::handle_bookmark_list_key(app, key),
-        AppState::BookmarkAdd => bookmark::handle_bookmark_add_key(app, key),
+        AppState::BookmarkAdd => bookmark::handle_bookmark_add_key(app, key, modifiers),
+        AppState::AllBookmarks => bookmark::handle_all_bookmarks_key(app, key),
+        AppState::HighlightList => highlight::handle_highlight_list_key(app, key),
+        AppState::AnnotationList => annotation::handle_annotation_list_key(app, key),
+        AppState::BookDetail => book_detail::handle_book_detail_key(app, key),
+        AppState::Setup => setup::handle_setup_key(app, key),
     }
 }
 
+/// 处理定时事件
+///
+/// 主循环每个 tick 调用一次，驱动自动滚动和定期保存进度等不依赖按键的行为。
+pub fn handle_tick(app: &mut App) {
+    reader::tick_auto_scroll(app);
+    app.autosave_progress();
+}
+
 /// 处理鼠标事件
 ///
 /// 将鼠标滚动事件转换为对应的键盘事件并分发。
+/// 阅读界面中左键点击正文右半边向后翻页、左半边向前翻页（竖排时相反）。
 ///
 /// # Arguments
 ///
 /// * `app` - 应用实例的可变引用
 /// * `mouse` - 鼠标事件
 pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    if app.confirm.is_some() {
+        return;
+    }
+
     match mouse.kind {
         MouseEventKind::ScrollUp => match app.state {
-            AppState::Reading => reader::handle_reader_key(app, KeyCode::Up),
+            AppState::Reading => reader::handle_reader_key(app, KeyCode::Up, KeyModifiers::NONE),
             AppState::Bookshelf => bookshelf::handle_bookshelf_key(app, KeyCode::Up),
             AppState::ChapterList => chapter_list::handle_chapter_list_key(app, KeyCode::Up),
             AppState::Settings => settings::handle_settings_key(app, KeyCode::Up),
-            AppState::Searching => search::handle_search_key(app, KeyCode::Up),
+            AppState::Searching => search::handle_search_key(app, KeyCode::Up, KeyModifiers::NONE),
             AppState::BookmarkList => bookmark::handle_bookmark_list_key(app, KeyCode::Up),
-            AppState::BookmarkAdd => {}
+            AppState::AllBookmarks => bookmark::handle_all_bookmarks_key(app, KeyCode::Up),
+            AppState::HighlightList => highlight::handle_highlight_list_key(app, KeyCode::Up),
+            AppState::AnnotationList => annotation::handle_annotation_list_key(app, KeyCode::Up),
+            AppState::BookDetail => book_detail::handle_book_detail_key(app, KeyCode::Up),
+            AppState::BookmarkAdd | AppState::Setup => {}
         },
         MouseEventKind::ScrollDown => match app.state {
-            AppState::Reading => reader::handle_reader_key(app, KeyCode::Down),
+            AppState::Reading => reader::handle_reader_key(app, KeyCode::Down, KeyModifiers::NONE),
             AppState::Bookshelf => bookshelf::handle_bookshelf_key(app, KeyCode::Down),
             AppState::ChapterList => chapter_list::handle_chapter_list_key(app, KeyCode::Down),
             AppState::Settings => settings::handle_settings_key(app, KeyCode::Down),
-            AppState::Searching => search::handle_search_key(app, KeyCode::Down),
+            AppState::Searching => {
+                search::handle_search_key(app, KeyCode::Down, KeyModifiers::NONE)
+            }
             AppState::BookmarkList => bookmark::handle_bookmark_list_key(app, KeyCode::Down),
-            AppState::BookmarkAdd => {}
+            AppState::AllBookmarks => bookmark::handle_all_bookmarks_key(app, KeyCode::Down),
+            AppState::HighlightList => highlight::handle_highlight_list_key(app, KeyCode::Down),
+            AppState::AnnotationList => annotation::handle_annotation_list_key(app, KeyCode::Down),
+            AppState::BookDetail => book_detail::handle_book_detail_key(app, KeyCode::Down),
+            AppState::BookmarkAdd | AppState::Setup => {}
         },
+        MouseEventKind::Down(MouseButton::Left) if app.state == AppState::Reading => {
+            let text_area = crate::ui::reader::text_area(app, app.terminal_size);
+            if !text_area.contains(Position::new(mouse.column, mouse.row)) {
+                return;
+            }
+            let right_half = mouse.column >= text_area.x + text_area.width / 2;
+            // 竖排从右往左读，左半边才是"下一页"
+            let forward = right_half != app.reader.vertical_layout;
+            let key = if forward {
+                KeyCode::Right
+            } else {
+                KeyCode::Left
+            };
+            reader::handle_reader_key(app, key, KeyModifiers::NONE);
+        }
         _ => {}
     }
 }
@@ -187,14 +477,22 @@ pub fn handle_mouse(app: &mut App, mouse: MouseEvent) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app::{App, BookmarkState, SearchState, SettingsState};
-    use crate::model::library::Library;
-    use crate::model::novel::Novel;
+    use crate::app::{
+        AnnotationState, App, BookmarkState, BookshelfRow, BookshelfState, ChapterListState,
+        HighlightListState, LeaderAction, PerformanceOption, ReaderOption, ReaderState,
+        SearchState, SettingsState, SetupState, SetupStep,
+    };
+    use crate::model::library::{Library, NovelInfo, ReadingStatus};
+    use crate::model::novel::{MAX_LINE_SPACING, Novel, ReadingProgress};
+    use crate::model::preferences::{Language, Preferences, ReaderPadding, ThemeName};
     use crate::state::{AppState, SettingsMode};
     use crate::sync::config::WebDavConfig;
-    use crossterm::event::{KeyModifiers, MouseEvent};
+    use crate::ui::reader::{ReaderLayout, wrap_line};
+    use crate::ui::theme::Theme;
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent};
     use ratatui::layout::Rect;
     use std::path::PathBuf;
+    use std::time::{Duration, Instant};
 
     fn create_test_app() -> App {
         App {
@@ -202,21 +500,43 @@ mod tests {
             library: Library::default(),
             novels: Vec::new(),
             selected_novel_index: None,
+            selected_category: None,
+            bookshelf: BookshelfState::default(),
+            setup: SetupState::default(),
             current_novel: None,
             should_quit: false,
             terminal_size: Rect::default(),
             selected_chapter_index: None,
+            chapter_list: ChapterListState::default(),
+            chapter_parse_rx: None,
             previous_state: AppState::Bookshelf,
             search: SearchState::default(),
             bookmark: BookmarkState::default(),
+            highlight_list: HighlightListState::default(),
+            annotation: AnnotationState::default(),
+            reader: ReaderState::default(),
+            navigation: std::collections::HashMap::new(),
             settings: SettingsState::default(),
+            preferences: Preferences::default(),
             error_message: None,
+            info_message: None,
+            confirm: None,
+            command_input: None,
+            leader_pending: false,
             webdav_config: WebDavConfig::default(),
             sync_rx: None,
             sync_status: SyncStatus::Idle,
+            novel_watcher: None,
+            config_watcher: None,
+            mouse_capture: true,
         }
     }
 
+    /// 不加连字符时一行正文占用的屏幕行数
+    fn count_physical_lines(line: &str, width: usize) -> usize {
+        wrap_line(line, width, false).len()
+    }
+
     #[test]
     fn test_count_physical_lines_empty() {
         assert_eq!(count_physical_lines("", 80), 1);
@@ -238,6 +558,22 @@ mod tests {
         assert_eq!(count_physical_lines("12345678", 4), 2);
     }
 
+    #[test]
+    fn test_indent_is_added_before_wrapping() {
+        let layout = ReaderLayout {
+            text_area: Rect::new(0, 0, 8, 10),
+            vertical: false,
+            hyphenate: false,
+            indent: 2,
+        };
+        assert_eq!(
+            layout.wrap("  你好世界"),
+            vec!["\u{3000}\u{3000}你好", "世界"]
+        );
+        assert_eq!(layout.wrap("   "), vec!["   "]);
+        assert_eq!(layout.line_rows("你好世界"), 2);
+    }
+
     #[test]
     fn test_count_physical_lines_chinese() {
         assert_eq!(count_physical_lines("你好", 4), 1);
@@ -257,6 +593,12 @@ mod tests {
         assert_eq!(navigate_list(Some(0), 3, true), Some(2));
         assert_eq!(navigate_list(Some(2), 3, false), Some(0));
         assert_eq!(navigate_list(Some(0), 0, false), None);
+
+        // 关闭循环后停在首尾
+        assert_eq!(step_list(Some(2), 3, false, false), Some(2));
+        assert_eq!(step_list(Some(0), 3, true, false), Some(0));
+        assert_eq!(step_list(Some(1), 3, true, false), Some(0));
+        assert_eq!(step_list(None, 3, true, false), Some(2));
     }
 
     #[test]
@@ -264,8 +606,81 @@ mod tests {
         let mut app = create_test_app();
         app.state = AppState::Bookshelf;
 
-        handle_key(&mut app, KeyCode::Char('q'));
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(!app.should_quit);
+        assert!(app.confirm.is_some());
+
+        // 再按一次 q 确认退出
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_quit_confirmation_can_be_cancelled_or_disabled() {
+        let mut app = create_test_app();
+        app.state = AppState::Bookshelf;
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        assert!(!app.should_quit);
+
+        app.preferences.confirm_quit = false;
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_quit_key_can_require_ctrl_q_or_command() {
+        let mut app = create_test_app();
+        app.preferences.confirm_quit = false;
+        app.preferences.quit_key = QuitKey::CtrlQ;
+        app.state = AppState::Bookshelf;
+
+        // 单独的 q 和书架上的 Esc 都不再退出
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.should_quit);
+        assert!(app.info_message.is_some());
+
+        // 输入时 Ctrl+q 也可以退出
+        app.state = AppState::Searching;
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.should_quit);
+
+        let mut app = create_test_app();
+        app.preferences.confirm_quit = false;
+        app.preferences.quit_key = QuitKey::Disabled;
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(!app.should_quit);
+        for key in [':', 'q'] {
+            handle_key(&mut app, KeyCode::Char(key), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_quit_dialog_confirms_only_with_the_quit_key() {
+        let mut app = create_test_app();
+        app.preferences.quit_key = QuitKey::CtrlQ;
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(app.should_quit);
 
+        let mut app = create_test_app();
+        app.preferences.quit_key = QuitKey::Disabled;
+        app.run_command("q");
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(!app.should_quit);
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
         assert!(app.should_quit);
     }
 
@@ -278,7 +693,7 @@ mod tests {
         app.search.results = vec![(7, "line".to_string())];
         app.search.selected_index = Some(0);
 
-        handle_key(&mut app, KeyCode::Enter);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
 
         assert!(app.state == AppState::Reading);
         assert_eq!(
@@ -287,102 +702,2341 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_search_jump_keeps_highlight_until_cleared() {
+        let mut app = create_test_app();
+        app.current_novel = Some(Novel::new(PathBuf::from("test.txt")));
+        app.state = AppState::Searching;
+        app.search.input = "line".to_string();
+        app.search.results = vec![(3, "a line".to_string())];
+        app.search.selected_index = Some(0);
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.reader.highlight_term.as_deref(), Some("line"));
+
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.reader.highlight_term.is_none());
+    }
+
     #[test]
     fn test_handle_key_bookshelf_settings_defaults_to_first_option() {
         let mut app = create_test_app();
         app.state = AppState::Bookshelf;
         app.settings.selected_option = None;
 
-        handle_key(&mut app, KeyCode::Char('s'));
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
 
         assert!(app.state == AppState::Settings);
         assert_eq!(app.settings.selected_option, Some(0));
     }
 
     #[test]
-    fn test_handle_key_esc_from_search_returns_reading() {
+    fn test_ctrl_f_toggles_fuzzy_search() {
         let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("第一章\n张三走进了客栈\n李四在喝酒".to_string());
+        app.current_novel = Some(novel);
         app.state = AppState::Searching;
-        app.previous_state = AppState::Bookshelf;
+        for c in "zhangsan".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert!(app.search.results.is_empty());
 
-        handle_key(&mut app, KeyCode::Esc);
+        handle_key(&mut app, KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert!(app.search.fuzzy);
+        assert_eq!(app.search.input, "zhangsan");
+        assert_eq!(app.search.results.len(), 1);
+        assert_eq!(app.search.results[0].0, 1);
 
+        // 跳转后阅读界面同样按模糊匹配高亮
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
         assert!(app.state == AppState::Reading);
+        assert_eq!(app.reader.highlight_term.as_deref(), Some("zhangsan"));
+        assert!(app.reader.highlight_fuzzy);
+
+        app.state = AppState::Searching;
+        handle_key(&mut app, KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert!(!app.search.fuzzy);
+        assert!(app.search.results.is_empty());
     }
 
     #[test]
-    fn test_handle_key_esc_from_bookmark_add_returns_bookmark_list_and_clears_input() {
+    fn test_search_jump_records_match_position_for_n() {
         let mut app = create_test_app();
-        app.state = AppState::BookmarkAdd;
-        app.bookmark.input = "abc".to_string();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("a\nhit\nb\nhit\nc\nhit".to_string());
+        app.current_novel = Some(novel);
+        app.state = AppState::Searching;
+        for c in "hit".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
 
-        handle_key(&mut app, KeyCode::Esc);
+        assert_eq!(app.reader.match_lines, vec![1, 3, 5]);
+        assert_eq!(app.match_position(), Some((2, 3)));
 
-        assert!(app.state == AppState::BookmarkList);
-        assert!(app.bookmark.input.is_empty());
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(app.match_position(), Some((3, 3)));
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(app.match_position(), Some((1, 3)));
+        handle_key(&mut app, KeyCode::Char('N'), KeyModifiers::NONE);
+        assert_eq!(app.match_position(), Some((3, 3)));
+
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert_eq!(app.match_position(), None);
     }
 
     #[test]
-    fn test_handle_key_esc_in_webdav_edit_mode_only_exit_edit_mode() {
+    fn test_ctrl_e_exports_search_results() {
         let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.title = "测试".to_string();
+        novel.set_content("第一章 起\n张三出场\n第二章 承\n又见张三".to_string());
+        novel.parse_chapters();
+        app.current_novel = Some(novel);
+        app.state = AppState::Searching;
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::CONTROL);
+        assert!(app.error_message.is_some());
+
+        for c in "张三".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::CONTROL);
+        assert!(app.error_message.is_none());
+        assert!(app.info_message.is_some());
+
+        let path = app.export_search_results().unwrap();
+        assert!(path.parent().unwrap().ends_with("exports"));
+        let text = std::fs::read_to_string(path).unwrap();
+        assert!(text.contains("- 第 2 行 · 第一章 起：张三出场"));
+        assert!(text.contains("- 第 4 行 · 第二章 承：又见张三"));
+    }
+
+    #[test]
+    fn test_settings_bookmark_export_and_import() {
+        let mut app = create_test_app();
+        let path = PathBuf::from("书签导出测试.txt");
+        app.novels = vec![Novel::new(path.clone())];
+        let progress = app.library.progress_mut(&path);
+        progress.add_bookmark("伏笔".to_string(), 41).note = "第二行\n备注".to_string();
+        progress.add_bookmark("开头".to_string(), 0);
         app.state = AppState::Settings;
-        app.settings.mode = SettingsMode::WebDavConfig;
-        app.settings.webdav_config_state.edit_mode = true;
+        app.settings.selected_option = Some(3);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.settings.mode == SettingsMode::BookmarkTransfer);
 
-        handle_key(&mut app, KeyCode::Esc);
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.error_message.is_none());
+        let markdown = app.export_bookmarks(0).unwrap();
+        let text = std::fs::read_to_string(markdown).unwrap();
+        assert!(text.contains("## 伏笔\n\n- 位置：第 42 行"));
+        assert!(text.contains("第二行\n备注"));
 
-        assert!(app.state == AppState::Settings);
-        assert!(app.settings.mode == SettingsMode::WebDavConfig);
-        assert!(!app.settings.webdav_config_state.edit_mode);
+        app.library.progress_mut(&path).bookmarks.remove(0);
+        handle_key(&mut app, KeyCode::Char('i'), KeyModifiers::NONE);
+        assert!(app.error_message.is_none());
+        let bookmarks = &app.library.get_novel_progress(&path).bookmarks;
+        assert_eq!(bookmarks.len(), 2);
+        assert_eq!(bookmarks[0].name, "开头");
+        assert_eq!(bookmarks[1].note, "第二行\n备注");
+        // 重复导入不会产生重复书签
+        assert_eq!(app.import_bookmarks(0).unwrap(), 0);
     }
 
     #[test]
-    fn test_handle_key_q_in_search_is_text_input_not_quit() {
+    fn test_all_bookmarks_opens_novel_at_bookmark() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let first = temp_dir.path().join("一.txt");
+        let second = temp_dir.path().join("二.txt");
+        let content: Vec<String> = (0..200).map(|i| format!("Line {}", i)).collect();
+        std::fs::write(&first, content.join("\n")).unwrap();
+        std::fs::write(&second, content.join("\n")).unwrap();
+
+        let mut app = create_test_app();
+        app.novels = vec![Novel::new(first.clone()), Novel::new(second.clone())];
+        app.library
+            .progress_mut(&first)
+            .add_bookmark("甲".to_string(), 10);
+        let progress = app.library.progress_mut(&second);
+        progress.add_bookmark("丙".to_string(), 150);
+        progress.add_bookmark("乙".to_string(), 5);
+
+        handle_key(&mut app, KeyCode::Char('b'), KeyModifiers::NONE);
+        assert!(app.state == AppState::AllBookmarks);
+        let names: Vec<String> = app
+            .all_bookmarks()
+            .into_iter()
+            .map(|entry| entry.bookmark.name)
+            .collect();
+        assert_eq!(names, vec!["甲", "乙", "丙"]);
+
+        handle_key(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        let novel = app.current_novel.as_ref().unwrap();
+        assert_eq!(novel.path, second);
+        assert_eq!(novel.progress.scroll_offset, 150);
+    }
+
+    #[test]
+    fn test_ctrl_g_jumps_to_numbered_result() {
         let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content((0..30).map(|_| "hit").collect::<Vec<_>>().join("\n"));
+        app.current_novel = Some(novel);
         app.state = AppState::Searching;
+        app.search.input = "hit".to_string();
+        app.perform_search();
 
-        handle_key(&mut app, KeyCode::Char('q'));
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::CONTROL);
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('9'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('9'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.error_message.is_some());
+        assert!(app.state == AppState::Searching);
+        assert_eq!(app.search.input, "hit");
 
-        assert!(!app.should_quit);
-        assert_eq!(app.search.input, "q");
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::CONTROL);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state == AppState::Searching);
+        assert!(app.search.goto_input.is_none());
+
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::CONTROL);
+        handle_key(&mut app, KeyCode::Char('1'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert_eq!(app.search.selected_index, Some(11));
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            11
+        );
     }
 
     #[test]
-    fn test_handle_mouse_scroll_down_bookshelf_changes_selection() {
+    fn test_chapter_edit_mode_delete_rename_add() {
         let mut app = create_test_app();
-        app.state = AppState::Bookshelf;
-        app.novels = vec![
-            Novel::new(PathBuf::from("a.txt")),
-            Novel::new(PathBuf::from("b.txt")),
-        ];
+        let mut novel = Novel::new(PathBuf::from("edit.txt"));
+        novel.set_content("第一章 起\n正文\n第二章 承\n广告\n正文".to_string());
+        novel.parse_chapters();
+        novel.progress.scroll_offset = 4;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
 
-        let mouse_down = MouseEvent {
-            kind: MouseEventKind::ScrollDown,
-            column: 0,
-            row: 0,
-            modifiers: KeyModifiers::NONE,
-        };
-        handle_mouse(&mut app, mouse_down);
-        handle_mouse(&mut app, mouse_down);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.chapter_list.editing);
 
-        assert_eq!(app.selected_novel_index, Some(1));
+        // 在阅读位置添加章节
+        handle_key(&mut app, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(app.selected_chapter_index, Some(2));
+
+        // 重命名时 q 是输入内容而不是退出
+        handle_key(&mut app, KeyCode::Char('r'), KeyModifiers::NONE);
+        for _ in 0.."正文".chars().count() {
+            handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        }
+        for c in "q尾".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+
+        // 删除误识别的第二章
+        handle_key(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+
+        let novel = app.current_novel.as_ref().unwrap();
+        let titles: Vec<&str> = novel.chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["第一章 起", "q尾"]);
+        assert_eq!(
+            novel.progress.custom_chapters.as_ref(),
+            Some(&novel.chapters)
+        );
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.chapter_list.editing);
+        assert!(app.state == AppState::ChapterList);
     }
 
     #[test]
-    fn test_handle_mouse_ignored_in_bookmark_add() {
+    fn test_chapter_list_filter_narrows_navigation() {
         let mut app = create_test_app();
-        app.state = AppState::BookmarkAdd;
-        app.bookmark.input = "abc".to_string();
+        let mut novel = Novel::new(PathBuf::from("filter.txt"));
+        novel.set_content("第一章 雨夜\n正文\n第二章 晴天\n正文\n第三章 雨停\n正文".to_string());
+        novel.parse_chapters();
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
 
-        let mouse_up = MouseEvent {
-            kind: MouseEventKind::ScrollUp,
-            column: 0,
-            row: 0,
-            modifiers: KeyModifiers::NONE,
-        };
-        handle_mouse(&mut app, mouse_up);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('/'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('雨'), KeyModifiers::NONE);
+        assert_eq!(app.visible_chapter_indices(), vec![0, 2]);
+        // 输入筛选词时 q 不退出
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
 
-        assert_eq!(app.bookmark.input, "abc");
-        assert!(app.state == AppState::BookmarkAdd);
+        assert_eq!(app.selected_chapter_index, Some(0));
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.selected_chapter_index, Some(2));
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            4
+        );
+    }
+
+    #[test]
+    fn test_chapter_list_collapse_and_expand_volume() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("volumes.txt"));
+        novel.set_content(
+            "第一卷 风起\n第一章 雨夜\n正文\n第二章 晴天\n正文\n第二卷 云涌\n第三章 雨停\n正文"
+                .to_string(),
+        );
+        novel.parse_chapters();
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        assert_eq!(app.visible_chapter_indices(), vec![0, 1, 2, 3, 4]);
+        app.selected_chapter_index = Some(2);
+
+        // 在卷内章节上折叠：折叠所属的卷并选中卷
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(app.selected_chapter_index, Some(0));
+        assert_eq!(app.visible_chapter_indices(), vec![0, 3, 4]);
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.selected_chapter_index, Some(3));
+
+        // 筛选时忽略折叠
+        app.chapter_list.filter = "章".to_string();
+        assert_eq!(app.visible_chapter_indices(), vec![1, 2, 4]);
+        app.chapter_list.filter.clear();
+
+        handle_key(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(app.visible_chapter_indices(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_handle_key_esc_from_search_returns_reading() {
+        let mut app = create_test_app();
+        app.state = AppState::Searching;
+        app.previous_state = AppState::Bookshelf;
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(app.state == AppState::Reading);
+    }
+
+    #[test]
+    fn test_handle_key_esc_from_bookmark_add_returns_bookmark_list_and_clears_input() {
+        let mut app = create_test_app();
+        app.state = AppState::BookmarkAdd;
+        app.bookmark.input = "abc".to_string();
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(app.state == AppState::BookmarkList);
+        assert!(app.bookmark.input.is_empty());
+    }
+
+    #[test]
+    fn test_handle_key_esc_in_webdav_edit_mode_only_exit_edit_mode() {
+        let mut app = create_test_app();
+        app.state = AppState::Settings;
+        app.settings.mode = SettingsMode::WebDavConfig;
+        app.settings.webdav_config_state.edit_mode = true;
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        assert!(app.state == AppState::Settings);
+        assert!(app.settings.mode == SettingsMode::WebDavConfig);
+        assert!(!app.settings.webdav_config_state.edit_mode);
+    }
+
+    #[test]
+    fn test_handle_key_q_in_search_is_text_input_not_quit() {
+        let mut app = create_test_app();
+        app.state = AppState::Searching;
+
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+
+        assert!(!app.should_quit);
+        assert_eq!(app.search.input, "q");
+    }
+
+    #[test]
+    fn test_setup_wizard_creates_dir_imports_and_opens_webdav() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source = source_dir.path().join("setup_wizard_import.txt");
+        std::fs::write(&source, "第一章\n正文").unwrap();
+        let mut app = create_test_app();
+        assert!(app.needs_setup());
+        app.start_setup();
+        assert!(app.state == AppState::Setup);
+        assert_eq!(app.setup.input, App::get_novels_dir().display().to_string());
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.setup.step, SetupStep::Import);
+        assert!(App::get_novels_dir().is_dir());
+
+        // 输入中的 q 是文字，不会退出
+        for c in source.display().to_string().chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(!app.should_quit);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.error_message.is_some());
+        assert_eq!(app.setup.step, SetupStep::Import);
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.setup.input.is_empty());
+        assert!(
+            app.novels
+                .iter()
+                .any(|novel| novel.title == "setup_wizard_import")
+        );
+        std::fs::remove_file(App::get_novels_dir().join("setup_wizard_import.txt")).unwrap();
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.setup.step, SetupStep::WebDav);
+        handle_key(&mut app, KeyCode::Char('w'), KeyModifiers::NONE);
+        assert!(app.state == AppState::Settings);
+        assert_eq!(app.settings.mode, SettingsMode::WebDavConfig);
+        assert!(app.preferences.setup_done);
+        assert!(!app.needs_setup());
+    }
+
+    #[test]
+    fn test_setup_wizard_can_be_skipped() {
+        let mut app = create_test_app();
+        app.start_setup();
+        handle_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(app.preferences.language, Language::English);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state == AppState::Bookshelf);
+        assert!(Preferences::load().setup_done);
+    }
+
+    #[test]
+    fn test_f2_toggles_mouse_capture_even_while_typing() {
+        let mut app = create_test_app();
+        app.state = AppState::Searching;
+
+        handle_key(&mut app, KeyCode::F(2), KeyModifiers::NONE);
+        assert!(!app.mouse_capture);
+        assert!(app.search.input.is_empty());
+        assert!(app.info_message.is_some());
+
+        handle_key(&mut app, KeyCode::F(2), KeyModifiers::NONE);
+        assert!(app.mouse_capture);
+    }
+
+    #[test]
+    fn test_handle_mouse_scroll_down_bookshelf_changes_selection() {
+        let mut app = create_test_app();
+        app.state = AppState::Bookshelf;
+        app.novels = vec![
+            Novel::new(PathBuf::from("a.txt")),
+            Novel::new(PathBuf::from("b.txt")),
+        ];
+
+        let mouse_down = MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse(&mut app, mouse_down);
+        handle_mouse(&mut app, mouse_down);
+
+        assert_eq!(app.selected_novel_index, Some(1));
+    }
+
+    #[test]
+    fn test_bookmark_note_add_and_edit() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("notes.txt"));
+        novel.set_content("line\n".repeat(20));
+        novel.progress.scroll_offset = 5;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char('M'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('甲'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        for key in [KeyCode::Char('a'), KeyCode::Enter, KeyCode::Char('b')] {
+            handle_key(&mut app, key, KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+        assert!(app.state == AppState::BookmarkList);
+        let bookmark = &app.get_current_bookmarks().unwrap()[0];
+        assert_eq!(bookmark.name, "甲");
+        assert_eq!(bookmark.note, "a\nb");
+        assert_eq!(bookmark.position, 5);
+
+        app.bookmark.selected_index = Some(0);
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.state == AppState::BookmarkAdd);
+        assert_eq!(app.bookmark.note_input, "a\nb");
+        handle_key(&mut app, KeyCode::Char('乙'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        let bookmarks = app.get_current_bookmarks().unwrap();
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].name, "甲乙");
+        assert_eq!(bookmarks[0].note, "a\nb");
+    }
+
+    #[test]
+    fn test_bookmark_tag_chosen_on_creation_and_filtered() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("tags.txt"));
+        novel.set_content("line\n".repeat(20));
+        novel.progress.add_bookmark("无标签".to_string(), 1);
+        novel.progress.record_auto_position("退出时", 9);
+        novel.progress.scroll_offset = 5;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char('M'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('甲'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::CONTROL);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::CONTROL);
+        assert_eq!(app.bookmark.tag_input, "名场面");
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.get_current_bookmarks().unwrap()[1].tag, "名场面");
+
+        app.bookmark.selected_index = Some(0);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        assert_eq!(app.bookmark.tag_filter.as_deref(), Some("伏笔"));
+        assert!(app.bookmark_display_order().is_empty());
+        assert_eq!(app.bookmark.selected_index, None);
+
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        // 筛选时只显示带该标签的书签，不显示自动记录
+        assert_eq!(app.bookmark_display_order(), vec![1]);
+        assert_eq!(app.bookmark.selected_index, Some(1));
+
+        for _ in 0..3 {
+            handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        }
+        assert!(app.bookmark.tag_filter.is_none());
+        assert_eq!(app.bookmark_display_order(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_bookmark_list_filter_by_name_and_note() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("filter.txt"));
+        novel.set_content("line\n".repeat(20));
+        novel.progress.add_bookmark("Boss 登场".to_string(), 1);
+        novel.progress.add_bookmark("无关".to_string(), 2).note = "提到了boss".to_string();
+        novel.progress.add_bookmark("其他".to_string(), 3);
+        novel.progress.record_auto_position("退出时", 9);
+        app.current_novel = Some(novel);
+        app.state = AppState::BookmarkList;
+        app.bookmark.selected_index = Some(2);
+
+        handle_key(&mut app, KeyCode::Char('/'), KeyModifiers::NONE);
+        assert!(is_text_input_mode(&app));
+        for c in "BOSS".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        assert_eq!(app.bookmark_display_order(), vec![0, 1]);
+        assert_eq!(app.bookmark.selected_index, Some(0));
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.bookmark.selected_index, Some(1));
+
+        // Enter 保留筛选结果，之后按键回到列表操作
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!is_text_input_mode(&app));
+        handle_key(&mut app, KeyCode::Char('k'), KeyModifiers::NONE);
+        assert_eq!(app.bookmark.selected_index, Some(0));
+
+        // Esc 先取消筛选，再返回阅读
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state == AppState::BookmarkList);
+        assert_eq!(app.bookmark_display_order(), vec![0, 1, 2, 3]);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+    }
+
+    #[test]
+    fn test_bookmark_sort_changes_navigation_order() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("sort.txt"));
+        novel.set_content("line\n".repeat(50));
+        for (name, position, timestamp) in [("乙", 10, 300), ("丙", 20, 100), ("甲", 30, 200)] {
+            novel
+                .progress
+                .add_bookmark(name.to_string(), position)
+                .timestamp = timestamp;
+        }
+        app.current_novel = Some(novel);
+        app.state = AppState::BookmarkList;
+        assert_eq!(app.bookmark_display_order(), vec![0, 1, 2]);
+
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        assert_eq!(app.bookmark_display_order(), vec![0, 2, 1]);
+
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        let names: Vec<String> = app
+            .bookmark_display_order()
+            .iter()
+            .map(|&i| app.get_current_bookmarks().unwrap()[i].name.clone())
+            .collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+
+        // 选择按显示顺序移动
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        app.bookmark.selected_index = Some(0);
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.bookmark.selected_index, Some(2));
+
+        // 删除后选中显示顺序中的下一项
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(app.get_current_bookmarks().unwrap().len(), 2);
+        assert_eq!(app.bookmark.selected_index, Some(1));
+    }
+
+    #[test]
+    fn test_handle_mouse_ignored_in_bookmark_add() {
+        let mut app = create_test_app();
+        app.state = AppState::BookmarkAdd;
+        app.bookmark.input = "abc".to_string();
+
+        let mouse_up = MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse(&mut app, mouse_up);
+
+        assert_eq!(app.bookmark.input, "abc");
+        assert!(app.state == AppState::BookmarkAdd);
+    }
+
+    #[test]
+    fn test_zen_mode_toggle_enlarges_page() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 12);
+        app.preferences.page_overlap = 0;
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            9
+        );
+
+        handle_key(&mut app, KeyCode::Char('z'), KeyModifiers::NONE);
+        assert!(app.reader.zen_mode);
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            20
+        );
+    }
+
+    #[test]
+    fn test_night_mode_toggle() {
+        let mut app = create_test_app();
+        app.current_novel = Some(Novel::new(PathBuf::from("test.txt")));
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.reader.night_mode);
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(!app.reader.night_mode);
+    }
+
+    #[test]
+    fn test_reading_guide_anchor_cycles_within_page() {
+        let mut app = create_test_app();
+        app.current_novel = Some(Novel::new(PathBuf::from("test.txt")));
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 6);
+
+        handle_key(&mut app, KeyCode::Char('u'), KeyModifiers::NONE);
+        assert!(app.reader.reading_guide);
+        assert_eq!(app.reader.guide_anchor, 0);
+
+        for _ in 0..3 {
+            handle_key(&mut app, KeyCode::Char('U'), KeyModifiers::NONE);
+        }
+        assert_eq!(app.reader.guide_anchor, 0);
+
+        handle_key(&mut app, KeyCode::Char('U'), KeyModifiers::NONE);
+        assert_eq!(app.reader.guide_anchor, 1);
+    }
+
+    #[test]
+    fn test_bookshelf_title_filter_matches_pinyin_initials() {
+        let mut app = create_test_app();
+        for (title, category) in [("斗破苍穹", "玄幻"), ("三体", ""), ("Dune", "")] {
+            let mut novel = Novel::new(PathBuf::from(format!("{}.txt", title)));
+            novel.category = category.to_string();
+            app.novels.push(novel);
+        }
+        app.preferences
+            .collapsed_categories
+            .insert("玄幻".to_string());
+        app.sort_novels();
+        app.selected_novel_index = Some(0);
+        app.state = AppState::Bookshelf;
+
+        handle_key(&mut app, KeyCode::Char('/'), KeyModifiers::NONE);
+        for c in "dp".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        // 按标题筛选时折叠的分类也会展开；q 作为关键词输入而不是退出
+        let titles = |app: &App| -> Vec<String> {
+            app.bookshelf_rows()
+                .iter()
+                .filter_map(|row| match row {
+                    BookshelfRow::Novel(index) => Some(app.novels[*index].title.clone()),
+                    _ => None,
+                })
+                .collect()
+        };
+        assert_eq!(titles(&app), vec!["斗破苍穹"]);
+        assert_eq!(app.selected_novel_index, Some(2));
+
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('U'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.bookshelf.filter_input);
+        assert_eq!(titles(&app), vec!["Dune"]);
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.bookshelf.filter.is_empty());
+        assert!(!app.should_quit);
+        assert_eq!(titles(&app), vec!["Dune", "三体"]);
+    }
+
+    #[test]
+    fn test_rescan_key_picks_up_new_files_and_orphans() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let missing = dir.join("已删除.txt");
+        app.library.register_novel(&missing);
+        let path = dir.join("新下载.txt");
+        std::fs::write(&path, "正文").unwrap();
+        app.state = AppState::Bookshelf;
+
+        handle_key(&mut app, KeyCode::Char('R'), KeyModifiers::NONE);
+        assert!(app.novels.iter().any(|n| n.path == path));
+        assert!(
+            app.settings
+                .orphaned_novels
+                .iter()
+                .any(|n| n.path == missing)
+        );
+        assert_eq!(
+            app.info_message.as_deref(),
+            Some("已重新扫描，共 1 本：发现 1 本新小说")
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        handle_key(&mut app, KeyCode::F(5), KeyModifiers::NONE);
+        assert!(app.novels.is_empty());
+    }
+
+    #[test]
+    fn test_reaching_last_page_marks_novel_finished() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("finish.txt"));
+        novel.set_content(
+            (0..30)
+                .map(|i| format!("第{}行", i))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        app.novels = vec![novel.clone()];
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 10);
+
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        app.save_current_progress();
+        assert_eq!(app.bookshelf_status(0), ReadingStatus::Reading);
+
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        assert_eq!(app.bookshelf_status(0), ReadingStatus::Finished);
+    }
+
+    #[test]
+    fn test_camouflage_mode_keeps_paging() {
+        let page_turns = |camouflage: bool| {
+            let mut app = create_test_app();
+            let mut novel = Novel::new(PathBuf::from("test.txt"));
+            let content: Vec<String> = (0..100)
+                .map(|i| format!("{}{}", i, "长".repeat(50)))
+                .collect();
+            novel.set_content(content.join("\n"));
+            app.current_novel = Some(novel);
+            app.state = AppState::Reading;
+            app.terminal_size = Rect::new(0, 0, 80, 24);
+            if camouflage {
+                handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+                assert!(app.reader.camouflage);
+            }
+            let text_area = crate::ui::reader::text_area(&app, app.terminal_size);
+            let offsets: Vec<_> = (0..3)
+                .map(|_| {
+                    handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+                    handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+                    let progress = &app.current_novel.as_ref().unwrap().progress;
+                    (progress.scroll_offset, progress.char_offset)
+                })
+                .collect();
+            (text_area, offsets)
+        };
+
+        assert_eq!(page_turns(true), page_turns(false));
+    }
+
+    #[test]
+    fn test_open_novel_starts_reading_session() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.txt");
+        std::fs::write(&path, "第一章\n内容").unwrap();
+
+        let mut app = create_test_app();
+        app.novels = vec![Novel::new(path)];
+        app.selected_novel_index = Some(0);
+        assert_eq!(app.reader.session_elapsed(), std::time::Duration::ZERO);
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.state == AppState::Reading);
+        assert!(app.reader.session_start.is_some());
+    }
+
+    #[test]
+    fn test_open_novel_parses_chapters_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("book.txt");
+        std::fs::write(&path, "第一章 开始\n内容\n第二章 结束\n内容").unwrap();
+
+        let mut app = create_test_app();
+        app.novels = vec![Novel::new(path)];
+        app.open_novel(0);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while app.is_parsing_chapters() && std::time::Instant::now() < deadline {
+            app.poll_chapter_parse();
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+
+        assert!(!app.is_parsing_chapters());
+        assert_eq!(app.current_novel.as_ref().unwrap().chapters.len(), 2);
+        assert_eq!(app.selected_chapter_index, Some(0));
+    }
+
+    #[test]
+    fn test_reader_options_adjust_open_book_and_preferences() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("options.txt"));
+        novel.set_content("line\n".repeat(20));
+        app.current_novel = Some(novel);
+        app.state = AppState::Settings;
+        app.settings.reset();
+        app.settings.selected_option = Some(7);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::ReaderOptions);
+
+        // 行间距作用于打开的小说，到上限后不再增大
+        for _ in 0..MAX_LINE_SPACING + 2 {
+            handle_key(&mut app, KeyCode::Right, KeyModifiers::NONE);
+        }
+        let spacing = app.current_novel.as_ref().unwrap().progress.line_spacing;
+        assert_eq!(spacing, MAX_LINE_SPACING);
+
+        // 第 4 项为翻页重叠，减到 0 后不再减小
+        for _ in 0..3 {
+            handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        }
+        assert_eq!(
+            ReaderOption::ALL[app.settings.selected_reader_option.unwrap()],
+            ReaderOption::PageOverlap
+        );
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(app.preferences.page_overlap, 0);
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::MainMenu);
+    }
+
+    #[test]
+    fn test_performance_options_step_through_choices() {
+        let mut app = create_test_app();
+        app.state = AppState::Settings;
+        app.settings.reset();
+        app.settings.selected_option = Some(9);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::Performance);
+
+        // 刷新间隔从默认的 100 毫秒逐档调整，到上限后不再增大
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(crate::config::config().performance.tick_ms, 50);
+        for _ in 0..10 {
+            handle_key(&mut app, KeyCode::Right, KeyModifiers::NONE);
+        }
+        assert_eq!(crate::config::config().performance.tick_ms, 1000);
+        assert!(!app.adjust_performance_option(PerformanceOption::TickRate, true));
+
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Left, KeyModifiers::NONE);
+        assert_eq!(
+            app.performance_option_value(PerformanceOption::IdleRedraw),
+            "每 500 毫秒"
+        );
+    }
+
+    #[test]
+    fn test_language_option_switches_labels_and_is_saved() {
+        let mut app = create_test_app();
+        app.state = AppState::Settings;
+        app.settings.reset();
+        app.settings.selected_option = Some(10);
+        assert_eq!(ReaderOption::Indent.label(), "段首缩进");
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.preferences.language, Language::English);
+        assert_eq!(crate::i18n::language(), Language::English);
+        assert_eq!(ReaderOption::Indent.label(), "Indent");
+        assert_eq!(
+            app.performance_option_value(PerformanceOption::TickRate),
+            format!("{} ms", crate::config::config().performance.tick_ms)
+        );
+        assert_eq!(Preferences::load().language, Language::English);
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(crate::i18n::language(), Language::Chinese);
+        assert_eq!(ReaderOption::Indent.label(), "段首缩进");
+    }
+
+    #[test]
+    fn test_auto_scroll_advances_and_stops_at_last_page() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("auto.txt"));
+        novel.set_content("line\n".repeat(12));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        assert!(app.reader.auto_scroll.is_some());
+        // 未到间隔时不滚动
+        handle_tick(&mut app);
+        assert_eq!(offset(&app), 0);
+
+        let long_ago = Instant::now() - Duration::from_secs(60);
+        app.reader.auto_scroll = Some(long_ago);
+        handle_tick(&mut app);
+        assert_eq!(offset(&app), 1);
+
+        // 正文区 10 行：滚到第 2 行后最后一页已完整显示，自动关闭
+        app.reader.auto_scroll = Some(long_ago);
+        handle_tick(&mut app);
+        app.reader.auto_scroll = Some(long_ago);
+        handle_tick(&mut app);
+        assert_eq!(offset(&app), 2);
+        assert!(app.reader.auto_scroll.is_none());
+    }
+
+    #[test]
+    fn test_progress_is_autosaved_only_when_changed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("autosave.txt");
+        let mut app = create_test_app();
+        let mut novel = Novel::new(path.clone());
+        novel.set_content("line\n".repeat(50));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app.save_current_progress();
+        assert!(!app.has_unsaved_progress());
+
+        // 第一次 tick 只记录检查时间
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_tick(&mut app);
+        assert!(app.has_unsaved_progress());
+
+        let long_ago = Instant::now() - Duration::from_secs(3600);
+        app.reader.autosave_checked_at = Some(long_ago);
+        handle_tick(&mut app);
+        assert!(!app.has_unsaved_progress());
+        assert_eq!(Library::load().get_novel_progress(&path).scroll_offset, 1);
+        assert!(app.reader.autosave_checked_at.unwrap() > long_ago);
+    }
+
+    #[test]
+    fn test_vertical_layout_pages_by_columns() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..50).map(|_| "一二三四五六".to_string()).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 24, 6);
+        app.preferences.page_overlap = 0;
+
+        handle_key(&mut app, KeyCode::Char('|'), KeyModifiers::NONE);
+        assert!(app.reader.vertical_layout);
+
+        // 正文区 20x3：每列 3 字，共 10 列，每行占 2 列
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            5
+        );
+    }
+
+    #[test]
+    fn test_page_turn_keeps_overlap_lines() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        // 正文区高度 10 行
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app.preferences.page_overlap = 2;
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            8
+        );
+        handle_key(&mut app, KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            0
+        );
+    }
+
+    #[test]
+    fn test_long_paragraph_pages_by_rows_and_survives_reflow() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let paragraph = "中".repeat(800);
+        novel.set_content(format!("{}\n结尾", paragraph));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        // 正文区高度 10 行，整段远超一页
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app.preferences.page_overlap = 0;
+        let top = |app: &App| {
+            let progress = &app.current_novel.as_ref().unwrap().progress;
+            (progress.scroll_offset, progress.char_offset)
+        };
+
+        // 翻页停在段落中间，字符偏移是页首屏幕行的实际位置
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        let rows = ReaderLayout::new(&app, app.terminal_size).wrap_rows(&paragraph);
+        let offset = rows[10].start;
+        assert_eq!(top(&app), (0, Some(offset)));
+
+        // 窗口变宽后按新的折行找到包含该位置的屏幕行，继续按屏幕行滚动
+        app.terminal_size = Rect::new(0, 0, 60, 13);
+        let layout = ReaderLayout::new(&app, app.terminal_size);
+        let rows = layout.wrap_rows(&paragraph);
+        let row = layout.row_of_offset(&paragraph, offset);
+        assert!(rows[row].start <= offset && offset < rows[row + 1].start);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(top(&app), (0, Some(rows[row + 1].start)));
+
+        handle_key(&mut app, KeyCode::Char('h'), KeyModifiers::NONE);
+        assert_eq!(top(&app), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_page_turn_always_moves_even_with_large_overlap() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        // 正文区高度 3 行，重叠行数不小于一页
+        app.terminal_size = Rect::new(0, 0, 40, 6);
+        app.preferences.page_overlap = 5;
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            1
+        );
+    }
+
+    #[test]
+    fn test_reader_padding_feeds_paging_width() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        // 每行宽 20：正文宽 20 时占 1 行，留白后宽 10 时占 2 行
+        let content: Vec<String> = (0..50).map(|_| "中".repeat(10)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 24, 13);
+        app.preferences.page_overlap = 0;
+        app.preferences.padding = ReaderPadding {
+            left: 4,
+            right: 6,
+            top: 2,
+            bottom: 0,
+        };
+
+        let area = crate::ui::reader::text_area(&app, app.terminal_size);
+        assert_eq!(area, Rect::new(6, 3, 10, 8));
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            4
+        );
+    }
+
+    #[test]
+    fn test_ctrl_half_and_full_page_scrolling() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        // 正文区高度 10 行
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app.preferences.page_overlap = 0;
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 5);
+        handle_key(&mut app, KeyCode::Char('f'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 15);
+        handle_key(&mut app, KeyCode::Char('b'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 5);
+        handle_key(&mut app, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 0);
+        // Ctrl+b 不应打开书签列表
+        assert!(app.state == AppState::Reading);
+    }
+
+    #[test]
+    fn test_gg_and_g_jump_to_start_and_end() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        // 正文区高度 10 行
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 90);
+
+        // 单个 g 后接其他键不触发跳转
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('k'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 89);
+
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 0);
+        assert_eq!(app.reader.pending_key, None);
+    }
+
+    #[test]
+    fn test_count_prefix_repeats_motion() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('5'), KeyModifiers::NONE);
+        assert_eq!(app.reader.pending_count, Some(25));
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 25);
+        assert_eq!(app.reader.pending_count, None);
+
+        handle_key(&mut app, KeyCode::Char('4'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('0'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 39);
+
+        // 非移动操作只执行一次
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('z'), KeyModifiers::NONE);
+        assert!(app.reader.zen_mode);
+    }
+
+    #[test]
+    fn test_command_mode_jumps_sets_options_and_reports_errors() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("volumes.txt"));
+        novel.set_content(
+            "第一卷 风起\n第一章 雨夜\n正文\n第二章 晴天\n正文\n第二卷 云涌\n第三章 雨停\n正文"
+                .to_string(),
+        );
+        novel.parse_chapters();
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        let run = |app: &mut App, command: &str| {
+            handle_key(app, KeyCode::Char(':'), KeyModifiers::NONE);
+            for c in command.chars() {
+                handle_key(app, KeyCode::Char(c), KeyModifiers::NONE);
+            }
+            handle_key(app, KeyCode::Enter, KeyModifiers::NONE);
+        };
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        // 章节序号不计卷标题
+        app.state = AppState::ChapterList;
+        run(&mut app, "chapter 3");
+        assert_eq!(offset(&app), 6);
+        assert!(app.state == AppState::Reading);
+        run(&mut app, "goto 3");
+        assert_eq!(offset(&app), 2);
+        run(&mut app, "50%");
+        assert_eq!(offset(&app), 4);
+        run(&mut app, "goto 999");
+        assert_eq!(offset(&app), 7);
+
+        run(&mut app, "chapter 9");
+        assert!(app.error_message.is_some());
+        assert_eq!(offset(&app), 7);
+        run(&mut app, "nonsense");
+        assert!(app.error_message.is_some());
+
+        run(&mut app, "set overlap=2");
+        assert_eq!(app.preferences.page_overlap, 2);
+        assert!(app.info_message.is_some());
+        run(&mut app, "set overlap 99");
+        assert_eq!(app.preferences.page_overlap, 2);
+        assert!(app.error_message.is_some());
+
+        // 命令行中的 q 只是输入，Backspace 删空后退出命令行
+        handle_key(&mut app, KeyCode::Char(':'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert_eq!(app.command_input.as_deref(), Some("q"));
+        assert!(app.confirm.is_none());
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        assert!(app.command_input.is_none());
+        assert!(app.state == AppState::Reading);
+
+        // 跳转命令需要打开的小说
+        app.state = AppState::Bookshelf;
+        run(&mut app, "goto 1");
+        assert!(app.error_message.is_some());
+
+        app.preferences.confirm_quit = false;
+        run(&mut app, "q");
+        assert!(app.should_quit);
+    }
+
+    #[test]
+    fn test_leader_chords_depend_on_state() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("第1章\n正文".to_string());
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char(','), KeyModifiers::NONE);
+        assert!(app.leader_pending);
+        assert_eq!(app.leader_action('b'), Some(LeaderAction::Bookmarks));
+        assert_eq!(app.leader_action('o'), None);
+        handle_key(&mut app, KeyCode::Char('b'), KeyModifiers::NONE);
+        assert!(!app.leader_pending);
+        assert!(app.state == AppState::BookmarkList);
+
+        // 书签列表中没有这个组合键
+        handle_key(&mut app, KeyCode::Char(','), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(app.error_message.is_some());
+        assert!(app.state == AppState::BookmarkList);
+
+        // Esc 只取消组合键，不返回上一级
+        handle_key(&mut app, KeyCode::Char(','), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(!app.leader_pending);
+        assert!(app.state == AppState::BookmarkList);
+
+        app.state = AppState::Bookshelf;
+        handle_key(&mut app, KeyCode::Char(','), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('b'), KeyModifiers::NONE);
+        assert!(app.state == AppState::AllBookmarks);
+    }
+
+    #[test]
+    fn test_count_prefix_repeats_chapter_jump() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (1..=5)
+            .flat_map(|i| vec![format!("第{}章", i), "正文".to_string()])
+            .collect();
+        novel.set_content(content.join("\n"));
+        novel.parse_chapters();
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+
+        handle_key(&mut app, KeyCode::Char('3'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char(']'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            6
+        );
+    }
+
+    #[test]
+    fn test_navigation_back_and_forward() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (1..=5)
+            .flat_map(|i| vec![format!("第{}章", i), "正文".to_string()])
+            .collect();
+        novel.set_content(content.join("\n"));
+        novel.parse_chapters();
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        // 从目录跳到第 4 章，再用 G 跳到结尾
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        app.selected_chapter_index = Some(3);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 6);
+        handle_key(&mut app, KeyCode::Char('3'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 2);
+
+        handle_key(&mut app, KeyCode::Char('o'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 6);
+        handle_key(&mut app, KeyCode::Char('o'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 0);
+        // 历史已到头
+        handle_key(&mut app, KeyCode::Char('o'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), 0);
+
+        handle_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 6);
+        handle_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 2);
+    }
+
+    #[test]
+    fn test_marks_set_and_jump() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('3'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('0'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('m'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('a'), KeyModifiers::NONE);
+        // m 不再打开添加书签界面
+        assert!(app.state == AppState::Reading);
+
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 30);
+
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('b'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 30);
+        assert!(app.error_message.is_some());
+        // 未设置的标记不能触发 b 的书签列表
+        assert!(app.state == AppState::Reading);
+    }
+
+    #[test]
+    fn test_quick_slots_set_and_jump() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('0'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('1'), KeyModifiers::ALT);
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('m'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        let end = offset(&app);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.quick_slots,
+            [(1, 20), (2, end)].into_iter().collect()
+        );
+
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('1'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 20);
+        // 快捷书签跳转计入跳转历史
+        handle_key(&mut app, KeyCode::Char('o'), KeyModifiers::CONTROL);
+        assert_eq!(offset(&app), end);
+
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('5'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), end);
+        assert!(app.error_message.is_some());
+        // 数字没有被当作数字前缀
+        assert!(app.reader.pending_count.is_none());
+    }
+
+    #[test]
+    fn test_auto_positions_recorded_on_big_jump_and_exit() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..500).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        novel.progress.add_bookmark("Manual".to_string(), 3);
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        // 小幅移动不记录
+        handle_key(&mut app, KeyCode::Char('5'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        let end = offset(&app);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        let positions: Vec<usize> = app
+            .current_novel
+            .as_ref()
+            .unwrap()
+            .progress
+            .auto_positions
+            .iter()
+            .map(|entry| entry.position)
+            .collect();
+        assert_eq!(positions, vec![end, 5]);
+
+        // 自动记录排在书签之后，可以跳转但不能编辑
+        app.state = AppState::BookmarkList;
+        assert_eq!(app.bookmark_display_order(), vec![0, 1, 2]);
+        app.bookmark.selected_index = Some(2);
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.state == AppState::BookmarkList);
+        assert!(app.error_message.is_some());
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert_eq!(offset(&app), 5);
+    }
+
+    #[test]
+    fn test_visual_selection_creates_highlight() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        novel.progress.scroll_offset = 10;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('v'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(app.visual_range(), Some((10, 12)));
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert!(app.visual_range().is_none());
+
+        handle_key(&mut app, KeyCode::Char('v'), KeyModifiers::NONE);
+        // 光标移出页面时跟随滚动
+        for _ in 0..20 {
+            handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        }
+        assert!(offset(&app) > 10);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        let highlights = app.get_current_highlights().unwrap();
+        assert_eq!((highlights[0].start, highlights[0].end), (10, 30));
+
+        handle_key(&mut app, KeyCode::Char('H'), KeyModifiers::NONE);
+        assert!(app.state == AppState::HighlightList);
+        assert_eq!(app.highlight_list.selected_index, Some(0));
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert_eq!(offset(&app), 10);
+
+        handle_key(&mut app, KeyCode::Char('H'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.get_current_highlights().unwrap().is_empty());
+        assert_eq!(app.highlight_list.selected_index, None);
+    }
+
+    #[test]
+    fn test_annotate_line_and_browse_annotations() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        novel.progress.scroll_offset = 10;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let type_text = |app: &mut App, text: &str| {
+            for c in text.chars() {
+                handle_key(app, KeyCode::Char(c), KeyModifiers::NONE);
+            }
+        };
+
+        handle_key(&mut app, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(is_text_input_mode(&app));
+        type_text(&mut app, "qj");
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        // 选择模式中批注光标所在行
+        handle_key(&mut app, KeyCode::Char('v'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('j'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('a'), KeyModifiers::NONE);
+        assert!(app.visual_range().is_none());
+        type_text(&mut app, "note");
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        let annotations = app.get_current_annotations().unwrap();
+        assert_eq!(annotations.len(), 2);
+        assert_eq!(
+            (annotations[0].line, annotations[0].note.as_str()),
+            (10, "qj")
+        );
+        assert_eq!(annotations[1].line, 11);
+
+        app.current_novel.as_mut().unwrap().progress.scroll_offset = 50;
+        handle_key(&mut app, KeyCode::Char('A'), KeyModifiers::NONE);
+        assert!(app.state == AppState::AnnotationList);
+        assert_eq!(app.annotation.selected_index, Some(1));
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.get_current_annotations().unwrap()[1].note, "not");
+
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert_eq!(app.annotation.selected_index, Some(0));
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            10
+        );
+    }
+
+    #[test]
+    fn test_shift_m_opens_bookmark_add() {
+        let mut app = create_test_app();
+        app.current_novel = Some(Novel::new(PathBuf::from("test.txt")));
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char('M'), KeyModifiers::NONE);
+        assert!(app.state == AppState::BookmarkAdd);
+    }
+
+    fn click(column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_click_page_halves_turn_pages() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        // 正文区 x:2..38，高 10 行
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app.preferences.page_overlap = 0;
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_mouse(&mut app, click(30, 5));
+        assert_eq!(offset(&app), 10);
+        handle_mouse(&mut app, click(5, 5));
+        assert_eq!(offset(&app), 0);
+
+        // 点击边框和状态栏不翻页
+        handle_mouse(&mut app, click(30, 12));
+        handle_mouse(&mut app, click(39, 5));
+        assert_eq!(offset(&app), 0);
+    }
+
+    fn create_series_app() -> App {
+        let mut app = create_test_app();
+        for title in ["Book 10", "Book 2", "Book 1"] {
+            let mut novel = Novel::new(PathBuf::from(format!("{}.txt", title)));
+            novel.set_content(format!("{} 第一行\n{} 第二行", title, title));
+            app.novels.push(novel);
+        }
+        app.current_novel = Some(app.novels[2].clone());
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app
+    }
+
+    #[test]
+    fn test_end_of_book_offers_next_novel() {
+        let mut app = create_series_app();
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+
+        // 对话框显示期间其他按键被忽略
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(!app.should_quit);
+
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        assert_eq!(app.current_novel.as_ref().unwrap().title, "Book 2");
+        assert!(app.state == AppState::Reading);
+    }
+
+    #[test]
+    fn test_end_of_book_prompt_shows_once_per_reaching_the_end() {
+        let mut app = create_series_app();
+        let mut novel = app.current_novel.take().unwrap();
+        let content: Vec<String> = (0..30).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.preferences.page_overlap = 0;
+
+        handle_key(&mut app, KeyCode::Char('G'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+
+        // 拒绝后继续翻页（包括带数字前缀的）不再询问
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        handle_key(&mut app, KeyCode::Char('5'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+
+        // 离开最后一页后再次到达结尾时重新询问，数字前缀的翻页中仍不询问
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('9'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+    }
+
+    #[test]
+    fn test_end_of_book_prompt_can_be_declined() {
+        let mut app = create_series_app();
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        assert_eq!(app.current_novel.as_ref().unwrap().title, "Book 1");
+        assert!(app.state == AppState::Reading);
+    }
+
+    #[test]
+    fn test_deleting_novel_in_settings_requires_confirmation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("待删除.txt");
+        std::fs::write(&path, "正文").unwrap();
+        let mut app = create_test_app();
+        app.novels = vec![Novel::new(path.clone())];
+        app.state = AppState::Settings;
+        app.settings.mode = SettingsMode::DeleteNovel;
+        app.settings.selected_delete_novel_index = Some(0);
+
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        assert!(path.exists());
+        assert_eq!(app.novels.len(), 1);
+
+        handle_key(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!path.exists());
+        assert!(app.novels.is_empty());
+    }
+
+    #[test]
+    fn test_book_detail_shows_stats_and_renames_and_deletes() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("详情.txt");
+        std::fs::write(&path, "第一章\n你好 世界\n").unwrap();
+        app.reload_novels();
+        app.state = AppState::Bookshelf;
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert!(app.state == AppState::BookDetail);
+        let stats = app.bookshelf.detail_stats.clone().unwrap();
+        assert_eq!((stats.encoding, stats.lines, stats.words), ("UTF-8", 2, 7));
+
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        for _ in 0..2 {
+            handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        }
+        for c in "页面".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        let renamed = dir.join("页面.txt");
+        assert!(renamed.exists());
+        assert!(app.state == AppState::BookDetail);
+
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!renamed.exists());
+        assert!(app.novels.is_empty());
+        assert!(app.state == AppState::Bookshelf);
+    }
+
+    #[test]
+    fn test_rescan_while_delete_confirm_is_open_deletes_the_confirmed_novel() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("b.txt");
+        std::fs::write(&target, "正文").unwrap();
+        std::fs::write(dir.join("c.txt"), "正文").unwrap();
+        app.reload_novels();
+        let index = |app: &App| app.novels.iter().position(|n| n.path == target).unwrap();
+        app.selected_novel_index = Some(index(&app));
+        app.state = AppState::Bookshelf;
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+
+        // 对话框显示期间外部新增的小说排到了前面
+        let before = index(&app);
+        std::fs::write(dir.join("a.txt"), "正文").unwrap();
+        app.rescan_bookshelf();
+        assert_ne!(index(&app), before);
+
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(!target.exists());
+        assert!(dir.join("a.txt").exists());
+        assert!(dir.join("c.txt").exists());
+        assert_eq!(app.novels.len(), 2);
+    }
+
+    #[test]
+    fn test_theme_is_previewed_and_saved_from_appearance_settings() {
+        let mut app = create_test_app();
+        app.state = AppState::Settings;
+        app.settings.reset();
+        app.settings.selected_option = Some(6);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::Appearance);
+        assert_eq!(app.settings.selected_theme_index, Some(0));
+
+        // 移动选择即时预览，Esc 恢复原来的主题
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        assert_eq!(app.preferences.theme, ThemeName::Light);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::MainMenu);
+        assert_eq!(app.preferences.theme, ThemeName::Dark);
+
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(app.preferences.theme, ThemeName::HighContrast);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::MainMenu);
+        assert_eq!(app.preferences.theme, ThemeName::HighContrast);
+        assert_eq!(app.theme(), Theme::of(ThemeName::HighContrast));
+    }
+
+    #[test]
+    fn test_settings_are_exported_and_imported_without_password() {
+        let mut app = create_test_app();
+        app.state = AppState::Settings;
+        app.settings.reset();
+        app.preferences.theme = ThemeName::Sepia;
+        app.webdav_config.url = "https://dav.example.com".to_string();
+        app.webdav_config.password = "secret".to_string();
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        let content = std::fs::read_to_string(App::settings_export_path()).unwrap();
+        assert!(content.contains("sepia"));
+        assert!(!content.contains("secret"));
+
+        // 导入前需要确认，覆盖修改过的设置但保留本机密码
+        app.preferences.theme = ThemeName::Light;
+        app.webdav_config.url = String::new();
+        handle_key(&mut app, KeyCode::Char('i'), KeyModifiers::NONE);
+        assert_eq!(app.preferences.theme, ThemeName::Light);
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert_eq!(app.preferences.theme, ThemeName::Sepia);
+        assert_eq!(app.webdav_config.url, "https://dav.example.com");
+        assert_eq!(app.webdav_config.password, "secret");
+    }
+
+    #[test]
+    fn test_imported_settings_are_validated_and_keep_local_paths() {
+        let mut app = create_test_app();
+        app.export_settings().unwrap();
+        let path = App::settings_export_path();
+        let exported: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let write_bundle = |edit: &dyn Fn(&mut serde_json::Value)| {
+            let mut bundle = exported.clone();
+            edit(&mut bundle);
+            std::fs::write(&path, bundle.to_string()).unwrap();
+        };
+
+        // 含有无效配置项时整个文件都不导入
+        write_bundle(&|bundle| {
+            bundle["config"]["performance"]["tick_ms"] = 0.into();
+            bundle["preferences"]["theme"] = "sepia".into();
+            bundle["webdav"]["url"] = "https://dav.example.com".into();
+        });
+        let before = crate::config::config();
+        assert!(app.import_settings().is_err());
+        assert_eq!(*crate::config::config(), *before);
+        assert_ne!(app.preferences.theme, ThemeName::Sepia);
+        assert!(app.webdav_config.url.is_empty());
+
+        // 另一台电脑上的小说目录和数据目录名不导入
+        write_bundle(&|bundle| {
+            bundle["config"]["novels_dir"] = "/elsewhere/novels".into();
+            bundle["config"]["dir_name"] = ".elsewhere".into();
+            bundle["preferences"]["theme"] = "sepia".into();
+        });
+        app.import_settings().unwrap();
+        assert_eq!(app.preferences.theme, ThemeName::Sepia);
+        assert_eq!(*crate::config::config(), *before);
+    }
+
+    #[test]
+    fn test_progress_backup_is_previewed_and_restored() {
+        let mut app = create_test_app();
+        let progress_path = crate::model::library::Library::get_progress_path();
+        let mut backup = crate::model::library::Library::new();
+        backup.register_novel(&App::get_novels_dir().join("备份里的书.txt"));
+        let timestamp = chrono::Utc::now().timestamp() as u64 - 1000;
+        std::fs::write(
+            progress_path.with_file_name(format!("progress.json.backup.{}", timestamp)),
+            serde_json::to_string(&backup).unwrap(),
+        )
+        .unwrap();
+
+        app.state = AppState::Settings;
+        app.settings.reset();
+        app.settings.selected_option = Some(8);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.settings.mode, SettingsMode::Backups);
+        let index = app
+            .settings
+            .backups
+            .iter()
+            .position(|b| b.timestamp == timestamp)
+            .unwrap();
+        assert_eq!(
+            app.settings.backups[index].titles,
+            Some(vec!["备份里的书".to_string()])
+        );
+
+        app.settings.selected_backup_index = Some(index);
+        handle_key(&mut app, KeyCode::Char('r'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert_eq!(app.library.novels.len(), 1);
+        assert_eq!(app.library.novels[0].title, "备份里的书");
+        assert_eq!(app.settings.selected_backup_index, Some(0));
+    }
+
+    #[test]
+    fn test_novels_dir_input_rejects_relative_path_and_cancels() {
+        let mut app = create_test_app();
+        app.state = AppState::Settings;
+        app.settings.mode = SettingsMode::LibraryDirs;
+        app.settings.selected_library_dir_index = Some(0);
+
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+        let current = App::get_novels_dir().display().to_string();
+        assert_eq!(
+            app.settings.novels_dir_input.as_deref(),
+            Some(current.as_str())
+        );
+        assert!(app.settings.novels_dir_move);
+        handle_key(&mut app, KeyCode::Tab, KeyModifiers::NONE);
+        assert!(!app.settings.novels_dir_move);
+
+        // 输入时 q 作为文字，不退出
+        app.settings.novels_dir_input = Some("books".to_string());
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(!app.should_quit);
+        assert!(app.error_message.as_ref().unwrap().contains("绝对路径"));
+
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(
+            app.error_message
+                .as_ref()
+                .unwrap()
+                .contains("已是当前的小说目录")
+        );
+
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.settings.novels_dir_input.is_none());
+        assert_eq!(app.settings.mode, SettingsMode::LibraryDirs);
+    }
+
+    #[test]
+    fn test_orphaned_record_is_relinked_to_moved_file() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(dir.join("已读")).unwrap();
+        let old_path = dir.join("搬家.txt");
+        let new_path = dir.join("已读").join("搬家.txt");
+        std::fs::write(&new_path, "正文").unwrap();
+        app.library
+            .progress_mut(&old_path)
+            .bookmarks
+            .push(crate::model::novel::Bookmark::new("开头".to_string(), 0));
+        app.reload_novels();
+        app.state = AppState::Settings;
+        app.settings.mode = SettingsMode::DeleteOrphaned;
+        app.detect_orphaned_novels();
+        app.settings.selected_orphaned_index = Some(0);
+
+        // 书架上同名的文件作为候选路径
+        handle_key(&mut app, KeyCode::Char('r'), KeyModifiers::NONE);
+        assert_eq!(
+            app.settings.relocate_input.as_deref(),
+            Some(new_path.display().to_string().as_str())
+        );
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+
+        assert!(app.settings.orphaned_novels.is_empty());
+        let index = app.novels.iter().position(|n| n.path == new_path).unwrap();
+        assert_eq!(app.bookshelf_bookmark_count(index), 1);
+        assert_eq!(app.library.novels.len(), 1);
+    }
+
+    #[test]
+    fn test_book_note_is_edited_in_detail_view() {
+        let mut app = create_test_app();
+        app.novels = vec![Novel::new(PathBuf::from("/novels/备注.txt"))];
+        app.selected_novel_index = Some(0);
+        app.open_book_detail(0);
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        for c in "读到比武大会".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.bookshelf_note(0), "读到比武大会");
+
+        // 再次编辑时带出原备注，取消不修改
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        assert_eq!(app.bookshelf.note_input.as_deref(), Some("读到比武大会"));
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(app.bookshelf_note(0), "读到比武大会");
+        assert!(app.state == AppState::BookDetail);
+
+        handle_key(&mut app, KeyCode::Char('e'), KeyModifiers::NONE);
+        for _ in 0..6 {
+            handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.bookshelf_note(0), "");
+    }
+
+    #[test]
+    fn test_reading_queue_is_shown_first_in_queue_order() {
+        let mut app = create_test_app();
+        app.novels = ["甲", "乙", "丙"]
+            .iter()
+            .map(|name| Novel::new(PathBuf::from(format!("/novels/{}.txt", name))))
+            .collect();
+        app.sort_novels();
+        app.state = AppState::Bookshelf;
+        let select = |app: &mut App, title: &str| {
+            app.selected_novel_index = app.novels.iter().position(|n| n.title == title);
+        };
+
+        select(&mut app, "丙");
+        handle_key(&mut app, KeyCode::Char('+'), KeyModifiers::NONE);
+        select(&mut app, "乙");
+        handle_key(&mut app, KeyCode::Char('+'), KeyModifiers::NONE);
+        // 乙 排到 丙 前面
+        handle_key(&mut app, KeyCode::Char('K'), KeyModifiers::NONE);
+
+        let rows: Vec<String> = app
+            .bookshelf_rows()
+            .iter()
+            .map(|row| match row {
+                BookshelfRow::Category { name, .. } => name.clone(),
+                BookshelfRow::Novel(index) => app.novels[*index].title.clone(),
+            })
+            .collect();
+        assert_eq!(rows, ["待读队列", "乙", "丙", "甲"]);
+
+        // 读完后自动移出队列
+        select(&mut app, "乙");
+        let index = app.selected_novel_index.unwrap();
+        let path = app.novels[index].path.clone();
+        app.library.set_status(&path, ReadingStatus::Finished);
+        assert_eq!(app.bookshelf_queue_position(index), None);
+        assert_eq!(
+            app.bookshelf_queue_position(app.novels.iter().position(|n| n.title == "丙").unwrap()),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_marked_novels_are_tagged_archived_and_deleted_together() {
+        let mut app = create_test_app();
+        let dir = App::get_novels_dir();
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["甲", "乙", "丙"] {
+            std::fs::write(dir.join(format!("{}.txt", name)), "正文").unwrap();
+        }
+        app.reload_novels();
+        app.state = AppState::Bookshelf;
+        let titles = |app: &App| -> Vec<String> {
+            app.batch_targets()
+                .into_iter()
+                .map(|index| app.novels[index].title.clone())
+                .collect()
+        };
+
+        // 标记第一本和第三本
+        handle_key(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Down, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        let marked = titles(&app);
+        assert_eq!(marked.len(), 2);
+
+        handle_key(&mut app, KeyCode::Char('a'), KeyModifiers::NONE);
+        for c in "待读".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert!(app.bookshelf.marked.is_empty());
+        let tagged: Vec<String> = (0..app.novels.len())
+            .filter(|&index| !app.bookshelf_tags(index).is_empty())
+            .map(|index| app.novels[index].title.clone())
+            .collect();
+        assert_eq!(tagged.len(), 2);
+
+        // 标记后按 Esc 取消标记而不是退出
+        handle_key(&mut app, KeyCode::Char(' '), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.bookshelf.marked.is_empty());
+        assert!(app.confirm.is_none() && !app.should_quit);
+
+        for index in 0..app.novels.len() {
+            if marked.contains(&app.novels[index].title) {
+                app.toggle_bookshelf_mark(index);
+            }
+        }
+        handle_key(&mut app, KeyCode::Char('A'), KeyModifiers::NONE);
+        for title in &marked {
+            let path = dir.join("归档").join(format!("{}.txt", title));
+            assert!(path.exists());
+            let index = app.novels.iter().position(|n| n.path == path).unwrap();
+            assert_eq!(app.bookshelf_tags(index), ["待读"]);
+        }
+
+        for index in 0..app.novels.len() {
+            app.toggle_bookshelf_mark(index);
+        }
+        handle_key(&mut app, KeyCode::Char('x'), KeyModifiers::NONE);
+        assert!(app.confirm.is_some());
+        handle_key(&mut app, KeyCode::Char('y'), KeyModifiers::NONE);
+        assert!(app.novels.is_empty());
+    }
+
+    #[test]
+    fn test_last_book_in_series_pages_normally() {
+        let mut app = create_series_app();
+        app.current_novel = Some(app.novels[0].clone());
+
+        handle_key(&mut app, KeyCode::Char('l'), KeyModifiers::NONE);
+        assert!(app.confirm.is_none());
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            1
+        );
+    }
+
+    #[test]
+    fn test_page_list_clamps_without_wrapping() {
+        assert_eq!(page_list(None, 0, 10, false), None);
+        assert_eq!(page_list(Some(5), 30, 10, false), Some(15));
+        assert_eq!(page_list(Some(25), 30, 10, false), Some(29));
+        assert_eq!(page_list(Some(5), 30, 10, true), Some(0));
+        assert_eq!(page_list(None, 30, 10, false), Some(10));
+    }
+
+    #[test]
+    fn test_page_keys_in_bookshelf_and_reader() {
+        let mut app = create_test_app();
+        for i in 0..40 {
+            app.novels
+                .push(Novel::new(PathBuf::from(format!("{}.txt", i))));
+        }
+        app.selected_novel_index = Some(0);
+        // 书架列表可见 20 行
+        app.terminal_size = Rect::new(0, 0, 40, 25);
+
+        handle_key(&mut app, KeyCode::PageDown, KeyModifiers::NONE);
+        assert_eq!(app.selected_novel_index, Some(20));
+        handle_key(&mut app, KeyCode::PageDown, KeyModifiers::NONE);
+        assert_eq!(app.selected_novel_index, Some(39));
+        handle_key(&mut app, KeyCode::PageUp, KeyModifiers::NONE);
+        assert_eq!(app.selected_novel_index, Some(19));
+
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..100).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        app.preferences.page_overlap = 0;
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::PageDown, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 10);
+        handle_key(&mut app, KeyCode::End, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 90);
+        handle_key(&mut app, KeyCode::PageUp, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 80);
+        handle_key(&mut app, KeyCode::Home, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 0);
+    }
+
+    #[test]
+    fn test_continue_reading_opens_most_recent_novel() {
+        let mut app = create_test_app();
+        for title in ["a", "b"] {
+            let mut novel = Novel::new(PathBuf::from(format!("{}.txt", title)));
+            novel.set_content("line\n".repeat(50));
+            app.novels.push(novel);
+        }
+        for (title, last_read) in [("a", 200), ("b", 100)] {
+            app.library.novels.push(NovelInfo {
+                title: title.to_string(),
+                path: PathBuf::from(format!("{}.txt", title)),
+                progress: ReadingProgress {
+                    scroll_offset: 42,
+                    ..Default::default()
+                },
+                last_read,
+                total_lines: 0,
+                chapter_settings: Default::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
+            });
+        }
+
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        let novel = app.current_novel.as_ref().unwrap();
+        assert_eq!(novel.title, "a");
+        assert_eq!(novel.progress.scroll_offset, 42);
+        assert_eq!(app.selected_novel_index, Some(0));
+    }
+
+    #[test]
+    fn test_continue_reading_without_history_shows_error() {
+        let mut app = create_test_app();
+        handle_key(&mut app, KeyCode::Char('c'), KeyModifiers::NONE);
+        assert!(app.state == AppState::Bookshelf);
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn test_paragraph_navigation() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        // 段落起始行：0, 3, 7
+        novel.set_content("甲一\n甲二\n\n乙一\n乙二\n\u{3000}\n\n丙一\n丙二".to_string());
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('}'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 3);
+        handle_key(&mut app, KeyCode::Char('}'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 7);
+        // 没有下一段时保持不动
+        handle_key(&mut app, KeyCode::Char('}'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 7);
+
+        // 位于段落中间时先回到本段开头
+        app.current_novel.as_mut().unwrap().progress.scroll_offset = 8;
+        handle_key(&mut app, KeyCode::Char('{'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 7);
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('{'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 0);
+    }
+
+    #[test]
+    fn test_return_to_position_before_toc_jump() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (1..=5)
+            .flat_map(|i| vec![format!("第{}章", i), "正文".to_string()])
+            .collect();
+        novel.set_content(content.join("\n"));
+        novel.parse_chapters();
+        novel.progress.scroll_offset = 3;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('t'), KeyModifiers::NONE);
+        app.selected_chapter_index = Some(4);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(offset(&app), 8);
+
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 3);
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('\''), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 8);
+    }
+
+    #[test]
+    fn test_in_reader_find_with_repeat() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..30)
+            .map(|i| {
+                if i % 10 == 5 {
+                    format!("Line {} Dragon", i)
+                } else {
+                    format!("Line {}", i)
+                }
+            })
+            .collect();
+        novel.set_content(content.join("\n"));
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+        app.terminal_size = Rect::new(0, 0, 40, 13);
+        let offset = |app: &App| app.current_novel.as_ref().unwrap().progress.scroll_offset;
+
+        handle_key(&mut app, KeyCode::Char('f'), KeyModifiers::NONE);
+        for c in "drag".chars() {
+            handle_key(&mut app, KeyCode::Char(c), KeyModifiers::NONE);
+        }
+        // 输入时即时跳转，q 作为文字输入而不是退出
+        assert_eq!(offset(&app), 5);
+        handle_key(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(!app.should_quit);
+        assert_eq!(offset(&app), 0);
+        handle_key(&mut app, KeyCode::Backspace, KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Enter, KeyModifiers::NONE);
+        assert_eq!(app.reader.find_input, None);
+        assert_eq!(app.reader.highlight_term.as_deref(), Some("drag"));
+        assert_eq!(offset(&app), 5);
+
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 15);
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('n'), KeyModifiers::NONE);
+        // 到末尾后循环回开头
+        assert_eq!(offset(&app), 5);
+        handle_key(&mut app, KeyCode::Char('N'), KeyModifiers::NONE);
+        assert_eq!(offset(&app), 25);
+    }
+
+    #[test]
+    fn test_in_reader_find_esc_restores_position() {
+        let mut app = create_test_app();
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        let content: Vec<String> = (0..30).map(|i| format!("Line {}", i)).collect();
+        novel.set_content(content.join("\n"));
+        novel.progress.scroll_offset = 3;
+        app.current_novel = Some(novel);
+        app.state = AppState::Reading;
+
+        handle_key(&mut app, KeyCode::Char('f'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('2'), KeyModifiers::NONE);
+        handle_key(&mut app, KeyCode::Char('0'), KeyModifiers::NONE);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            20
+        );
+
+        handle_key(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state == AppState::Reading);
+        assert_eq!(app.reader.find_input, None);
+        assert_eq!(app.reader.highlight_term, None);
+        assert_eq!(
+            app.current_novel.as_ref().unwrap().progress.scroll_offset,
+            3
+        );
     }
 }
@@ -1,8 +1,13 @@
-use crate::app::App;
+use crate::app::{App, ChapterListState};
+use crate::config::config;
+use crate::model::novel::{MAX_LINE_SPACING, Novel};
+use crate::model::preferences::MAX_PAGE_OVERLAP;
 use crate::state::AppState;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::time::{Duration, Instant};
 
-use super::count_physical_lines;
+use crate::i18n::{tr, trf};
+use crate::ui::reader::ReaderLayout;
 
 /// 处理阅读器模式下的键盘事件
 ///
@@ -10,82 +15,332 @@ use super::count_physical_lines;
 ///
 /// * `app` - 应用实例的可变引用
 /// * `key` - 按下的键位代码
+/// * `modifiers` - 同时按下的修饰键
 ///
 /// # Behavior
 ///
 /// - `Up`/`k`: 向上滚动一行
 /// - `Down`/`j`: 向下滚动一行
-/// - `Left`/`h`: 向上翻页
-/// - `Right`/`l`: 向下翻页
+/// - `Left`/`h`/`PageUp`: 向上翻页（保留 `page_overlap` 行重叠）
+/// - `Right`/`l`/`PageDown`: 向下翻页（保留 `page_overlap` 行重叠）；在最后一页时询问是否打开下一本
+/// - `Ctrl+d`/`Ctrl+u`: 向下/向上滚动半页
+/// - `Ctrl+f`/`Ctrl+b`: 向下/向上翻页（同 `l`/`h`）
+/// - `Ctrl+o`/`Tab`(`Ctrl+i`): 在跳转历史中后退/前进
+/// - `{`/`}`: 跳转到上一段/下一段开头（段落以空行分隔）
+/// - `gg`/`Home`: 跳转到开头
+/// - `G`/`End`: 跳转到结尾（最后一页）；带数字前缀时跳转到指定行
+/// - 数字: 作为下一个移动操作的重复次数（如 `25j`、`3]`）
 /// - `/`: 进入搜索模式
+/// - `f`: 在阅读界面内增量查找（输入时实时跳转，Enter 确认，Esc 取消）
+/// - `n`/`N`: 跳转到高亮关键词的下一处/上一处匹配
 /// - `t`: 进入章节目录
 /// - `b`: 进入书签列表
-/// - `m`: 添加书签
+/// - `M`: 添加书签
+/// - `v`: 进入选择模式，`j`/`k` 扩展选中的行，`Enter`/`v` 保存为高亮，`Esc` 取消
+/// - `H`: 进入高亮列表
+/// - `a`: 给引导线所在行（未开启时为页首；选择模式中为光标行）添加或编辑批注
+/// - `A`: 进入批注列表
+/// - `m` + 字母: 在当前位置设置标记
+/// - `'` + 字母: 跳转到标记
+/// - `Alt+1`..`Alt+9`（或 `m` + 数字）: 把当前位置保存到编号快捷书签
+/// - `'` + 数字: 跳转到编号快捷书签（单独的数字键已用作数字前缀，所以跳转需要 `'` 前缀）
+/// - `''`: 返回最近一次跳转（目录、搜索等）前的位置，再按一次回到跳转后
 /// - `[`: 跳转到上一章
 /// - `]`: 跳转到下一章
 /// - `-`/`_`: 减小行间距
 /// - `=`/`+`: 增大行间距
-pub(super) fn handle_reader_key(app: &mut App, key: KeyCode) {
+/// - `z`: 切换专注模式
+/// - `e`: 切换夜间模式
+/// - `x`: 清除搜索关键词高亮
+/// - `u`: 切换阅读引导线
+/// - `U`: 将引导线移到下一行（到底后回到顶部）
+/// - `c`: 切换日志伪装模式
+/// - `|`: 切换竖排模式
+/// - `(`/`)`: 减少/增加翻页重叠行数
+/// - `s`: 开启/关闭自动滚动
+pub(super) fn handle_reader_key(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    if app.reader.find_input.is_some() {
+        handle_find_key(app, key);
+        return;
+    }
+    if app.annotation.input.is_some() {
+        super::annotation::handle_annotation_input_key(app, key);
+        return;
+    }
+    if app.reader.visual_anchor.is_some() {
+        handle_visual_key(app, key);
+        return;
+    }
+
+    let ctrl = modifiers.contains(KeyModifiers::CONTROL);
+    if let KeyCode::Char(c @ '0'..='9') = key
+        && !ctrl
+        && !modifiers.contains(KeyModifiers::ALT)
+        && app.reader.pending_key.is_none()
+        && (c != '0' || app.reader.pending_count.is_some())
+    {
+        let digit = c as usize - '0' as usize;
+        let count = app.reader.pending_count.unwrap_or(0) * 10 + digit;
+        app.reader.pending_count = Some(count.min(MAX_COUNT));
+        return;
+    }
+
+    let Some(count) = app.reader.pending_count.take() else {
+        // 只在单次翻页时询问，数字前缀的重复翻页中不询问
+        if is_page_forward(key, modifiers) && offer_next_novel_once(app) {
+            return;
+        }
+        handle_reader_key_once(app, key, modifiers);
+        sync_top(app);
+        mark_finished_on_last_page(app);
+        return;
+    };
+
+    match key {
+        KeyCode::Char('G') => {
+            // 带数字前缀的 G 跳转到指定行
+            if let Some(novel) = &app.current_novel {
+                app.jump_to((count - 1).min(novel.line_count().saturating_sub(1)));
+            }
+        }
+        _ if is_motion(key, ctrl) => {
+            for _ in 0..count {
+                let before = app.current_novel.as_ref().map(Novel::top_char_offset);
+                handle_reader_key_once(app, key, modifiers);
+                let after = app.current_novel.as_ref().map(Novel::top_char_offset);
+                // 已经到头，不必继续重复
+                if before == after {
+                    break;
+                }
+            }
+        }
+        _ => handle_reader_key_once(app, key, modifiers),
+    }
+    sync_top(app);
+    mark_finished_on_last_page(app);
+}
+
+/// 每次按键后把进度中的字符偏移更新为页首的实际位置
+fn sync_top(app: &mut App) {
+    if let Some(novel) = &mut app.current_novel {
+        novel.sync_char_offset();
+    }
+}
+
+/// 翻到最后一页时把小说标记为读完；离开最后一页时允许再次询问是否打开下一本
+fn mark_finished_on_last_page(app: &mut App) {
+    if app.state != AppState::Reading {
+        return;
+    }
+    if is_last_page(app, &ReaderLayout::new(app, app.terminal_size)) {
+        app.mark_current_novel_finished();
+    } else {
+        app.reader.next_novel_offered = false;
+    }
+}
+
+/// 判断按键是否为向后翻页
+fn is_page_forward(key: KeyCode, modifiers: KeyModifiers) -> bool {
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        key == KeyCode::Char('f')
+    } else {
+        matches!(key, KeyCode::Right | KeyCode::PageDown | KeyCode::Char('l'))
+    }
+}
+
+/// 在最后一页继续向后翻页时询问是否打开下一本，每次到达结尾只询问一次
+///
+/// # Returns
+///
+/// 弹出了确认对话框时返回 `true`，此时不再处理这次翻页。
+fn offer_next_novel_once(app: &mut App) -> bool {
+    if app.reader.next_novel_offered
+        || !is_last_page(app, &ReaderLayout::new(app, app.terminal_size))
+    {
+        return false;
+    }
+    app.reader.next_novel_offered = true;
+    app.offer_next_novel()
+}
+
+/// 把数字键转换为快捷书签编号
+fn quick_slot_number(slot: char) -> u8 {
+    slot as u8 - b'0'
+}
+
+/// 保存编号快捷书签并提示
+fn set_quick_slot(app: &mut App, slot: char) {
+    app.set_quick_slot(quick_slot_number(slot));
+    app.set_info(trf!(
+        "已保存快捷书签 {}，按 '{} 跳回",
+        "Saved quick bookmark {}; press '{} to jump back",
+        slot,
+        slot
+    ));
+}
+
+/// 数字前缀的上限，避免误输入过多位数时溢出
+const MAX_COUNT: usize = 99_999;
+
+/// 判断按键是否为可以被数字前缀重复的移动操作
+fn is_motion(key: KeyCode, ctrl: bool) -> bool {
+    if ctrl {
+        return matches!(key, KeyCode::Char('d' | 'u' | 'f' | 'b'));
+    }
+    matches!(
+        key,
+        KeyCode::Up
+            | KeyCode::Down
+            | KeyCode::Left
+            | KeyCode::Right
+            | KeyCode::PageUp
+            | KeyCode::PageDown
+            | KeyCode::Char('j' | 'k' | 'h' | 'l' | '[' | ']' | '{' | '}' | 'n' | 'N')
+    )
+}
+
+/// 处理一次阅读器按键（不含数字前缀）
+fn handle_reader_key_once(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    let layout = ReaderLayout::new(app, app.terminal_size);
+    let overlap = app.preferences.page_overlap;
     if let Some(novel) = &mut app.current_novel {
         let max_scroll = novel.line_count().saturating_sub(1);
 
-        let content_width = app.terminal_size.width.saturating_sub(4) as usize;
-        let content_height = (app.terminal_size.height as usize)
-            .saturating_sub(1)
-            .saturating_sub(2)
-            .saturating_sub(1);
         let spacing = novel.progress.line_spacing;
         let line_physical_height = (spacing + 1).max(1);
-        let page_size = (content_height / line_physical_height).max(1);
+        let page_size = (layout.page_rows() / line_physical_height).max(1);
 
-        match key {
-            KeyCode::Up | KeyCode::Char('k') if novel.progress.scroll_offset > 0 => {
-                novel.progress.scroll_offset -= 1;
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            let half_page = (page_size / 2).max(1);
+            match key {
+                KeyCode::Char('d') => scroll_forward(novel, &layout, half_page, 0),
+                KeyCode::Char('u') => scroll_backward(novel, &layout, half_page, 0),
+                KeyCode::Char('f') => scroll_forward(novel, &layout, page_size, overlap),
+                KeyCode::Char('b') => scroll_backward(novel, &layout, page_size, overlap),
+                KeyCode::Char('o') => {
+                    app.jump_back();
+                }
+                _ => {}
             }
-            KeyCode::Down | KeyCode::Char('j')
-                if novel.progress.scroll_offset < max_scroll.saturating_sub(page_size) =>
-            {
-                novel.progress.scroll_offset += 1;
+            return;
+        }
+
+        if modifiers.contains(KeyModifiers::ALT)
+            && let KeyCode::Char(slot @ '1'..='9') = key
+        {
+            app.reader.pending_key = None;
+            set_quick_slot(app, slot);
+            return;
+        }
+
+        // 前缀键只对紧随其后的一次按键有效
+        match (app.reader.pending_key.take(), key) {
+            (Some('g'), KeyCode::Char('g')) => {
+                app.jump_to(0);
+                return;
+            }
+            (Some('m'), KeyCode::Char(name)) if name.is_ascii_alphabetic() => {
+                app.set_mark(name);
+                return;
+            }
+            (Some('m'), KeyCode::Char(slot @ '1'..='9')) => {
+                set_quick_slot(app, slot);
+                return;
+            }
+            (Some('\''), KeyCode::Char('\'')) => {
+                app.return_to_last_position();
+                return;
+            }
+            (Some('\''), KeyCode::Char(name)) if name.is_ascii_alphabetic() => {
+                if app.jump_to_mark(name).is_none() {
+                    app.set_error(trf!("标记 {} 未设置", "Mark {} is not set", name));
+                }
+                return;
+            }
+            // 单独的数字键是数字前缀（`5j`、`120G`），快捷书签跳转和标记一样使用 `'` 前缀
+            (Some('\''), KeyCode::Char(slot @ '1'..='9')) => {
+                if app.jump_to_quick_slot(quick_slot_number(slot)).is_none() {
+                    app.set_error(trf!(
+                        "快捷书签 {} 未设置",
+                        "Quick bookmark {} is not set",
+                        slot
+                    ));
+                }
+                return;
             }
-            KeyCode::Left | KeyCode::Char('h') => {
-                let mut physical_lines_in_prev_page = 0;
-                let mut logical_lines_to_jump = 0;
+            // 前缀后跟无效按键时只取消前缀
+            (Some('m' | '\''), _) => return,
+            _ => {}
+        }
 
-                for line in novel
-                    .lines()
-                    .iter()
-                    .take(novel.progress.scroll_offset)
-                    .rev()
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                scroll_rows_backward(novel, &layout, config().reader.scroll_lines);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                scroll_rows_forward(novel, &layout, config().reader.scroll_lines);
+            }
+            KeyCode::Left | KeyCode::PageUp | KeyCode::Char('h') => {
+                scroll_backward(novel, &layout, page_size, overlap);
+            }
+            KeyCode::Right | KeyCode::PageDown | KeyCode::Char('l') => {
+                scroll_forward(novel, &layout, page_size, overlap);
+            }
+            KeyCode::Char('g') => {
+                app.reader.pending_key = Some('g');
+            }
+            // 终端中 Ctrl+i 与 Tab 无法区分
+            KeyCode::Tab => {
+                app.jump_forward();
+            }
+            KeyCode::Char('}') => {
+                if let Some(line) =
+                    next_paragraph_start(novel.lines(), novel.progress.scroll_offset)
                 {
-                    let line_height = count_physical_lines(line, content_width);
-                    if physical_lines_in_prev_page + line_height > page_size {
-                        break;
-                    }
-                    physical_lines_in_prev_page += line_height;
-                    logical_lines_to_jump += 1;
+                    novel.progress.scroll_offset = line;
                 }
-
-                novel.progress.scroll_offset = novel
-                    .progress
-                    .scroll_offset
-                    .saturating_sub(logical_lines_to_jump.max(1));
             }
-            KeyCode::Right | KeyCode::Char('l') => {
-                let mut physical_lines_on_current_page = 0;
-                let mut logical_lines_to_jump = 0;
-
-                for line in novel.lines().iter().skip(novel.progress.scroll_offset) {
-                    let line_height = count_physical_lines(line, content_width);
-                    if physical_lines_on_current_page + line_height > page_size {
-                        break;
+            KeyCode::Char('{') => {
+                novel.progress.scroll_offset =
+                    prev_paragraph_start(novel.lines(), novel.progress.scroll_offset);
+            }
+            KeyCode::Home => {
+                app.jump_to(0);
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                // 从末尾向前倒推一页，使最后一页正好填满屏幕
+                let from = novel.progress.scroll_offset;
+                novel.progress.scroll_offset = novel.line_count();
+                scroll_backward(novel, &layout, page_size, 0);
+                let target = novel.progress.scroll_offset;
+                novel.progress.scroll_offset = from;
+                app.jump_to(target);
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') => {
+                app.reader.find_origin = novel.progress.scroll_offset;
+                app.reader.find_input = Some(String::new());
+            }
+            KeyCode::Char('n') | KeyCode::Char('N') => {
+                let from = novel.progress.scroll_offset;
+                let forward = key == KeyCode::Char('n');
+                let lines = &app.reader.match_lines;
+                if !lines.is_empty() {
+                    // 优先在记录的匹配行之间跳转，保留搜索的范围和模糊匹配结果
+                    let target = if forward {
+                        let next = lines.partition_point(|&line| line <= from);
+                        lines.get(next).unwrap_or(&lines[0])
+                    } else {
+                        let prev = lines.partition_point(|&line| line < from);
+                        prev.checked_sub(1)
+                            .map_or(&lines[lines.len() - 1], |i| &lines[i])
+                    };
+                    app.jump_to(*target);
+                } else if let Some(term) = app.reader.highlight_term.clone() {
+                    match app.find_match_line(&term, from, forward, true) {
+                        Some(line) => app.jump_to(line),
+                        None => app.set_error(trf!("未找到: {}", "Not found: {}", term)),
                     }
-                    physical_lines_on_current_page += line_height;
-                    logical_lines_to_jump += 1;
                 }
-
-                let jump = logical_lines_to_jump.max(1);
-                novel.progress.scroll_offset =
-                    (novel.progress.scroll_offset + jump).min(max_scroll);
             }
             KeyCode::Char('/') => {
                 app.previous_state = AppState::Reading;
@@ -96,17 +351,61 @@ pub(super) fn handle_reader_key(app: &mut App, key: KeyCode) {
                 app.previous_state = AppState::Reading;
                 app.state = AppState::ChapterList;
                 app.selected_chapter_index = app.find_current_chapter_index();
+                app.chapter_list = ChapterListState::default();
             }
             KeyCode::Char('b') | KeyCode::Char('B') => {
                 app.previous_state = AppState::Reading;
                 app.state = AppState::BookmarkList;
                 app.bookmark.selected_index = None;
+                app.bookmark.filter.clear();
             }
-            KeyCode::Char('m') | KeyCode::Char('M') => {
+            KeyCode::Char('m') => {
+                app.reader.pending_key = Some('m');
+            }
+            KeyCode::Char('\'') => {
+                app.reader.pending_key = Some('\'');
+            }
+            KeyCode::Char('M') => {
                 app.previous_state = AppState::Reading;
                 app.state = AppState::BookmarkAdd;
                 app.clear_bookmark_inputs();
             }
+            KeyCode::Char('v') | KeyCode::Char('V') | KeyCode::Char('a') => {
+                // 从引导线所在行（未开启时为页首）开始选择或批注
+                let offset = if app.reader.reading_guide {
+                    app.reader.guide_anchor
+                } else {
+                    0
+                };
+                let line = (novel.progress.scroll_offset + offset).min(max_scroll);
+                if key == KeyCode::Char('a') {
+                    app.start_annotation_edit(line);
+                } else {
+                    app.start_visual_selection(line);
+                }
+            }
+            KeyCode::Char('A') => {
+                let scroll_offset = novel.progress.scroll_offset;
+                let annotations = &novel.progress.annotations;
+                // 选中当前位置或之后的第一条批注
+                app.annotation.selected_index = annotations
+                    .iter()
+                    .position(|a| a.line >= scroll_offset)
+                    .or(annotations.len().checked_sub(1));
+                app.previous_state = AppState::Reading;
+                app.state = AppState::AnnotationList;
+            }
+            KeyCode::Char('H') => {
+                let scroll_offset = novel.progress.scroll_offset;
+                let highlights = &novel.progress.highlights;
+                // 选中当前位置所在或之后的第一个高亮
+                app.highlight_list.selected_index = highlights
+                    .iter()
+                    .position(|h| h.end >= scroll_offset)
+                    .or(highlights.len().checked_sub(1));
+                app.previous_state = AppState::Reading;
+                app.state = AppState::HighlightList;
+            }
             KeyCode::Char('[') if !novel.chapters.is_empty() => {
                 let current_idx =
                     App::find_chapter_index(&novel.chapters, novel.progress.scroll_offset);
@@ -127,11 +426,388 @@ pub(super) fn handle_reader_key(app: &mut App, key: KeyCode) {
                 novel.progress.line_spacing -= 1;
                 app.save_current_progress();
             }
-            KeyCode::Char('=') | KeyCode::Char('+') if novel.progress.line_spacing < 5 => {
+            KeyCode::Char('=') | KeyCode::Char('+')
+                if novel.progress.line_spacing < MAX_LINE_SPACING =>
+            {
                 novel.progress.line_spacing += 1;
                 app.save_current_progress();
             }
+            KeyCode::Char('z') | KeyCode::Char('Z') => {
+                app.reader.zen_mode = !app.reader.zen_mode;
+            }
+            KeyCode::Char('e') | KeyCode::Char('E') => {
+                app.reader.night_mode = !app.reader.night_mode;
+            }
+            KeyCode::Char('x') | KeyCode::Char('X') => {
+                app.reader.highlight_term = None;
+                app.reader.match_lines.clear();
+            }
+            KeyCode::Char('u') => {
+                app.reader.reading_guide = !app.reader.reading_guide;
+            }
+            KeyCode::Char('U') => {
+                app.reader.reading_guide = true;
+                app.reader.guide_anchor = (app.reader.guide_anchor + 1) % page_size;
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                app.reader.camouflage = !app.reader.camouflage;
+            }
+            KeyCode::Char('|') => {
+                app.reader.vertical_layout = !app.reader.vertical_layout;
+            }
+            KeyCode::Char('(') if app.preferences.page_overlap > 0 => {
+                app.preferences.page_overlap -= 1;
+                app.save_preferences();
+            }
+            KeyCode::Char(')') if app.preferences.page_overlap < MAX_PAGE_OVERLAP => {
+                app.preferences.page_overlap += 1;
+                app.save_preferences();
+            }
+            KeyCode::Char('s') => {
+                if app.reader.auto_scroll.take().is_some() {
+                    app.set_info(tr("已关闭自动滚动", "Auto-scroll off"));
+                } else {
+                    app.reader.auto_scroll = Some(Instant::now());
+                    let seconds = config().reader.auto_scroll_ms as f64 / 1000.0;
+                    app.set_info(trf!(
+                        "已开启自动滚动：每 {} 秒一行，s 关闭",
+                        "Auto-scroll on: one line every {} s, s to stop",
+                        seconds
+                    ));
+                }
+            }
             _ => {}
         }
     }
 }
+
+/// 自动滚动：距上次滚动超过设置的间隔时向下滚动一行
+///
+/// 只在阅读界面且没有对话框、输入框和选择时滚动；到达最后一页后自动关闭。
+pub(super) fn tick_auto_scroll(app: &mut App) {
+    let Some(last) = app.reader.auto_scroll else {
+        return;
+    };
+    if app.state != AppState::Reading
+        || app.confirm.is_some()
+        || app.reader.find_input.is_some()
+        || app.annotation.input.is_some()
+        || app.reader.visual_anchor.is_some()
+        || last.elapsed() < Duration::from_millis(config().reader.auto_scroll_ms)
+    {
+        return;
+    }
+    let layout = ReaderLayout::new(app, app.terminal_size);
+    if is_last_page(app, &layout) {
+        app.reader.auto_scroll = None;
+        app.set_info(tr(
+            "已滚动到末尾，自动滚动已关闭",
+            "Reached the end; auto-scroll off",
+        ));
+        return;
+    }
+    if let Some(novel) = &mut app.current_novel {
+        scroll_forward(novel, &layout, 1, 0);
+    }
+    app.reader.auto_scroll = Some(Instant::now());
+}
+
+/// 页首所在的位置：`(首行, 页首在首行中的屏幕行序号)`
+fn top_row(novel: &Novel, layout: &ReaderLayout) -> (usize, usize) {
+    let line = novel.progress.scroll_offset;
+    let row = novel.lines().get(line).map_or(0, |text| {
+        layout.row_of_offset(text, novel.top_offset_in_line())
+    });
+    (line, row)
+}
+
+/// 把页首设为第 `line` 行的第 `row` 个屏幕行
+fn set_top_row(novel: &mut Novel, layout: &ReaderLayout, line: usize, row: usize) {
+    let offset = novel
+        .lines()
+        .get(line)
+        .map_or(0, |text| layout.row_start(text, row));
+    novel.set_top(line, offset);
+}
+
+/// 页首向下移动 `rows` 个屏幕行，已显示到最后一行时停止
+fn scroll_rows_forward(novel: &mut Novel, layout: &ReaderLayout, rows: usize) {
+    for _ in 0..rows {
+        if shows_last_line(novel, layout) {
+            break;
+        }
+        let (line, row) = top_row(novel, layout);
+        if row + 1 < layout.scroll_rows(&novel.lines()[line]) {
+            set_top_row(novel, layout, line, row + 1);
+        } else {
+            set_top_row(novel, layout, line + 1, 0);
+        }
+    }
+}
+
+/// 页首向上移动 `rows` 个屏幕行，最多到第一行开头
+fn scroll_rows_backward(novel: &mut Novel, layout: &ReaderLayout, rows: usize) {
+    let (mut line, mut row) = top_row(novel, layout);
+    for _ in 0..rows {
+        if row > 0 {
+            row -= 1;
+        } else if line > 0 {
+            line -= 1;
+            row = layout.scroll_rows(&novel.lines()[line]) - 1;
+        } else {
+            break;
+        }
+    }
+    set_top_row(novel, layout, line, row);
+}
+
+/// 向下滚动不超过 `budget` 个物理行的内容，并保留 `overlap` 行重叠
+///
+/// 至少前进一行，且不会越过最后一行。首行剩余部分比一页还长时在行内前进。
+fn scroll_forward(novel: &mut Novel, layout: &ReaderLayout, budget: usize, overlap: usize) {
+    let max_scroll = novel.line_count().saturating_sub(1);
+    let (top, row) = top_row(novel, layout);
+    let mut physical_lines = 0;
+    let mut logical_lines_to_jump: usize = 0;
+
+    for (i, line) in novel.lines().iter().skip(top).enumerate() {
+        let mut line_height = layout.line_rows(line);
+        if i == 0 {
+            line_height -= row;
+            if line_height > budget && !layout.vertical {
+                let step = budget.saturating_sub(overlap).max(1);
+                set_top_row(novel, layout, top, row + step);
+                return;
+            }
+        }
+        if physical_lines + line_height > budget {
+            break;
+        }
+        physical_lines += line_height;
+        logical_lines_to_jump += 1;
+    }
+
+    let jump = logical_lines_to_jump.saturating_sub(overlap).max(1);
+    set_top_row(novel, layout, (top + jump).min(max_scroll), 0);
+}
+
+/// 向上滚动不超过 `budget` 个物理行的内容，并保留 `overlap` 行重叠
+///
+/// 至少后退一行，且不会越过第一行。页首在长段落中间时先在行内后退；
+/// 上一行比一页还长时停在它的最后一页。
+fn scroll_backward(novel: &mut Novel, layout: &ReaderLayout, budget: usize, overlap: usize) {
+    let (top, row) = top_row(novel, layout);
+    if row > 0 {
+        let step = budget.saturating_sub(overlap).max(1);
+        set_top_row(novel, layout, top, row.saturating_sub(step));
+        return;
+    }
+
+    let mut physical_lines = 0;
+    let mut logical_lines_to_jump: usize = 0;
+
+    // 从当前页顶部往下 overlap 行处向前累计，使上一页末尾与当前页开头重叠
+    let anchor = (top + overlap).min(novel.line_count());
+    for line in novel.lines().iter().take(anchor).rev() {
+        let line_height = layout.line_rows(line);
+        if physical_lines + line_height > budget {
+            break;
+        }
+        physical_lines += line_height;
+        logical_lines_to_jump += 1;
+    }
+
+    let jump = logical_lines_to_jump.saturating_sub(overlap).max(1);
+    let target = top.saturating_sub(jump);
+    let target_rows = novel
+        .lines()
+        .get(target)
+        .map_or(1, |line| layout.scroll_rows(line));
+    let target_row = if target < top && target_rows > budget {
+        target_rows - budget
+    } else {
+        0
+    };
+    set_top_row(novel, layout, target, target_row);
+}
+
+/// 从页首开始各逻辑行在本页中占用的物理行数，首行不计页首之前的屏幕行
+fn rows_from_top<'a>(
+    novel: &'a Novel,
+    layout: &'a ReaderLayout,
+) -> impl Iterator<Item = usize> + 'a {
+    let (top, row) = top_row(novel, layout);
+    novel
+        .lines()
+        .iter()
+        .skip(top)
+        .enumerate()
+        .map(move |(i, line)| layout.line_rows(line) - if i == 0 { row } else { 0 })
+}
+
+/// 判断当前页是否已经显示到小说的最后一行
+fn is_last_page(app: &App, layout: &ReaderLayout) -> bool {
+    app.current_novel
+        .as_ref()
+        .is_some_and(|novel| shows_last_line(novel, layout))
+}
+
+/// 从页首开始到小说最后一行能否在一页内显示完
+fn shows_last_line(novel: &Novel, layout: &ReaderLayout) -> bool {
+    let spacing = novel.progress.line_spacing;
+    let page_size = (layout.page_rows() / (spacing + 1)).max(1);
+
+    let mut physical_lines = 0;
+    for line_rows in rows_from_top(novel, layout) {
+        physical_lines += line_rows;
+        if physical_lines > page_size {
+            return false;
+        }
+    }
+    true
+}
+
+/// 从当前页首开始，完整显示在一页内的逻辑行数（至少为 1）
+fn visible_line_count(novel: &Novel, layout: &ReaderLayout) -> usize {
+    let spacing = novel.progress.line_spacing;
+    let page_size = (layout.page_rows() / (spacing + 1)).max(1);
+    let mut physical_lines = 0;
+    let mut count = 0;
+    for line_rows in rows_from_top(novel, layout) {
+        physical_lines += line_rows;
+        if physical_lines > page_size {
+            break;
+        }
+        count += 1;
+    }
+    count.max(1)
+}
+
+/// 处理选择模式下的按键
+///
+/// `j`/`k` 移动光标扩展选中范围（光标离开当前页时滚动），`Enter`/`v` 保存为高亮，
+/// `a` 退出选择并批注光标所在行。
+fn handle_visual_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Down | KeyCode::Char('j') => move_visual_cursor(app, false),
+        KeyCode::Up | KeyCode::Char('k') => move_visual_cursor(app, true),
+        KeyCode::Enter | KeyCode::Char('v') | KeyCode::Char('V') => {
+            app.confirm_visual_selection();
+            app.set_info(tr(
+                "已高亮选中的行，按 H 查看高亮列表",
+                "Highlighted the selected lines; press H to list highlights",
+            ));
+        }
+        KeyCode::Char('a') => {
+            // 改为批注光标所在行
+            let line = app.reader.visual_cursor;
+            app.reader.visual_anchor = None;
+            app.start_annotation_edit(line);
+        }
+        _ => {}
+    }
+}
+
+/// 在选择模式中上下移动光标一行，并保持光标在视口内
+fn move_visual_cursor(app: &mut App, up: bool) {
+    let layout = ReaderLayout::new(app, app.terminal_size);
+    let Some(novel) = &mut app.current_novel else {
+        return;
+    };
+    let cursor = &mut app.reader.visual_cursor;
+    *cursor = if up {
+        cursor.saturating_sub(1)
+    } else {
+        (*cursor + 1).min(novel.line_count().saturating_sub(1))
+    };
+    if *cursor < novel.progress.scroll_offset {
+        novel.progress.scroll_offset = *cursor;
+    }
+    while *cursor >= novel.progress.scroll_offset + visible_line_count(novel, &layout) {
+        novel.progress.scroll_offset += 1;
+    }
+}
+
+/// 判断一行是否为段落分隔用的空行（包括只含全角空格等空白的行）
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// 查找 `from` 之后下一段的起始行
+///
+/// 先跳过当前段剩余的文字行，再跳过分隔的空行。之后没有段落时返回 `None`。
+fn next_paragraph_start(lines: &[String], from: usize) -> Option<usize> {
+    let rest = lines.get(from..)?;
+    let gap = rest.iter().position(|line| is_blank(line))?;
+    rest[gap..]
+        .iter()
+        .position(|line| !is_blank(line))
+        .map(|offset| from + gap + offset)
+}
+
+/// 查找 `from` 之前一段的起始行
+///
+/// 如果 `from` 位于某段中间，返回该段开头；已在段首时返回上一段开头。
+fn prev_paragraph_start(lines: &[String], from: usize) -> usize {
+    let mut line = from.min(lines.len());
+    // 跳过前面的空行
+    while line > 0 && is_blank(&lines[line - 1]) {
+        line -= 1;
+    }
+    // 向前走到段首
+    while line > 0 && !is_blank(&lines[line - 1]) {
+        line -= 1;
+    }
+    line
+}
+
+/// 处理阅读界面内查找的输入
+///
+/// 每次输入都从开始查找的位置重新定位到第一处匹配；
+/// Enter 确认后关键词保留为高亮词，可继续用 `n`/`N` 跳转。
+fn handle_find_key(app: &mut App, key: KeyCode) {
+    let Some(input) = app.reader.find_input.as_mut() else {
+        return;
+    };
+    match key {
+        KeyCode::Enter => {
+            let term = std::mem::take(input);
+            app.reader.find_input = None;
+            if term.is_empty() {
+                return;
+            }
+            let origin = app.reader.find_origin;
+            match app.find_match_line(&term, origin, true, false) {
+                Some(line) => {
+                    // 从查找前的位置记一次跳转，方便用 Ctrl+o 返回
+                    if let Some(novel) = &mut app.current_novel {
+                        novel.progress.scroll_offset = origin;
+                    }
+                    app.jump_to(line);
+                }
+                None => app.set_error(trf!("未找到: {}", "Not found: {}", term)),
+            }
+            app.reader.match_lines = app.collect_match_lines(&term);
+            app.reader.highlight_term = Some(term);
+            app.reader.highlight_fuzzy = false;
+            return;
+        }
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Char(c) => input.push(c),
+        _ => return,
+    }
+
+    let term = input.clone();
+    let origin = app.reader.find_origin;
+    let target = app
+        .find_match_line(&term, origin, true, false)
+        .unwrap_or(origin);
+    if let Some(novel) = &mut app.current_novel {
+        novel.progress.scroll_offset = target;
+    }
+    app.reader.match_lines.clear();
+    app.reader.highlight_term = (!term.is_empty()).then_some(term);
+    app.reader.highlight_fuzzy = false;
+}
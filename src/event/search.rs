@@ -1,6 +1,9 @@
 use crate::app::App;
+use crate::i18n::{tr, trf};
 use crate::state::AppState;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+use super::{list_page_size, page_list};
 
 /// 处理搜索模式下的键盘事件
 ///
@@ -8,28 +11,53 @@ use crossterm::event::KeyCode;
 ///
 /// * `app` - 应用实例的可变引用
 /// * `key` - 按下的键位代码
+/// * `modifiers` - 按键修饰符
 ///
 /// # Behavior
 ///
 /// - `Enter`: 跳转到选中的搜索结果
 /// - `Up`: 向上选择搜索结果
 /// - `Down`: 向下选择搜索结果
+/// - `PageUp`/`PageDown`: 搜索结果向上/向下翻页
+/// - `Ctrl+f`: 切换模糊模式（拼音、容错）并重新搜索
+/// - `Ctrl+e`: 把搜索结果导出为 Markdown 文件
+/// - `Ctrl+g`: 输入结果序号，按 `Enter` 直接跳转到该条结果
+/// - `Tab`: 切换搜索范围（全书/本章/章节范围）并重新搜索
 /// - `Backspace`: 删除输入的最后一个字符
 /// - 其他字符: 添加到搜索框并执行搜索
-pub(super) fn handle_search_key(app: &mut App, key: KeyCode) {
+pub(super) fn handle_search_key(app: &mut App, key: KeyCode, modifiers: KeyModifiers) {
+    if app.search.goto_input.is_some() {
+        handle_goto_key(app, key);
+        return;
+    }
+
     match key {
+        KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.search.fuzzy = !app.search.fuzzy;
+            app.perform_search();
+        }
+        KeyCode::Char('e') if modifiers.contains(KeyModifiers::CONTROL) => {
+            if app.search.results.is_empty() {
+                app.set_error(tr("没有可导出的搜索结果", "No search results to export"));
+                return;
+            }
+            match app.export_search_results() {
+                Ok(path) => app.set_info(trf!("已导出到 {}", "Exported to {}", path.display())),
+                Err(e) => app.set_error(trf!("导出失败: {}", "Export failed: {}", e)),
+            }
+        }
+        KeyCode::Tab => {
+            app.search.scope = app.search.scope.next();
+            app.perform_search();
+        }
         KeyCode::Enter => {
-            if let Some(index) = app.search.selected_index
-                && index < app.search.results.len()
-            {
-                let (line_num, _) = app.search.results[index];
-                if let Some(novel) = &mut app.current_novel {
-                    novel.progress.scroll_offset = line_num;
-                    app.save_current_progress();
-                }
-                app.state = AppState::Reading;
+            if let Some(index) = app.search.selected_index {
+                jump_to_result(app, index);
             }
         }
+        KeyCode::Char('g') if modifiers.contains(KeyModifiers::CONTROL) => {
+            app.search.goto_input = (!app.search.results.is_empty()).then(String::new);
+        }
         KeyCode::Up if !app.search.results.is_empty() => {
             let current = app.search.selected_index.unwrap_or(0);
             let next = if current > 0 {
@@ -46,6 +74,14 @@ pub(super) fn handle_search_key(app: &mut App, key: KeyCode) {
             };
             app.search.selected_index = Some(next);
         }
+        KeyCode::PageUp | KeyCode::PageDown => {
+            app.search.selected_index = page_list(
+                app.search.selected_index,
+                app.search.results.len(),
+                list_page_size(app, 8),
+                key == KeyCode::PageUp,
+            );
+        }
         KeyCode::Backspace => {
             app.search.input.pop();
             app.perform_search();
@@ -57,3 +93,44 @@ pub(super) fn handle_search_key(app: &mut App, key: KeyCode) {
         _ => {}
     }
 }
+
+/// 跳转到第 `index` 条搜索结果并返回阅读界面
+fn jump_to_result(app: &mut App, index: usize) {
+    let Some(&(line_num, _)) = app.search.results.get(index) else {
+        return;
+    };
+    app.search.selected_index = Some(index);
+    app.jump_to(line_num);
+    app.reader.highlight_term = Some(app.search.highlight_term());
+    app.reader.highlight_fuzzy = app.search.fuzzy;
+    app.reader.match_lines = app.search.results.iter().map(|(line, _)| *line).collect();
+    app.state = AppState::Reading;
+}
+
+/// 处理结果序号的输入
+///
+/// 只接受数字；`Enter` 跳转到对应序号（从 1 开始）的结果，序号超出范围时提示错误。
+fn handle_goto_key(app: &mut App, key: KeyCode) {
+    let Some(input) = app.search.goto_input.as_mut() else {
+        return;
+    };
+    match key {
+        KeyCode::Char(c) if c.is_ascii_digit() => input.push(c),
+        KeyCode::Backspace => {
+            input.pop();
+        }
+        KeyCode::Enter => {
+            let input = app.search.goto_input.take().unwrap_or_default();
+            let total = app.search.results.len();
+            match input.parse::<usize>() {
+                Ok(n) if (1..=total).contains(&n) => jump_to_result(app, n - 1),
+                _ => app.set_error(trf!(
+                    "结果序号应在 1-{} 之间",
+                    "The result number must be between 1 and {}",
+                    total
+                )),
+            }
+        }
+        _ => {}
+    }
+}
@@ -1,5 +1,9 @@
-use crate::app::App;
-use crate::config::CONFIG;
+use crate::app::{App, ConfirmAction, PerformanceOption, ReaderOption};
+use crate::config::SETTINGS_MENU_COUNT;
+use crate::i18n::{tr, trf};
+use crate::model::library::Library;
+use crate::model::preferences::ThemeName;
+use crate::model::trash::Trash;
 use crate::state::SettingsMode;
 use crate::sync::webdav_client::WebDavClient;
 use crossterm::event::KeyCode;
@@ -20,6 +24,13 @@ pub(super) fn handle_settings_key(app: &mut App, key: KeyCode) {
         SettingsMode::DeleteNovel => handle_delete_novel_key(app, key),
         SettingsMode::DeleteOrphaned => handle_delete_orphaned_key(app, key),
         SettingsMode::WebDavConfig => handle_webdav_config_key(app, key),
+        SettingsMode::BookmarkTransfer => handle_bookmark_transfer_key(app, key),
+        SettingsMode::LibraryDirs => handle_library_dirs_key(app, key),
+        SettingsMode::Trash => handle_trash_key(app, key),
+        SettingsMode::Appearance => handle_appearance_key(app, key),
+        SettingsMode::ReaderOptions => handle_reader_options_key(app, key),
+        SettingsMode::Backups => handle_backups_key(app, key),
+        SettingsMode::Performance => handle_performance_key(app, key),
     }
 }
 
@@ -35,21 +46,17 @@ pub(super) fn handle_settings_key(app: &mut App, key: KeyCode) {
 /// - `Up`/`k`: 向上选择
 /// - `Down`/`j`: 向下选择
 /// - `Enter`: 进入选中的子菜单
+/// - `e`: 把全部设置（不含 WebDAV 密码）导出到一个文件
+/// - `i`: 确认后从导出的设置文件导入
 fn handle_settings_main_menu_key(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Up | KeyCode::Char('k') => {
-            app.settings.selected_option = navigate_list(
-                app.settings.selected_option,
-                CONFIG.settings_menu_count,
-                true,
-            );
+            app.settings.selected_option =
+                navigate_list(app.settings.selected_option, SETTINGS_MENU_COUNT, true);
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            app.settings.selected_option = navigate_list(
-                app.settings.selected_option,
-                CONFIG.settings_menu_count,
-                false,
-            );
+            app.settings.selected_option =
+                navigate_list(app.settings.selected_option, SETTINGS_MENU_COUNT, false);
         }
         KeyCode::Enter => {
             if let Some(index) = app.settings.selected_option {
@@ -68,17 +75,348 @@ fn handle_settings_main_menu_key(app: &mut App, key: KeyCode) {
                         app.settings.selected_orphaned_index =
                             navigate_list(None, app.settings.orphaned_novels.len(), false);
                     }
-                    2 => {
-                        app.settings.mode = SettingsMode::WebDavConfig;
-                        app.settings.webdav_config_state.temp_config = app.webdav_config.clone();
-                        app.settings.webdav_config_state.selected_field = 0;
-                        app.settings.webdav_config_state.edit_mode = false;
-                        app.settings.webdav_config_state.show_password = false;
+                    2 => app.open_webdav_config(),
+                    3 => {
+                        app.settings.mode = SettingsMode::BookmarkTransfer;
+                        app.settings.selected_bookmark_novel_index =
+                            navigate_list(None, app.novels.len(), false);
+                    }
+                    4 => {
+                        app.settings.mode = SettingsMode::LibraryDirs;
+                        app.settings.selected_library_dir_index = Some(0);
+                        app.settings.library_dir_input = None;
+                    }
+                    5 => {
+                        app.settings.mode = SettingsMode::Trash;
+                        app.settings.trash = Trash::load();
+                        app.settings.selected_trash_index =
+                            navigate_list(None, app.settings.trash.entries.len(), false);
+                    }
+                    6 => {
+                        app.settings.mode = SettingsMode::Appearance;
+                        app.settings.saved_theme = app.preferences.theme;
+                        app.settings.selected_theme_index = ThemeName::ALL
+                            .iter()
+                            .position(|&theme| theme == app.preferences.theme);
                     }
+                    7 => {
+                        app.settings.mode = SettingsMode::ReaderOptions;
+                        app.settings.selected_reader_option = Some(0);
+                    }
+                    8 => {
+                        app.settings.mode = SettingsMode::Backups;
+                        app.settings.backups = Library::list_backups();
+                        app.settings.selected_backup_index =
+                            navigate_list(None, app.settings.backups.len(), false);
+                    }
+                    9 => {
+                        app.settings.mode = SettingsMode::Performance;
+                        app.settings.selected_performance_option = Some(0);
+                    }
+                    10 => app.toggle_language(),
                     _ => {}
                 }
             }
         }
+        KeyCode::Char('e') | KeyCode::Char('E') => match app.export_settings() {
+            Ok(path) => app.set_info(trf!(
+                "已导出设置到 {}（不含 WebDAV 密码）",
+                "Exported settings to {} (without the WebDAV password)",
+                path.display()
+            )),
+            Err(e) => app.set_error(trf!("导出失败：{}", "Export failed: {}", e)),
+        },
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            let path = App::settings_export_path();
+            if path.exists() {
+                let message = trf!(
+                    "从 {} 导入设置，覆盖本机设置？",
+                    "Import settings from {} and overwrite the local settings?",
+                    path.display()
+                );
+                app.request_confirm(message, ConfirmAction::ImportSettings);
+            } else {
+                app.set_error(trf!(
+                    "没有找到设置文件 {}",
+                    "Settings file {} not found",
+                    path.display()
+                ));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 处理外观页面的键盘事件
+///
+/// 移动选择时立即应用选中的主题作为预览，`Esc` 恢复进入页面前的主题。
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择并预览
+/// - `Down`/`j`: 向下选择并预览
+/// - `Enter`: 保存选中的主题并返回设置菜单
+fn handle_appearance_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Up | KeyCode::Char('k') | KeyCode::Down | KeyCode::Char('j') => {
+            let up = matches!(key, KeyCode::Up | KeyCode::Char('k'));
+            app.settings.selected_theme_index =
+                navigate_list(app.settings.selected_theme_index, ThemeName::ALL.len(), up);
+            if let Some(index) = app.settings.selected_theme_index {
+                app.preferences.theme = ThemeName::ALL[index];
+            }
+        }
+        KeyCode::Enter => {
+            app.settings.saved_theme = app.preferences.theme;
+            app.save_preferences();
+            app.settings.mode = SettingsMode::MainMenu;
+            app.set_info(trf!(
+                "已切换到「{}」主题",
+                "Switched to the {} theme",
+                app.preferences.theme.label()
+            ));
+        }
+        _ => {}
+    }
+}
+
+/// 处理阅读选项页面的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `Left`/`h`/`-`: 减小选中的选项
+/// - `Right`/`l`/`+`/`=`: 增大选中的选项
+fn handle_reader_options_key(app: &mut App, key: KeyCode) {
+    let count = ReaderOption::ALL.len();
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings.selected_reader_option =
+                navigate_list(app.settings.selected_reader_option, count, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings.selected_reader_option =
+                navigate_list(app.settings.selected_reader_option, count, false);
+        }
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('-') => {
+            if let Some(index) = app.settings.selected_reader_option {
+                app.adjust_reader_option(ReaderOption::ALL[index], false);
+            }
+        }
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('+') | KeyCode::Char('=') => {
+            if let Some(index) = app.settings.selected_reader_option {
+                app.adjust_reader_option(ReaderOption::ALL[index], true);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 处理性能页面的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `Left`/`h`/`-`: 把选中的选项调到上一档
+/// - `Right`/`l`/`+`/`=`: 把选中的选项调到下一档
+fn handle_performance_key(app: &mut App, key: KeyCode) {
+    let count = PerformanceOption::ALL.len();
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings.selected_performance_option =
+                navigate_list(app.settings.selected_performance_option, count, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings.selected_performance_option =
+                navigate_list(app.settings.selected_performance_option, count, false);
+        }
+        KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('-') => {
+            if let Some(index) = app.settings.selected_performance_option {
+                app.adjust_performance_option(PerformanceOption::ALL[index], false);
+            }
+        }
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('+') | KeyCode::Char('=') => {
+            if let Some(index) = app.settings.selected_performance_option {
+                app.adjust_performance_option(PerformanceOption::ALL[index], true);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 处理书签导入导出模式的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `e`: 把选中小说的书签导出为 Markdown 和 JSON 文件
+/// - `i`: 从导出的 JSON 文件导入选中小说的书签
+fn handle_bookmark_transfer_key(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings.selected_bookmark_novel_index = navigate_list(
+                app.settings.selected_bookmark_novel_index,
+                app.novels.len(),
+                true,
+            );
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings.selected_bookmark_novel_index = navigate_list(
+                app.settings.selected_bookmark_novel_index,
+                app.novels.len(),
+                false,
+            );
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            let Some(index) = app.settings.selected_bookmark_novel_index else {
+                return;
+            };
+            match app.export_bookmarks(index) {
+                Ok(path) => app.set_info(trf!(
+                    "已导出到 {}（及同名 .json）",
+                    "Exported to {} (and a .json with the same name)",
+                    path.display()
+                )),
+                Err(e) => app.set_error(trf!("导出失败：{}", "Export failed: {}", e)),
+            }
+        }
+        KeyCode::Char('i') | KeyCode::Char('I') => {
+            let Some(index) = app.settings.selected_bookmark_novel_index else {
+                return;
+            };
+            match app.import_bookmarks(index) {
+                Ok(added) => app.set_info(trf!("已导入 {} 个书签", "Imported {} bookmarks", added)),
+                Err(e) => app.set_error(trf!("导入失败：{}", "Import failed: {}", e)),
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 处理小说目录管理模式的键盘事件
+///
+/// 列表第一项为默认小说目录，不能移除；其余为偏好设置中的额外目录。
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `a`: 输入新目录，`Enter` 确认添加
+/// - `d`: 移除选中的额外目录（不删除文件）
+/// - `c`: 输入新的默认小说目录，`Tab` 切换是否移动原有文件，`Enter` 确认更换
+fn handle_library_dirs_key(app: &mut App, key: KeyCode) {
+    if let Some(input) = &mut app.settings.novels_dir_input {
+        match key {
+            KeyCode::Enter => {
+                let input = app.settings.novels_dir_input.take().unwrap_or_default();
+                match app.set_novels_dir(&input, app.settings.novels_dir_move) {
+                    Ok(moved) if app.settings.novels_dir_move => app.set_info(trf!(
+                        "已更换小说目录并移动 {} 个文件和文件夹，书架共 {} 本", "Changed the novel directory and moved {} files and folders; {} novels on the bookshelf",
+                        moved,
+                        app.novels.len()
+                    )),
+                    Ok(_) => {
+                        app.set_info(trf!("已更换小说目录，书架共 {} 本", "Changed the novel directory; {} novels on the bookshelf", app.novels.len()))
+                    }
+                    Err(e) => app.set_error(trf!("更换失败：{}", "Change failed: {}", e)),
+                }
+            }
+            KeyCode::Tab => app.settings.novels_dir_move = !app.settings.novels_dir_move,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return;
+    }
+    if let Some(input) = &mut app.settings.library_dir_input {
+        match key {
+            KeyCode::Enter => {
+                let input = app.settings.library_dir_input.take().unwrap_or_default();
+                match app.add_library_dir(&input) {
+                    Ok(()) => {
+                        app.settings.selected_library_dir_index =
+                            Some(app.preferences.library_dirs.len());
+                        app.set_info(trf!(
+                            "已添加小说目录，书架共 {} 本",
+                            "Added the novel directory; {} novels on the bookshelf",
+                            app.novels.len()
+                        ));
+                    }
+                    Err(e) => app.set_error(e),
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return;
+    }
+
+    // 默认目录加上额外目录
+    let dir_count = app.preferences.library_dirs.len() + 1;
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings.selected_library_dir_index =
+                navigate_list(app.settings.selected_library_dir_index, dir_count, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings.selected_library_dir_index =
+                navigate_list(app.settings.selected_library_dir_index, dir_count, false);
+        }
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            app.settings.library_dir_input = Some(String::new());
+        }
+        KeyCode::Char('c') | KeyCode::Char('C') => {
+            app.settings.novels_dir_input = Some(App::get_novels_dir().display().to_string());
+            app.settings.novels_dir_move = true;
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => match app.settings.selected_library_dir_index {
+            Some(0) => app.set_error(tr(
+                "默认小说目录不能移除",
+                "The default novel directory cannot be removed",
+            )),
+            Some(index) if index < dir_count => {
+                app.remove_library_dir(index - 1);
+                app.settings.selected_library_dir_index = Some(index - 1);
+                app.set_info(tr(
+                    "已移除小说目录（文件未删除）",
+                    "Removed the novel directory (files were kept)",
+                ));
+            }
+            _ => {}
+        },
         _ => {}
     }
 }
@@ -94,7 +432,7 @@ fn handle_settings_main_menu_key(app: &mut App, key: KeyCode) {
 ///
 /// - `Up`/`k`: 向上选择
 /// - `Down`/`j`: 向下选择
-/// - `d`: 删除选中的小说
+/// - `d`: 把选中的小说移到回收站
 fn handle_delete_novel_key(app: &mut App, key: KeyCode) {
     match key {
         KeyCode::Up | KeyCode::Char('k') => {
@@ -114,15 +452,113 @@ fn handle_delete_novel_key(app: &mut App, key: KeyCode) {
         KeyCode::Char('d') | KeyCode::Char('D') => {
             if let Some(index) = app.settings.selected_delete_novel_index
                 && index < app.novels.len()
-                && let Err(e) = app.delete_novel(index)
             {
-                app.set_error(format!("Failed to delete novel: {}", e));
+                let message = trf!(
+                    "把《{}》移到回收站？",
+                    "Move {} to the trash?",
+                    app.novels[index].title
+                );
+                let path = app.novels[index].path.clone();
+                app.request_confirm(message, ConfirmAction::DeleteNovel(path));
             }
         }
         _ => {}
     }
 }
 
+/// 处理回收站模式的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `r`: 恢复选中的小说
+/// - `d`: 彻底删除选中的小说
+fn handle_trash_key(app: &mut App, key: KeyCode) {
+    let count = app.settings.trash.entries.len();
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings.selected_trash_index =
+                navigate_list(app.settings.selected_trash_index, count, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings.selected_trash_index =
+                navigate_list(app.settings.selected_trash_index, count, false);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            let Some(index) = app.settings.selected_trash_index.filter(|&i| i < count) else {
+                return;
+            };
+            match app.restore_trashed_novel(index) {
+                Ok(path) => app.set_info(trf!("已恢复到 {}", "Restored to {}", path.display())),
+                Err(e) => app.set_error(trf!("恢复失败：{}", "Restore failed: {}", e)),
+            }
+        }
+        KeyCode::Char('d') | KeyCode::Char('D') => {
+            let Some(index) = app.settings.selected_trash_index.filter(|&i| i < count) else {
+                return;
+            };
+            let message = trf!(
+                "彻底删除《{}》？删除后无法恢复",
+                "Delete {} permanently? This cannot be undone",
+                app.settings.trash.entries[index].title()
+            );
+            app.request_confirm(message, ConfirmAction::PurgeTrashed(index));
+        }
+        _ => {}
+    }
+}
+
+/// 处理进度备份页面的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - `Up`/`k`: 向上选择
+/// - `Down`/`j`: 向下选择
+/// - `r`/`Enter`: 确认后用选中的备份替换当前进度
+fn handle_backups_key(app: &mut App, key: KeyCode) {
+    let count = app.settings.backups.len();
+    match key {
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.settings.selected_backup_index =
+                navigate_list(app.settings.selected_backup_index, count, true);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.settings.selected_backup_index =
+                navigate_list(app.settings.selected_backup_index, count, false);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') | KeyCode::Enter => {
+            let Some(index) = app.settings.selected_backup_index.filter(|&i| i < count) else {
+                return;
+            };
+            let Some(titles) = &app.settings.backups[index].titles else {
+                app.set_error(tr(
+                    "备份文件已损坏，无法恢复",
+                    "The backup file is corrupted and cannot be restored",
+                ));
+                return;
+            };
+            let message = trf!(
+                "用这个备份（{} 本小说）替换当前的阅读进度和书签？",
+                "Replace the current reading progress and bookmarks with this backup ({} novels)?",
+                titles.len()
+            );
+            app.request_confirm(message, ConfirmAction::RestoreBackup(index));
+        }
+        _ => {}
+    }
+}
+
 /// 处理删除孤立记录模式的键盘事件
 ///
 /// # Arguments
@@ -135,7 +571,30 @@ fn handle_delete_novel_key(app: &mut App, key: KeyCode) {
 /// - `Up`/`k`: 向上选择
 /// - `Down`/`j`: 向下选择
 /// - `d`: 删除选中的孤立记录
+/// - `r`: 输入文件移动后的新路径，把记录（进度、书签等）重新关联到新文件；
+///   书架上有同名文件时自动填入该文件的路径
 fn handle_delete_orphaned_key(app: &mut App, key: KeyCode) {
+    if let Some(input) = &mut app.settings.relocate_input {
+        match key {
+            KeyCode::Enter => {
+                let input = app.settings.relocate_input.take().unwrap_or_default();
+                let Some(index) = app.settings.selected_orphaned_index else {
+                    return;
+                };
+                match app.relocate_orphaned_novel(index, &input) {
+                    Ok(path) => app.set_info(trf!("已关联到 {}", "Relinked to {}", path.display())),
+                    Err(e) => app.set_error(trf!("关联失败：{}", "Relink failed: {}", e)),
+                }
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return;
+    }
+
     match key {
         KeyCode::Up | KeyCode::Char('k') => {
             app.settings.selected_orphaned_index = navigate_list(
@@ -155,17 +614,20 @@ fn handle_delete_orphaned_key(app: &mut App, key: KeyCode) {
             if let Some(index) = app.settings.selected_orphaned_index
                 && index < app.settings.orphaned_novels.len()
             {
-                let orphaned_novel = &app.settings.orphaned_novels[index];
-                app.library.novels.retain(|n| n.path != orphaned_novel.path);
-                if let Err(e) = app.library.save() {
-                    app.set_error(format!("Failed to save: {}", e));
-                }
-                app.detect_orphaned_novels();
-
-                if !app.settings.orphaned_novels.is_empty() {
-                    let new_index = index.min(app.settings.orphaned_novels.len() - 1);
-                    app.settings.selected_orphaned_index = Some(new_index);
-                }
+                let message = trf!(
+                    "删除《{}》的孤立记录？阅读进度和书签将一并删除",
+                    "Delete the orphaned record of {}? Its reading progress and bookmarks will be deleted too",
+                    app.settings.orphaned_novels[index].title
+                );
+                let path = app.settings.orphaned_novels[index].path.clone();
+                app.request_confirm(message, ConfirmAction::DeleteOrphaned(path));
+            }
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            if let Some(index) = app.settings.selected_orphaned_index
+                && let Some(candidate) = app.relocate_candidate(index)
+            {
+                app.settings.relocate_input = Some(candidate.display().to_string());
             }
         }
         _ => {}
@@ -221,7 +683,7 @@ fn handle_webdav_config_key(app: &mut App, key: KeyCode) {
             KeyCode::Up if config_state.selected_field > 0 => {
                 config_state.selected_field -= 1;
             }
-            KeyCode::Down if config_state.selected_field < 4 => {
+            KeyCode::Down if config_state.selected_field < 5 => {
                 config_state.selected_field += 1;
             }
             KeyCode::Char('p') | KeyCode::Char('P') => {
@@ -234,6 +696,10 @@ fn handle_webdav_config_key(app: &mut App, key: KeyCode) {
                 1..=4 => {
                     config_state.edit_mode = true;
                 }
+                5 => {
+                    config_state.temp_config.bookmark_merge =
+                        config_state.temp_config.bookmark_merge.next();
+                }
                 _ => {}
             },
             KeyCode::Char('s') | KeyCode::Char('S') => {
@@ -0,0 +1,48 @@
+use crate::app::{App, SetupStep};
+use crate::i18n::trf;
+use crossterm::event::KeyCode;
+
+/// 处理首次启动向导的键盘事件
+///
+/// # Arguments
+///
+/// * `app` - 应用实例的可变引用
+/// * `key` - 按下的键位代码
+///
+/// # Behavior
+///
+/// - 小说目录：输入目录，`Enter` 确认（不存在时创建）并进入下一步
+/// - 导入小说：输入文件路径，`Enter` 复制到小说目录；留空按 `Enter` 进入下一步
+/// - WebDAV 同步：`w` 打开 WebDAV 配置，`Enter` 完成向导回到书架
+/// - `Tab`: 切换界面语言
+/// - `Esc`（在 `handle_back` 中处理）：跳过向导
+pub(super) fn handle_setup_key(app: &mut App, key: KeyCode) {
+    if key == KeyCode::Tab {
+        app.toggle_language();
+        return;
+    }
+    match app.setup.step {
+        SetupStep::NovelsDir | SetupStep::Import => match key {
+            KeyCode::Enter => {
+                let result = if app.setup.step == SetupStep::NovelsDir {
+                    app.setup_confirm_novels_dir()
+                } else {
+                    app.setup_import()
+                };
+                if let Err(e) = result {
+                    app.set_error(trf!("操作失败：{}", "Failed: {}", e));
+                }
+            }
+            KeyCode::Backspace => {
+                app.setup.input.pop();
+            }
+            KeyCode::Char(c) => app.setup.input.push(c),
+            _ => {}
+        },
+        SetupStep::WebDav => match key {
+            KeyCode::Char('w') | KeyCode::Char('W') => app.finish_setup(true),
+            KeyCode::Enter => app.finish_setup(false),
+            _ => {}
+        },
+    }
+}
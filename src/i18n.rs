@@ -0,0 +1,65 @@
+//! 界面语言：界面文字在调用处同时写出中文和英文，按当前语言选用其一
+
+use crate::model::preferences::Language;
+
+#[cfg(not(test))]
+static LANGUAGE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+    /// 测试并行运行，每个线程单独设置语言，互不影响
+    static LANGUAGE: std::cell::Cell<Language> = const { std::cell::Cell::new(Language::Chinese) };
+}
+
+/// 当前界面语言
+pub fn language() -> Language {
+    #[cfg(not(test))]
+    {
+        if LANGUAGE.load(std::sync::atomic::Ordering::Relaxed) {
+            Language::English
+        } else {
+            Language::Chinese
+        }
+    }
+
+    #[cfg(test)]
+    {
+        LANGUAGE.with(|language| language.get())
+    }
+}
+
+/// 切换界面语言，之后渲染的文字立即使用新语言
+pub fn set_language(language: Language) {
+    #[cfg(not(test))]
+    LANGUAGE.store(
+        language == Language::English,
+        std::sync::atomic::Ordering::Relaxed,
+    );
+
+    #[cfg(test)]
+    LANGUAGE.with(|current| current.set(language));
+}
+
+/// 按当前界面语言选用中文或英文文字
+pub fn tr(zh: &'static str, en: &'static str) -> &'static str {
+    match language() {
+        Language::Chinese => zh,
+        Language::English => en,
+    }
+}
+
+/// 按当前界面语言选用中文或英文格式串并格式化，参数与 `format!` 相同
+///
+/// ```ignore
+/// trf!("共 {} 本", "{} books", count)
+/// ```
+macro_rules! trf {
+    ($zh:literal, $en:literal $(, $arg:expr)* $(,)?) => {
+        match $crate::i18n::language() {
+            $crate::model::preferences::Language::Chinese => format!($zh $(, $arg)*),
+            $crate::model::preferences::Language::English => format!($en $(, $arg)*),
+        }
+    };
+}
+
+pub(crate) use trf;
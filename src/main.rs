@@ -1,16 +1,18 @@
 mod app;
 mod config;
 mod event;
+mod i18n;
 mod model;
 mod state;
 mod sync;
 mod ui;
 
 use anyhow::{Context, Result};
-use clap::Command;
+use clap::{Arg, ArgAction, Command};
 use crossterm::ExecutableCommand;
 use crossterm::event::{
     self as crossterm_event, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind,
+    MouseEventKind,
 };
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
@@ -20,56 +22,132 @@ use std::io::{Stdout, stdout};
 use std::time::{Duration, Instant};
 
 use crate::app::App;
+use crate::i18n::tr;
 
 /// 终端守卫，确保程序退出时（包括 panic）正确恢复终端状态
 struct TerminalGuard {
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    /// 终端当前是否在捕获鼠标事件
+    mouse_capture: bool,
 }
 
 impl TerminalGuard {
-    fn new() -> Result<Self> {
+    fn new(mouse_capture: bool) -> Result<Self> {
         enable_raw_mode()?;
-        stdout()
-            .execute(EnterAlternateScreen)?
-            .execute(EnableMouseCapture)?;
+        stdout().execute(EnterAlternateScreen)?;
         let terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
-        Ok(Self { terminal })
+        let mut guard = Self {
+            terminal,
+            mouse_capture: false,
+        };
+        guard.set_mouse_capture(mouse_capture)?;
+        Ok(guard)
+    }
+
+    /// 开启或关闭鼠标捕获，与当前状态相同时不做任何事
+    fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        if enabled != self.mouse_capture {
+            if enabled {
+                stdout().execute(EnableMouseCapture)?;
+            } else {
+                stdout().execute(DisableMouseCapture)?;
+            }
+            self.mouse_capture = enabled;
+        }
+        Ok(())
     }
 }
 
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
         let _ = disable_raw_mode();
-        let _ = stdout().execute(DisableMouseCapture);
+        if self.mouse_capture {
+            let _ = stdout().execute(DisableMouseCapture);
+        }
         let _ = stdout().execute(LeaveAlternateScreen);
     }
 }
 
 fn main() -> Result<()> {
-    Command::new("fish_reader")
+    let matches = Command::new("fish_reader")
         .version(env!("CARGO_PKG_VERSION"))
         .author("haukuen")
         .about("A terminal-based novel reader with bookshelf management")
+        .arg(
+            Arg::new("continue")
+                .short('c')
+                .long("continue")
+                .action(ArgAction::SetTrue)
+                .help("Reopen the most recently read novel at its saved position"),
+        )
+        .arg(
+            Arg::new("no-mouse")
+                .long("no-mouse")
+                .action(ArgAction::SetTrue)
+                .help("Start without mouse capture so the terminal can select and copy text"),
+        )
         .get_matches();
 
-    let mut app = App::new().context("创建应用失败")?;
-    run(&mut app).context("运行应用失败")?;
+    // 配置决定数据目录等，必须在创建应用之前读取；
+    // 界面语言保存在数据目录的偏好设置中，创建应用后才能生成错误说明
+    let config_errors = config::init();
+    let mut app = App::new().context(tr("创建应用失败", "Failed to create the application"))?;
+    if !config_errors.is_empty() {
+        let messages: Vec<String> = config_errors.iter().map(|e| e.message()).collect();
+        app.set_error(messages.join(tr("；", "; ")));
+    }
+    if matches.get_flag("no-mouse") {
+        app.mouse_capture = false;
+    }
+    if matches.get_flag("continue") {
+        app.continue_reading();
+    }
+    run(&mut app).context(tr("运行应用失败", "Failed to run the application"))?;
 
     Ok(())
 }
 
 fn run(app: &mut App) -> Result<()> {
-    let mut guard = TerminalGuard::new()?;
+    let mut guard = TerminalGuard::new(app.mouse_capture)?;
 
-    let tick_rate = Duration::from_millis(100);
     let mut last_tick = Instant::now();
+    let mut last_draw: Option<Instant> = None;
+    let mut needs_redraw = true;
+    let mut was_busy = false;
 
+    // 没有输入时 poll 也会在一个 tick 后超时，从而处理后台任务；
+    // 空闲时按 idle_redraw_ms 定期重绘（状态栏时钟依赖于此）
     while !app.should_quit {
+        let performance = config::config().performance.clone();
+        let tick_rate = Duration::from_millis(performance.tick_ms);
+        let idle_redraw = Duration::from_millis(performance.idle_redraw_ms);
+
         app.poll_sync_status();
+        app.poll_search();
+        app.poll_chapter_parse();
+        app.poll_novel_watcher();
+        app.poll_config_watcher();
+        event::handle_tick(app);
+        guard.set_mouse_capture(app.mouse_capture)?;
         let size = guard.terminal.size()?;
-        app.terminal_size = Rect::new(0, 0, size.width, size.height);
+        let terminal_size = Rect::new(0, 0, size.width, size.height);
+        if terminal_size != app.terminal_size {
+            app.terminal_size = terminal_size;
+            needs_redraw = true;
+        }
 
-        guard.terminal.draw(|f| ui::render(f, app))?;
+        // 后台任务结束的那次循环也要重绘，以显示最终结果
+        let busy = app.has_background_activity();
+        if needs_redraw
+            || busy
+            || was_busy
+            || last_draw.is_none_or(|at| at.elapsed() >= idle_redraw)
+        {
+            guard.terminal.draw(|f| ui::render(f, app))?;
+            last_draw = Some(Instant::now());
+            needs_redraw = false;
+        }
+        was_busy = busy;
 
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
@@ -78,11 +156,15 @@ fn run(app: &mut App) -> Result<()> {
         if crossterm_event::poll(timeout)? {
             match crossterm_event::read()? {
                 Event::Key(key) if key.kind == KeyEventKind::Press => {
-                    event::handle_key(app, key.code);
+                    event::handle_key(app, key.code, key.modifiers);
+                    needs_redraw = true;
                 }
-                Event::Mouse(mouse) => {
+                // 鼠标移动不改变界面，不必重绘
+                Event::Mouse(mouse) if mouse.kind != MouseEventKind::Moved => {
                     event::handle_mouse(app, mouse);
+                    needs_redraw = true;
                 }
+                Event::Resize(..) | Event::FocusGained => needs_redraw = true,
                 _ => {}
             }
         }
@@ -92,13 +174,8 @@ fn run(app: &mut App) -> Result<()> {
         }
     }
 
-    if let Some(novel) = &app.current_novel {
-        app.library
-            .update_novel_progress(&novel.path, novel.progress.clone());
-    }
-    if let Err(e) = app.library.save() {
-        eprintln!("Failed to save progress: {}", e);
-    }
+    // 与切换小说时相同，保存前同步页首的字符偏移
+    app.save_current_progress();
 
     Ok(())
 }
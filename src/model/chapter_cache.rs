@@ -0,0 +1,221 @@
+//! 章节目录缓存：避免每次打开小说都重新解析整本书
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use super::chapter_parser;
+use super::library::Library;
+use super::novel::Chapter;
+
+/// 一本小说的章节缓存
+///
+/// 文件大小、修改时间、自定义章节正则、自动识别开关或解析规则版本任一变化都会使缓存失效。
+#[derive(Debug, Serialize, Deserialize)]
+struct ChapterCache {
+    /// 文件大小（字节）
+    size: u64,
+    /// 文件修改时间（自 UNIX 纪元起的纳秒数）
+    modified: u128,
+    /// 解析时使用的自定义章节正则
+    patterns: Vec<String>,
+    /// 解析时是否开启了内置规则自动识别
+    auto_detect: bool,
+    /// 解析规则版本，旧版本写入的缓存没有此字段，读作 0
+    #[serde(default)]
+    parser_version: u32,
+    chapters: Vec<Chapter>,
+}
+
+/// 缓存目录与进度文件位于同一目录（测试环境下同样被隔离）
+fn cache_dir() -> PathBuf {
+    Library::get_progress_path().with_file_name("cache")
+}
+
+/// 缓存文件名取小说路径的 CRC32，避免路径中的特殊字符
+fn cache_path(novel_path: &Path) -> PathBuf {
+    let hash = crc32fast::hash(novel_path.to_string_lossy().as_bytes());
+    cache_dir().join(format!("chapters-{:08x}.json", hash))
+}
+
+/// 读取文件的大小和修改时间
+fn file_stamp(novel_path: &Path) -> Option<(u64, u128)> {
+    let metadata = std::fs::metadata(novel_path).ok()?;
+    let modified = metadata
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_nanos();
+    Some((metadata.len(), modified))
+}
+
+/// 读取缓存的章节目录，缓存不存在或已失效时解析并写入缓存
+///
+/// # Arguments
+///
+/// * `novel_path` - 小说文件路径，用于定位缓存和判断文件是否变化
+/// * `lines` - 小说的所有行
+/// * `patterns` - 用户配置的章节标题正则
+/// * `auto_detect` - 是否使用内置规则自动识别
+///
+/// # Returns
+///
+/// 章节列表。缓存写入失败不影响结果。
+pub fn load_or_parse(
+    novel_path: &Path,
+    lines: &[String],
+    patterns: &[Regex],
+    auto_detect: bool,
+) -> Vec<Chapter> {
+    let pattern_sources: Vec<String> = patterns.iter().map(|re| re.as_str().to_string()).collect();
+    let stamp = file_stamp(novel_path);
+    let path = cache_path(novel_path);
+
+    if let Some(cache) = read_valid(novel_path)
+        && cache.patterns == pattern_sources
+        && cache.auto_detect == auto_detect
+    {
+        return cache.chapters;
+    }
+
+    let chapters = chapter_parser::parse(lines, patterns, auto_detect);
+    if let Some((size, modified)) = stamp {
+        let cache = ChapterCache {
+            size,
+            modified,
+            patterns: pattern_sources,
+            auto_detect,
+            parser_version: chapter_parser::PARSER_VERSION,
+            chapters,
+        };
+        // 写入失败只是下次需要重新解析，不打扰用户
+        let _ = store(&path, &cache);
+        return cache.chapters;
+    }
+    chapters
+}
+
+/// 只读取缓存的章节目录，不解析小说
+///
+/// 不检查章节正则，仅用于书架等不需要精确目录的场合。
+///
+/// # Returns
+///
+/// 缓存不存在或文件已变化时返回 `None`。
+pub fn load_cached(novel_path: &Path) -> Option<Vec<Chapter>> {
+    read_valid(novel_path).map(|cache| cache.chapters)
+}
+
+/// 小说改名或移动后把缓存一并改名（改名不改变文件大小和修改时间，缓存仍然有效）
+pub fn rename(old_path: &Path, new_path: &Path) {
+    let _ = std::fs::rename(cache_path(old_path), cache_path(new_path));
+}
+
+/// 删除小说的缓存，小说移到回收站、被合并或记录被删除时调用
+pub fn remove(novel_path: &Path) {
+    let _ = std::fs::remove_file(cache_path(novel_path));
+}
+
+/// 读取与小说文件当前大小、修改时间及当前解析规则版本一致的缓存
+fn read_valid(novel_path: &Path) -> Option<ChapterCache> {
+    let (size, modified) = file_stamp(novel_path)?;
+    let content = std::fs::read_to_string(cache_path(novel_path)).ok()?;
+    let cache = serde_json::from_str::<ChapterCache>(&content).ok()?;
+    (cache.size == size
+        && cache.modified == modified
+        && cache.parser_version == chapter_parser::PARSER_VERSION)
+        .then_some(cache)
+}
+
+fn store(path: &Path, cache: &ChapterCache) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string(cache)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_cache_is_reused_until_file_changes() {
+        let dir = tempdir().unwrap();
+        let novel_path = dir.path().join("cached.txt");
+        std::fs::write(&novel_path, "第一章 起\n正文").unwrap();
+        let lines = vec!["第一章 起".to_string(), "正文".to_string()];
+
+        let chapters = load_or_parse(&novel_path, &lines, &[], true);
+        assert_eq!(chapters.len(), 1);
+        assert!(cache_path(&novel_path).exists());
+
+        // 内容未变时直接读取缓存（传入不同的行以验证没有重新解析）
+        let other_lines = vec!["第一章 起".to_string(), "第二章 承".to_string()];
+        assert_eq!(load_or_parse(&novel_path, &other_lines, &[], true).len(), 1);
+
+        // 自定义正则变化时缓存失效
+        let patterns = vec![Regex::new("^正文$").unwrap()];
+        assert_eq!(load_or_parse(&novel_path, &lines, &patterns, true).len(), 2);
+        assert_eq!(
+            load_cached(&novel_path).map(|chapters| chapters.len()),
+            Some(2)
+        );
+
+        // 文件变化后重新解析
+        // 关闭自动识别时缓存失效
+        assert_eq!(
+            load_or_parse(&novel_path, &lines, &[], false)[0].title,
+            "全文"
+        );
+
+        assert!(load_cached(&novel_path).is_some());
+        std::fs::write(&novel_path, "第一章 起\n第二章 承\n").unwrap();
+        assert!(load_cached(&novel_path).is_none());
+        assert_eq!(load_or_parse(&novel_path, &other_lines, &[], true).len(), 2);
+    }
+
+    #[test]
+    fn test_cache_follows_renamed_novel_and_is_removed() {
+        let dir = tempdir().unwrap();
+        let old_path = dir.path().join("旧名.txt");
+        let new_path = dir.path().join("新名.txt");
+        std::fs::write(&old_path, "第一章 起\n正文").unwrap();
+        let lines = vec!["第一章 起".to_string(), "正文".to_string()];
+        load_or_parse(&old_path, &lines, &[], true);
+
+        std::fs::rename(&old_path, &new_path).unwrap();
+        rename(&old_path, &new_path);
+        assert!(!cache_path(&old_path).exists());
+        assert_eq!(
+            load_cached(&new_path).map(|chapters| chapters.len()),
+            Some(1)
+        );
+
+        remove(&new_path);
+        assert!(!cache_path(&new_path).exists());
+    }
+
+    #[test]
+    fn test_cache_from_older_parser_is_ignored() {
+        let dir = tempdir().unwrap();
+        let novel_path = dir.path().join("old.txt");
+        std::fs::write(&novel_path, "第一章 起\n第二章 承").unwrap();
+        let lines = vec!["第一章 起".to_string(), "第二章 承".to_string()];
+        load_or_parse(&novel_path, &lines, &[], true);
+
+        // 模拟旧版本解析规则写入的缓存（没有版本字段，目录也不同）
+        let path = cache_path(&novel_path);
+        let mut cache: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let cache = cache.as_object_mut().unwrap();
+        cache.remove("parser_version");
+        cache["chapters"].as_array_mut().unwrap().pop();
+        std::fs::write(&path, serde_json::to_string(cache).unwrap()).unwrap();
+
+        assert!(load_cached(&novel_path).is_none());
+        assert_eq!(load_or_parse(&novel_path, &lines, &[], true).len(), 2);
+    }
+}
@@ -1,4 +1,17 @@
 use crate::model::novel::Chapter;
+use regex::Regex;
+
+/// 解析规则的版本号，写入章节缓存；修改规则使解析结果变化时必须加一，让旧缓存失效
+pub const PARSER_VERSION: u32 = 2;
+
+/// 强候选标题的最大字符数，更长的行视为正文（如正文中以“第一章”开头的句子）
+const MAX_STRONG_TITLE_CHARS: usize = 40;
+
+/// 编号序列的第一项与前一章之间至少相隔的行数，更近的可能是该章正文中的列表
+const MIN_CHAPTER_SPACING: usize = 3;
+
+/// 紧跟在标题后的编号序列中，相邻两项至少相隔此行数才视为章节（标题只是书名等），否则视为列表
+const MIN_RUN_CHAPTER_LINES: usize = 10;
 
 /// 候选类型：用于同类编号的分组评分
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,7 +52,7 @@ fn is_excluded(line: &str) -> bool {
 /// 强候选：匹配后立即作为章节标题，无需评分
 fn try_strong_candidate(line: &str) -> Option<String> {
     let line = line.trim();
-    if line.is_empty() {
+    if line.is_empty() || line.chars().count() > MAX_STRONG_TITLE_CHARS {
         return None;
     }
 
@@ -60,7 +73,12 @@ fn try_strong_candidate(line: &str) -> Option<String> {
     }
 
     // 2. English "Chapter X" 格式
-    if line.to_lowercase().starts_with("chapter") {
+    //    "chapter" 后必须是空白，且不以句末标点结尾，避免正文中提到 chapter 的句子被误匹配
+    let lower = line.to_lowercase();
+    if let Some(rest) = lower.strip_prefix("chapter")
+        && rest.starts_with(char::is_whitespace)
+        && !line.chars().last().is_some_and(is_sentence_punctuation)
+    {
         return Some(line.to_string());
     }
 
@@ -87,6 +105,21 @@ fn try_strong_candidate(line: &str) -> Option<String> {
     None
 }
 
+/// 判断章节标题是否为卷/部级标题（“第X卷”、“第X部”、“卷X”），在目录中作为父节点
+pub fn is_volume_title(title: &str) -> bool {
+    let title = title.trim();
+    if let Some(rest) = title.strip_prefix("第")
+        && let Some(keyword_pos) = rest.find(['卷', '部'])
+    {
+        // “第二部分”之类是普通章节
+        let after = &rest[keyword_pos + '卷'.len_utf8()..];
+        return is_chapter_number(&rest[..keyword_pos]) && !after.starts_with('分');
+    }
+    title.strip_prefix("卷").is_some_and(|rest| {
+        rest.starts_with(|c: char| c.is_ascii_digit()) || is_chinese_number_start(rest)
+    })
+}
+
 /// 判断字符是否可能是中文数字的开头
 fn is_chinese_number_start(s: &str) -> bool {
     let chinese_numbers = [
@@ -457,6 +490,11 @@ fn score_weak_candidate(
         score -= 2;
     }
 
+    // 11. 全文没有编号相邻（±1）的同类候选，编号不连续: -4
+    if !has_adjacent_number(candidate, &same_type) {
+        score -= 4;
+    }
+
     score
 }
 
@@ -562,6 +600,13 @@ fn has_close_sequential_sibling(candidate: &WeakCandidate, same_type: &[&WeakCan
     false
 }
 
+/// 是否存在编号与候选相差 1 的同类候选（真正的章节编号是连续的）
+fn has_adjacent_number(candidate: &WeakCandidate, same_type: &[&WeakCandidate]) -> bool {
+    same_type
+        .iter()
+        .any(|c| c.line_num != candidate.line_num && c.number.abs_diff(candidate.number) == 1)
+}
+
 /// 检查紧邻的前后行是否有不同类型的弱候选（无空行间隔）
 fn has_different_type_neighbor(
     candidate: &WeakCandidate,
@@ -591,6 +636,39 @@ fn has_different_type_neighbor(
     false
 }
 
+/// 把通过评分的弱候选按编号序列分组：同类且编号比该类上一项大 1 的接在同一序列后面
+///
+/// 返回的序列按第一项的行号排列。
+fn numbering_runs<'a>(candidates: &[&'a WeakCandidate]) -> Vec<Vec<&'a WeakCandidate>> {
+    let mut runs: Vec<Vec<&WeakCandidate>> = Vec::new();
+    for &candidate in candidates {
+        let same_type = runs
+            .iter_mut()
+            .rev()
+            .find(|run| run[0].candidate_type == candidate.candidate_type);
+        match same_type {
+            Some(run) if run[run.len() - 1].number + 1 == candidate.number => run.push(candidate),
+            _ => runs.push(vec![candidate]),
+        }
+    }
+    runs
+}
+
+/// 编号序列是否是前一章正文中的列表
+///
+/// 第一项距前一章（卷标题除外）不足 [`MIN_CHAPTER_SPACING`] 行，且序列只有一项或相邻两项
+/// 之间不足 [`MIN_RUN_CHAPTER_LINES`] 行时成立。整个序列一起判断，避免只丢掉真正的第一章。
+fn is_list_under(run: &[&WeakCandidate], prev: Option<&Chapter>) -> bool {
+    let close_to_prev = prev.is_some_and(|prev| {
+        run[0].line_num - prev.start_line < MIN_CHAPTER_SPACING && !is_volume_title(&prev.title)
+    });
+    let spaced_like_chapters = run.len() > 1
+        && run
+            .windows(2)
+            .all(|pair| pair[1].line_num - pair[0].line_num >= MIN_RUN_CHAPTER_LINES);
+    close_to_prev && !spaced_like_chapters
+}
+
 /// 检查两个行号之间是否存在空行（不含起点和终点行）
 fn has_empty_line_between(lines: &[String], from: usize, to: usize) -> bool {
     lines
@@ -604,11 +682,18 @@ fn has_empty_line_between(lines: &[String], from: usize, to: usize) -> bool {
 ///
 /// # Arguments
 /// * `lines` - 小说的所有行
+/// * `custom_patterns` - 用户配置的章节标题正则
+/// * `auto_detect` - 是否使用内置规则自动识别；为 `false` 时只按自定义正则识别
 ///
 /// # Returns
 /// 章节列表。空文件或无章节时返回单章 `[("全文", 0)]`。
 /// 第一个章节前有内容时自动生成 `("前言", 0)` 章。
-pub fn parse(lines: &[String]) -> Vec<Chapter> {
+///
+/// # Note
+///
+/// 匹配任一自定义正则的行（去掉首尾空白后）直接作为章节标题，优先于内置规则和排除规则。
+/// 紧跟在前一章标题后的弱候选编号序列视为该章中的列表，整体丢弃（见 [`is_list_under`]）。
+pub fn parse(lines: &[String], custom_patterns: &[Regex], auto_detect: bool) -> Vec<Chapter> {
     if lines.iter().all(|l| l.trim().is_empty()) {
         return vec![Chapter {
             title: "全文".to_string(),
@@ -625,7 +710,14 @@ pub fn parse(lines: &[String]) -> Vec<Chapter> {
         if trimmed.is_empty() {
             continue;
         }
-        if is_excluded(trimmed) {
+        if custom_patterns.iter().any(|re| re.is_match(trimmed)) {
+            strong_chapters.push(Chapter {
+                title: trimmed.to_string(),
+                start_line: line_num,
+            });
+            continue;
+        }
+        if !auto_detect || is_excluded(trimmed) {
             continue;
         }
 
@@ -645,24 +737,27 @@ pub fn parse(lines: &[String]) -> Vec<Chapter> {
     }
 
     // Phase 2: 弱候选评分
-    let mut all_chapters: Vec<Chapter> = Vec::new();
-
-    // 添加强候选
-    all_chapters.extend(strong_chapters);
-
-    // 添加通过评分的弱候选
-    for candidate in &weak_candidates {
-        let score = score_weak_candidate(candidate, lines, &weak_candidates);
-        if score >= 2 {
-            all_chapters.push(Chapter {
-                title: candidate.title.clone(),
-                start_line: candidate.line_num,
-            });
+    let passed: Vec<&WeakCandidate> = weak_candidates
+        .iter()
+        .filter(|candidate| score_weak_candidate(candidate, lines, &weak_candidates) >= 2)
+        .collect();
+
+    // Phase 3: 按编号序列合并，丢弃紧跟在前一章标题后的列表
+    let mut all_chapters = strong_chapters;
+    for run in numbering_runs(&passed) {
+        let prev = all_chapters
+            .iter()
+            .filter(|chapter| chapter.start_line < run[0].line_num)
+            .max_by_key(|chapter| chapter.start_line);
+        if is_list_under(&run, prev) {
+            continue;
         }
+        all_chapters.extend(run.iter().map(|candidate| Chapter {
+            title: candidate.title.clone(),
+            start_line: candidate.line_num,
+        }));
     }
-
-    // 按行号排序
-    all_chapters.sort_by_key(|ch| ch.start_line);
+    all_chapters.sort_by_key(|chapter| chapter.start_line);
 
     // 如果没有章节，返回单章"全文"
     if all_chapters.is_empty() {
@@ -824,7 +919,7 @@ mod tests {
     #[test]
     fn test_empty_file() {
         let lines = lines_from("");
-        let chapters = parse(&lines);
+        let chapters = parse(&lines, &[], true);
         assert_eq!(chapters.len(), 1);
         assert_eq!(chapters[0].title, "全文");
         assert_eq!(chapters[0].start_line, 0);
@@ -833,7 +928,7 @@ mod tests {
     #[test]
     fn test_no_chapters() {
         let lines = lines_from("这是一段普通的文字。\n没有章节标题。\n只是正文。");
-        let chapters = parse(&lines);
+        let chapters = parse(&lines, &[], true);
         assert_eq!(chapters.len(), 1);
         assert_eq!(chapters[0].title, "全文");
     }
@@ -845,7 +940,7 @@ mod tests {
 正文内容...
 第二章 归来
 正文内容...";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 2);
         assert_eq!(chapters[0].title, "第一章 雨夜");
         assert_eq!(chapters[1].title, "第二章 归来");
@@ -860,7 +955,7 @@ mod tests {
 2、不要出声。
 3、天亮之前不要开门。
 第二章 后续";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 2, "应该只有两章，列表不应被切章");
         assert_eq!(chapters[0].title, "第一章 开始");
         assert_eq!(chapters[1].title, "第二章 后续");
@@ -880,7 +975,7 @@ mod tests {
 3、旧事
 
 正文内容...";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 3, "三个数字章节应该被识别");
         assert_eq!(chapters[0].title, "1、雨夜");
         assert_eq!(chapters[1].title, "2、归来");
@@ -898,7 +993,7 @@ mod tests {
 三、其他物品
 1.一支羽毛笔
 第二章 后续";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 2, "嵌套列表不应被切章");
         assert_eq!(chapters[0].title, "第一章 开始");
         assert_eq!(chapters[1].title, "第二章 后续");
@@ -907,7 +1002,7 @@ mod tests {
     #[test]
     fn test_excluded_patterns() {
         let text = "正文完\n后面还有内容";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 1);
         assert_eq!(chapters[0].title, "全文");
     }
@@ -915,7 +1010,7 @@ mod tests {
     #[test]
     fn test_first_class_excluded() {
         let text = "第一节课 数学\n第一章 真正的开始";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 1);
         assert_eq!(chapters[0].title, "第一章 真正的开始");
     }
@@ -923,7 +1018,7 @@ mod tests {
     #[test]
     fn test_preface_auto_generation() {
         let text = "这是前言内容\n\n第一章 开始\n正文";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(chapters.len(), 2);
         assert_eq!(chapters[0].title, "前言");
         assert_eq!(chapters[0].start_line, 0);
@@ -949,7 +1044,7 @@ mod tests {
 更多正文
 
 第二章 继续";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         assert_eq!(
             chapters.len(),
             3,
@@ -990,9 +1085,107 @@ mod tests {
 二、真正章节
 
 正文";
-        let chapters = parse(&lines_from(text));
+        let chapters = parse(&lines_from(text), &[], true);
         let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
         assert!(!titles.contains(&"一、\u{201c}第一章开始\u{201d}。"));
         assert!(titles.contains(&"二、真正章节"));
     }
+
+    #[test]
+    fn test_strong_false_positives_rejected() {
+        assert!(try_strong_candidate("Chapter 3 was the best part, he said.").is_none());
+        assert!(try_strong_candidate("Chapters of history").is_none());
+        let long_line = format!("第一章{}", "讲的是他如何离开家乡".repeat(5));
+        assert!(try_strong_candidate(&long_line).is_none());
+    }
+
+    #[test]
+    fn test_isolated_number_not_chapter() {
+        // 正文中孤立的 "三、" 编号没有前后连续的编号
+        let text = "\
+第一章 开始
+
+他说道：
+
+三、我们必须马上出发
+
+正文";
+        let chapters = parse(&lines_from(text), &[], true);
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(chapters[0].title, "第一章 开始");
+    }
+
+    #[test]
+    fn test_weak_candidate_too_close_to_previous_chapter() {
+        let text = "\
+第一章 开始
+
+1、其一
+
+正文
+
+2、其二
+
+正文";
+        let chapters = parse(&lines_from(text), &[], true);
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["第一章 开始"]);
+    }
+
+    #[test]
+    fn test_numbered_chapters_right_after_heading_are_kept() {
+        // 书名等标题后紧跟真正的编号章节，各章篇幅较长
+        let body = "正文\n".repeat(MIN_RUN_CHAPTER_LINES);
+        let text = format!("【序】\n1、雨夜\n{body}2、归来\n{body}3、旧事\n{body}");
+        let chapters = parse(&lines_from(&text), &[Regex::new("^【序】$").unwrap()], true);
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["【序】", "1、雨夜", "2、归来", "3、旧事"]);
+    }
+
+    #[test]
+    fn test_auto_detect_disabled_uses_only_custom_patterns() {
+        let lines = lines_from("第一章 开始\n正文\n【幕间】\n正文");
+        let chapters = parse(&lines, &[], false);
+        assert_eq!(chapters.len(), 1);
+        assert_eq!(chapters[0].title, "全文");
+
+        let patterns = vec![Regex::new("^【.+】$").unwrap()];
+        let chapters = parse(&lines, &patterns, false);
+        let titles: Vec<&str> = chapters.iter().map(|c| c.title.as_str()).collect();
+        assert_eq!(titles, vec!["前言", "【幕间】"]);
+    }
+
+    #[test]
+    fn test_custom_patterns_add_chapters() {
+        let lines: Vec<String> = [
+            "【第１章】雨夜",
+            "正文",
+            "Part One",
+            "正文",
+            "【第２章】晴天",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        assert_eq!(parse(&lines, &[], true).len(), 1);
+
+        let patterns = vec![
+            Regex::new("^【第.+章】").unwrap(),
+            Regex::new(r"^Part \w+$").unwrap(),
+        ];
+        let chapters = parse(&lines, &patterns, true);
+        let starts: Vec<usize> = chapters.iter().map(|c| c.start_line).collect();
+        assert_eq!(starts, vec![0, 2, 4]);
+        assert_eq!(chapters[1].title, "Part One");
+    }
+
+    #[test]
+    fn test_is_volume_title() {
+        assert!(is_volume_title("第一卷 风起"));
+        assert!(is_volume_title("第3部 终局"));
+        assert!(is_volume_title("卷五 长夜"));
+        assert!(!is_volume_title("第一章 雨夜"));
+        assert!(!is_volume_title("第一部分"));
+        assert!(!is_volume_title("卷帘"));
+    }
 }
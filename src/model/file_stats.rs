@@ -0,0 +1,90 @@
+//! 书籍详情中显示的文件统计：编码、行数和字数
+
+use std::path::Path;
+
+use crate::i18n::tr;
+
+/// 小说文件的编码和文本统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStats {
+    /// 识别出的文件编码，如 “UTF-8”、“GBK”
+    pub encoding: &'static str,
+    /// 总行数
+    pub lines: usize,
+    /// 总字数（不含空白字符，与章节字数的统计方式一致）
+    pub words: usize,
+}
+
+impl FileStats {
+    /// 读取文件并统计
+    ///
+    /// 依次按 BOM、UTF-8、GBK 识别编码，都不符合时按 UTF-8 容错解码后统计。
+    ///
+    /// # Errors
+    ///
+    /// 文件读取失败时返回 IO 错误。
+    pub fn read(path: &Path) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        let (encoding, text) = if let Some(rest) = bytes.strip_prefix(b"\xEF\xBB\xBF") {
+            ("UTF-8（BOM）", String::from_utf8_lossy(rest))
+        } else if bytes.starts_with(b"\xFF\xFE") || bytes.starts_with(b"\xFE\xFF") {
+            // decode 会按 BOM 选择 UTF-16LE/BE
+            let (text, encoding, _) = encoding_rs::UTF_16LE.decode(&bytes);
+            (encoding.name(), text)
+        } else if let Ok(text) = std::str::from_utf8(&bytes) {
+            ("UTF-8", text.into())
+        } else {
+            match encoding_rs::GBK.decode_without_bom_handling_and_without_replacement(&bytes) {
+                Some(text) => ("GBK", text),
+                None => (tr("未知", "unknown"), String::from_utf8_lossy(&bytes)),
+            }
+        };
+
+        Ok(Self {
+            encoding,
+            lines: text.lines().count(),
+            words: text.chars().filter(|c| !c.is_whitespace()).count(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_read_detects_encoding_and_counts() {
+        let dir = tempdir().unwrap();
+        let cases: [(&str, Vec<u8>, &str); 3] = [
+            (
+                "utf8.txt",
+                "第一章\n  你好 世界\n".as_bytes().to_vec(),
+                "UTF-8",
+            ),
+            (
+                "bom.txt",
+                [b"\xEF\xBB\xBF".as_slice(), "第一章\n你好\n".as_bytes()].concat(),
+                "UTF-8（BOM）",
+            ),
+            (
+                "gbk.txt",
+                encoding_rs::GBK.encode("第一章\n你好\n").0.into_owned(),
+                "GBK",
+            ),
+        ];
+        for (name, bytes, encoding) in cases {
+            let path = dir.path().join(name);
+            std::fs::write(&path, bytes).unwrap();
+            let stats = FileStats::read(&path).unwrap();
+            assert_eq!(stats.encoding, encoding, "{}", name);
+            assert_eq!(stats.lines, 2, "{}", name);
+            assert_eq!(
+                stats.words,
+                if name == "utf8.txt" { 7 } else { 5 },
+                "{}",
+                name
+            );
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use super::novel::ReadingProgress;
-use crate::config::CONFIG;
+use crate::config::{BACKUP_SUFFIX, PROGRESS_FILENAME, config};
+use crate::i18n::tr;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
@@ -22,6 +23,115 @@ pub struct NovelInfo {
     )]
     pub path: PathBuf,
     pub progress: ReadingProgress,
+    /// 最近一次保存进度的时间（Unix 秒），0 表示从未记录
+    #[serde(default)]
+    pub last_read: u64,
+    /// 最近一次打开时的总行数，用于在书架上按阅读进度排序；0 表示未知
+    #[serde(default)]
+    pub total_lines: usize,
+    /// 本书的章节识别设置
+    #[serde(default, skip_serializing_if = "ChapterSettings::is_default")]
+    pub chapter_settings: ChapterSettings,
+    /// 用户添加的标签，如 “玄幻”、“已完结”
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// 是否收藏：收藏的小说固定在书架最上方
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub favorite: bool,
+    /// 手动设置或读到结尾时自动设置的阅读状态，`None` 时按阅读记录推断
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<ReadingStatus>,
+    /// 用户写的备注，如 “读到比武大会，主角刚拿到剑”
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    /// 在待读队列中的位置（越小越先读），`None` 表示不在队列中
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue: Option<usize>,
+}
+
+/// 进度文件的一个自动备份
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackupInfo {
+    /// 备份文件路径
+    pub path: PathBuf,
+    /// 备份时间（Unix 秒）
+    pub timestamp: u64,
+    /// 备份中记录的小说书名，最近读过的在前；`None` 表示备份文件无法解析
+    pub titles: Option<Vec<String>>,
+    /// 备份中最近一次阅读的时间（Unix 秒），0 表示没有记录
+    pub last_read: u64,
+}
+
+/// 小说的阅读状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingStatus {
+    /// 未读
+    Unread,
+    /// 在读
+    Reading,
+    /// 读完
+    Finished,
+}
+
+impl ReadingStatus {
+    /// 切换到下一种状态
+    pub fn next(self) -> Self {
+        match self {
+            ReadingStatus::Unread => ReadingStatus::Reading,
+            ReadingStatus::Reading => ReadingStatus::Finished,
+            ReadingStatus::Finished => ReadingStatus::Unread,
+        }
+    }
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            ReadingStatus::Unread => tr("未读", "Unread"),
+            ReadingStatus::Reading => tr("在读", "Reading"),
+            ReadingStatus::Finished => tr("读完", "Finished"),
+        }
+    }
+}
+
+impl NovelInfo {
+    /// 阅读状态：没有设置过时，从未阅读的为未读，其余为在读
+    pub fn reading_status(&self) -> ReadingStatus {
+        self.status
+            .unwrap_or(if self.last_read == 0 && self.progress.scroll_offset == 0 {
+                ReadingStatus::Unread
+            } else {
+                ReadingStatus::Reading
+            })
+    }
+}
+
+/// 单本小说的章节识别设置
+///
+/// 只影响这一本书，格式特殊的小说不必修改全局规则。
+/// 手动编辑过的目录保存在 [`ReadingProgress::custom_chapters`]。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ChapterSettings {
+    /// 本书额外的章节标题正则，与全局规则合并
+    pub patterns: Vec<String>,
+    /// 是否使用内置规则自动识别；关闭后只按章节正则识别
+    pub auto_detect: bool,
+}
+
+impl Default for ChapterSettings {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            auto_detect: true,
+        }
+    }
+}
+
+impl ChapterSettings {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 fn serialize_novel_path<S>(path: &Path, serializer: S) -> Result<S::Ok, S::Error>
@@ -54,7 +164,7 @@ impl Library {
             .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
         path.push(format!(
             "{}_test_{}_{}",
-            CONFIG.dir_name,
+            config().dir_name,
             std::process::id(),
             thread_id
         ));
@@ -164,14 +274,14 @@ impl Library {
         {
             let mut path = Self::get_test_data_dir();
             let _ = std::fs::create_dir_all(&path);
-            path.push(CONFIG.progress_filename);
-            return path;
+            path.push(PROGRESS_FILENAME);
+            path
         }
 
         #[cfg(not(test))]
         {
             let mut path = home::home_dir().unwrap_or_else(|| PathBuf::from("."));
-            path.push(CONFIG.dir_name);
+            path.push(&config().dir_name);
 
             if !path.exists()
                 && let Err(e) = std::fs::create_dir_all(&path)
@@ -179,25 +289,26 @@ impl Library {
                 eprintln!("Failed to create directory: {}", e);
             }
 
-            path.push(CONFIG.progress_filename);
+            path.push(PROGRESS_FILENAME);
             path
         }
     }
 
-    fn get_novels_dir() -> PathBuf {
+    /// 获取默认小说目录（配置文件中的 `novels_dir`），不存在时自动创建
+    ///
+    /// 进度文件中该目录下的小说记录为 `novels/` 开头的相对路径，换目录或跨设备同步后仍能对应。
+    pub fn get_novels_dir() -> PathBuf {
         #[cfg(test)]
         {
             let mut path = Self::get_test_data_dir();
             path.push("novels");
             let _ = std::fs::create_dir_all(&path);
-            return path;
+            path
         }
 
         #[cfg(not(test))]
         {
-            let mut path = home::home_dir().unwrap_or_else(|| PathBuf::from("."));
-            path.push(CONFIG.dir_name);
-            path.push("novels");
+            let path = config().novels_dir();
             if !path.exists()
                 && let Err(e) = std::fs::create_dir_all(&path)
             {
@@ -218,17 +329,9 @@ impl Library {
             .as_secs();
 
         let period_timestamp =
-            timestamp / CONFIG.backup_timestamp_interval * CONFIG.backup_timestamp_interval;
+            timestamp / config().backup.interval_secs * config().backup.interval_secs;
 
-        let file_name = progress_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or(CONFIG.progress_filename);
-        let backup_name = format!(
-            "{}.{}.{}",
-            file_name, CONFIG.backup_suffix, period_timestamp
-        );
-        let backup_path = progress_path.with_file_name(backup_name);
+        let backup_path = Self::backup_path(progress_path, period_timestamp);
 
         if backup_path.exists() {
             return Ok(());
@@ -237,7 +340,7 @@ impl Library {
         std::fs::copy(progress_path, &backup_path)?;
 
         let cutoff_timestamp =
-            timestamp.saturating_sub(CONFIG.backup_retention_days * 24 * 60 * 60);
+            timestamp.saturating_sub(config().backup.retention_days * 24 * 60 * 60);
         if let Some(backup_dir) = progress_path.parent() {
             Self::cleanup_old_backups(backup_dir, cutoff_timestamp);
         }
@@ -246,28 +349,114 @@ impl Library {
     }
 
     fn cleanup_old_backups(backup_dir: &Path, cutoff_timestamp: u64) {
+        for (path, file_timestamp) in Self::backup_files(backup_dir) {
+            if file_timestamp < cutoff_timestamp {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+
+    /// 指定时间戳的备份文件路径
+    fn backup_path(progress_path: &Path, timestamp: u64) -> PathBuf {
+        let file_name = progress_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(PROGRESS_FILENAME);
+        progress_path.with_file_name(format!("{}.{}.{}", file_name, BACKUP_SUFFIX, timestamp))
+    }
+
+    /// 目录中的备份文件及其时间戳
+    fn backup_files(backup_dir: &Path) -> Vec<(PathBuf, u64)> {
         let Ok(entries) = std::fs::read_dir(backup_dir) else {
-            return;
+            return Vec::new();
         };
 
-        let backup_prefix = format!("{}.{}.", CONFIG.progress_filename, CONFIG.backup_suffix);
+        let backup_prefix = format!("{}.{}.", PROGRESS_FILENAME, BACKUP_SUFFIX);
 
-        for entry in entries.flatten() {
-            let path = entry.path();
-            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
-                continue;
-            };
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = path.file_name()?.to_str()?;
+                let timestamp = name.strip_prefix(&backup_prefix)?.parse::<u64>().ok()?;
+                Some((path, timestamp))
+            })
+            .collect()
+    }
 
-            if let Some(ts_str) = name.strip_prefix(&backup_prefix)
-                && let Ok(file_timestamp) = ts_str.parse::<u64>()
-                && file_timestamp < cutoff_timestamp
-            {
-                let _ = std::fs::remove_file(&path);
-            }
+    /// 列出进度文件的所有备份，最新的在前
+    ///
+    /// 每个备份都会读取一遍，用于预览其中的小说数和最近阅读时间。
+    pub fn list_backups() -> Vec<BackupInfo> {
+        let progress_path = Self::get_progress_path();
+        let Some(backup_dir) = progress_path.parent() else {
+            return Vec::new();
+        };
+        let mut backups: Vec<BackupInfo> = Self::backup_files(backup_dir)
+            .into_iter()
+            .map(|(path, timestamp)| {
+                let mut novels = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|content| serde_json::from_str::<Self>(&content).ok())
+                    .map(|library| library.novels);
+                if let Some(novels) = &mut novels {
+                    novels.sort_by_key(|novel| std::cmp::Reverse(novel.last_read));
+                }
+                BackupInfo {
+                    last_read: novels
+                        .as_ref()
+                        .and_then(|novels| novels.first())
+                        .map_or(0, |novel| novel.last_read),
+                    titles: novels
+                        .map(|novels| novels.into_iter().map(|novel| novel.title).collect()),
+                    path,
+                    timestamp,
+                }
+            })
+            .collect();
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.timestamp));
+        backups
+    }
+
+    /// 用备份替换当前的进度文件
+    ///
+    /// 替换前把当前的进度文件另存为一个新备份，恢复错了可以再恢复回来。
+    ///
+    /// # Arguments
+    ///
+    /// * `backup_path` - 备份文件路径
+    ///
+    /// # Returns
+    ///
+    /// 恢复后的图书馆数据。
+    ///
+    /// # Errors
+    ///
+    /// 备份文件无法读取或解析时返回错误，此时进度文件不变；保存失败时返回 IO 错误。
+    pub fn restore_backup(backup_path: &Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(backup_path)?;
+        let mut library: Self = serde_json::from_str(&content)?;
+        library.normalize_novel_paths();
+
+        let progress_path = Self::get_progress_path();
+        if progress_path.exists() {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            std::fs::copy(&progress_path, Self::backup_path(&progress_path, timestamp))?;
         }
+
+        library.save()?;
+        Ok(library)
     }
 
     fn novel_rel_path(path: &Path) -> Option<PathBuf> {
+        if let Ok(rel) = path.strip_prefix(Self::get_novels_dir())
+            && rel.components().next().is_some()
+        {
+            return Some(rel.to_path_buf());
+        }
         let raw = path.to_string_lossy();
         let parts: Vec<&str> = raw.split(['/', '\\']).filter(|p| !p.is_empty()).collect();
         let novels_idx = parts
@@ -307,7 +496,24 @@ impl Library {
         changed
     }
 
-    fn same_novel_path(a: &Path, b: &Path) -> bool {
+    /// 把位于 `old_dir` 中的小说记录改到 `new_dir` 中的相同位置（更换默认小说目录时调用）
+    ///
+    /// # Returns
+    ///
+    /// 改动的记录数。
+    pub fn relocate_novels(&mut self, old_dir: &Path, new_dir: &Path) -> usize {
+        let mut count = 0;
+        for novel in &mut self.novels {
+            if let Ok(rel) = novel.path.strip_prefix(old_dir) {
+                novel.path = new_dir.join(rel);
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// 判断两个路径是否指向同一本小说（兼容同步后的跨平台路径）
+    pub fn same_novel_path(a: &Path, b: &Path) -> bool {
         if a == b {
             return true;
         }
@@ -319,13 +525,17 @@ impl Library {
 
     /// 更新或添加小说的阅读进度
     ///
-    /// 如果小说已存在则更新进度，否则创建新条目。
+    /// 如果小说已存在则更新进度，否则创建新条目。同时把最近阅读时间记为当前时间。
     ///
     /// # Arguments
     ///
     /// * `novel_path` - 小说文件路径
     /// * `progress` - 阅读进度
     pub fn update_novel_progress(&mut self, novel_path: &Path, progress: ReadingProgress) {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
         if let Some(novel) = self
             .novels
             .iter_mut()
@@ -333,6 +543,7 @@ impl Library {
         {
             novel.progress = progress;
             novel.path = novel_path.to_path_buf();
+            novel.last_read = now;
         } else {
             let title = novel_path
                 .file_stem()
@@ -344,10 +555,253 @@ impl Library {
                 title,
                 path: novel_path.to_path_buf(),
                 progress,
+                last_read: now,
+                total_lines: 0,
+                chapter_settings: ChapterSettings::default(),
+                tags: Vec::new(),
+                favorite: false,
+                status: None,
+                note: String::new(),
+                queue: None,
             });
         }
     }
 
+    /// 获取小说的章节识别设置，没有记录时返回默认设置
+    pub fn chapter_settings(&self, novel_path: &Path) -> ChapterSettings {
+        self.novels
+            .iter()
+            .find(|n| Self::same_novel_path(&n.path, novel_path))
+            .map(|n| n.chapter_settings.clone())
+            .unwrap_or_default()
+    }
+
+    /// 获取小说章节识别设置的可变引用，没有记录时创建一条（不计入最近阅读）
+    pub fn chapter_settings_mut(&mut self, novel_path: &Path) -> &mut ChapterSettings {
+        &mut self.novel_info_mut(novel_path).chapter_settings
+    }
+
+    /// 获取小说阅读进度的可变引用，没有记录时创建一条（不计入最近阅读）
+    ///
+    /// 与 [`Library::update_novel_progress`] 不同，不会更新 `last_read`。
+    pub fn progress_mut(&mut self, novel_path: &Path) -> &mut ReadingProgress {
+        &mut self.novel_info_mut(novel_path).progress
+    }
+
+    /// 获取小说标签的可变引用，没有记录时创建一条（不计入最近阅读）
+    pub fn tags_mut(&mut self, novel_path: &Path) -> &mut Vec<String> {
+        &mut self.novel_info_mut(novel_path).tags
+    }
+
+    /// 设置小说的备注，空白备注视为清除（不计入最近阅读）
+    pub fn set_note(&mut self, novel_path: &Path, note: &str) {
+        self.novel_info_mut(novel_path).note = note.trim().to_string();
+    }
+
+    /// 切换小说的收藏状态（不计入最近阅读）
+    ///
+    /// # Returns
+    ///
+    /// 切换后是否为收藏。
+    pub fn toggle_favorite(&mut self, novel_path: &Path) -> bool {
+        let info = self.novel_info_mut(novel_path);
+        info.favorite = !info.favorite;
+        info.favorite
+    }
+
+    /// 设置小说的阅读状态（不计入最近阅读），读完的小说移出待读队列
+    pub fn set_status(&mut self, novel_path: &Path, status: ReadingStatus) {
+        let info = self.novel_info_mut(novel_path);
+        info.status = Some(status);
+        if status == ReadingStatus::Finished && info.queue.take().is_some() {
+            self.renumber_queue();
+        }
+    }
+
+    /// 把小说加入待读队列末尾，或移出队列（不计入最近阅读）
+    ///
+    /// # Returns
+    ///
+    /// 操作后是否在队列中。
+    pub fn toggle_queued(&mut self, novel_path: &Path) -> bool {
+        let end = self.novels.iter().filter(|n| n.queue.is_some()).count();
+        let info = self.novel_info_mut(novel_path);
+        let queued = if info.queue.take().is_some() {
+            false
+        } else {
+            info.queue = Some(end);
+            true
+        };
+        self.renumber_queue();
+        queued
+    }
+
+    /// 在待读队列中把小说与前一本（`up`）或后一本交换位置
+    ///
+    /// # Returns
+    ///
+    /// 是否移动了；不在队列中或已在队首/队尾时返回 `false`。
+    pub fn move_in_queue(&mut self, novel_path: &Path, up: bool) -> bool {
+        self.renumber_queue();
+        let queue = self.queue_indices();
+        let Some(position) = queue
+            .iter()
+            .position(|&i| Self::same_novel_path(&self.novels[i].path, novel_path))
+        else {
+            return false;
+        };
+        let target = if up {
+            position.checked_sub(1)
+        } else {
+            Some(position + 1)
+        };
+        let Some(target) = target.filter(|&target| target < queue.len()) else {
+            return false;
+        };
+        self.novels[queue[position]].queue = Some(target);
+        self.novels[queue[target]].queue = Some(position);
+        true
+    }
+
+    /// 待读队列中的小说在 `novels` 中的索引，按队列顺序
+    fn queue_indices(&self) -> Vec<usize> {
+        let mut queue: Vec<usize> = (0..self.novels.len())
+            .filter(|&i| self.novels[i].queue.is_some())
+            .collect();
+        queue.sort_by_key(|&i| self.novels[i].queue);
+        queue
+    }
+
+    /// 重新编号待读队列（删除小说等操作后位置可能不连续）
+    fn renumber_queue(&mut self) {
+        for (position, index) in self.queue_indices().into_iter().enumerate() {
+            self.novels[index].queue = Some(position);
+        }
+    }
+
+    /// 小说文件改名后迁移它的记录，进度、书签和标签都保留，标题改为新文件名
+    ///
+    /// # Arguments
+    ///
+    /// * `old_path` - 改名前的文件路径
+    /// * `new_path` - 改名后的文件路径
+    pub fn rename_novel(&mut self, old_path: &Path, new_path: &Path) {
+        if let Some(info) = self
+            .novels
+            .iter_mut()
+            .find(|n| Self::same_novel_path(&n.path, old_path))
+        {
+            info.path = new_path.to_path_buf();
+            if let Some(title) = new_path.file_stem().and_then(|s| s.to_str()) {
+                info.title = title.to_string();
+            }
+        }
+    }
+
+    /// 合并内容重复的两本小说的记录：`drop_path` 的记录并入 `keep_path` 后删除
+    ///
+    /// 书签、高亮、批注、标签取并集；阅读位置取最近读过的那本；
+    /// 任一本收藏即收藏，任一本读完即读完。
+    ///
+    /// # Arguments
+    ///
+    /// * `keep_path` - 保留的小说路径
+    /// * `drop_path` - 并入后删除记录的小说路径
+    pub fn merge_novels(&mut self, keep_path: &Path, drop_path: &Path) {
+        let Some(drop_index) = self
+            .novels
+            .iter()
+            .position(|n| Self::same_novel_path(&n.path, drop_path))
+        else {
+            return;
+        };
+        let dropped = self.novels.remove(drop_index);
+        let keep = self.novel_info_mut(keep_path);
+
+        keep.progress.merge_from(&dropped.progress);
+        if dropped.last_read > keep.last_read {
+            keep.progress.scroll_offset = dropped.progress.scroll_offset;
+            keep.progress.char_offset = dropped.progress.char_offset;
+            keep.last_read = dropped.last_read;
+        }
+        for tag in dropped.tags {
+            if !keep.tags.contains(&tag) {
+                keep.tags.push(tag);
+            }
+        }
+        keep.favorite |= dropped.favorite;
+        keep.queue = keep.queue.or(dropped.queue);
+        if keep.note.is_empty() {
+            keep.note = dropped.note;
+        }
+        if dropped.status == Some(ReadingStatus::Finished) || keep.status.is_none() {
+            keep.status = dropped.status.or(keep.status);
+        }
+        self.renumber_queue();
+    }
+
+    /// 所有小说用过的标签，去重并排序
+    pub fn all_tags(&self) -> Vec<String> {
+        let tags: std::collections::BTreeSet<&String> =
+            self.novels.iter().flat_map(|n| &n.tags).collect();
+        tags.into_iter().cloned().collect()
+    }
+
+    /// 登记一本小说，已有记录时不做改动（不计入最近阅读）
+    pub fn register_novel(&mut self, novel_path: &Path) {
+        self.novel_info_mut(novel_path);
+    }
+
+    /// 记录小说的总行数（不计入最近阅读）
+    pub fn set_total_lines(&mut self, novel_path: &Path, total_lines: usize) {
+        self.novel_info_mut(novel_path).total_lines = total_lines;
+    }
+
+    /// 获取小说记录的可变引用，没有记录时创建一条 `last_read` 为 0 的记录
+    fn novel_info_mut(&mut self, novel_path: &Path) -> &mut NovelInfo {
+        let index = match self
+            .novels
+            .iter()
+            .position(|n| Self::same_novel_path(&n.path, novel_path))
+        {
+            Some(index) => index,
+            None => {
+                let title = novel_path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("未知标题")
+                    .to_string();
+                self.novels.push(NovelInfo {
+                    title,
+                    path: novel_path.to_path_buf(),
+                    progress: ReadingProgress::default(),
+                    last_read: 0,
+                    total_lines: 0,
+                    chapter_settings: ChapterSettings::default(),
+                    tags: Vec::new(),
+                    favorite: false,
+                    status: None,
+                    note: String::new(),
+                    queue: None,
+                });
+                self.novels.len() - 1
+            }
+        };
+        &mut self.novels[index]
+    }
+
+    /// 获取最近阅读的小说记录
+    ///
+    /// # Returns
+    ///
+    /// `last_read` 最大的记录；没有任何阅读记录时返回 `None`。
+    pub fn most_recent_novel(&self) -> Option<&NovelInfo> {
+        self.novels
+            .iter()
+            .filter(|n| n.last_read > 0)
+            .max_by_key(|n| n.last_read)
+    }
+
     /// 获取小说的阅读进度
     ///
     /// # Arguments
@@ -382,7 +836,7 @@ mod tests {
         if let Some(parent) = progress_path.parent()
             && let Ok(entries) = std::fs::read_dir(parent)
         {
-            let prefix = format!("{}.", CONFIG.progress_filename);
+            let prefix = format!("{}.", PROGRESS_FILENAME);
             for entry in entries.flatten() {
                 let p = entry.path();
                 let Some(name) = p.file_name().and_then(|n| n.to_str()) else {
@@ -403,6 +857,13 @@ mod tests {
             scroll_offset: 100,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
 
         library.update_novel_progress(&novel_path, progress.clone());
@@ -414,6 +875,13 @@ mod tests {
             scroll_offset: 200,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
         library.update_novel_progress(&novel_path, new_progress.clone());
         assert_eq!(library.get_novel_progress(&novel_path), new_progress);
@@ -434,6 +902,13 @@ mod tests {
             scroll_offset: 50,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
 
         library.update_novel_progress(&path, progress.clone());
@@ -443,6 +918,20 @@ mod tests {
         assert_eq!(library.novels[0].progress, progress);
     }
 
+    #[test]
+    fn test_update_progress_records_last_read() {
+        let mut library = Library::new();
+        library.update_novel_progress(Path::new("/a/novels/old.txt"), ReadingProgress::default());
+        library.update_novel_progress(Path::new("/a/novels/new.txt"), ReadingProgress::default());
+        assert!(library.novels.iter().all(|n| n.last_read > 0));
+
+        library.novels[0].last_read = 1;
+        assert_eq!(library.most_recent_novel().unwrap().title, "new");
+
+        library.novels[1].last_read = 0;
+        assert_eq!(library.most_recent_novel().unwrap().title, "old");
+    }
+
     #[test]
     fn test_get_novel_progress_not_found() {
         let library = Library::new();
@@ -453,6 +942,23 @@ mod tests {
         assert_eq!(progress, ReadingProgress::default());
     }
 
+    #[test]
+    fn test_chapter_settings_default_and_create_entry() {
+        let mut library = Library::new();
+        let path = PathBuf::from("/novels/格式特殊.txt");
+        assert!(library.chapter_settings(&path).auto_detect);
+
+        library.chapter_settings_mut(&path).auto_detect = false;
+        assert_eq!(library.novels.len(), 1);
+        assert_eq!(library.novels[0].last_read, 0);
+        assert!(!library.chapter_settings(&path).auto_detect);
+
+        // 默认设置不写入文件
+        library.chapter_settings_mut(&path).auto_detect = true;
+        let json = serde_json::to_string(&library).unwrap();
+        assert!(!json.contains("chapter_settings"));
+    }
+
     #[test]
     fn test_get_novel_progress_matches_cross_platform_paths() {
         let mut library = Library::new();
@@ -463,7 +969,22 @@ mod tests {
                 scroll_offset: 123,
                 bookmarks: Vec::new(),
                 line_spacing: 0,
+                char_offset: None,
+                custom_chapters: None,
+                furthest_line: 0,
+                quick_slots: Default::default(),
+                auto_positions: Vec::new(),
+                highlights: Vec::new(),
+                annotations: Vec::new(),
             },
+            last_read: 0,
+            total_lines: 0,
+            chapter_settings: ChapterSettings::default(),
+            tags: Vec::new(),
+            favorite: false,
+            status: None,
+            note: String::new(),
+            queue: None,
         });
 
         let progress =
@@ -481,7 +1002,22 @@ mod tests {
                 scroll_offset: 10,
                 bookmarks: Vec::new(),
                 line_spacing: 0,
+                char_offset: None,
+                custom_chapters: None,
+                furthest_line: 0,
+                quick_slots: Default::default(),
+                auto_positions: Vec::new(),
+                highlights: Vec::new(),
+                annotations: Vec::new(),
             },
+            last_read: 0,
+            total_lines: 0,
+            chapter_settings: ChapterSettings::default(),
+            tags: Vec::new(),
+            favorite: false,
+            status: None,
+            note: String::new(),
+            queue: None,
         });
 
         let local_path = PathBuf::from("/Users/alice/.fish_reader/novels/demo.txt");
@@ -489,6 +1025,13 @@ mod tests {
             scroll_offset: 456,
             bookmarks: Vec::new(),
             line_spacing: 0,
+            char_offset: None,
+            custom_chapters: None,
+            furthest_line: 0,
+            quick_slots: Default::default(),
+            auto_positions: Vec::new(),
+            highlights: Vec::new(),
+            annotations: Vec::new(),
         };
         library.update_novel_progress(&local_path, new_progress.clone());
 
@@ -552,6 +1095,13 @@ mod tests {
                 scroll_offset: 42,
                 bookmarks: Vec::new(),
                 line_spacing: 0,
+                char_offset: None,
+                custom_chapters: None,
+                furthest_line: 0,
+                quick_slots: Default::default(),
+                auto_positions: Vec::new(),
+                highlights: Vec::new(),
+                annotations: Vec::new(),
             },
         );
         library.save().unwrap();
@@ -564,6 +1114,52 @@ mod tests {
         clean_progress_artifacts(&progress_path);
     }
 
+    #[test]
+    fn test_restore_backup_keeps_current_progress_as_new_backup() {
+        let _guard = progress_test_lock()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let progress_path = Library::get_progress_path();
+        clean_progress_artifacts(&progress_path);
+
+        let mut library = Library::new();
+        library.register_novel(Path::new("/tmp/backup_a.txt"));
+        library.save().unwrap();
+        // 早于本次保存所在的备份间隔，也未超过保留天数
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let (old_time, broken_time) = (now - 1000, now - 2000);
+        let old_backup = Library::backup_path(&progress_path, old_time);
+        std::fs::copy(&progress_path, &old_backup).unwrap();
+        std::fs::write(
+            Library::backup_path(&progress_path, broken_time),
+            "{ broken",
+        )
+        .unwrap();
+
+        library.register_novel(Path::new("/tmp/backup_b.txt"));
+        library.save().unwrap();
+
+        let backups = Library::list_backups();
+        assert_eq!(backups.last().unwrap().timestamp, broken_time);
+        assert_eq!(backups.last().unwrap().titles, None);
+        let old = backups.iter().find(|b| b.timestamp == old_time).unwrap();
+        assert_eq!(old.titles, Some(vec!["backup_a".to_string()]));
+
+        let restored = Library::restore_backup(&old_backup).unwrap();
+        assert_eq!(restored.novels.len(), 1);
+        assert_eq!(Library::load().novels.len(), 1);
+        // 恢复前的两本小说另存为最新的备份
+        assert_eq!(
+            Library::list_backups()[0].titles.as_ref().map(Vec::len),
+            Some(2)
+        );
+
+        clean_progress_artifacts(&progress_path);
+    }
+
     #[test]
     fn test_load_corrupted_file_returns_new_and_creates_backup() {
         let _guard = progress_test_lock()
@@ -604,4 +1200,32 @@ mod tests {
             .unwrap();
         assert_ne!(current, other);
     }
+
+    #[test]
+    fn test_reading_queue_order() {
+        let mut library = Library::default();
+        let [a, b, c] = ["a.txt", "b.txt", "c.txt"].map(PathBuf::from);
+        let queue = |library: &Library| -> Vec<PathBuf> {
+            library
+                .queue_indices()
+                .into_iter()
+                .map(|i| library.novels[i].path.clone())
+                .collect()
+        };
+        for path in [&a, &b, &c] {
+            assert!(library.toggle_queued(path));
+        }
+        assert_eq!(queue(&library), [a.clone(), b.clone(), c.clone()]);
+
+        assert!(library.move_in_queue(&c, true));
+        assert!(!library.move_in_queue(&a, true));
+        assert_eq!(queue(&library), [a.clone(), c.clone(), b.clone()]);
+
+        // 移出队列和读完后后面的小说依次前移
+        assert!(!library.toggle_queued(&a));
+        library.set_status(&c, ReadingStatus::Finished);
+        assert_eq!(library.novels[1].queue, Some(0));
+        assert!(!library.move_in_queue(&b, false));
+        assert_eq!(queue(&library), [b]);
+    }
 }
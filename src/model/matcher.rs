@@ -0,0 +1,500 @@
+//! 搜索用的模糊匹配：拼音（全拼/首字母）与容错匹配
+
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// GB2312 一级汉字按拼音排序，这里是各声母第一个字的区位码
+///
+/// 只覆盖一级汉字（3755 个常用字），二级汉字按部首排序，无法据此推出拼音。
+const INITIAL_BOUNDARIES: [(u16, char); 23] = [
+    (0xB0A1, 'a'),
+    (0xB0C5, 'b'),
+    (0xB2C1, 'c'),
+    (0xB4EE, 'd'),
+    (0xB6EA, 'e'),
+    (0xB7A2, 'f'),
+    (0xB8C1, 'g'),
+    (0xB9FE, 'h'),
+    (0xBBF7, 'j'),
+    (0xBFA6, 'k'),
+    (0xC0AC, 'l'),
+    (0xC2E8, 'm'),
+    (0xC4C3, 'n'),
+    (0xC5B6, 'o'),
+    (0xC5BE, 'p'),
+    (0xC6DA, 'q'),
+    (0xC8BB, 'r'),
+    (0xC8F6, 's'),
+    (0xCBFA, 't'),
+    (0xCDDA, 'w'),
+    (0xCEF4, 'x'),
+    (0xD1B9, 'y'),
+    (0xD4D1, 'z'),
+];
+
+/// GB2312 一级汉字的最后一个区位码
+const GB2312_LEVEL1_END: u16 = 0xD7F9;
+
+const PINYIN_INITIALS: [&str; 24] = [
+    "", "b", "p", "m", "f", "d", "t", "n", "l", "g", "k", "h", "j", "q", "x", "zh", "ch", "sh",
+    "r", "z", "c", "s", "y", "w",
+];
+
+const PINYIN_FINALS: [&str; 36] = [
+    "a", "o", "e", "i", "u", "v", "ai", "ei", "ui", "ao", "ou", "iu", "ie", "ve", "er", "an", "en",
+    "in", "un", "vn", "ang", "eng", "ing", "ong", "ia", "iao", "ian", "iang", "iong", "ua", "uo",
+    "uai", "uan", "uang", "ue", "i",
+];
+
+/// 汉字 → 拼音首字母表，首次使用时由 GB2312 一级字库生成
+fn initial_table() -> &'static HashMap<char, char> {
+    static TABLE: OnceLock<HashMap<char, char>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        for (i, &(start, letter)) in INITIAL_BOUNDARIES.iter().enumerate() {
+            let end = INITIAL_BOUNDARIES
+                .get(i + 1)
+                .map(|&(next, _)| next - 1)
+                .unwrap_or(GB2312_LEVEL1_END);
+            for code in start..=end {
+                // 每区只有 0xA1..=0xFE 是有效位
+                if code & 0xFF < 0xA1 || code & 0xFF > 0xFE {
+                    continue;
+                }
+                let bytes = code.to_be_bytes();
+                let (decoded, had_errors) = encoding_rs::GBK.decode_without_bom_handling(&bytes);
+                if !had_errors && let Some(ch) = decoded.chars().next() {
+                    table.insert(ch, letter);
+                }
+            }
+        }
+        table
+    })
+}
+
+/// 所有合法拼音音节（声母 + 韵母的组合，略有冗余）的前缀集合
+fn syllable_prefixes() -> &'static HashSet<String> {
+    static PREFIXES: OnceLock<HashSet<String>> = OnceLock::new();
+    PREFIXES.get_or_init(|| {
+        let mut prefixes = HashSet::new();
+        for initial in PINYIN_INITIALS {
+            for final_ in PINYIN_FINALS {
+                let syllable = format!("{}{}", initial, final_);
+                for end in 1..=syllable.len() {
+                    prefixes.insert(syllable[..end].to_string());
+                }
+            }
+        }
+        prefixes
+    })
+}
+
+/// 获取汉字的拼音首字母（小写）
+///
+/// 仅支持 GB2312 一级汉字，其他字符返回 `None`。
+pub fn pinyin_initial(ch: char) -> Option<char> {
+    initial_table().get(&ch).copied()
+}
+
+/// 字符的小写形式，只取第一个字符，保证与原文逐字对应
+fn lowercase(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+/// 第 `index` 个字符在原文中的字节偏移，`index` 等于字符数时为原文长度
+fn byte_offset(line: &str, chars: &[(usize, char)], index: usize) -> usize {
+    chars.get(index).map_or(line.len(), |&(offset, _)| offset)
+}
+
+/// 拼音匹配的状态：按（字符位置，查询位置）记住结果，回溯时不重复计算
+///
+/// 同一位置的结果与从哪个字符开始匹配无关，所以一行中所有起点共用一张表。
+struct PinyinMatcher<'a> {
+    chars: &'a [char],
+    query: &'a [u8],
+    /// `memo[i * (query.len() + 1) + j]`：从第 i 个字符起能否拼出 `query[j..]`，能时为结束的字符位置
+    memo: Vec<Option<Option<usize>>>,
+}
+
+impl<'a> PinyinMatcher<'a> {
+    fn new(chars: &'a [char], query: &'a [u8]) -> Self {
+        Self {
+            chars,
+            query,
+            memo: vec![None; (chars.len() + 1) * (query.len() + 1)],
+        }
+    }
+
+    /// 从第 `i` 个字符起能否拼出 `query[j..]`
+    ///
+    /// 每个汉字消耗查询中以其首字母开头、且是某个拼音音节前缀的一段，
+    /// 所以 `zhangsan`、`zhangs`、`zs` 都能匹配“张三”。非汉字按原样（不区分大小写）比较。
+    ///
+    /// # Returns
+    ///
+    /// 能拼出时返回匹配结束的字符位置（不含）。
+    fn match_at(&mut self, i: usize, j: usize) -> Option<usize> {
+        let query = self.query;
+        if j == query.len() {
+            return Some(i);
+        }
+        let key = i * (query.len() + 1) + j;
+        if let Some(result) = self.memo[key] {
+            return result;
+        }
+
+        let query_rest = &query[j..];
+        let result = match self.chars.get(i) {
+            None => None,
+            Some(&ch) => match pinyin_initial(ch) {
+                Some(initial) if query_rest[0] == initial as u8 => {
+                    let prefixes = syllable_prefixes();
+                    (1..=query_rest.len().min(6)).rev().find_map(|len| {
+                        let segment = std::str::from_utf8(&query_rest[..len]).unwrap_or_default();
+                        if prefixes.contains(segment) {
+                            self.match_at(i + 1, j + len)
+                        } else {
+                            None
+                        }
+                    })
+                }
+                Some(_) => None,
+                None if ch.is_ascii() && ch.to_ascii_lowercase() as u8 == query_rest[0] => {
+                    self.match_at(i + 1, j + 1)
+                }
+                None => None,
+            },
+        };
+        self.memo[key] = Some(result);
+        result
+    }
+}
+
+/// 查找一行文本中第一处拼音查询（全拼、首字母或混合）的匹配
+///
+/// # Arguments
+///
+/// * `line` - 要匹配的文本
+/// * `query` - 拼音查询，只能包含 ASCII 字母，空格和 `'` 会被忽略
+///
+/// # Returns
+///
+/// 匹配部分在 `line` 中的字节范围。
+pub fn pinyin_find(line: &str, query: &str) -> Option<Range<usize>> {
+    let query: Vec<u8> = query
+        .bytes()
+        .filter(|b| *b != b' ' && *b != b'\'')
+        .map(|b| b.to_ascii_lowercase())
+        .collect();
+    if query.is_empty() || !query.iter().all(u8::is_ascii_lowercase) {
+        return None;
+    }
+    let indexed: Vec<(usize, char)> = line.char_indices().collect();
+    let chars: Vec<char> = indexed.iter().map(|&(_, ch)| ch).collect();
+    let mut matcher = PinyinMatcher::new(&chars, &query);
+    (0..chars.len()).find_map(|start| {
+        let end = matcher.match_at(start, 0)?;
+        Some(byte_offset(line, &indexed, start)..byte_offset(line, &indexed, end))
+    })
+}
+
+/// 判断一行文本是否包含拼音查询（全拼、首字母或混合），参数同 [`pinyin_find`]
+pub fn pinyin_contains(line: &str, query: &str) -> bool {
+    pinyin_find(line, query).is_some()
+}
+
+/// Sellers 算法（子串起点不计代价的编辑距离）：`chars` 中与 `query` 编辑距离不超过
+/// `max_edits` 的第一个子串的结束位置（不含）
+///
+/// 找到后继续向后延伸，直到编辑距离开始增大，使匹配尽量完整。
+fn approx_end(chars: &[char], query: &[char], max_edits: usize) -> Option<usize> {
+    // column[i] = query 前 i 个字符与以当前位置结尾的某个子串的最小编辑距离
+    let mut column: Vec<usize> = (0..=query.len()).collect();
+    let mut best: Option<(usize, usize)> = (query.len() <= max_edits).then_some((0, query.len()));
+    for (index, &ch) in chars.iter().enumerate() {
+        let mut diagonal = column[0];
+        for i in 1..=query.len() {
+            let substitution = diagonal + usize::from(query[i - 1] != ch);
+            diagonal = column[i];
+            column[i] = substitution.min(column[i] + 1).min(column[i - 1] + 1);
+        }
+        let distance = column[query.len()];
+        match best {
+            Some((_, best_distance)) if distance > best_distance => break,
+            Some(_) => best = Some((index + 1, distance)),
+            None if distance <= max_edits => best = Some((index + 1, distance)),
+            None => {}
+        }
+    }
+    best.map(|(end, _)| end)
+}
+
+/// 查找容错子串匹配：`line` 中与 `query` 编辑距离不超过 `max_edits` 的第一个子串
+///
+/// 按字符比较，不区分大小写。
+///
+/// # Returns
+///
+/// 匹配部分在 `line` 中的字节范围。
+pub fn approx_find(line: &str, query: &str, max_edits: usize) -> Option<Range<usize>> {
+    let query: Vec<char> = query.chars().map(lowercase).collect();
+    let indexed: Vec<(usize, char)> = line.char_indices().collect();
+    let chars: Vec<char> = indexed.iter().map(|&(_, ch)| lowercase(ch)).collect();
+    let end = approx_end(&chars, &query, max_edits)?;
+    // 在结束位置之前反向再找一次，得到匹配的起点
+    let reversed: Vec<char> = chars[..end].iter().rev().copied().collect();
+    let reversed_query: Vec<char> = query.iter().rev().copied().collect();
+    let len = approx_end(&reversed, &reversed_query, max_edits).unwrap_or_default();
+    Some(byte_offset(line, &indexed, end - len)..byte_offset(line, &indexed, end))
+}
+
+/// 查找一行中关键词的所有普通匹配（不区分大小写）
+///
+/// # Returns
+///
+/// 各处匹配在 `line` 中的字节范围，互不重叠。
+pub fn find_all(line: &str, term: &str) -> Vec<Range<usize>> {
+    let term: Vec<char> = term.chars().map(lowercase).collect();
+    if term.is_empty() {
+        return Vec::new();
+    }
+    let indexed: Vec<(usize, char)> = line.char_indices().collect();
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    while start + term.len() <= indexed.len() {
+        let matched = indexed[start..start + term.len()]
+            .iter()
+            .zip(&term)
+            .all(|(&(_, ch), &t)| lowercase(ch) == t);
+        if matched {
+            ranges.push(
+                byte_offset(line, &indexed, start)..byte_offset(line, &indexed, start + term.len()),
+            );
+            start += term.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// 模糊查找：依次尝试普通子串、拼音和少量错字，返回第一种策略找到的第一处匹配
+///
+/// 容错的编辑次数随查询长度增加：少于 4 个字符不容错，4~7 个允许 1 处，更长允许 2 处。
+///
+/// # Returns
+///
+/// 匹配部分在 `line` 中的字节范围。
+pub fn fuzzy_find(line: &str, query: &str) -> Option<Range<usize>> {
+    if let Some(range) = find_all(line, query).into_iter().next() {
+        return Some(range);
+    }
+    if let Some(range) = pinyin_find(line, query) {
+        return Some(range);
+    }
+    let max_edits = match query.chars().count() {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    if max_edits == 0 {
+        return None;
+    }
+    approx_find(line, query, max_edits)
+}
+
+/// 模糊搜索：普通子串、拼音或少量错字任一匹配即可，规则见 [`fuzzy_find`]
+pub fn fuzzy_contains(line: &str, query: &str) -> bool {
+    fuzzy_find(line, query).is_some()
+}
+
+/// 高亮一行中的关键词要用的范围
+///
+/// 有普通匹配时高亮所有普通匹配；`fuzzy` 为真且没有普通匹配时，高亮拼音或容错匹配到的那一处。
+///
+/// # Returns
+///
+/// 各处匹配在 `line` 中的字节范围，升序且互不重叠。
+pub fn highlight_ranges(line: &str, term: &str, fuzzy: bool) -> Vec<Range<usize>> {
+    let ranges = find_all(line, term);
+    if !ranges.is_empty() || !fuzzy {
+        return ranges;
+    }
+    fuzzy_find(line, term).into_iter().collect()
+}
+
+/// 搜索查询，支持简单的布尔语法
+///
+/// 输入中出现 `+词` 或 `-词` 时按空格拆分：`+` 开头的词必须出现，`-` 开头的词不能出现，
+/// 其余的词至少出现一个。例如 `张三 +李四 -梦` 匹配同时包含张三和李四、且不含梦的行。
+/// 不含运算符时整个输入（包括空格）作为一个关键词。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// 至少出现一个的词
+    any: Vec<String>,
+    /// 必须全部出现的词
+    all: Vec<String>,
+    /// 不能出现的词
+    none: Vec<String>,
+}
+
+impl SearchQuery {
+    /// 解析查询字符串，关键词统一转为小写
+    pub fn parse(input: &str) -> Self {
+        let input = input.to_lowercase();
+        let is_operator =
+            |word: &str| word.len() > 1 && (word.starts_with('+') || word.starts_with('-'));
+        if !input.split_whitespace().any(is_operator) {
+            return Self {
+                any: if input.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![input]
+                },
+                all: Vec::new(),
+                none: Vec::new(),
+            };
+        }
+
+        let mut query = Self {
+            any: Vec::new(),
+            all: Vec::new(),
+            none: Vec::new(),
+        };
+        for word in input.split_whitespace() {
+            if !is_operator(word) {
+                query.any.push(word.to_string());
+            } else if let Some(word) = word.strip_prefix('+') {
+                query.all.push(word.to_string());
+            } else if let Some(word) = word.strip_prefix('-') {
+                query.none.push(word.to_string());
+            }
+        }
+        query
+    }
+
+    /// 查询中没有任何正向关键词（只有排除词也视为空查询）
+    pub fn is_empty(&self) -> bool {
+        self.any.is_empty() && self.all.is_empty()
+    }
+
+    /// 用于正文高亮的关键词：第一个必须出现的词，否则为第一个普通词
+    pub fn highlight_term(&self) -> &str {
+        self.all
+            .first()
+            .or_else(|| self.any.first())
+            .map_or("", String::as_str)
+    }
+
+    /// 判断一行是否满足查询
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - 要匹配的文本
+    /// * `fuzzy` - 是否对每个词使用 [`fuzzy_contains`]
+    pub fn matches(&self, line: &str, fuzzy: bool) -> bool {
+        let lower = if fuzzy {
+            String::new()
+        } else {
+            line.to_lowercase()
+        };
+        let contains = |term: &str| {
+            if fuzzy {
+                fuzzy_contains(line, term)
+            } else {
+                lower.contains(term)
+            }
+        };
+
+        self.all.iter().all(|term| contains(term))
+            && !self.none.iter().any(|term| contains(term))
+            && (self.any.is_empty() || self.any.iter().any(|term| contains(term)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinyin_initial_of_common_chars() {
+        assert_eq!(pinyin_initial('张'), Some('z'));
+        assert_eq!(pinyin_initial('三'), Some('s'));
+        assert_eq!(pinyin_initial('阿'), Some('a'));
+        assert_eq!(pinyin_initial('a'), None);
+    }
+
+    #[test]
+    fn test_pinyin_contains_full_partial_and_initials() {
+        let line = "那天张三来到了城里";
+        assert!(pinyin_contains(line, "zhangsan"));
+        assert!(pinyin_contains(line, "zhangs"));
+        assert!(pinyin_contains(line, "zs"));
+        assert!(pinyin_contains(line, "Zhang San"));
+        assert!(!pinyin_contains(line, "lisi"));
+        // 非合法拼音片段不能匹配
+        assert!(!pinyin_contains(line, "zxsan"));
+    }
+
+    #[test]
+    fn test_approx_find_tolerates_typos() {
+        assert!(approx_find("The dragon roared", "dargon", 2).is_some());
+        assert!(approx_find("The dragon roared", "drgon", 1).is_some());
+        assert!(approx_find("The dragon roared", "wizard", 1).is_none());
+        assert!(approx_find("他拔出了倚天剑", "倚天刀", 1).is_some());
+    }
+
+    #[test]
+    fn test_search_query_boolean_operators() {
+        let query = SearchQuery::parse("张三 +李四 -梦");
+        assert!(query.matches("张三和李四喝酒", false));
+        assert!(!query.matches("张三独自喝酒", false));
+        assert!(!query.matches("张三和李四做梦", false));
+        assert!(!query.matches("李四喝酒", false));
+        assert_eq!(query.highlight_term(), "李四");
+
+        let query = SearchQuery::parse("王五 赵六 -酒");
+        assert!(query.matches("赵六吃饭", false));
+        assert!(!query.matches("王五喝酒", false));
+
+        // 不含运算符时空格是关键词的一部分
+        let query = SearchQuery::parse("Hello World");
+        assert!(query.matches("say hello world", false));
+        assert!(!query.matches("hello there world", false));
+
+        assert!(SearchQuery::parse("-梦").is_empty());
+        assert!(SearchQuery::parse("").is_empty());
+    }
+
+    #[test]
+    fn test_fuzzy_contains_combines_strategies() {
+        assert!(fuzzy_contains("张三说", "张三"));
+        assert!(fuzzy_contains("张三说", "zhangsan"));
+        assert!(fuzzy_contains("Hermione smiled", "hermoine"));
+        assert!(!fuzzy_contains("abc", "xyz"));
+    }
+
+    #[test]
+    fn test_find_ranges_for_highlighting() {
+        let line = "那天张三来到了城里";
+        let zhangsan = line.find("张三").unwrap()..line.find("来").unwrap();
+        assert_eq!(pinyin_find(line, "zs"), Some(zhangsan.clone()));
+        assert_eq!(highlight_ranges(line, "zhangsan", true), vec![zhangsan]);
+        assert!(highlight_ranges(line, "zhangsan", false).is_empty());
+
+        assert_eq!(approx_find("The dragon roared", "dargon", 2), Some(4..10));
+        assert_eq!(find_all("Ab ab AB", "ab"), vec![0..2, 3..5, 6..8]);
+        // 有普通匹配时不再做模糊匹配
+        assert_eq!(highlight_ranges("张三说 zs", "zs", true), vec![10..12]);
+    }
+
+    #[test]
+    fn test_pinyin_find_on_long_ambiguous_lines() {
+        // 每个起点都要匹配到行尾才失败，所有起点共用记忆表，每个位置只计算一次
+        let line = "西安先".repeat(200);
+        let query = format!("{}q", "xianxian".repeat(100));
+        assert_eq!(pinyin_find(&line, &query), None);
+        assert!(pinyin_contains(&line, &"xianxian".repeat(100)));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod chapter_cache;
 pub mod chapter_parser;
+pub mod file_stats;
 pub mod library;
+pub mod matcher;
 pub mod novel;
+pub mod preferences;
+pub mod series;
+pub mod trash;
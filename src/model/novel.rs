@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -13,10 +14,22 @@ pub struct Novel {
     content: Arc<String>,
     /// 缓存的行数据（避免重复 lines().collect()）
     lines: Arc<Vec<String>>,
+    /// 每行起始位置的字符偏移（前缀和），用于行号与字符偏移互相换算
+    line_starts: Arc<Vec<usize>>,
+    /// 每行之前的正文字数（不含空白的字符数，前缀和，比行数多一项），用于统计章节字数
+    word_starts: Arc<Vec<usize>>,
     /// 当前阅读进度
     pub progress: ReadingProgress,
     /// 章节目录
     pub chapters: Vec<Chapter>,
+    /// 最近一次跳转前的位置（不持久化），用于 `''` 快速返回
+    pub last_position: Option<usize>,
+    /// 文件大小（字节），扫描书架时记录
+    pub file_size: u64,
+    /// 所在分类：相对小说目录的子文件夹路径（以 `/` 分隔），直接放在小说目录下时为空
+    pub category: String,
+    /// 扫描到这本小说的小说目录
+    pub root: PathBuf,
 }
 
 impl Novel {
@@ -43,14 +56,20 @@ impl Novel {
             path: path.clone(),
             content: Arc::new(String::new()),
             lines: Arc::new(Vec::new()),
+            line_starts: Arc::new(Vec::new()),
+            word_starts: Arc::new(vec![0]),
             progress: ReadingProgress::default(),
             chapters: Vec::new(),
+            last_position: None,
+            file_size: 0,
+            category: String::new(),
+            root: PathBuf::new(),
         }
     }
 
     /// 从文件加载小说内容
     ///
-    /// 读取文件内容并自动解析章节目录。
+    /// 只读取内容，章节目录需要另外解析（见 [`crate::model::chapter_cache::load_or_parse`]）。
     ///
     /// # Errors
     ///
@@ -58,7 +77,6 @@ impl Novel {
     pub fn load_content(&mut self) -> std::io::Result<()> {
         let content = std::fs::read_to_string(&self.path)?;
         self.set_content(content);
-        self.parse_chapters();
         Ok(())
     }
 
@@ -74,6 +92,12 @@ impl Novel {
         &self.lines
     }
 
+    /// 获取行数据的共享引用，供后台线程使用
+    #[inline]
+    pub fn shared_lines(&self) -> Arc<Vec<String>> {
+        Arc::clone(&self.lines)
+    }
+
     /// 检查内容是否为空
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -85,15 +109,167 @@ impl Novel {
     /// 此方法确保 content 和 lines 始终同步。
     pub fn set_content(&mut self, content: String) {
         let lines: Vec<String> = content.lines().map(String::from).collect();
+        let mut line_starts = Vec::with_capacity(lines.len());
+        let mut word_starts = Vec::with_capacity(lines.len() + 1);
+        let mut offset = 0;
+        let mut words = 0;
+        for line in &lines {
+            line_starts.push(offset);
+            word_starts.push(words);
+            // 换行符计为一个字符，与 CRLF/LF 无关
+            offset += line.chars().count() + 1;
+            words += line.chars().filter(|c| !c.is_whitespace()).count();
+        }
+        word_starts.push(words);
+        self.line_starts = Arc::new(line_starts);
+        self.word_starts = Arc::new(word_starts);
         self.lines = Arc::new(lines);
         self.content = Arc::new(content);
     }
 
+    /// 章节覆盖的行区间（到下一章开头或全文末尾为止）
+    pub fn chapter_line_range(&self, index: usize) -> Option<std::ops::Range<usize>> {
+        let len = self.line_count();
+        let start = self.chapters.get(index)?.start_line.min(len);
+        let end = self
+            .chapters
+            .get(index + 1)
+            .map_or(len, |next| next.start_line.min(len));
+        Some(start..end.max(start))
+    }
+
+    /// 包含指定行的段落的行区间（段落以空行分隔）
+    ///
+    /// 该行本身是空行或超出范围时只包含该行（超出范围时为空区间）。
+    pub fn paragraph_range(&self, line: usize) -> std::ops::Range<usize> {
+        let is_blank = |line: &String| line.trim().is_empty();
+        match self.lines.get(line) {
+            Some(text) if !is_blank(text) => {
+                let start = self.lines[..line]
+                    .iter()
+                    .rposition(is_blank)
+                    .map_or(0, |blank| blank + 1);
+                let end = self.lines[line..]
+                    .iter()
+                    .position(is_blank)
+                    .map_or(self.lines.len(), |blank| line + blank);
+                start..end
+            }
+            Some(_) => line..line + 1,
+            None => line..line,
+        }
+    }
+
+    /// 统计章节的行数和字数（字数不含空白字符）
+    ///
+    /// # Returns
+    ///
+    /// `(行数, 字数)`；章节索引无效时返回 `None`。
+    pub fn chapter_stats(&self, index: usize) -> Option<(usize, usize)> {
+        let range = self.chapter_line_range(index)?;
+        let words = self.word_starts[range.end] - self.word_starts[range.start];
+        Some((range.len(), words))
+    }
+
+    /// 计算当前阅读位置所在章节及章内进度
+    ///
+    /// # Returns
+    ///
+    /// `(章节索引, 百分比)`，百分比为首行在本章中已读过的行数比例（0-100）；没有章节时返回 `None`。
+    pub fn chapter_progress(&self) -> Option<(usize, usize)> {
+        let line = self.progress.scroll_offset;
+        let index = self
+            .chapters
+            .partition_point(|chapter| chapter.start_line <= line)
+            .saturating_sub(1);
+        let range = self.chapter_line_range(index)?;
+        let read = line.saturating_sub(range.start).min(range.len());
+        let percent = (read * 100).checked_div(range.len()).unwrap_or(0);
+        Some((index, percent))
+    }
+
+    /// 把当前阅读位置计入读到过的最远位置
+    pub fn record_furthest_line(&mut self) {
+        self.progress.furthest_line = self.progress.furthest_line.max(self.progress.scroll_offset);
+    }
+
+    /// 章节是否已读完：整章都在读到过的最远位置（含当前位置）之前
+    pub fn is_chapter_read(&self, index: usize) -> bool {
+        let reached = self.progress.furthest_line.max(self.progress.scroll_offset);
+        self.chapter_line_range(index)
+            .is_some_and(|range| range.end <= reached)
+    }
+
+    /// 获取指定行起始位置的字符偏移
+    ///
+    /// 超出范围的行号返回全文末尾的偏移。
+    pub fn char_offset_of_line(&self, line: usize) -> usize {
+        match self.line_starts.get(line) {
+            Some(&offset) => offset,
+            None => self
+                .line_starts
+                .last()
+                .zip(self.lines.last())
+                .map(|(start, text)| start + text.chars().count())
+                .unwrap_or(0),
+        }
+    }
+
+    /// 获取包含指定字符偏移的行号
+    pub fn line_at_char_offset(&self, offset: usize) -> usize {
+        self.line_starts
+            .partition_point(|&start| start <= offset)
+            .saturating_sub(1)
+    }
+
+    /// 页首屏幕行第一个字符在全文中的字符偏移
+    ///
+    /// 进度中的字符偏移落在当前首行内时即为页首位置（长段落中间的某个屏幕行）；
+    /// 按行跳转后字符偏移不在首行内，此时取首行开头。
+    pub fn top_char_offset(&self) -> usize {
+        let line_start = self.char_offset_of_line(self.progress.scroll_offset);
+        let line_end = self.char_offset_of_line(self.progress.scroll_offset + 1);
+        match self.progress.char_offset {
+            Some(offset) if (line_start..line_end).contains(&offset) => offset,
+            _ => line_start,
+        }
+    }
+
+    /// 页首在首行内的字符位置
+    pub fn top_offset_in_line(&self) -> usize {
+        self.top_char_offset() - self.char_offset_of_line(self.progress.scroll_offset)
+    }
+
+    /// 把页首设为第 `line` 行中字符位置 `offset_in_line` 处
+    pub fn set_top(&mut self, line: usize, offset_in_line: usize) {
+        self.progress.scroll_offset = line;
+        self.progress.char_offset = Some(self.char_offset_of_line(line) + offset_in_line);
+    }
+
+    /// 把进度中的字符偏移更新为页首的实际位置，每次滚动后和保存前调用
+    ///
+    /// 首行没变时保留行内位置；按行跳转到别的行后更新为该行开头。
+    pub fn sync_char_offset(&mut self) {
+        self.progress.char_offset = Some(self.top_char_offset());
+    }
+
+    /// 根据进度中的字符偏移恢复首行，用于加载进度后
+    ///
+    /// 字符偏移本身保留，渲染时据此找到首行中的屏幕行；排版变化后仍从同一处文字开始。
+    /// 旧版本的进度没有字符偏移，此时保留原行号。
+    pub fn restore_char_offset(&mut self) {
+        if let Some(offset) = self.progress.char_offset {
+            self.progress.scroll_offset = self.line_at_char_offset(offset);
+        }
+    }
+
     /// 解析章节目录
     ///
     /// 使用两阶段解析（候选分类 + 弱候选评分），委托给 `chapter_parser::parse`。
+    /// 不使用缓存和自定义正则，供测试直接由内容构造章节。
+    #[cfg(test)]
     pub fn parse_chapters(&mut self) {
-        self.chapters = crate::model::chapter_parser::parse(&self.lines);
+        self.chapters = crate::model::chapter_parser::parse(&self.lines, &[], true);
     }
 }
 
@@ -119,6 +295,12 @@ pub struct Bookmark {
     pub position: usize,
     /// 创建时间戳
     pub timestamp: u64,
+    /// 备注（可多行）
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    /// 标签，如 “伏笔”，为空表示没有标签
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub tag: String,
 }
 
 impl Bookmark {
@@ -142,10 +324,62 @@ impl Bookmark {
             name,
             position,
             timestamp,
+            note: String::new(),
+            tag: String::new(),
         }
     }
 }
 
+/// 高亮的一段正文（行号闭区间）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Highlight {
+    /// 起始行号
+    pub start: usize,
+    /// 结束行号（包含）
+    pub end: usize,
+    /// 创建时间戳
+    pub timestamp: u64,
+}
+
+impl Highlight {
+    /// 行号是否在高亮范围内
+    pub fn contains(&self, line: usize) -> bool {
+        (self.start..=self.end).contains(&line)
+    }
+}
+
+/// 批注：附在某一行上的自由文字
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Annotation {
+    /// 批注所在的行号
+    pub line: usize,
+    /// 批注内容
+    pub note: String,
+    /// 最后修改时间戳
+    pub timestamp: u64,
+}
+
+/// 可供选择的书签标签
+pub const BOOKMARK_TAGS: &[&str] = &["伏笔", "名场面", "摘抄", "疑问"];
+
+/// 按 “无标签 → 各个标签 → 无标签” 的顺序返回下一个标签
+///
+/// # Arguments
+///
+/// * `tag` - 当前标签，空字符串表示没有标签；不在列表中的标签视为没有标签
+pub fn next_bookmark_tag(tag: &str) -> &'static str {
+    match BOOKMARK_TAGS.iter().position(|&t| t == tag) {
+        Some(index) => BOOKMARK_TAGS.get(index + 1).copied().unwrap_or(""),
+        None => BOOKMARK_TAGS[0],
+    }
+}
+
+/// 每本小说最多保留的自动记录位置数
+pub const MAX_AUTO_POSITIONS: usize = 10;
+
+/// 行间距的上限
+pub const MAX_LINE_SPACING: usize = 5;
+
 /// 阅读进度
 ///
 /// 跟踪用户在小说中的阅读位置和书签列表。
@@ -155,9 +389,33 @@ pub struct ReadingProgress {
     pub scroll_offset: usize,
     #[serde(default)]
     pub bookmarks: Vec<Bookmark>,
-    /// 行间距（每行之间的额外空行数），范围 0..=5
+    /// 行间距（每行之间的额外空行数），范围 0..=[`MAX_LINE_SPACING`]
     #[serde(default)]
     pub line_spacing: usize,
+    /// 页首屏幕行第一个字符在全文中的字符偏移（权威位置，`scroll_offset` 由它推导）
+    ///
+    /// 行号会因文件换行方式等原因在不同设备间对不上，字符偏移则不会；
+    /// 窗口宽度变化使长段落重新折行后，页首仍从同一处文字开始。
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub char_offset: Option<usize>,
+    /// 手动编辑过的章节目录，存在时取代自动识别的结果
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_chapters: Option<Vec<Chapter>>,
+    /// 读到过的最远行号，用于在目录中标记已读章节
+    #[serde(default)]
+    pub furthest_line: usize,
+    /// 编号快捷书签（1–9 → 行号），不需要命名，用 `Alt+数字` 设置、`'` + 数字跳转
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub quick_slots: BTreeMap<u8, usize>,
+    /// 自动记录的阅读位置（最近的在前），退出阅读和大跨度跳转时记录，防止误操作丢失进度
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub auto_positions: Vec<Bookmark>,
+    /// 高亮的行范围，按起始行排序且互不重叠
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<Highlight>,
+    /// 行批注，按行号排序，每行最多一条
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
 }
 
 impl ReadingProgress {
@@ -169,10 +427,138 @@ impl ReadingProgress {
     ///
     /// * `name` - 书签名称
     /// * `position` - 书签位置（行号）
-    pub fn add_bookmark(&mut self, name: String, position: usize) {
-        let bookmark = Bookmark::new(name, position);
-        self.bookmarks.push(bookmark);
-        self.bookmarks.sort_by_key(|a| a.position);
+    ///
+    /// # Returns
+    ///
+    /// 新书签的可变引用，便于继续设置备注等字段。
+    pub fn add_bookmark(&mut self, name: String, position: usize) -> &mut Bookmark {
+        let index = self.bookmarks.partition_point(|b| b.position <= position);
+        self.bookmarks.insert(index, Bookmark::new(name, position));
+        &mut self.bookmarks[index]
+    }
+
+    /// 自动记录一个阅读位置
+    ///
+    /// 新记录排在最前，同一位置的旧记录会被替换，最多保留 [`MAX_AUTO_POSITIONS`] 条。
+    ///
+    /// # Arguments
+    ///
+    /// * `reason` - 记录原因，作为显示名称
+    /// * `position` - 阅读位置（行号）
+    pub fn record_auto_position(&mut self, reason: &str, position: usize) {
+        self.auto_positions
+            .retain(|entry| entry.position != position);
+        self.auto_positions
+            .insert(0, Bookmark::new(reason.to_string(), position));
+        self.auto_positions.truncate(MAX_AUTO_POSITIONS);
+    }
+
+    /// 高亮一段正文
+    ///
+    /// 与已有高亮重叠或相邻时合并为一段，保持高亮按起始行排序且互不重叠。
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - 起始行号
+    /// * `end` - 结束行号（包含），小于 `start` 时两者互换
+    pub fn add_highlight(&mut self, start: usize, end: usize) {
+        let (mut start, mut end) = (start.min(end), start.max(end));
+        self.highlights.retain(|h| {
+            let overlaps = h.start <= end + 1 && start <= h.end + 1;
+            if overlaps {
+                start = start.min(h.start);
+                end = end.max(h.end);
+            }
+            !overlaps
+        });
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let index = self.highlights.partition_point(|h| h.start < start);
+        self.highlights.insert(
+            index,
+            Highlight {
+                start,
+                end,
+                timestamp,
+            },
+        );
+    }
+
+    /// 设置一行的批注
+    ///
+    /// 该行已有批注时替换内容；内容为空（只含空白）时删除该行的批注。
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - 行号
+    /// * `note` - 批注内容
+    pub fn set_annotation(&mut self, line: usize, note: String) {
+        let index = self.annotations.partition_point(|a| a.line < line);
+        let exists = self.annotations.get(index).is_some_and(|a| a.line == line);
+        if note.trim().is_empty() {
+            if exists {
+                self.annotations.remove(index);
+            }
+            return;
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let annotation = Annotation {
+            line,
+            note,
+            timestamp,
+        };
+        if exists {
+            self.annotations[index] = annotation;
+        } else {
+            self.annotations.insert(index, annotation);
+        }
+    }
+
+    /// 把另一份进度（同一内容的另一个文件）中的书签、高亮、批注和编号书签并入
+    ///
+    /// 已有相同位置和名称的书签、已有批注的行和已占用的编号不会被覆盖；
+    /// 阅读位置不变，读到过的最远行取两者中较大者。
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - 要并入的进度
+    pub fn merge_from(&mut self, other: &ReadingProgress) {
+        for bookmark in &other.bookmarks {
+            let exists = self
+                .bookmarks
+                .iter()
+                .any(|b| b.position == bookmark.position && b.name == bookmark.name);
+            if !exists {
+                let index = self
+                    .bookmarks
+                    .partition_point(|b| b.position <= bookmark.position);
+                self.bookmarks.insert(index, bookmark.clone());
+            }
+        }
+        for highlight in &other.highlights {
+            self.add_highlight(highlight.start, highlight.end);
+        }
+        for annotation in &other.annotations {
+            let index = self
+                .annotations
+                .partition_point(|a| a.line < annotation.line);
+            if self
+                .annotations
+                .get(index)
+                .is_none_or(|a| a.line != annotation.line)
+            {
+                self.annotations.insert(index, annotation.clone());
+            }
+        }
+        for (&slot, &line) in &other.quick_slots {
+            self.quick_slots.entry(slot).or_insert(line);
+        }
+        self.furthest_line = self.furthest_line.max(other.furthest_line);
     }
 
     /// 删除书签
@@ -294,4 +680,152 @@ Final content"
         assert_eq!(bookmark.position, 42);
         assert!(bookmark.timestamp > 0);
     }
+
+    #[test]
+    fn test_char_offset_round_trip() {
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("第一行\n\n第三行内容\nabc".to_string());
+
+        assert_eq!(novel.char_offset_of_line(0), 0);
+        assert_eq!(novel.char_offset_of_line(2), 5);
+        assert_eq!(novel.char_offset_of_line(3), 11);
+        assert_eq!(novel.char_offset_of_line(99), 14);
+        for line in 0..novel.line_count() {
+            assert_eq!(
+                novel.line_at_char_offset(novel.char_offset_of_line(line)),
+                line
+            );
+        }
+        // 落在行中间的偏移对应该行
+        assert_eq!(novel.line_at_char_offset(7), 2);
+    }
+
+    #[test]
+    fn test_top_char_offset_keeps_position_inside_first_line() {
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("一\n很长的一段正文\n三".to_string());
+        novel.set_top(1, 4);
+        assert_eq!(novel.top_char_offset(), 6);
+        assert_eq!(novel.top_offset_in_line(), 4);
+        novel.sync_char_offset();
+        assert_eq!(novel.progress.char_offset, Some(6));
+
+        // 按行跳到别的行后，页首是该行开头
+        novel.progress.scroll_offset = 2;
+        assert_eq!(novel.top_char_offset(), 10);
+        novel.sync_char_offset();
+        assert_eq!(novel.progress.char_offset, Some(10));
+    }
+
+    #[test]
+    fn test_restore_char_offset_survives_line_ending_changes() {
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("一\n二\n三\n四".to_string());
+        novel.progress.scroll_offset = 2;
+        novel.sync_char_offset();
+
+        let mut reopened = Novel::new(PathBuf::from("test.txt"));
+        reopened.set_content("一\r\n二\r\n三\r\n四".to_string());
+        reopened.progress = novel.progress.clone();
+        reopened.progress.scroll_offset = 0;
+        reopened.restore_char_offset();
+        assert_eq!(reopened.progress.scroll_offset, 2);
+    }
+
+    #[test]
+    fn test_chapter_stats_counts_lines_and_words() {
+        let mut novel = Novel::new(PathBuf::from("stats.txt"));
+        novel.set_content("第一章\n　　你好 世界\n第二章\nabc".to_string());
+        novel.parse_chapters();
+
+        assert_eq!(novel.chapter_stats(0), Some((2, 7)));
+        assert_eq!(novel.chapter_stats(1), Some((2, 6)));
+        assert_eq!(novel.chapter_stats(2), None);
+    }
+
+    #[test]
+    fn test_chapter_progress() {
+        let mut novel = Novel::new(PathBuf::from("progress.txt"));
+        let mut lines = vec!["第一章".to_string()];
+        lines.extend((0..9).map(|i| format!("正文{}", i)));
+        lines.push("第二章".to_string());
+        lines.extend((0..3).map(|i| format!("正文{}", i)));
+        novel.set_content(lines.join("\n"));
+        novel.parse_chapters();
+
+        assert_eq!(novel.chapter_progress(), Some((0, 0)));
+        novel.progress.scroll_offset = 5;
+        assert_eq!(novel.chapter_progress(), Some((0, 50)));
+        novel.progress.scroll_offset = 12;
+        assert_eq!(novel.chapter_progress(), Some((1, 50)));
+
+        novel.chapters.clear();
+        assert_eq!(novel.chapter_progress(), None);
+    }
+
+    #[test]
+    fn test_is_chapter_read_uses_furthest_line() {
+        let mut novel = Novel::new(PathBuf::from("read.txt"));
+        novel.set_content("第一章\n正文\n第二章\n正文\n第三章\n正文".to_string());
+        novel.parse_chapters();
+        assert!(!novel.is_chapter_read(0));
+
+        novel.progress.scroll_offset = 4;
+        novel.record_furthest_line();
+        assert!(novel.is_chapter_read(1));
+
+        // 往回跳后仍保留已读标记
+        novel.progress.scroll_offset = 0;
+        assert!(novel.is_chapter_read(0));
+        assert!(novel.is_chapter_read(1));
+        assert!(!novel.is_chapter_read(2));
+    }
+
+    #[test]
+    fn test_add_highlight_merges_overlapping_ranges() {
+        let mut progress = ReadingProgress::default();
+        progress.add_highlight(10, 12);
+        progress.add_highlight(3, 1);
+        progress.add_highlight(20, 25);
+        let ranges = |p: &ReadingProgress| -> Vec<(usize, usize)> {
+            p.highlights.iter().map(|h| (h.start, h.end)).collect()
+        };
+        assert_eq!(ranges(&progress), vec![(1, 3), (10, 12), (20, 25)]);
+
+        // 与两段都重叠或相邻时合并为一段
+        progress.add_highlight(13, 21);
+        assert_eq!(ranges(&progress), vec![(1, 3), (10, 25)]);
+        assert!(progress.highlights[1].contains(25));
+        assert!(!progress.highlights[1].contains(26));
+    }
+
+    #[test]
+    fn test_set_annotation_replaces_and_removes() {
+        let mut progress = ReadingProgress::default();
+        progress.set_annotation(8, "second".to_string());
+        progress.set_annotation(2, "first".to_string());
+        progress.set_annotation(8, "replaced".to_string());
+        let notes: Vec<(usize, &str)> = progress
+            .annotations
+            .iter()
+            .map(|a| (a.line, a.note.as_str()))
+            .collect();
+        assert_eq!(notes, vec![(2, "first"), (8, "replaced")]);
+
+        progress.set_annotation(2, "  ".to_string());
+        assert_eq!(progress.annotations.len(), 1);
+        assert_eq!(progress.annotations[0].line, 8);
+    }
+
+    #[test]
+    fn test_paragraph_range_stops_at_blank_lines() {
+        let mut novel = Novel::new(PathBuf::from("test.txt"));
+        novel.set_content("a1\na2\n\nb1\nb2\nb3\n\u{3000}\nc1".to_string());
+        assert_eq!(novel.paragraph_range(0), 0..2);
+        assert_eq!(novel.paragraph_range(4), 3..6);
+        assert_eq!(novel.paragraph_range(7), 7..8);
+        // 空行（包括只含全角空格的行）只包含自身
+        assert_eq!(novel.paragraph_range(6), 6..7);
+        assert_eq!(novel.paragraph_range(20), 20..20);
+    }
 }
@@ -0,0 +1,358 @@
+use super::library::Library;
+use crate::i18n::{tr, trf};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// 翻页重叠行数的上限
+pub const MAX_PAGE_OVERLAP: usize = 5;
+
+/// 阅读区正文与边框之间的留白（终端列数/行数）
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReaderPadding {
+    pub left: u16,
+    pub right: u16,
+    pub top: u16,
+    pub bottom: u16,
+}
+
+/// 书架的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookshelfSort {
+    /// 按标题
+    #[default]
+    Title,
+    /// 按最近阅读时间，最近读过的在前
+    RecentlyRead,
+    /// 按阅读进度，读得多的在前
+    Progress,
+    /// 按文件大小，大的在前
+    FileSize,
+}
+
+impl BookshelfSort {
+    /// 切换到下一种排序方式
+    pub fn next(self) -> Self {
+        match self {
+            BookshelfSort::Title => BookshelfSort::RecentlyRead,
+            BookshelfSort::RecentlyRead => BookshelfSort::Progress,
+            BookshelfSort::Progress => BookshelfSort::FileSize,
+            BookshelfSort::FileSize => BookshelfSort::Title,
+        }
+    }
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            BookshelfSort::Title => tr("按标题", "By title"),
+            BookshelfSort::RecentlyRead => tr("按最近阅读", "By last read"),
+            BookshelfSort::Progress => tr("按阅读进度", "By progress"),
+            BookshelfSort::FileSize => tr("按文件大小", "By file size"),
+        }
+    }
+}
+
+/// 界面主题
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeName {
+    /// 深色，沿用终端背景
+    #[default]
+    Dark,
+    /// 浅色
+    Light,
+    /// 护眼（米黄底）
+    Sepia,
+    /// 高对比度
+    HighContrast,
+}
+
+impl ThemeName {
+    /// 所有主题，按设置中的显示顺序
+    pub const ALL: [ThemeName; 4] = [
+        ThemeName::Dark,
+        ThemeName::Light,
+        ThemeName::Sepia,
+        ThemeName::HighContrast,
+    ];
+
+    /// 界面上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            ThemeName::Dark => tr("深色", "Dark"),
+            ThemeName::Light => tr("浅色", "Light"),
+            ThemeName::Sepia => tr("护眼", "Sepia"),
+            ThemeName::HighContrast => tr("高对比度", "High contrast"),
+        }
+    }
+}
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// 中文
+    #[default]
+    Chinese,
+    /// 英文
+    English,
+}
+
+impl Language {
+    /// 切换到另一种语言
+    pub fn next(self) -> Self {
+        match self {
+            Language::Chinese => Language::English,
+            Language::English => Language::Chinese,
+        }
+    }
+
+    /// 界面上显示的名称（用该语言本身书写）
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::Chinese => "中文",
+            Language::English => "English",
+        }
+    }
+}
+
+/// 退出程序的按键
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuitKey {
+    /// 按 `q` 退出（输入时除外），书架上按 `Esc` 也会退出
+    #[default]
+    Q,
+    /// 只有 `Ctrl+q` 退出，输入时也可用
+    CtrlQ,
+    /// 不用单个按键退出，只能输入 `:q` 或按 `,q`
+    Disabled,
+}
+
+impl QuitKey {
+    /// 帮助栏中显示的退出方式
+    pub fn hint(self) -> &'static str {
+        match self {
+            QuitKey::Q => "q",
+            QuitKey::CtrlQ => "Ctrl+q",
+            QuitKey::Disabled => ":q",
+        }
+    }
+}
+
+/// 用户偏好设置
+///
+/// 与具体小说无关的全局阅读选项，保存在 `preferences.json`。
+/// 缺失的字段使用默认值，便于后续新增选项时兼容旧文件。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preferences {
+    /// 翻页时保留的上一页行数（逻辑行）
+    pub page_overlap: usize,
+    /// 英文长单词被强制断行时是否添加连字符
+    pub hyphenate: bool,
+    /// 阅读区留白
+    pub padding: ReaderPadding,
+    /// 退出前是否弹出确认对话框
+    pub confirm_quit: bool,
+    /// 退出程序的按键
+    pub quit_key: QuitKey,
+    /// 自定义章节标题正则，对所有小说生效，优先于内置规则
+    pub chapter_patterns: Vec<String>,
+    /// 书架的排序方式
+    pub bookshelf_sort: BookshelfSort,
+    /// 书架上折叠的分类（子文件夹路径）
+    pub collapsed_categories: BTreeSet<String>,
+    /// 默认小说目录之外的小说目录（如 Syncthing 同步文件夹）
+    pub library_dirs: Vec<PathBuf>,
+    /// 在书架上显示由书名生成的色块、在详情中显示字符封面
+    pub book_covers: bool,
+    /// 界面主题
+    pub theme: ThemeName,
+    /// 界面语言
+    pub language: Language,
+    /// 首次启动向导已完成或跳过，书架为空时也不再显示
+    pub setup_done: bool,
+    /// 旧版本按小说标题保存的章节正则，启动时迁移到各书的章节设置后清空
+    #[serde(rename = "novel_chapter_patterns", skip_serializing)]
+    pub legacy_novel_chapter_patterns: HashMap<String, Vec<String>>,
+    /// 旧版本关闭章节自动识别的小说标题，启动时迁移到各书的章节设置后清空
+    #[serde(rename = "auto_chapters_disabled", skip_serializing)]
+    pub legacy_auto_chapters_disabled: BTreeSet<String>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            page_overlap: 1,
+            hyphenate: false,
+            padding: ReaderPadding::default(),
+            confirm_quit: true,
+            quit_key: QuitKey::default(),
+            chapter_patterns: Vec::new(),
+            bookshelf_sort: BookshelfSort::default(),
+            collapsed_categories: BTreeSet::new(),
+            library_dirs: Vec::new(),
+            book_covers: true,
+            theme: ThemeName::default(),
+            language: Language::default(),
+            setup_done: false,
+            legacy_novel_chapter_patterns: HashMap::new(),
+            legacy_auto_chapters_disabled: BTreeSet::new(),
+        }
+    }
+}
+
+impl Preferences {
+    /// 从文件加载偏好设置
+    ///
+    /// 文件不存在或解析失败时返回默认值。
+    pub fn load() -> Self {
+        let path = Self::preferences_path();
+        if path.exists() {
+            match std::fs::read_to_string(&path) {
+                Ok(content) => match serde_json::from_str(&content) {
+                    Ok(preferences) => return preferences,
+                    Err(e) => {
+                        eprintln!("Failed to parse preferences.json: {}", e);
+                        return Self::default();
+                    }
+                },
+                Err(e) => {
+                    eprintln!("Failed to read preferences.json: {}", e);
+                    return Self::default();
+                }
+            }
+        }
+        Self::default()
+    }
+
+    /// 重新读取偏好设置文件（程序运行期间文件被修改时调用）
+    ///
+    /// # Errors
+    ///
+    /// 文件无法读取或解析时返回错误说明。
+    pub fn reload() -> Result<Self, String> {
+        let content = std::fs::read_to_string(Self::preferences_path()).map_err(|e| {
+            trf!(
+                "读取 preferences.json 失败：{}",
+                "Failed to read preferences.json: {}",
+                e
+            )
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            trf!(
+                "preferences.json 解析失败：{}",
+                "Failed to parse preferences.json: {}",
+                e
+            )
+        })
+    }
+
+    /// 保存偏好设置到文件
+    ///
+    /// # Errors
+    ///
+    /// 返回 IO 操作或序列化错误。
+    pub fn save(&self) -> std::io::Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(Self::preferences_path(), content)
+    }
+
+    /// 编译适用于一本小说的自定义章节标题正则（全局规则在前）
+    ///
+    /// # Arguments
+    ///
+    /// * `novel_patterns` - 该小说章节设置中的正则
+    ///
+    /// # Errors
+    ///
+    /// 任一正则无法编译时返回错误，错误信息中包含该正则。
+    pub fn chapter_regexes(&self, novel_patterns: &[String]) -> Result<Vec<Regex>, String> {
+        self.chapter_patterns
+            .iter()
+            .chain(novel_patterns)
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| {
+                    trf!(
+                        "章节正则 {} 无效: {}",
+                        "Invalid chapter regex {}: {}",
+                        pattern,
+                        e
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// 偏好设置文件与进度文件位于同一目录（测试环境下同样被隔离）
+    pub fn preferences_path() -> PathBuf {
+        Library::get_progress_path().with_file_name("preferences.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let preferences: Preferences = serde_json::from_str("{}").unwrap();
+        assert_eq!(preferences, Preferences::default());
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let preferences = Preferences {
+            page_overlap: 3,
+            hyphenate: true,
+            padding: ReaderPadding {
+                left: 2,
+                right: 2,
+                top: 1,
+                bottom: 0,
+            },
+            confirm_quit: false,
+            quit_key: QuitKey::CtrlQ,
+            chapter_patterns: vec![r"^Part \w+$".to_string()],
+            bookshelf_sort: BookshelfSort::RecentlyRead,
+            ..Default::default()
+        };
+        preferences.save().unwrap();
+        assert_eq!(Preferences::load(), preferences);
+        let _ = std::fs::remove_file(Preferences::preferences_path());
+    }
+
+    #[test]
+    fn test_legacy_per_novel_fields_are_read_but_not_written() {
+        let json = r#"{"novel_chapter_patterns": {"测试": ["^【第.+章】"]}, "auto_chapters_disabled": ["测试"]}"#;
+        let preferences: Preferences = serde_json::from_str(json).unwrap();
+        assert_eq!(preferences.legacy_novel_chapter_patterns["测试"].len(), 1);
+        assert!(preferences.legacy_auto_chapters_disabled.contains("测试"));
+
+        let saved = serde_json::to_string(&preferences).unwrap();
+        assert!(!saved.contains("novel_chapter_patterns"));
+        assert!(!saved.contains("auto_chapters_disabled"));
+    }
+
+    #[test]
+    fn test_chapter_regexes_merge_global_and_per_novel() {
+        let mut preferences = Preferences {
+            chapter_patterns: vec![r"^Part \w+$".to_string()],
+            ..Default::default()
+        };
+        let novel_patterns = vec!["^【第.+章】".to_string()];
+
+        assert_eq!(preferences.chapter_regexes(&[]).unwrap().len(), 1);
+        assert_eq!(
+            preferences.chapter_regexes(&novel_patterns).unwrap().len(),
+            2
+        );
+
+        preferences.chapter_patterns.push("(".to_string());
+        assert!(preferences.chapter_regexes(&[]).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! 从文件名识别系列小说的卷号，如 “三体2”、“斗破苍穹 第三部”、“Книга 1”、“Dune Book 2”
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// 卷号：阿拉伯数字，或带 “第” 前缀/“部卷册” 后缀的中文数字（避免把 “三体” 的 “三” 当成卷号）
+static VOLUME_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(第\s*)?(\d+|[零〇一二两三四五六七八九十百]+)(\s*[部卷册集季篇])?").unwrap()
+});
+
+/// 卷号前面表示 “第几卷” 的词，识别系列名时去掉
+const VOLUME_WORDS: &[&str] = &[
+    "volume",
+    "vol",
+    "book",
+    "part",
+    "книга",
+    "том",
+    "часть",
+    "卷",
+    "部",
+];
+
+/// 解析中文数字（一 ~ 九百九十九）
+fn parse_chinese_number(text: &str) -> Option<u32> {
+    let value_of = |c: char| match c {
+        '零' | '〇' => Some(0),
+        '一' => Some(1),
+        '二' | '两' => Some(2),
+        '三' => Some(3),
+        '四' => Some(4),
+        '五' => Some(5),
+        '六' => Some(6),
+        '七' => Some(7),
+        '八' => Some(8),
+        '九' => Some(9),
+        _ => None,
+    };
+    let mut total = 0;
+    let mut current = 0;
+    for c in text.chars() {
+        match c {
+            '百' => {
+                total += current.max(1) * 100;
+                current = 0;
+            }
+            '十' => {
+                total += current.max(1) * 10;
+                current = 0;
+            }
+            _ => current = current * 10 + value_of(c)?,
+        }
+    }
+    Some(total + current)
+}
+
+/// 从标题中识别系列名和卷号
+///
+/// 取标题中最后一个卷号，卷号前面的部分去掉分隔符和 “Book”、“第” 等词后作为系列名。
+///
+/// # Returns
+///
+/// `(系列名（小写）, 卷号)`；没有卷号或卷号前没有系列名时返回 `None`。
+pub fn parse_volume(title: &str) -> Option<(String, u32)> {
+    let captures = VOLUME_RE
+        .captures_iter(title)
+        .filter(|caps| {
+            let is_arabic = caps[2].chars().all(|c| c.is_ascii_digit());
+            is_arabic || caps.get(1).is_some() || caps.get(3).is_some()
+        })
+        .last()?;
+    let number = &captures[2];
+    let volume = if number.chars().all(|c| c.is_ascii_digit()) {
+        number.parse().ok()?
+    } else {
+        parse_chinese_number(number)?
+    };
+
+    let is_separator = |c: char| c.is_whitespace() || "_-—·.,，、:：()（）[]【】《》#".contains(c);
+    let prefix = title[..captures.get(0)?.start()]
+        .trim_end_matches(is_separator)
+        .to_lowercase();
+    let mut name = prefix.as_str();
+    for word in VOLUME_WORDS {
+        if let Some(stripped) = name.strip_suffix(word) {
+            name = stripped.trim_end_matches(is_separator);
+            break;
+        }
+    }
+    // “Книга1” 这样只有卷名的标题，保留卷名作为系列名
+    let name = if name.is_empty() {
+        prefix.as_str()
+    } else {
+        name
+    };
+    if name.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), volume))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_volume_recognizes_common_patterns() {
+        let cases = [
+            ("三体2", Some(("三体", 2))),
+            ("三体 2：黑暗森林", Some(("三体", 2))),
+            ("斗破苍穹 第三部", Some(("斗破苍穹", 3))),
+            ("斗破苍穹第十二卷", Some(("斗破苍穹", 12))),
+            ("Dune Book 2", Some(("dune", 2))),
+            ("Foundation_vol.03", Some(("foundation", 3))),
+            ("Война и мир. Книга 1", Some(("война и мир", 1))),
+            ("книга1", Some(("книга", 1))),
+            ("第二十一部", None),
+            ("三体", None),
+            ("2001", None),
+            ("围城", None),
+        ];
+        for (title, expected) in cases {
+            let expected = expected.map(|(name, volume)| (name.to_string(), volume));
+            assert_eq!(parse_volume(title), expected, "{}", title);
+        }
+    }
+}
@@ -0,0 +1,326 @@
+//! 回收站：删除的小说先移到 `trash/` 目录，可以在设置中恢复，超过保留天数后自动清除
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::chapter_cache;
+use super::library::{Library, NovelInfo};
+use crate::i18n::{tr, trf};
+
+/// 回收站中的一本小说
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    /// 回收站目录中的文件名（带删除时间前缀，同一秒内删除同名文件时再加序号，避免冲突）
+    pub file_name: String,
+    /// 删除前的文件路径，恢复时放回原处
+    pub original_path: PathBuf,
+    /// 删除时间（Unix 秒）
+    pub deleted_at: u64,
+    /// 删除前的书库记录（阅读进度、书签、标签等），恢复时一并还原
+    #[serde(default)]
+    pub info: Option<NovelInfo>,
+}
+
+impl TrashEntry {
+    /// 删除前的书名
+    pub fn title(&self) -> String {
+        self.original_path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 文件在回收站中的路径
+    pub fn trashed_path(&self) -> PathBuf {
+        Trash::dir().join(&self.file_name)
+    }
+}
+
+/// 回收站索引，保存在 `trash/trash.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Trash {
+    /// 按删除时间先后排列的条目
+    pub entries: Vec<TrashEntry>,
+}
+
+impl Trash {
+    /// 回收站目录与进度文件位于同一目录（测试环境下同样被隔离）
+    pub fn dir() -> PathBuf {
+        Library::get_progress_path().with_file_name("trash")
+    }
+
+    fn index_path() -> PathBuf {
+        Self::dir().join("trash.json")
+    }
+
+    /// 读取回收站索引，文件不存在或解析失败时返回空回收站
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::index_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// 保存回收站索引
+    pub fn save(&self) -> std::io::Result<()> {
+        std::fs::create_dir_all(Self::dir())?;
+        std::fs::write(Self::index_path(), serde_json::to_string_pretty(self)?)
+    }
+
+    /// 把小说文件移入回收站并保存索引，同时删除它的章节缓存
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - 小说文件路径
+    /// * `info` - 小说在书库中的记录，恢复时还原
+    pub fn move_in(&mut self, path: &Path, info: Option<NovelInfo>) -> std::io::Result<()> {
+        let deleted_at = now();
+        let file_name = self.unique_file_name(
+            deleted_at,
+            &path.file_name().unwrap_or_default().to_string_lossy(),
+        );
+        std::fs::create_dir_all(Self::dir())?;
+        move_file(path, &Self::dir().join(&file_name))?;
+        chapter_cache::remove(path);
+        self.entries.push(TrashEntry {
+            file_name,
+            original_path: path.to_path_buf(),
+            deleted_at,
+            info,
+        });
+        self.save()
+    }
+
+    /// 回收站中未被占用的文件名：`删除时间-文件名`，已被占用时为 `删除时间-序号-文件名`
+    ///
+    /// 不同文件夹中可以有同名小说，同一秒内删除它们（如批量删除）时靠序号区分。
+    fn unique_file_name(&self, deleted_at: u64, name: &str) -> String {
+        let mut file_name = format!("{}-{}", deleted_at, name);
+        let mut n = 1;
+        while Self::dir().join(&file_name).exists()
+            || self
+                .entries
+                .iter()
+                .any(|entry| entry.file_name == file_name)
+        {
+            file_name = format!("{}-{}-{}", deleted_at, n, name);
+            n += 1;
+        }
+        file_name
+    }
+
+    /// 把回收站中的小说放回原处并保存索引
+    ///
+    /// # Returns
+    ///
+    /// 恢复的条目；原位置已有同名文件或移动失败时返回错误，条目保留在回收站中。
+    pub fn restore(&mut self, index: usize) -> std::io::Result<TrashEntry> {
+        let Some(entry) = self.entries.get(index) else {
+            return Err(std::io::Error::other(tr(
+                "回收站中没有这本小说",
+                "This novel is not in the trash",
+            )));
+        };
+        if entry.original_path.exists() {
+            return Err(std::io::Error::other(trf!(
+                "{} 已存在",
+                "{} already exists",
+                entry.original_path.display()
+            )));
+        }
+        if let Some(parent) = entry.original_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        move_file(&entry.trashed_path(), &entry.original_path)?;
+        let entry = self.entries.remove(index);
+        self.save()?;
+        Ok(entry)
+    }
+
+    /// 彻底删除回收站中的小说并保存索引
+    pub fn remove(&mut self, index: usize) -> std::io::Result<()> {
+        if index >= self.entries.len() {
+            return Ok(());
+        }
+        let entry = self.entries.remove(index);
+        remove_if_exists(&entry.trashed_path())?;
+        self.save()
+    }
+
+    /// 清除删除时间超过保留天数的小说
+    ///
+    /// 某个文件删除失败时继续清除其余文件，失败的条目留在回收站中，索引总会保存。
+    ///
+    /// # Returns
+    ///
+    /// 清除的数量。
+    ///
+    /// # Errors
+    ///
+    /// 返回第一个删除失败的 IO 错误，或保存索引时的错误。
+    pub fn purge_expired(&mut self, retention_days: u64) -> std::io::Result<usize> {
+        let cutoff = now().saturating_sub(retention_days * 24 * 60 * 60);
+        let mut purged = 0;
+        let mut first_error = None;
+        self.entries.retain(|entry| {
+            if entry.deleted_at >= cutoff {
+                return true;
+            }
+            match remove_if_exists(&entry.trashed_path()) {
+                Ok(()) => {
+                    purged += 1;
+                    false
+                }
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                    true
+                }
+            }
+        });
+        if purged == 0 && first_error.is_none() {
+            return Ok(0);
+        }
+        self.save()?;
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(purged),
+        }
+    }
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// 移动文件，无法直接重命名（如跨磁盘）时先复制再删除
+///
+/// 目标文件已存在时返回错误，不覆盖。
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if to.exists() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            trf!("{} 已存在", "{} already exists", to.display()),
+        ));
+    }
+    if std::fs::rename(from, to).is_err() {
+        std::fs::copy(from, to)?;
+        std::fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_move_in_restore_and_purge() {
+        let _ = std::fs::remove_dir_all(Trash::dir());
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("回收.txt");
+        std::fs::write(&path, "正文").unwrap();
+        chapter_cache::load_or_parse(&path, &["正文".to_string()], &[], true);
+
+        let mut trash = Trash::load();
+        trash.move_in(&path, None).unwrap();
+        assert!(!path.exists());
+        assert_eq!(trash.entries.len(), 1);
+        assert_eq!(trash.entries[0].title(), "回收");
+        assert!(trash.entries[0].trashed_path().exists());
+
+        // 原位置被占用时不覆盖
+        std::fs::write(&path, "新文件").unwrap();
+        assert!(trash.restore(0).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let entry = Trash::load().restore(0).unwrap();
+        assert_eq!(entry.original_path, path);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "正文");
+        assert!(Trash::load().entries.is_empty());
+        // 移入回收站时章节缓存已删除
+        assert!(chapter_cache::load_cached(&path).is_none());
+
+        let mut trash = Trash::load();
+        trash.move_in(&path, None).unwrap();
+        assert_eq!(trash.purge_expired(30).unwrap(), 0);
+        trash.entries[0].deleted_at -= 31 * 24 * 60 * 60;
+        let trashed = trash.entries[0].trashed_path();
+        assert_eq!(trash.purge_expired(30).unwrap(), 1);
+        assert!(!trashed.exists());
+        assert!(Trash::load().entries.is_empty());
+    }
+
+    #[test]
+    fn test_same_named_novels_deleted_together_keep_separate_files() {
+        let _ = std::fs::remove_dir_all(Trash::dir());
+        let dir = tempdir().unwrap();
+        let paths = [dir.path().join("a/同名.txt"), dir.path().join("b/同名.txt")];
+        for (i, path) in paths.iter().enumerate() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, format!("第{}本", i)).unwrap();
+        }
+
+        let mut trash = Trash::load();
+        for path in &paths {
+            trash.move_in(path, None).unwrap();
+        }
+        assert_ne!(trash.entries[0].file_name, trash.entries[1].file_name);
+        assert!(
+            trash
+                .entries
+                .iter()
+                .all(|entry| entry.trashed_path().exists())
+        );
+
+        // 目标已存在时不覆盖
+        let trashed: Vec<_> = trash.entries.iter().map(TrashEntry::trashed_path).collect();
+        assert!(move_file(&trashed[0], &trashed[1]).is_err());
+
+        let mut trash = Trash::load();
+        trash.restore(1).unwrap();
+        trash.restore(0).unwrap();
+        for (i, path) in paths.iter().enumerate() {
+            assert_eq!(std::fs::read_to_string(path).unwrap(), format!("第{}本", i));
+        }
+    }
+
+    #[test]
+    fn test_purge_keeps_index_consistent_when_a_removal_fails() {
+        let _ = std::fs::remove_dir_all(Trash::dir());
+        let dir = tempdir().unwrap();
+        let mut trash = Trash::load();
+        for name in ["甲.txt", "乙.txt", "丙.txt"] {
+            let path = dir.path().join(name);
+            std::fs::write(&path, "正文").unwrap();
+            trash.move_in(&path, None).unwrap();
+        }
+        for entry in &mut trash.entries {
+            entry.deleted_at -= 31 * 24 * 60 * 60;
+        }
+        // 第二个文件换成非空目录，删除会失败
+        let stuck = trash.entries[1].trashed_path();
+        std::fs::remove_file(&stuck).unwrap();
+        std::fs::create_dir_all(stuck.join("占用")).unwrap();
+        let removable: Vec<_> = [0, 2].map(|i| trash.entries[i].trashed_path()).into();
+
+        assert!(trash.purge_expired(30).is_err());
+        assert!(removable.iter().all(|path| !path.exists()));
+        let saved = Trash::load();
+        assert_eq!(saved.entries.len(), 1);
+        assert_eq!(saved.entries[0].trashed_path(), stuck);
+        assert_eq!(trash.entries.len(), 1);
+        std::fs::remove_dir_all(&stuck).unwrap();
+    }
+}
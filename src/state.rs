@@ -10,6 +10,16 @@ pub enum AppState {
     Settings,
     BookmarkList,
     BookmarkAdd,
+    /// 书架上所有小说的书签
+    AllBookmarks,
+    /// 当前小说的高亮列表
+    HighlightList,
+    /// 当前小说的批注列表
+    AnnotationList,
+    /// 书架上选中小说的详情（编辑标签）
+    BookDetail,
+    /// 首次启动向导
+    Setup,
 }
 
 /// 设置界面的子模式
@@ -26,4 +36,18 @@ pub enum SettingsMode {
     DeleteOrphaned,
     /// WebDAV配置模式
     WebDavConfig,
+    /// 书签导入导出模式
+    BookmarkTransfer,
+    /// 小说目录管理模式
+    LibraryDirs,
+    /// 回收站：恢复或彻底删除已删除的小说
+    Trash,
+    /// 外观：选择界面主题，移动选择时即时预览
+    Appearance,
+    /// 阅读选项：行间距、缩进、最大宽度、翻页重叠和自动滚动速度
+    ReaderOptions,
+    /// 进度备份：预览并恢复进度文件的自动备份
+    Backups,
+    /// 性能：主循环间隔和空闲时的重绘间隔
+    Performance,
 }
@@ -1,3 +1,44 @@
+use crate::i18n::tr;
+
+/// 下载同步时合并两端书签的策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookmarkMergePolicy {
+    /// 保留两端的全部书签，只去掉完全相同的重复项
+    Union,
+    /// 只保留本地的书签，远端新增或删除的书签不生效
+    PreferLocal,
+    /// 只保留远端的书签，本地新增或删除的书签不生效
+    PreferRemote,
+    /// 取并集，名称和位置都相同的书签视为同一个（保留远端的版本）
+    #[default]
+    DedupeByNamePosition,
+}
+
+impl BookmarkMergePolicy {
+    /// 在设置界面中按顺序切换到下一个策略
+    pub fn next(self) -> Self {
+        match self {
+            Self::Union => Self::PreferLocal,
+            Self::PreferLocal => Self::PreferRemote,
+            Self::PreferRemote => Self::DedupeByNamePosition,
+            Self::DedupeByNamePosition => Self::Union,
+        }
+    }
+
+    /// 显示名称
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Union => tr("全部保留", "Keep all"),
+            Self::PreferLocal => tr("以本地为准", "Prefer local"),
+            Self::PreferRemote => tr("以远端为准", "Prefer remote"),
+            Self::DedupeByNamePosition => {
+                tr("按名称和位置去重", "Deduplicate by name and position")
+            }
+        }
+    }
+}
+
 /// WebDAV 同步配置
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WebDavConfig {
@@ -6,6 +47,9 @@ pub struct WebDavConfig {
     pub password: String,
     pub enabled: bool,
     pub remote_path: String,
+    /// 下载同步时书签的合并策略
+    #[serde(default)]
+    pub bookmark_merge: BookmarkMergePolicy,
 }
 
 impl Default for WebDavConfig {
@@ -16,6 +60,7 @@ impl Default for WebDavConfig {
             password: String::new(),
             enabled: false,
             remote_path: "/fish_reader/".to_string(),
+            bookmark_merge: BookmarkMergePolicy::default(),
         }
     }
 }
@@ -55,10 +100,18 @@ impl WebDavConfig {
     }
 
     fn config_path() -> std::path::PathBuf {
-        let mut path = home::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
-        path.push(".fish_reader");
-        path.push("webdav.json");
-        path
+        #[cfg(test)]
+        {
+            crate::model::library::Library::get_progress_path().with_file_name("webdav.json")
+        }
+
+        #[cfg(not(test))]
+        {
+            let mut path = home::home_dir().unwrap_or_else(|| std::path::PathBuf::from("."));
+            path.push(".fish_reader");
+            path.push("webdav.json");
+            path
+        }
     }
 
     pub fn is_configured(&self) -> bool {
@@ -91,4 +144,18 @@ mod tests {
         config.enabled = false;
         assert!(!config.is_configured());
     }
+
+    #[test]
+    fn test_bookmark_merge_policy_defaults_when_missing() {
+        let json = r#"{"url":"","username":"","password":"","enabled":false,"remote_path":"/"}"#;
+        let config: WebDavConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.bookmark_merge,
+            BookmarkMergePolicy::DedupeByNamePosition
+        );
+
+        let json = json.replace("}", r#","bookmark_merge":"prefer-local"}"#);
+        let config: WebDavConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(config.bookmark_merge, BookmarkMergePolicy::PreferLocal);
+    }
 }
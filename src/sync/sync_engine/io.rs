@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 use std::path::{Component, Path, PathBuf};
 
-#[cfg(test)]
-use crate::config::CONFIG;
+use crate::config::config;
 
 use super::{FileEntry, SyncEngine, SyncManifest};
+use crate::model::library::Library;
 
 impl SyncEngine {
     #[cfg(test)]
@@ -14,7 +14,7 @@ impl SyncEngine {
             .replace(|c: char| !c.is_ascii_alphanumeric(), "_");
         path.push(format!(
             "{}_test_{}_{}",
-            CONFIG.dir_name,
+            config().dir_name,
             std::process::id(),
             thread_id
         ));
@@ -26,7 +26,7 @@ impl SyncEngine {
         {
             let path = Self::test_data_dir();
             let _ = std::fs::create_dir_all(&path);
-            return path;
+            path
         }
 
         #[cfg(not(test))]
@@ -50,6 +50,8 @@ impl SyncEngine {
     }
 
     /// 校验 rel_path 不包含路径穿越，返回安全的本地路径
+    ///
+    /// `novels/` 开头的键对应小说目录中的文件，其余对应数据目录中的文件。
     pub(super) fn safe_local_path(data_dir: &Path, rel_path: &str) -> anyhow::Result<PathBuf> {
         let rel = Path::new(rel_path);
         for component in rel.components() {
@@ -60,7 +62,10 @@ impl SyncEngine {
                 _ => {}
             }
         }
-        let full = data_dir.join(rel);
+        let full = match rel_path.strip_prefix("novels/") {
+            Some(novel_rel) => Library::get_novels_dir().join(novel_rel),
+            None => data_dir.join(rel),
+        };
         Ok(full)
     }
 
@@ -106,14 +111,19 @@ impl SyncEngine {
         let data_dir = Self::data_dir();
         let mut files = HashMap::new();
 
-        let novels_dir = data_dir.join("novels");
+        // 小说目录可能不在数据目录中，远程统一使用 `novels/` 开头的键
+        let novels_dir = Library::get_novels_dir();
         if novels_dir.exists() {
             for entry in walkdir::WalkDir::new(&novels_dir) {
                 let entry = entry?;
                 let path = entry.path();
-                if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("txt") {
-                    let relative = path.strip_prefix(&data_dir)?;
-                    let key = relative.to_string_lossy().replace('\\', "/");
+                let is_novel = path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| config().is_supported_extension(ext));
+                if path.is_file() && is_novel {
+                    let relative = path.strip_prefix(&novels_dir)?;
+                    let key = format!("novels/{}", relative.to_string_lossy().replace('\\', "/"));
                     let meta = std::fs::metadata(path)?;
                     let mtime = meta
                         .modified()?
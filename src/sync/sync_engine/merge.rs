@@ -2,10 +2,16 @@ use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use super::SyncEngine;
+use crate::model::novel::MAX_AUTO_POSITIONS;
+use crate::sync::config::BookmarkMergePolicy;
 
 impl SyncEngine {
-    /// 合并远程 progress.json 与本地：取较大的 scroll_offset，书签取并集
-    pub(super) fn merge_progress(data_dir: &Path, remote_bytes: &[u8]) -> anyhow::Result<()> {
+    /// 合并远程 progress.json 与本地：取较靠后的阅读位置，书签按 `policy` 合并
+    pub(super) fn merge_progress(
+        data_dir: &Path,
+        remote_bytes: &[u8],
+        policy: BookmarkMergePolicy,
+    ) -> anyhow::Result<()> {
         let progress_path = data_dir.join("progress.json");
 
         let remote: serde_json::Value = serde_json::from_slice(remote_bytes)?;
@@ -27,17 +33,18 @@ impl SyncEngine {
             }
         };
 
-        let merged = Self::merge_library_json(&local, &remote);
+        let merged = Self::merge_library_json(&local, &remote, policy);
         let output = serde_json::to_string_pretty(&merged)?;
         std::fs::write(&progress_path, output)?;
 
         Ok(())
     }
 
-    /// 按小说合并 Library JSON：取较大 scroll_offset，书签取并集
+    /// 按小说合并 Library JSON：取较靠后的阅读位置（优先比较 char_offset），书签按 `policy` 合并
     pub(super) fn merge_library_json(
         local: &serde_json::Value,
         remote: &serde_json::Value,
+        policy: BookmarkMergePolicy,
     ) -> serde_json::Value {
         let empty_arr = serde_json::Value::Array(vec![]);
 
@@ -73,7 +80,7 @@ impl SyncEngine {
             seen_titles.insert(title.clone());
 
             if let Some(local_novel) = local_map.get(&title) {
-                merged_novels.push(Self::merge_novel(local_novel, remote_novel));
+                merged_novels.push(Self::merge_novel(local_novel, remote_novel, policy));
             } else {
                 merged_novels.push(remote_novel.clone());
             }
@@ -120,11 +127,20 @@ impl SyncEngine {
     pub(super) fn merge_novel(
         local: &serde_json::Value,
         remote: &serde_json::Value,
+        policy: BookmarkMergePolicy,
     ) -> serde_json::Value {
         let mut merged = remote.clone();
         if let Some(local_path) = local.get("path") {
             merged["path"] = local_path.clone();
         }
+        // 章节识别设置、标签、收藏和阅读状态以远端为准，远端没有时保留本地的
+        for field in ["chapter_settings", "tags", "favorite", "status"] {
+            if merged.get(field).is_none()
+                && let Some(value) = local.get(field)
+            {
+                merged[field] = value.clone();
+            }
+        }
 
         let local_offset = local
             .get("progress")
@@ -136,40 +152,134 @@ impl SyncEngine {
             .and_then(|p| p.get("scroll_offset"))
             .and_then(|v| v.as_u64())
             .unwrap_or(0);
-        let max_offset = local_offset.max(remote_offset);
-
-        let empty_arr = serde_json::Value::Array(vec![]);
-        let local_bookmarks = local
-            .get("progress")
-            .and_then(|p| p.get("bookmarks"))
-            .unwrap_or(&empty_arr)
-            .as_array()
-            .cloned()
-            .unwrap_or_default();
-        let remote_bookmarks = remote
-            .get("progress")
-            .and_then(|p| p.get("bookmarks"))
-            .unwrap_or(&empty_arr)
-            .as_array()
-            .cloned()
-            .unwrap_or_default();
-
-        let mut seen_positions: HashSet<u64> = HashSet::new();
-        let mut merged_bookmarks: Vec<serde_json::Value> = Vec::new();
+        // 两端都有字符偏移时以字符偏移比较远近，否则退回比较行号
+        let char_offset = |value: &serde_json::Value| {
+            value
+                .get("progress")
+                .and_then(|p| p.get("char_offset"))
+                .and_then(|v| v.as_u64())
+        };
+        let local_ahead = match (char_offset(local), char_offset(remote)) {
+            (Some(l), Some(r)) => l > r,
+            _ => local_offset > remote_offset,
+        };
+        let (max_offset, max_char_offset) = if local_ahead {
+            (local_offset, char_offset(local))
+        } else {
+            (remote_offset, char_offset(remote))
+        };
 
-        for bm in remote_bookmarks.iter().chain(local_bookmarks.iter()) {
-            let pos = bm.get("position").and_then(|p| p.as_u64()).unwrap_or(0);
-            if seen_positions.insert(pos) {
-                merged_bookmarks.push(bm.clone());
-            }
-        }
-        merged_bookmarks.sort_by_key(|bm| bm.get("position").and_then(|p| p.as_u64()).unwrap_or(0));
+        let merged_bookmarks = Self::merge_bookmarks(local, remote, policy);
 
         if let Some(progress) = merged.get_mut("progress") {
             progress["scroll_offset"] = serde_json::json!(max_offset);
+            match max_char_offset {
+                Some(offset) => progress["char_offset"] = serde_json::json!(offset),
+                None => {
+                    if let Some(obj) = progress.as_object_mut() {
+                        obj.remove("char_offset");
+                    }
+                }
+            }
             progress["bookmarks"] = serde_json::json!(merged_bookmarks);
+            let furthest = |value: &serde_json::Value| {
+                value
+                    .get("progress")
+                    .and_then(|p| p.get("furthest_line"))
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or(0)
+            };
+            progress["furthest_line"] = serde_json::json!(furthest(local).max(furthest(remote)));
+            progress["auto_positions"] =
+                serde_json::json!(Self::merge_auto_positions(local, remote));
+            // 手动编辑的目录、快捷书签、高亮和批注以远端为准，远端没有时保留本地的数据
+            for field in [
+                "custom_chapters",
+                "quick_slots",
+                "highlights",
+                "annotations",
+            ] {
+                if progress.get(field).is_none()
+                    && let Some(value) = local.get("progress").and_then(|p| p.get(field))
+                {
+                    progress[field] = value.clone();
+                }
+            }
         }
 
         merged
     }
+
+    /// 按策略合并两端的书签，结果按位置排序
+    ///
+    /// 取并集时远端的书签在前，去重时保留远端的版本。
+    fn merge_bookmarks(
+        local: &serde_json::Value,
+        remote: &serde_json::Value,
+        policy: BookmarkMergePolicy,
+    ) -> Vec<serde_json::Value> {
+        let bookmarks = |value: &serde_json::Value| {
+            value
+                .get("progress")
+                .and_then(|p| p.get("bookmarks"))
+                .and_then(|b| b.as_array())
+                .cloned()
+                .unwrap_or_default()
+        };
+        let position = |bm: &serde_json::Value| bm.get("position").and_then(|p| p.as_u64());
+
+        let mut merged = match policy {
+            BookmarkMergePolicy::PreferLocal => bookmarks(local),
+            BookmarkMergePolicy::PreferRemote => bookmarks(remote),
+            BookmarkMergePolicy::Union => {
+                let mut merged = bookmarks(remote);
+                for bm in bookmarks(local) {
+                    if !merged.contains(&bm) {
+                        merged.push(bm);
+                    }
+                }
+                merged
+            }
+            BookmarkMergePolicy::DedupeByNamePosition => {
+                let mut seen: HashSet<(String, Option<u64>)> = HashSet::new();
+                let mut merged = bookmarks(remote);
+                merged.extend(bookmarks(local));
+                merged.retain(|bm| {
+                    let name = bm.get("name").and_then(|n| n.as_str()).unwrap_or_default();
+                    seen.insert((name.to_string(), position(bm)))
+                });
+                merged
+            }
+        };
+        merged.sort_by_key(|bm| position(bm).unwrap_or(0));
+        merged
+    }
+
+    /// 合并两端自动记录的阅读位置：取并集，同一位置保留较新的记录，按时间从新到旧排列
+    fn merge_auto_positions(
+        local: &serde_json::Value,
+        remote: &serde_json::Value,
+    ) -> Vec<serde_json::Value> {
+        let entries = |value: &serde_json::Value| {
+            value
+                .get("progress")
+                .and_then(|p| p.get("auto_positions"))
+                .and_then(|a| a.as_array())
+                .cloned()
+                .unwrap_or_default()
+        };
+        let timestamp = |entry: &serde_json::Value| {
+            entry.get("timestamp").and_then(|t| t.as_u64()).unwrap_or(0)
+        };
+        let mut merged = entries(remote);
+        merged.extend(entries(local));
+        merged.sort_by_key(|entry| std::cmp::Reverse(timestamp(entry)));
+
+        let mut seen_positions: HashSet<u64> = HashSet::new();
+        merged.retain(|entry| {
+            seen_positions.insert(entry.get("position").and_then(|p| p.as_u64()).unwrap_or(0))
+        });
+        merged.truncate(MAX_AUTO_POSITIONS);
+        merged
+    }
 }
@@ -1,3 +1,4 @@
+use crate::i18n::{tr, trf};
 use crate::sync::config::WebDavConfig;
 use crate::sync::webdav_client::WebDavClient;
 use serde::{Deserialize, Serialize};
@@ -78,8 +79,10 @@ impl SyncEngine {
     fn do_sync_up(&self, tx: &Sender<SyncMessage>) -> anyhow::Result<()> {
         let data_dir = Self::data_dir();
 
-        tx.send(SyncMessage::Progress("扫描本地文件...".into()))
-            .ok();
+        tx.send(SyncMessage::Progress(
+            tr("扫描本地文件...", "Scanning local files...").into(),
+        ))
+        .ok();
         let old_manifest = Self::load_local_manifest();
         let local_files = Self::scan_local_files(&old_manifest)?;
 
@@ -89,8 +92,10 @@ impl SyncEngine {
 
         let actions = diff_for_upload(&local_files, &remote_manifest.files);
         if actions.is_empty() {
-            tx.send(SyncMessage::Progress("没有需要同步的变更".into()))
-                .ok();
+            tx.send(SyncMessage::Progress(
+                tr("没有需要同步的变更", "Nothing to sync").into(),
+            ))
+            .ok();
             tx.send(SyncMessage::UploadComplete).ok();
             return Ok(());
         }
@@ -128,14 +133,15 @@ impl SyncEngine {
                         .file_name()
                         .unwrap_or_default()
                         .to_string_lossy();
-                    tx.send(SyncMessage::Progress(format!(
+                    tx.send(SyncMessage::Progress(trf!(
                         "上传 ({}/{}) {}...",
+                        "Uploading ({}/{}) {}...",
                         i + 1,
                         total,
                         display_name
                     )))
                     .ok();
-                    let local_path = data_dir.join(rel_path);
+                    let local_path = Self::safe_local_path(&data_dir, rel_path)?;
                     let contents = std::fs::read(&local_path)?;
                     let remote_path = self.remote_file_path(rel_path);
                     self.client.upload_bytes(&contents, &remote_path)?;
@@ -145,8 +151,9 @@ impl SyncEngine {
                         .file_name()
                         .unwrap_or_default()
                         .to_string_lossy();
-                    tx.send(SyncMessage::Progress(format!(
+                    tx.send(SyncMessage::Progress(trf!(
                         "删除 ({}/{}) {}...",
+                        "Deleting ({}/{}) {}...",
                         i + 1,
                         total,
                         display_name
@@ -177,19 +184,23 @@ impl SyncEngine {
     fn do_sync_down(&self, tx: &Sender<SyncMessage>) -> anyhow::Result<()> {
         let data_dir = Self::data_dir();
 
-        tx.send(SyncMessage::Progress("获取远程清单...".into()))
-            .ok();
+        tx.send(SyncMessage::Progress(
+            tr("获取远程清单...", "Fetching the remote manifest...").into(),
+        ))
+        .ok();
         let remote_manifest = self
             .download_remote_manifest()?
-            .ok_or_else(|| anyhow::anyhow!("远程没有同步数据"))?;
+            .ok_or_else(|| anyhow::anyhow!(tr("远程没有同步数据", "No sync data on the remote")))?;
 
         let old_manifest = Self::load_local_manifest();
         let local_files = Self::scan_local_files(&old_manifest)?;
         let actions = diff_for_download(&local_files, &remote_manifest.files);
 
         if actions.is_empty() {
-            tx.send(SyncMessage::Progress("没有需要同步的变更".into()))
-                .ok();
+            tx.send(SyncMessage::Progress(
+                tr("没有需要同步的变更", "Nothing to sync").into(),
+            ))
+            .ok();
             tx.send(SyncMessage::DownloadComplete).ok();
             return Ok(());
         }
@@ -203,8 +214,9 @@ impl SyncEngine {
                         .file_name()
                         .unwrap_or_default()
                         .to_string_lossy();
-                    tx.send(SyncMessage::Progress(format!(
+                    tx.send(SyncMessage::Progress(trf!(
                         "下载 ({}/{}) {}...",
+                        "Downloading ({}/{}) {}...",
                         i + 1,
                         total,
                         display_name
@@ -215,7 +227,7 @@ impl SyncEngine {
                     let bytes = self.client.download_bytes(&remote_path)?;
 
                     if rel_path == "progress.json" {
-                        Self::merge_progress(&data_dir, &bytes)?;
+                        Self::merge_progress(&data_dir, &bytes, self.config.bookmark_merge)?;
                         downloaded_progress = true;
                     } else {
                         let local_path = Self::safe_local_path(&data_dir, rel_path)?;
@@ -230,8 +242,9 @@ impl SyncEngine {
                         .file_name()
                         .unwrap_or_default()
                         .to_string_lossy();
-                    tx.send(SyncMessage::Progress(format!(
+                    tx.send(SyncMessage::Progress(trf!(
                         "删除 ({}/{}) {}...",
+                        "Deleting ({}/{}) {}...",
                         i + 1,
                         total,
                         display_name
@@ -275,6 +288,7 @@ impl SyncEngine {
 mod tests {
     use super::diff::{DiffAction, diff_for_download, diff_for_upload};
     use super::*;
+    use crate::sync::config::BookmarkMergePolicy;
 
     fn entry(hash: u32) -> FileEntry {
         FileEntry {
@@ -284,6 +298,19 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scan_uses_the_configured_extensions() {
+        let novels_dir = crate::model::library::Library::get_novels_dir();
+        std::fs::write(novels_dir.join("小写.txt"), "正文").unwrap();
+        std::fs::write(novels_dir.join("大写.TXT"), "正文").unwrap();
+        std::fs::write(novels_dir.join("笔记.md"), "正文").unwrap();
+
+        let files = SyncEngine::scan_local_files(&SyncManifest::new()).unwrap();
+        assert!(files.contains_key("novels/小写.txt"));
+        assert!(files.contains_key("novels/大写.TXT"));
+        assert!(!files.contains_key("novels/笔记.md"));
+    }
+
     #[test]
     fn test_diff_for_upload_detects_upload_and_delete() {
         let mut local = HashMap::new();
@@ -357,7 +384,7 @@ mod tests {
     }
 
     #[test]
-    fn test_merge_novel_uses_max_offset_and_dedup_bookmarks() {
+    fn test_merge_novel_uses_max_offset_and_merges_bookmarks_by_policy() {
         let local = serde_json::json!({
             "title": "A",
             "path": "/local/.fish_reader/novels/A.txt",
@@ -381,19 +408,160 @@ mod tests {
             }
         });
 
-        let merged = SyncEngine::merge_novel(&local, &remote);
+        let merged = SyncEngine::merge_novel(&local, &remote, BookmarkMergePolicy::default());
         let mut normalized = merged.clone();
         SyncEngine::normalize_novel_json_path(&mut normalized);
         assert_eq!(normalized["path"].as_str().unwrap(), "novels/A.txt");
         assert_eq!(merged["progress"]["scroll_offset"].as_u64().unwrap(), 200);
 
-        let bookmarks = merged["progress"]["bookmarks"].as_array().unwrap();
-        let positions: Vec<u64> = bookmarks
+        let names = |merged: &serde_json::Value| -> Vec<String> {
+            merged["progress"]["bookmarks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|b| b["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        // 同一行名称不同的书签都保留
+        assert_eq!(names(&merged), vec!["r10", "l10", "l20", "r30"]);
+
+        let merge = |policy| SyncEngine::merge_novel(&local, &remote, policy);
+        assert_eq!(
+            names(&merge(BookmarkMergePolicy::PreferLocal)),
+            vec!["l10", "l20"]
+        );
+        assert_eq!(
+            names(&merge(BookmarkMergePolicy::PreferRemote)),
+            vec!["r10", "r30"]
+        );
+
+        // 名称和位置相同的书签去重时保留远端的版本，全部保留时只去掉完全相同的项
+        let mut edited = local.clone();
+        edited["progress"]["bookmarks"][0] =
+            serde_json::json!({"name": "r10", "position": 10, "timestamp": 9, "note": "本地备注"});
+        edited["progress"]["bookmarks"][1] = remote["progress"]["bookmarks"][1].clone();
+        let dedup = SyncEngine::merge_novel(&edited, &remote, BookmarkMergePolicy::default());
+        assert_eq!(names(&dedup), vec!["r10", "r30"]);
+        assert!(dedup["progress"]["bookmarks"][0].get("note").is_none());
+        let union = SyncEngine::merge_novel(&edited, &remote, BookmarkMergePolicy::Union);
+        assert_eq!(names(&union), vec!["r10", "r10", "r30"]);
+    }
+
+    #[test]
+    fn test_merge_novel_keeps_local_custom_chapters_when_remote_has_none() {
+        let chapters = serde_json::json!([{"title": "序", "start_line": 0}]);
+        let local = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 1, "bookmarks": [], "custom_chapters": chapters}
+        });
+        let remote = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 2, "bookmarks": []}
+        });
+
+        let merged = SyncEngine::merge_novel(&local, &remote, BookmarkMergePolicy::default());
+        assert_eq!(merged["progress"]["custom_chapters"], chapters);
+    }
+
+    #[test]
+    fn test_merge_novel_keeps_local_chapter_settings_and_tags_when_remote_has_none() {
+        let settings = serde_json::json!({"patterns": ["^【.+】$"], "auto_detect": false});
+        let tags = serde_json::json!(["玄幻", "追更中"]);
+        let local = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 1, "bookmarks": []},
+            "chapter_settings": settings,
+            "tags": tags
+        });
+        let remote = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 2, "bookmarks": []}
+        });
+
+        let merged = SyncEngine::merge_novel(&local, &remote, BookmarkMergePolicy::default());
+        assert_eq!(merged["chapter_settings"], settings);
+        assert_eq!(merged["tags"], tags);
+    }
+
+    #[test]
+    fn test_merge_novel_keeps_larger_furthest_line() {
+        let local = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 1, "bookmarks": [], "furthest_line": 300}
+        });
+        let remote = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 200, "bookmarks": [], "furthest_line": 200}
+        });
+
+        let merged = SyncEngine::merge_novel(&local, &remote, BookmarkMergePolicy::default());
+        assert_eq!(merged["progress"]["scroll_offset"], 200);
+        assert_eq!(merged["progress"]["furthest_line"], 300);
+    }
+
+    #[test]
+    fn test_merge_novel_unions_auto_positions() {
+        let local = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 1, "bookmarks": [], "auto_positions": [
+                {"name": "退出时", "position": 500, "timestamp": 30},
+                {"name": "跳转前", "position": 10, "timestamp": 20}
+            ]}
+        });
+        let remote = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 2, "bookmarks": [], "auto_positions": [
+                {"name": "退出时", "position": 10, "timestamp": 25}
+            ]}
+        });
+
+        let merged = SyncEngine::merge_novel(&local, &remote, BookmarkMergePolicy::default());
+        let positions: Vec<u64> = merged["progress"]["auto_positions"]
+            .as_array()
+            .unwrap()
             .iter()
-            .map(|b| b["position"].as_u64().unwrap())
+            .map(|entry| entry["position"].as_u64().unwrap())
             .collect();
-        assert_eq!(positions, vec![10, 20, 30]);
-        assert_eq!(bookmarks[0]["name"].as_str().unwrap(), "r10");
+        assert_eq!(positions, vec![500, 10]);
+        assert_eq!(merged["progress"]["auto_positions"][1]["timestamp"], 25);
+    }
+
+    #[test]
+    fn test_merge_novel_prefers_char_offset_when_both_present() {
+        // 行号更大的一端字符偏移反而更小（例如换行方式不同），应以字符偏移为准
+        let local = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 300, "char_offset": 9000, "bookmarks": []}
+        });
+        let remote = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 250, "char_offset": 9500, "bookmarks": []}
+        });
+
+        let merged = SyncEngine::merge_novel(&local, &remote, BookmarkMergePolicy::default());
+        assert_eq!(merged["progress"]["scroll_offset"].as_u64().unwrap(), 250);
+        assert_eq!(merged["progress"]["char_offset"].as_u64().unwrap(), 9500);
+
+        // 只有一端有字符偏移时按行号比较，并且不能把落后一端的字符偏移带过来
+        let remote_without = serde_json::json!({
+            "title": "A",
+            "path": "novels/A.txt",
+            "progress": {"scroll_offset": 400, "bookmarks": []}
+        });
+        let merged =
+            SyncEngine::merge_novel(&local, &remote_without, BookmarkMergePolicy::default());
+        assert_eq!(merged["progress"]["scroll_offset"].as_u64().unwrap(), 400);
+        assert!(merged["progress"].get("char_offset").is_none());
     }
 
     #[test]
@@ -427,7 +595,8 @@ mod tests {
             ]
         });
 
-        let merged = SyncEngine::merge_library_json(&local, &remote);
+        let merged =
+            SyncEngine::merge_library_json(&local, &remote, BookmarkMergePolicy::default());
         let novels = merged["novels"].as_array().unwrap();
         assert_eq!(novels.len(), 3);
 
@@ -0,0 +1,159 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::utils::{render_help_info, split_preview_area};
+use crate::app::App;
+use crate::i18n::{tr, trf};
+
+/// 批注列表中每项预览的最大字符数
+const PREVIEW_CHARS: usize = 30;
+
+/// 编辑批注时帮助栏中的输入提示
+pub fn annotation_prompt(app: &App) -> Option<String> {
+    let input = app.annotation.input.as_ref()?;
+    Some(trf!(
+        "批注第 {} 行: {}▏ │ Enter:保存（留空删除） Esc:取消",
+        "Annotate line {}: {}▏ │ Enter:Save (empty deletes) Esc:Cancel",
+        app.annotation.editing_line + 1,
+        input
+    ))
+}
+
+/// 渲染当前小说的批注列表
+/// # 参数
+/// - `f`: 渲染框架
+/// - `app`: 应用状态
+pub fn render_annotation_list(f: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let area = f.area();
+
+    let title = Paragraph::new(tr("批注", "Annotation"))
+        .style(Style::default().fg(theme.secondary))
+        .alignment(Alignment::Center);
+    f.render_widget(
+        title,
+        Rect {
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: 2,
+        },
+    );
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let (Some(novel), Some(annotations)) = (&app.current_novel, app.get_current_annotations())
+    else {
+        return;
+    };
+    if annotations.is_empty() {
+        let empty = Paragraph::new(tr(
+            "暂无批注\n\n阅读时按 'a' 给当前行添加批注",
+            "No annotations yet\n\nPress 'a' while reading to annotate the current line",
+        ))
+        .style(Style::default().fg(theme.emphasis))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("提示", "Hint")),
+        );
+        f.render_widget(empty, content_area);
+        render_help_info(
+            f,
+            theme,
+            &trf!(
+                "Esc: 返回阅读 | {}: 退出",
+                "Esc: Back to reading | {}: Quit",
+                app.preferences.quit_key.hint()
+            ),
+            area,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = annotations
+        .iter()
+        .enumerate()
+        .map(|(index, annotation)| {
+            let prefix = if Some(index) == app.annotation.selected_index {
+                ">> "
+            } else {
+                "   "
+            };
+            let preview: String = annotation.note.chars().take(PREVIEW_CHARS).collect();
+            ListItem::new(Line::from(vec![
+                Span::raw(trf!(
+                    "{}第 {} 行  ",
+                    "{}Line {}  ",
+                    prefix,
+                    annotation.line + 1
+                )),
+                Span::styled(preview, Style::default().fg(theme.emphasis)),
+            ]))
+            .style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(trf!(
+            "批注列表 (共{}条)",
+            "Annotations ({} total)",
+            annotations.len()
+        )))
+        .highlight_style(Style::default().bg(theme.selection_bg))
+        .highlight_symbol("");
+
+    let (list_area, detail_area) = split_preview_area(content_area);
+    if let Some(annotation) = app
+        .annotation
+        .selected_index
+        .and_then(|index| annotations.get(index))
+    {
+        let line = novel
+            .lines()
+            .get(annotation.line)
+            .map_or("", |line| line.trim());
+        let text = vec![
+            Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(theme.dim),
+            )),
+            Line::default(),
+            Line::from(annotation.note.clone()),
+        ];
+        let detail = Paragraph::new(text)
+            .style(Style::default().fg(theme.text))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr("内容", "Content")),
+            );
+        f.render_widget(detail, detail_area);
+    }
+
+    let mut state = ListState::default();
+    state.select(app.annotation.selected_index);
+    f.render_stateful_widget(list, list_area, &mut state);
+
+    if let Some(prompt) = annotation_prompt(app) {
+        render_help_info(f, theme, &prompt, area);
+        return;
+    }
+    render_help_info(
+        f,
+        theme,
+        &trf!(
+            "↑/↓: 选择批注 | Enter: 跳转 | e: 编辑 | d: 删除 | Esc: 返回阅读 | {}: 退出",
+            "↑/↓: Select annotation | Enter: Jump | e: Edit | d: Delete | Esc: Back to reading | {}: Quit",
+            app.preferences.quit_key.hint()
+        ),
+        area,
+    );
+}
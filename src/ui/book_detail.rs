@@ -0,0 +1,242 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use unicode_width::UnicodeWidthStr;
+
+use super::bookshelf::format_size;
+use super::chapter_list::format_word_count;
+use super::cover::{COVER_HEIGHT, COVER_WIDTH, cover_lines};
+use super::utils::{format_relative_time, format_timestamp, render_help_info};
+use crate::app::App;
+use crate::i18n::{tr, trf};
+
+/// 渲染书架上选中小说的详情和标签列表
+pub fn render_book_detail(f: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let area = f.area();
+    let Some(index) = app.selected_novel_index else {
+        return;
+    };
+    let Some(novel) = app.novels.get(index) else {
+        return;
+    };
+
+    let title = Paragraph::new(format!("《{}》", novel.title))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Center);
+    f.render_widget(
+        title,
+        Rect {
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: 2,
+        },
+    );
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+    let [info_area, tags_area] =
+        Layout::vertical([Constraint::Length(14), Constraint::Min(3)]).areas(content_area);
+
+    let field = |label: &str, value: String| {
+        let padding = " ".repeat(10usize.saturating_sub(label.width()));
+        Line::from(vec![
+            Span::styled(
+                format!("{}{}", label, padding),
+                Style::default().fg(theme.muted),
+            ),
+            Span::raw(value),
+        ])
+    };
+    let chapter_count = app.bookshelf_chapters(index).len();
+    let info = vec![
+        field(tr("路径", "Path"), novel.path.display().to_string()),
+        field(
+            tr("分类", "Category"),
+            if novel.category.is_empty() {
+                tr("无", "None").to_string()
+            } else {
+                novel.category.clone()
+            },
+        ),
+        field(tr("大小", "Size"), format_size(novel.file_size)),
+        field(
+            tr("编码", "Encoding"),
+            app.bookshelf.detail_stats.as_ref().map_or(
+                tr("未知（读取失败）", "Unknown (read failed)").to_string(),
+                |stats| stats.encoding.to_string(),
+            ),
+        ),
+        field(
+            tr("篇幅", "Length"),
+            app.bookshelf.detail_stats.as_ref().map_or(
+                tr("未知", "Unknown").to_string(),
+                |stats| {
+                    trf!(
+                        "{}行 · {}",
+                        "{} lines · {}",
+                        stats.lines,
+                        format_word_count(stats.words)
+                    )
+                },
+            ),
+        ),
+        field(
+            tr("章节", "Chapters"),
+            if chapter_count == 0 {
+                tr("未知（打开后识别）", "Unknown (detected when opened)").to_string()
+            } else {
+                trf!("共 {} 章", "{} chapters", chapter_count)
+            },
+        ),
+        field(
+            tr("进度", "Progress"),
+            match (
+                app.bookshelf_progress_percent(index),
+                app.bookshelf_chapter_label(index),
+            ) {
+                (Some(percent), Some(label)) => format!("{}%  {}", percent, label),
+                (Some(percent), None) => format!("{}%", percent),
+                _ => tr("未读", "Unread").to_string(),
+            },
+        ),
+        field(
+            tr("书签", "Bookmarks"),
+            match app.bookshelf_bookmark_count(index) {
+                0 => tr("无", "None").to_string(),
+                count => trf!("{} 个", "{}", count),
+            },
+        ),
+        field(
+            tr("状态", "Status"),
+            app.bookshelf_status(index).label().to_string(),
+        ),
+        field(
+            tr("最近阅读", "Last read"),
+            app.bookshelf_last_read(index)
+                .map(|time| {
+                    format!(
+                        "{}（{}）",
+                        format_timestamp(time),
+                        format_relative_time(time)
+                    )
+                })
+                .unwrap_or_else(|| tr("从未", "Never").to_string()),
+        ),
+        field(
+            tr("备注", "Note"),
+            match app.bookshelf_note(index) {
+                "" => tr("无，按 e 添加", "None, press e to add").to_string(),
+                note => note.to_string(),
+            },
+        ),
+        field(tr("重复", "Duplicates"), {
+            let duplicates: Vec<String> = app
+                .bookshelf_duplicates(index)
+                .into_iter()
+                .map(|other| format!("《{}》", app.novels[other].title))
+                .collect();
+            if duplicates.is_empty() {
+                tr("无", "None").to_string()
+            } else {
+                trf!(
+                    "与 {} 内容相同，按 m 合并",
+                    "Same content as {}, press m to merge",
+                    duplicates.join("、")
+                )
+            }
+        }),
+    ];
+    // 宽度足够时在详情右边显示由书名生成的封面
+    let info_area = if app.preferences.book_covers && info_area.width >= 60 {
+        let [info_area, cover_area] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(COVER_WIDTH + 6)])
+                .areas(info_area);
+        let cover = Paragraph::new(cover_lines(&novel.title))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).padding(Padding::top(
+                cover_area.height.saturating_sub(COVER_HEIGHT + 2) / 2,
+            )));
+        f.render_widget(cover, cover_area);
+        info_area
+    } else {
+        info_area
+    };
+    f.render_widget(
+        Paragraph::new(info).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("详情", "Details")),
+        ),
+        info_area,
+    );
+
+    let tags = app.bookshelf_tags(index);
+    let block = Block::default().borders(Borders::ALL).title(trf!(
+        "标签 (共{}个)",
+        "Tags ({} total)",
+        tags.len()
+    ));
+    if tags.is_empty() {
+        let empty = Paragraph::new(tr(
+            "暂无标签，按 'a' 添加，如 玄幻、已完结、追更中",
+            "No tags yet; press 'a' to add some, e.g. fantasy, finished, ongoing",
+        ))
+        .style(Style::default().fg(theme.emphasis))
+        .alignment(Alignment::Center)
+        .block(block);
+        f.render_widget(empty, tags_area);
+    } else {
+        let items: Vec<ListItem> = tags
+            .iter()
+            .enumerate()
+            .map(|(tag_index, tag)| {
+                let prefix = if Some(tag_index) == app.bookshelf.detail_tag_index {
+                    ">> "
+                } else {
+                    "   "
+                };
+                ListItem::new(format!("{}{}", prefix, tag)).style(Style::default().fg(theme.text))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().bg(theme.selection_bg))
+            .highlight_symbol("");
+        let mut state = ListState::default();
+        state.select(app.bookshelf.detail_tag_index);
+        f.render_stateful_widget(list, tags_area, &mut state);
+    }
+
+    let help_text = match (
+        &app.bookshelf.tag_input,
+        &app.bookshelf.rename_input,
+        &app.bookshelf.note_input,
+    ) {
+        (Some(input), _, _) => trf!(
+            "新标签: {}▏ | 逗号分隔多个 | Enter: 添加 | Esc: 取消",
+            "New tags: {}▏ | Separate with commas | Enter: Add | Esc: Cancel",
+            input
+        ),
+        (_, _, Some(input)) => trf!(
+            "备注: {}▏ | Enter: 保存（清空即删除） | Esc: 取消",
+            "Note: {}▏ | Enter: Save (empty deletes) | Esc: Cancel",
+            input
+        ),
+        (None, Some(input), None) => trf!(
+            "重命名: {}▏ | Enter: 确定（保留扩展名，进度和书签随之迁移） | Esc: 取消",
+            "Rename: {}▏ | Enter: Confirm (extension kept, progress and bookmarks follow) | Esc: Cancel",
+            input
+        ),
+        (None, None, None) => trf!(
+            "↑/↓: 选择标签 | a: 添加标签 | d: 删除标签 | e: 备注 | n: 重命名 | x: 删除 | m: 合并重复 | Enter: 打开 | Esc: 返回书架 | {}: 退出",
+            "↑/↓: Select tag | a: Add tag | d: Delete tag | e: Note | n: Rename | x: Delete | m: Merge duplicates | Enter: Open | Esc: Back to bookshelf | {}: Quit",
+            app.preferences.quit_key.hint()
+        ),
+    };
+    render_help_info(f, theme, &help_text, area);
+}
@@ -1,8 +1,11 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use super::utils::render_help_info;
+use super::theme::Theme;
+use super::utils::{format_timestamp, render_help_info, split_preview_area};
 use crate::app::App;
+use crate::i18n::{tr, trf};
+use crate::model::novel::{BOOKMARK_TAGS, Bookmark};
 use crate::state::AppState;
 
 /// 渲染书签管理界面
@@ -13,6 +16,7 @@ pub fn render_bookmark(f: &mut Frame, app: &App) {
     match app.state {
         AppState::BookmarkList => render_bookmark_list(f, app),
         AppState::BookmarkAdd => render_bookmark_add(f, app),
+        AppState::AllBookmarks => render_all_bookmarks(f, app),
         _ => {}
     }
 }
@@ -22,10 +26,11 @@ pub fn render_bookmark(f: &mut Frame, app: &App) {
 /// - `f`: 渲染框架
 /// - `app`: 应用状态
 fn render_bookmark_list(f: &mut Frame, app: &App) {
+    let theme = app.theme();
     let area = f.area();
 
-    let title = Paragraph::new("书签管理")
-        .style(Style::default().fg(Color::Blue))
+    let title = Paragraph::new(tr("书签管理", "Bookmarks"))
+        .style(Style::default().fg(theme.secondary))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -38,13 +43,23 @@ fn render_bookmark_list(f: &mut Frame, app: &App) {
     f.render_widget(title, title_area);
 
     if let Some(bookmarks) = app.get_current_bookmarks() {
-        if bookmarks.is_empty() {
-            let no_bookmarks = Paragraph::new(
-                "暂无书签\n\n按 'a' 或 'A' 添加书签\n按 'm' 或 'M' 在阅读时快速添加书签",
-            )
-            .style(Style::default().fg(Color::Yellow))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("提示"));
+        let order = app.bookmark_display_order();
+        if order.is_empty() {
+            let text = match &app.bookmark.tag_filter {
+                _ if !app.bookmark.filter.is_empty() => {
+                    trf!("没有名称或备注包含“{}”的书签", "No bookmark name or note contains \"{}\"", app.bookmark.filter)
+                }
+                Some(tag) => trf!("没有标签为“{}”的书签\n\n按 't' 切换筛选", "No bookmarks tagged \"{}\"\n\nPress 't' to change the filter", tag),
+                None => tr("暂无书签\n\n按 'a' 或 'A' 添加书签\n阅读时按 'M' 快速添加书签", "No bookmarks yet\n\nPress 'a' or 'A' to add one\nPress 'M' while reading to add one quickly").to_string(),
+            };
+            let no_bookmarks = Paragraph::new(text)
+                .style(Style::default().fg(theme.emphasis))
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(tr("提示", "Hint")),
+                );
 
             let content_area = Rect {
                 x: area.x + 2,
@@ -55,52 +70,102 @@ fn render_bookmark_list(f: &mut Frame, app: &App) {
 
             f.render_widget(no_bookmarks, content_area);
         } else {
-            let items: Vec<ListItem> = bookmarks
-                .iter()
-                .enumerate()
-                .map(|(index, bookmark)| {
-                    let prefix = if Some(index) == app.bookmark.selected_index {
-                        ">> "
-                    } else {
-                        "   "
-                    };
-
-                    let display_text = format!(
-                        "{}{} (行: {})",
-                        prefix,
-                        bookmark.name,
-                        bookmark.position + 1
+            let mut items: Vec<ListItem> = Vec::new();
+            // 选中项所在的行（自动记录前插入了一行分组标题）
+            let mut selected_row = None;
+            for &index in &order {
+                let Some(bookmark) = app.bookmark_entry(index) else {
+                    continue;
+                };
+                if index == bookmarks.len() {
+                    items.push(
+                        ListItem::new(tr("── 自动记录 ──", "── Automatic ──"))
+                            .style(Style::default().fg(theme.dim)),
                     );
+                }
+                let selected = Some(index) == app.bookmark.selected_index;
+                if selected {
+                    selected_row = Some(items.len());
+                }
+                let prefix = if selected { ">> " } else { "   " };
 
-                    ListItem::new(display_text).style(Style::default().fg(Color::White))
-                })
-                .collect();
+                let note_mark = if bookmark.note.is_empty() { "" } else { " ✎" };
+                let display_text = trf!(
+                    "{} (行: {}){}",
+                    "{} (line: {}){}",
+                    bookmark.name,
+                    bookmark.position + 1,
+                    note_mark
+                );
+
+                items.push(
+                    ListItem::new(Line::from(vec![
+                        Span::raw(prefix),
+                        tag_span(theme, &bookmark.tag),
+                        Span::raw(display_text),
+                        Span::styled(
+                            format!("  {}", format_timestamp(bookmark.timestamp)),
+                            Style::default().fg(theme.dim),
+                        ),
+                    ]))
+                    .style(Style::default().fg(theme.text)),
+                );
+            }
+            let row_count = items.len();
 
             let bookmarks_list = List::new(items)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(format!("书签列表 (共{}个)", bookmarks.len())),
-                )
-                .highlight_style(Style::default().bg(Color::DarkGray))
+                .block(Block::default().borders(Borders::ALL).title(trf!(
+                    "书签列表 (共{}个 · {}{}{})", "Bookmarks ({} total · {}{}{})",
+                    bookmarks.len(),
+                    app.bookmark.sort.label(),
+                    app.bookmark
+                        .tag_filter
+                        .as_ref()
+                        .map(|tag| trf!(" · 仅{}", " · only {}", tag))
+                        .unwrap_or_default(),
+                    if app.bookmark.filter.is_empty() {
+                        String::new()
+                    } else {
+                        trf!(" · 筛选“{}”", " · filter \"{}\"", app.bookmark.filter)
+                    }
+                )))
+                .highlight_style(Style::default().bg(theme.selection_bg))
                 .highlight_symbol("");
 
-            let list_area = Rect {
+            let content_area = Rect {
                 x: area.x + 2,
                 y: area.y + 2,
                 width: area.width - 4,
                 height: area.height - 3,
             };
+            let (list_area, detail_area) = split_preview_area(content_area);
+            if let Some(index) = app.bookmark.selected_index
+                && let Some(bookmark) = app.bookmark_entry(index)
+            {
+                let placeholder = if app.is_auto_bookmark(index) {
+                    tr(
+                        "（自动记录的位置，按 d 删除）",
+                        "(automatic position, press d to delete)",
+                    )
+                } else {
+                    tr(
+                        "（无备注，按 e 编辑，按 m 移到当前阅读位置）",
+                        "(no note; press e to edit, m to move to the reading position)",
+                    )
+                };
+                let text = app.current_novel.as_ref().map_or(&[][..], |n| n.lines());
+                render_bookmark_detail(f, theme, bookmark, text, placeholder, detail_area);
+            }
 
             let mut state = ListState::default();
-            state.select(app.bookmark.selected_index);
+            state.select(selected_row);
 
-            if let Some(selected) = app.bookmark.selected_index {
+            if let Some(selected) = selected_row {
                 let visible_height = list_area.height.saturating_sub(2) as usize;
                 let half_height = visible_height / 2;
 
                 if selected >= half_height {
-                    let max_offset = bookmarks.len().saturating_sub(visible_height);
+                    let max_offset = row_count.saturating_sub(visible_height);
                     let offset = (selected.saturating_sub(half_height)).min(max_offset);
                     state = state.with_offset(offset);
                 }
@@ -110,23 +175,270 @@ fn render_bookmark_list(f: &mut Frame, app: &App) {
         }
     }
 
-    let help_text = if app.get_current_bookmarks().is_none_or(|b| b.is_empty()) {
-        "a: 添加书签 | Esc: 返回阅读 | q: 退出"
+    if app.bookmark.filter_input {
+        let prompt = trf!(
+            "筛选书签: {}▏ │ ↑/↓:选择 Enter:确定 Esc:取消",
+            "Filter bookmarks: {}▏ │ ↑/↓:Select Enter:Confirm Esc:Cancel",
+            app.bookmark.filter
+        );
+        render_help_info(f, theme, &prompt, area);
+        return;
+    }
+    let help_text = if app.bookmark_display_order().is_empty() {
+        trf!(
+            "a: 添加书签 | u: 撤销删除 | t: 切换标签筛选 | Esc: 返回阅读 | {}: 退出",
+            "a: Add bookmark | u: Undo delete | t: Tag filter | Esc: Back to reading | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
     } else {
-        "↑/↓: 选择书签 | Enter: 跳转 | d: 删除 | a: 添加 | Esc: 返回阅读 | q: 退出"
+        trf!(
+            "↑/↓: 选择书签 | Enter: 跳转 | e: 编辑 | m: 移到当前位置 | y/Y: 复制行/段落 | d: 删除 | u: 撤销删除 | a: 添加 | s: 排序 | t: 标签筛选 | /: 筛选 | Esc: 返回阅读 | {}: 退出",
+            "↑/↓: Select | Enter: Jump | e: Edit | m: Move here | y/Y: Copy line/paragraph | d: Delete | u: Undo delete | a: Add | s: Sort | t: Tag filter | /: Filter | Esc: Back to reading | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
     };
-    render_help_info(f, help_text, area);
+    render_help_info(f, theme, &help_text, area);
 }
 
-/// 渲染添加书签界面
+/// 标签的显示颜色，按标签在 [`BOOKMARK_TAGS`] 中的位置区分，其他标签为强调色
+fn tag_color(theme: &Theme, tag: &str) -> Color {
+    let colors = [theme.danger, theme.special, theme.success, theme.accent];
+    BOOKMARK_TAGS
+        .iter()
+        .position(|&t| t == tag)
+        .and_then(|index| colors.get(index).copied())
+        .unwrap_or(theme.emphasis)
+}
+
+/// 书签名称前的彩色标签，如 “[伏笔] ”；没有标签时为空
+fn tag_span(theme: &Theme, tag: &str) -> Span<'static> {
+    if tag.is_empty() {
+        return Span::raw("");
+    }
+    Span::styled(
+        format!("[{}] ", tag),
+        Style::default().fg(tag_color(theme, tag)),
+    )
+}
+
+/// 书签详情中显示书签行前后各多少行上下文
+const CONTEXT_LINES: usize = 2;
+
+/// 渲染选中书签的详情：名称、位置、所在行的上下文和备注
+///
+/// `text` 为小说正文（未加载时为空，不显示上下文），`placeholder` 在没有备注时显示。
+fn render_bookmark_detail(
+    f: &mut Frame,
+    theme: &Theme,
+    bookmark: &Bookmark,
+    text: &[String],
+    placeholder: &str,
+    area: Rect,
+) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            bookmark.name.clone(),
+            Style::default().fg(theme.accent),
+        )),
+        Line::from(Span::styled(
+            trf!("第 {} 行", "Line {}", bookmark.position + 1),
+            Style::default().fg(theme.dim),
+        )),
+        Line::from(""),
+    ];
+    if !text.is_empty() {
+        let start = bookmark.position.saturating_sub(CONTEXT_LINES);
+        let end = (bookmark.position + CONTEXT_LINES + 1).min(text.len());
+        for (line_num, line) in text.iter().enumerate().take(end).skip(start) {
+            // 书签所在行高亮，其余行变暗
+            let style = if line_num == bookmark.position {
+                Style::default().fg(theme.emphasis)
+            } else {
+                Style::default().fg(theme.dim)
+            };
+            lines.push(Line::from(Span::styled(line.trim().to_string(), style)));
+        }
+        lines.push(Line::from(""));
+    }
+    if bookmark.note.is_empty() {
+        lines.push(Line::from(Span::styled(
+            placeholder.to_string(),
+            Style::default().fg(theme.dim),
+        )));
+    } else {
+        lines.extend(
+            bookmark
+                .note
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+    }
+
+    let detail = Paragraph::new(lines)
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("详情", "Details")),
+        );
+    f.render_widget(detail, area);
+}
+
+/// 渲染书架上所有小说的书签，按书分组
+/// # 参数
+/// - `f`: 渲染框架
+/// - `app`: 应用状态
+fn render_all_bookmarks(f: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let area = f.area();
+
+    let title = Paragraph::new(tr("全部书签", "All bookmarks"))
+        .style(Style::default().fg(theme.secondary))
+        .alignment(Alignment::Center);
+    f.render_widget(
+        title,
+        Rect {
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: 2,
+        },
+    );
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let entries = app.all_bookmarks();
+    if entries.is_empty() {
+        let empty = Paragraph::new(tr(
+            "书架上的小说还没有书签\n\n阅读时按 'M' 添加书签",
+            "No novel on the bookshelf has bookmarks yet\n\nPress 'M' while reading to add one",
+        ))
+        .style(Style::default().fg(theme.emphasis))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("提示", "Hint")),
+        );
+        f.render_widget(empty, content_area);
+        render_help_info(
+            f,
+            theme,
+            &trf!(
+                "Esc: 返回书架 | {}: 退出",
+                "Esc: Back to bookshelf | {}: Quit",
+                app.preferences.quit_key.hint()
+            ),
+            area,
+        );
+        return;
+    }
+
+    let mut items: Vec<ListItem> = Vec::new();
+    // 选中项所在的行（每本书前插入了一行书名）
+    let mut selected_row = None;
+    for (index, entry) in entries.iter().enumerate() {
+        let novel = &app.novels[entry.novel_index];
+        if index == 0 || entries[index - 1].novel_index != entry.novel_index {
+            items.push(
+                ListItem::new(format!("《{}》", novel.title))
+                    .style(Style::default().fg(theme.accent)),
+            );
+        }
+        let selected = Some(index) == app.bookmark.all_selected_index;
+        if selected {
+            selected_row = Some(items.len());
+        }
+        let prefix = if selected { ">> " } else { "   " };
+        let note_mark = if entry.bookmark.note.is_empty() {
+            ""
+        } else {
+            " ✎"
+        };
+        items.push(
+            ListItem::new(Line::from(vec![
+                Span::raw(prefix),
+                tag_span(theme, &entry.bookmark.tag),
+                Span::raw(trf!(
+                    "{} (行: {}){}",
+                    "{} (line: {}){}",
+                    entry.bookmark.name,
+                    entry.bookmark.position + 1,
+                    note_mark
+                )),
+            ]))
+            .style(Style::default().fg(theme.text)),
+        );
+    }
+    let row_count = items.len();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(trf!(
+            "书签列表 (共{}个)",
+            "Bookmarks ({} total)",
+            entries.len()
+        )))
+        .highlight_style(Style::default().bg(theme.selection_bg))
+        .highlight_symbol("");
+
+    let (list_area, detail_area) = split_preview_area(content_area);
+    if let Some(entry) = app.bookmark.all_selected_index.and_then(|i| entries.get(i)) {
+        let novel = &app.novels[entry.novel_index];
+        render_bookmark_detail(
+            f,
+            theme,
+            &entry.bookmark,
+            novel.lines(),
+            tr("（无备注）", "(no note)"),
+            detail_area,
+        );
+    }
+
+    let mut state = ListState::default();
+    state.select(selected_row);
+    if let Some(selected) = selected_row {
+        let visible_height = list_area.height.saturating_sub(2) as usize;
+        let half_height = visible_height / 2;
+        if selected >= half_height {
+            let max_offset = row_count.saturating_sub(visible_height);
+            state = state.with_offset(selected.saturating_sub(half_height).min(max_offset));
+        }
+    }
+    f.render_stateful_widget(list, list_area, &mut state);
+
+    render_help_info(
+        f,
+        theme,
+        &trf!(
+            "↑/↓: 选择书签 | Enter: 打开小说并跳转 | Esc: 返回书架 | {}: 退出",
+            "↑/↓: Select bookmark | Enter: Open novel and jump | Esc: Back to bookshelf | {}: Quit",
+            app.preferences.quit_key.hint()
+        ),
+        area,
+    );
+}
+
+/// 渲染添加/编辑书签界面
 /// # 参数
 /// - `f`: 渲染框架
 /// - `app`: 应用状态
 fn render_bookmark_add(f: &mut Frame, app: &App) {
+    let theme = app.theme();
     let area = f.area();
 
-    let title = Paragraph::new("添加书签")
-        .style(Style::default().fg(Color::Green))
+    let title_text = if app.bookmark.editing_index.is_some() {
+        tr("编辑书签", "Edit bookmark")
+    } else {
+        tr("添加书签", "Add bookmark")
+    };
+    let title = Paragraph::new(title_text)
+        .style(Style::default().fg(theme.success))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -138,16 +450,44 @@ fn render_bookmark_add(f: &mut Frame, app: &App) {
 
     f.render_widget(title, title_area);
 
-    let position_info = if let Some(novel) = &app.current_novel {
-        format!("当前位置: 第 {} 行", novel.progress.scroll_offset + 1)
-    } else {
-        "当前位置: 未知".to_string()
+    let position = match app.bookmark.editing_index {
+        Some(index) => app
+            .get_current_bookmarks()
+            .and_then(|bookmarks| bookmarks.get(index))
+            .map(|bookmark| bookmark.position),
+        None => app
+            .current_novel
+            .as_ref()
+            .map(|novel| novel.progress.scroll_offset),
+    };
+    let position_info = match position {
+        Some(line) => trf!("位置: 第 {} 行", "Position: line {}", line + 1),
+        None => tr("位置: 未知", "Position: unknown").to_string(),
     };
 
+    let tag = if app.bookmark.tag_input.is_empty() {
+        Span::styled(
+            tr("无（Ctrl+t 选择）", "None (Ctrl+t to choose)"),
+            Style::default().fg(theme.dim),
+        )
+    } else {
+        Span::styled(
+            app.bookmark.tag_input.clone(),
+            Style::default().fg(tag_color(theme, &app.bookmark.tag_input)),
+        )
+    };
+    let position_info = Line::from(vec![
+        Span::raw(position_info + tr(" · 标签: ", " · Tag: ")),
+        tag,
+    ]);
     let position_paragraph = Paragraph::new(position_info)
-        .style(Style::default().fg(Color::Cyan))
+        .style(Style::default().fg(theme.accent))
         .alignment(Alignment::Center)
-        .block(Block::default().borders(Borders::ALL).title("位置信息"));
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("位置信息", "Position")),
+        );
 
     let position_area = Rect {
         x: area.x + 2,
@@ -158,10 +498,31 @@ fn render_bookmark_add(f: &mut Frame, app: &App) {
 
     f.render_widget(position_paragraph, position_area);
 
-    let name_text = format!("书签名称: {}", app.bookmark.input);
+    // 当前焦点所在的输入框边框高亮，并在末尾显示光标
+    let border_style = |focused: bool| {
+        if focused {
+            Style::default().fg(theme.emphasis)
+        } else {
+            Style::default()
+        }
+    };
+    let cursor = |focused: bool| if focused { "▏" } else { "" };
+
+    let name_focused = !app.bookmark.note_focused;
+    let name_text = trf!(
+        "书签名称: {}{}",
+        "Name: {}{}",
+        app.bookmark.input,
+        cursor(name_focused)
+    );
     let name_input = Paragraph::new(name_text)
-        .style(Style::default().fg(Color::White))
-        .block(Block::default().borders(Borders::ALL).title("输入书签名称"));
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style(name_focused))
+                .title(tr("输入书签名称", "Bookmark name")),
+        );
 
     let name_area = Rect {
         x: area.x + 2,
@@ -172,6 +533,37 @@ fn render_bookmark_add(f: &mut Frame, app: &App) {
 
     f.render_widget(name_input, name_area);
 
-    let help_text = "输入书签名称 | Enter: 确认添加 | Esc: 取消并返回列表";
-    render_help_info(f, help_text, area);
+    let note_focused = app.bookmark.note_focused;
+    let note_text = format!("{}{}", app.bookmark.note_input, cursor(note_focused));
+    let note_input = Paragraph::new(note_text)
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border_style(note_focused))
+                .title(tr("备注（可选，可多行）", "Note (optional, multi-line)")),
+        );
+
+    let note_area = Rect {
+        x: area.x + 2,
+        y: area.y + 8,
+        width: area.width - 4,
+        height: area.height.saturating_sub(10),
+    };
+
+    f.render_widget(note_input, note_area);
+
+    let help_text = if note_focused {
+        tr(
+            "Enter: 换行 | Tab: 切换到名称 | Ctrl+t: 切换标签 | Ctrl+s: 保存 | Esc: 取消并返回列表",
+            "Enter: New line | Tab: Edit name | Ctrl+t: Cycle tag | Ctrl+s: Save | Esc: Cancel and go back",
+        )
+    } else {
+        tr(
+            "Enter/Ctrl+s: 保存 | Tab: 编辑备注 | Ctrl+t: 切换标签 | Esc: 取消并返回列表",
+            "Enter/Ctrl+s: Save | Tab: Edit note | Ctrl+t: Cycle tag | Esc: Cancel and go back",
+        )
+    };
+    render_help_info(f, theme, help_text, area);
 }
@@ -1,14 +1,159 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use super::utils::render_help_info;
-use crate::app::App;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::cover::accent_color;
+use super::utils::{format_relative_time, render_help_info, render_list_scrollbar};
+use crate::app::{App, BookshelfRow, QUEUE_CATEGORY};
+use crate::i18n::{tr, trf};
+use crate::model::preferences::QuitKey;
+
+/// 书架每行标题至少保留的显示宽度，放不下的元数据列会被省略
+const MIN_TITLE_WIDTH: usize = 12;
+
+/// 分类中的小说相对分类标题的缩进
+const CATEGORY_INDENT: &str = "  ";
+
+/// 标题前由书名决定颜色的色块
+const ACCENT_BLOCK: &str = "▌";
+
+/// 书架行中标题之后的元数据列
+#[derive(Clone, Copy, PartialEq)]
+enum Column {
+    /// 阅读位置所在章节
+    Chapter,
+    /// 阅读进度百分比
+    Progress,
+    /// 阅读状态
+    Status,
+    /// 章节数
+    ChapterCount,
+    /// 文件大小
+    Size,
+    /// 最近阅读时间
+    LastRead,
+}
+
+impl Column {
+    /// 按显示顺序排列的全部列
+    const ALL: [Column; 6] = [
+        Column::Chapter,
+        Column::Progress,
+        Column::Status,
+        Column::ChapterCount,
+        Column::Size,
+        Column::LastRead,
+    ];
+
+    /// 终端较窄时按此顺序保留列
+    const PRIORITY: [Column; 6] = [
+        Column::Progress,
+        Column::Status,
+        Column::Chapter,
+        Column::LastRead,
+        Column::Size,
+        Column::ChapterCount,
+    ];
+
+    /// 列的显示宽度
+    fn width(self) -> usize {
+        match self {
+            Column::Chapter => 8,
+            Column::Progress => 4,
+            Column::Status => 4,
+            Column::ChapterCount => 7,
+            Column::Size => 7,
+            Column::LastRead => 10,
+        }
+    }
+
+    /// 书架上第 `index` 本小说在该列的内容，没有数据时为空
+    fn text(self, app: &App, index: usize) -> String {
+        match self {
+            Column::Chapter => app.bookshelf_chapter_label(index).unwrap_or_default(),
+            Column::Progress => app
+                .bookshelf_progress_percent(index)
+                .map(|percent| format!("{}%", percent))
+                .unwrap_or_default(),
+            Column::Status => app.bookshelf_status(index).label().to_string(),
+            Column::ChapterCount => match app.bookshelf_chapters(index).len() {
+                0 => String::new(),
+                count => trf!("共{}章", "{} ch.", count),
+            },
+            Column::Size => app
+                .novels
+                .get(index)
+                .map(|novel| format_size(novel.file_size))
+                .unwrap_or_default(),
+            Column::LastRead => app
+                .bookshelf_last_read(index)
+                .map(format_relative_time)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// 把字节数格式化为 “850KB”、“2.3MB” 这样的大小
+pub(super) fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    if bytes >= MB {
+        format!("{:.1}MB", bytes as f64 / MB as f64)
+    } else {
+        format!("{}KB", bytes.div_ceil(KB))
+    }
+}
+
+/// 按显示宽度截断或补齐文本，截断时以 “…” 结尾
+fn fit_width(text: &str, width: usize) -> String {
+    let text_width = text.width();
+    if text_width <= width {
+        return format!("{}{}", text, " ".repeat(width - text_width));
+    }
+    let mut fitted = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        if used + char_width + 1 > width {
+            break;
+        }
+        fitted.push(c);
+        used += char_width;
+    }
+    fitted.push('…');
+    format!("{}{}", fitted, " ".repeat(width.saturating_sub(used + 1)))
+}
+
+/// 在给定的行宽内按优先级选出能显示的列（按显示顺序返回）以及留给标题的宽度
+fn visible_columns(row_width: usize) -> (Vec<Column>, usize) {
+    let mut title_width = row_width;
+    let mut chosen = Vec::new();
+    for column in Column::PRIORITY {
+        let needed = column.width() + 2;
+        if title_width < MIN_TITLE_WIDTH + needed {
+            break;
+        }
+        title_width -= needed;
+        chosen.push(column);
+    }
+    let columns = Column::ALL
+        .into_iter()
+        .filter(|column| chosen.contains(column))
+        .collect();
+    (columns, title_width)
+}
 
 pub fn render_bookshelf(f: &mut Frame, app: &App) {
+    let theme = app.theme();
     let area = f.area();
 
-    let title = Paragraph::new("书架")
-        .style(Style::default().fg(Color::Cyan))
+    let title = match app.bookshelf.marked.len() {
+        0 => tr("书架", "Bookshelf").to_string(),
+        count => trf!("书架 · 已标记 {} 本", "Bookshelf · {} marked", count),
+    };
+    let title = Paragraph::new(title)
+        .style(Style::default().fg(theme.accent))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -20,38 +165,202 @@ pub fn render_bookshelf(f: &mut Frame, app: &App) {
 
     f.render_widget(title, title_area);
 
-    let items: Vec<ListItem> = app
-        .novels
+    let list_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+    // 去掉边框和选中标记 “>> ” 后的行宽
+    let row_width = (list_area.width as usize).saturating_sub(2 + 3);
+    let (columns, title_width) = visible_columns(row_width);
+
+    let rows = app.bookshelf_rows();
+    let selected_row = app.bookshelf_selected_row(&rows);
+    // 同一系列相邻的各卷用树形线连起来
+    let series: Vec<Option<String>> = rows
+        .iter()
+        .map(|row| match row {
+            BookshelfRow::Novel(index) => {
+                app.bookshelf_series(*index).map(|(name, _)| name.clone())
+            }
+            BookshelfRow::Category { .. } => None,
+        })
+        .collect();
+    let same_series = |a: usize, b: usize| matches!((series.get(a), series.get(b)), (Some(Some(x)), Some(Some(y))) if x == y);
+    let items: Vec<ListItem> = rows
         .iter()
         .enumerate()
-        .map(|(index, novel)| {
-            let prefix = if Some(index) == app.selected_novel_index {
+        .map(|(row_index, row)| {
+            let prefix = if Some(row_index) == selected_row {
                 ">> "
             } else {
                 "   "
             };
-            ListItem::new(format!("{}{}", prefix, novel.title))
-                .style(Style::default().fg(Color::White))
+            let index = match row {
+                BookshelfRow::Category {
+                    name,
+                    count,
+                    collapsed,
+                } => {
+                    let marker = if *collapsed { "▸" } else { "▾" };
+                    // 待读队列是内置分类，名称按界面语言显示；其他分类是文件夹名，原样显示
+                    let name = if name == QUEUE_CATEGORY {
+                        tr(QUEUE_CATEGORY, "Reading queue")
+                    } else {
+                        name.as_str()
+                    };
+                    return ListItem::new(format!("{}{} {} ({})", prefix, marker, name, count))
+                        .style(
+                            Style::default()
+                                .fg(theme.emphasis)
+                                .add_modifier(Modifier::BOLD),
+                        );
+                }
+                BookshelfRow::Novel(index) => *index,
+            };
+            let novel = &app.novels[index];
+            let indent = if app.bookshelf_category(index).is_empty() {
+                ""
+            } else {
+                CATEGORY_INDENT
+            };
+            let mut marker = String::new();
+            if app.bookshelf_is_marked(index) {
+                marker.push_str("✓ ");
+            }
+            if let Some(position) = app.bookshelf_queue_position(index) {
+                marker.push_str(&format!("{}. ", position + 1));
+            }
+            if row_index > 0 && same_series(row_index - 1, row_index) {
+                marker.push_str(if same_series(row_index, row_index + 1) {
+                    "├ "
+                } else {
+                    "└ "
+                });
+            }
+            if app.bookshelf_is_favorite(index) {
+                marker.push_str("★ ");
+            }
+            if !app.bookshelf_duplicates(index).is_empty() {
+                // 内容重复的小说，可在详情中合并
+                marker.push_str("⧉ ");
+            }
+            let accent = if app.preferences.book_covers {
+                ACCENT_BLOCK
+            } else {
+                ""
+            };
+            let width = title_width.saturating_sub(indent.len() + accent.width() + marker.width());
+            let mut spans = vec![
+                Span::raw(format!("{}{}{}", prefix, indent, marker)),
+                Span::styled(accent, Style::default().fg(accent_color(&novel.title))),
+                Span::raw(fit_width(&novel.title, width)),
+            ];
+            for &column in &columns {
+                let text = column.text(app, index);
+                let padding = column.width().saturating_sub(text.width());
+                spans.push(Span::styled(
+                    format!("  {}{}", " ".repeat(padding), text),
+                    Style::default().fg(theme.dim),
+                ));
+            }
+            ListItem::new(Line::from(spans)).style(Style::default().fg(theme.text))
         })
         .collect();
 
+    let mut list_title = trf!(
+        "可用小说 ({}",
+        "Novels ({}",
+        app.preferences.bookshelf_sort.label()
+    );
+    if let Some(status) = app.bookshelf.status_filter {
+        list_title.push_str(&trf!(" · 状态: {}", " · status: {}", status.label()));
+    }
+    if let Some(tag) = &app.bookshelf.tag_filter {
+        list_title.push_str(&trf!(" · 标签: {}", " · tag: {}", tag));
+    }
+    if !app.bookshelf.filter.is_empty() {
+        list_title.push_str(&trf!(
+            " · 筛选“{}”",
+            " · filter \"{}\"",
+            app.bookshelf.filter
+        ));
+    }
+    list_title.push(')');
     let novels_list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title("可用小说"))
-        .highlight_style(Style::default().bg(Color::DarkGray))
+        .block(Block::default().borders(Borders::ALL).title(list_title))
+        .highlight_style(Style::default().bg(theme.selection_bg))
         .highlight_symbol("");
 
-    let list_area = Rect {
-        x: area.x + 2,
-        y: area.y + 2,
-        width: area.width - 4,
-        height: area.height - 3,
-    };
-
-    let mut state = ListState::default();
-    state.select(app.selected_novel_index);
+    // 选中行尽量保持在列表中间，书很多时也能看到前后的书
+    let visible_height = list_area.height.saturating_sub(2) as usize;
+    let max_offset = rows.len().saturating_sub(visible_height);
+    let offset = selected_row
+        .map(|selected| selected.saturating_sub(visible_height / 2).min(max_offset))
+        .unwrap_or(0);
+    let mut state = ListState::default()
+        .with_selected(selected_row)
+        .with_offset(offset);
 
     f.render_stateful_widget(novels_list, list_area, &mut state);
+    render_list_scrollbar(f, theme, list_area, rows.len(), state.offset());
 
-    let help_text = "↑/k: 上移  ↓/j: 下移  Enter: 选择  s: 设置  w: 上传  d: 下载  Esc/q: 退出";
-    render_help_info(f, help_text, area);
+    if let Some(input) = &app.bookshelf.import_input {
+        let mode = if app.bookshelf.import_move {
+            tr("移动", "Move")
+        } else {
+            tr("复制", "Copy")
+        };
+        let help_text = trf!(
+            "导入文件: {}▏ | Enter: {}到小说目录 | Tab: 切换复制/移动 | Esc: 取消",
+            "Import file: {}▏ | Enter: {} into the novel directory | Tab: Toggle copy/move | Esc: Cancel",
+            input,
+            mode
+        );
+        render_help_info(f, theme, &help_text, area);
+        return;
+    }
+
+    if let Some(input) = &app.bookshelf.rename_input {
+        let help_text = trf!(
+            "重命名: {}▏ | Enter: 确定（保留扩展名，进度和书签随之迁移） | Esc: 取消",
+            "Rename: {}▏ | Enter: Confirm (extension kept, progress and bookmarks follow) | Esc: Cancel",
+            input
+        );
+        render_help_info(f, theme, &help_text, area);
+        return;
+    }
+
+    if let Some(input) = &app.bookshelf.tag_input {
+        let help_text = trf!(
+            "批量添加标签（{} 本）: {}▏ | 逗号分隔多个 | Enter: 添加 | Esc: 取消",
+            "Tag {} novels: {}▏ | Separate with commas | Enter: Add | Esc: Cancel",
+            app.batch_targets().len(),
+            input
+        );
+        render_help_info(f, theme, &help_text, area);
+        return;
+    }
+
+    if app.bookshelf.filter_input {
+        let prompt = trf!(
+            "筛选书名: {}▏ │ 支持拼音首字母 ↑/↓:选择 Enter:确定 Esc:取消",
+            "Filter titles: {}▏ │ pinyin initials work ↑/↓:Select Enter:Confirm Esc:Cancel",
+            app.bookshelf.filter
+        );
+        render_help_info(f, theme, &prompt, area);
+        return;
+    }
+
+    let quit = match app.preferences.quit_key {
+        QuitKey::Q => "Esc/q",
+        key => key.hint(),
+    };
+    let help_text = trf!(
+        "↑/k: 上移  ↓/j: 下移  Enter: 选择/展开  h/l: 折叠/展开分类  c: 继续阅读  o: 排序  e: 详情/标签  /: 筛选  f: 收藏  m: 标记状态  r: 按状态筛选  R: 重新扫描  t: 按标签筛选  i: 导入  n: 重命名  +: 待读队列  K/J: 调整队列  空格: 标记  a: 批量标签  x: 删除  A: 归档  b: 全部书签  s: 设置  w: 上传  d: 下载  {}: 退出",
+        "↑/k: Up  ↓/j: Down  Enter: Open/expand  h/l: Collapse/expand category  c: Continue reading  o: Sort  e: Details/tags  /: Filter  f: Favorite  m: Mark status  r: Filter by status  R: Rescan  t: Filter by tag  i: Import  n: Rename  +: Reading queue  K/J: Reorder queue  Space: Mark  a: Tag marked  x: Delete  A: Archive  b: All bookmarks  s: Settings  w: Upload  d: Download  {}: Quit",
+        quit
+    );
+    render_help_info(f, theme, &help_text, area);
 }
@@ -1,14 +1,25 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use super::utils::render_help_info;
+use super::theme::Theme;
+use super::utils::{render_help_info, split_preview_area};
 use crate::app::App;
+use crate::i18n::{language, tr, trf};
+use crate::model::chapter_parser::is_volume_title;
+use crate::model::novel::Novel;
+use crate::model::preferences::Language;
 
 pub fn render_chapter_list(f: &mut Frame, app: &App) {
+    let theme = app.theme();
     let area = f.area();
 
-    let title = Paragraph::new("章节目录")
-        .style(Style::default().fg(Color::Green))
+    let title_text = if app.chapter_list.editing {
+        tr("章节目录（编辑模式）", "Chapters (editing)")
+    } else {
+        tr("章节目录", "Chapters")
+    };
+    let title = Paragraph::new(title_text)
+        .style(Style::default().fg(theme.success))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -21,11 +32,29 @@ pub fn render_chapter_list(f: &mut Frame, app: &App) {
     f.render_widget(title, title_area);
 
     if let Some(novel) = &app.current_novel {
-        if novel.chapters.is_empty() {
-            let no_chapters = Paragraph::new("未检测到章节信息\n\n可能原因：\n• 小说格式不规范\n• 章节标题格式特殊\n• 文件内容较短")
-                .style(Style::default().fg(Color::Yellow))
+        if novel.chapters.is_empty() && app.is_parsing_chapters() {
+            let parsing = Paragraph::new(tr("正在解析章节…", "Parsing chapters…"))
+                .style(Style::default().fg(theme.emphasis))
                 .alignment(Alignment::Center)
-                .block(Block::default().borders(Borders::ALL).title("提示"));
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(tr("提示", "Hint")),
+                );
+
+            let content_area = Rect {
+                x: area.x + 2,
+                y: area.y + 2,
+                width: area.width - 4,
+                height: area.height - 3,
+            };
+
+            f.render_widget(parsing, content_area);
+        } else if novel.chapters.is_empty() {
+            let no_chapters = Paragraph::new(tr("未检测到章节信息\n\n可能原因：\n• 小说格式不规范\n• 章节标题格式特殊\n• 文件内容较短", "No chapters detected\n\nPossible reasons:\n• The novel is not formatted consistently\n• The chapter titles use an unusual format\n• The file is short"))
+                .style(Style::default().fg(theme.emphasis))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).title(tr("提示", "Hint")));
 
             let content_area = Rect {
                 x: area.x + 2,
@@ -36,46 +65,110 @@ pub fn render_chapter_list(f: &mut Frame, app: &App) {
 
             f.render_widget(no_chapters, content_area);
         } else {
-            let items: Vec<ListItem> = novel
+            let current_progress = novel.chapter_progress();
+            let visible = app.visible_chapter_indices();
+            // 第一卷之后的章节缩进显示为卷的子节点
+            let first_volume = novel
                 .chapters
                 .iter()
-                .enumerate()
-                .map(|(index, chapter)| {
+                .position(|chapter| is_volume_title(&chapter.title));
+            let items: Vec<ListItem> = visible
+                .iter()
+                .map(|&index| {
+                    let chapter = &novel.chapters[index];
                     let prefix = if Some(index) == app.selected_chapter_index {
                         ">> "
                     } else {
                         "   "
                     };
-                    let display_text = format!("{}{}", prefix, chapter.title);
-                    ListItem::new(display_text).style(Style::default().fg(Color::White))
+                    let marker = if is_volume_title(&chapter.title) {
+                        if app.chapter_list.collapsed.contains(&index) {
+                            "▸ "
+                        } else {
+                            "▾ "
+                        }
+                    } else if first_volume.is_some_and(|first| index > first) {
+                        "    "
+                    } else {
+                        ""
+                    };
+                    let mut spans =
+                        vec![Span::raw(format!("{}{}{}", prefix, marker, chapter.title))];
+                    if let Some((lines, words)) = novel.chapter_stats(index) {
+                        spans.push(Span::styled(
+                            trf!(
+                                "  {}行 · {}",
+                                "  {} lines · {}",
+                                lines,
+                                format_word_count(words)
+                            ),
+                            Style::default().fg(theme.dim),
+                        ));
+                    }
+                    match current_progress {
+                        Some((current, percent)) if current == index => {
+                            spans.push(Span::styled(
+                                trf!("  ◀ 已读{}%", "  ◀ {}% read", percent),
+                                Style::default().fg(theme.emphasis),
+                            ));
+                            ListItem::new(Line::from(spans))
+                                .style(Style::default().fg(theme.emphasis))
+                        }
+                        _ if novel.is_chapter_read(index) => {
+                            spans.push(Span::raw("  ✓"));
+                            ListItem::new(Line::from(spans)).style(Style::default().fg(theme.dim))
+                        }
+                        _ => {
+                            ListItem::new(Line::from(spans)).style(Style::default().fg(theme.text))
+                        }
+                    }
                 })
                 .collect();
 
-            let chapters_list = List::new(items)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(format!("章节列表 (共{}章)", novel.chapters.len())),
+            let list_title = if app.chapter_list.filter.is_empty() {
+                trf!(
+                    "章节列表 (共{}章)",
+                    "Chapters ({} total)",
+                    novel.chapters.len()
+                )
+            } else {
+                trf!(
+                    "章节列表 (筛选“{}”: {}/{}章)",
+                    "Chapters (filter \"{}\": {}/{})",
+                    app.chapter_list.filter,
+                    visible.len(),
+                    novel.chapters.len()
                 )
-                .highlight_style(Style::default().bg(Color::DarkGray))
+            };
+            let chapters_list = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(list_title))
+                .highlight_style(Style::default().bg(theme.selection_bg))
                 .highlight_symbol("");
 
-            let list_area = Rect {
+            let content_area = Rect {
                 x: area.x + 2,
                 y: area.y + 2,
                 width: area.width - 4,
                 height: area.height - 3,
             };
+            let (list_area, preview_area) = split_preview_area(content_area);
+            if let Some(index) = app.selected_chapter_index {
+                render_preview(f, theme, novel, index, preview_area);
+            }
 
+            // 选中项在可见列表中的位置
+            let selected_position = app
+                .selected_chapter_index
+                .and_then(|index| visible.iter().position(|&i| i == index));
             let mut state = ListState::default();
-            state.select(app.selected_chapter_index);
+            state.select(selected_position);
 
-            if let Some(selected) = app.selected_chapter_index {
+            if let Some(selected) = selected_position {
                 let visible_height = list_area.height.saturating_sub(2) as usize;
                 let half_height = visible_height / 2;
 
                 if selected >= half_height {
-                    let max_offset = novel.chapters.len().saturating_sub(visible_height);
+                    let max_offset = visible.len().saturating_sub(visible_height);
                     let offset = (selected.saturating_sub(half_height)).min(max_offset);
                     state = state.with_offset(offset);
                 }
@@ -85,6 +178,76 @@ pub fn render_chapter_list(f: &mut Frame, app: &App) {
         }
     }
 
-    let help_text = "↑/↓: 选择章节 | Enter: 跳转到章节 | Esc: 返回阅读 | q: 退出";
-    render_help_info(f, help_text, area);
+    if let Some(input) = &app.chapter_list.rename_input {
+        let prompt = trf!(
+            "新标题: {}▏ │ Enter:确定 Esc:取消",
+            "New title: {}▏ │ Enter:Confirm Esc:Cancel",
+            input
+        );
+        render_help_info(f, theme, &prompt, area);
+        return;
+    }
+    if app.chapter_list.filter_input {
+        let prompt = trf!(
+            "筛选章节: {}▏ │ ↑/↓:选择 Enter:确定 Esc:取消",
+            "Filter chapters: {}▏ │ ↑/↓:Select Enter:Confirm Esc:Cancel",
+            app.chapter_list.filter
+        );
+        render_help_info(f, theme, &prompt, area);
+        return;
+    }
+
+    let help_text = if app.chapter_list.editing {
+        tr(
+            "d: 删除 | r: 重命名 | a: 在阅读位置添加 | R: 恢复自动识别 | A: 开关自动识别 | e/Esc: 退出编辑",
+            "d: Delete | r: Rename | a: Add at reading position | R: Restore detected chapters | A: Toggle detection | e/Esc: Stop editing",
+        )
+        .to_string()
+    } else {
+        trf!(
+            "↑/↓: 选择章节 | Enter: 跳转到章节 | ←/→: 折叠/展开卷 | /: 筛选 | e: 编辑目录 | Esc: 返回阅读 | {}: 退出",
+            "↑/↓: Select chapter | Enter: Jump | ←/→: Collapse/expand volume | /: Filter | e: Edit chapters | Esc: Back to reading | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
+    };
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 预览窗格的行内容：章节标题之后的非空行（去掉首尾空白）
+fn preview_lines(novel: &Novel, index: usize, count: usize) -> Vec<Line<'static>> {
+    let Some(range) = novel.chapter_line_range(index) else {
+        return Vec::new();
+    };
+    novel.lines()[range]
+        .iter()
+        .skip(1)
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .take(count)
+        .map(|line| Line::from(line.to_string()))
+        .collect()
+}
+
+/// 在目录旁显示选中章节的开头几行
+fn render_preview(f: &mut Frame, theme: &Theme, novel: &Novel, index: usize, area: Rect) {
+    let lines = preview_lines(novel, index, area.height.saturating_sub(2) as usize);
+    let preview = Paragraph::new(lines)
+        .style(Style::default().fg(theme.muted))
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("预览", "Preview")),
+        );
+    f.render_widget(preview, area);
+}
+
+/// 字数不足一万时显示原值，否则以“万字”为单位保留一位小数（英文界面以千字为单位）
+pub(super) fn format_word_count(words: usize) -> String {
+    match language() {
+        Language::Chinese if words < 10_000 => format!("{}字", words),
+        Language::Chinese => format!("{:.1}万字", words as f64 / 10_000.0),
+        Language::English if words < 1_000 => format!("{} chars", words),
+        Language::English => format!("{:.1}k chars", words as f64 / 1_000.0),
+    }
 }
@@ -0,0 +1,49 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use unicode_width::UnicodeWidthStr;
+
+use super::theme::Theme;
+use crate::app::ConfirmDialog;
+use crate::i18n::tr;
+
+/// 在屏幕中央绘制确认对话框
+/// # 参数
+/// - `f`: 渲染框架
+/// - `theme`: 界面配色
+/// - `dialog`: 对话框内容
+pub fn render_confirm(f: &mut Frame, theme: &Theme, dialog: &ConfirmDialog) {
+    let area = f.area();
+    let hint = tr(
+        "y/Enter: 确认 | n/Esc: 取消",
+        "y/Enter: Confirm | n/Esc: Cancel",
+    );
+
+    let content_width = dialog.message.width().max(hint.width()) as u16;
+    let width = (content_width + 4).min(area.width);
+    let height = 5.min(area.height);
+    let popup_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+
+    let text = vec![
+        Line::from(dialog.message.as_str()),
+        Line::default(),
+        Line::from(Span::styled(hint, Style::default().fg(theme.muted))),
+    ];
+    let popup = Paragraph::new(text)
+        .style(Style::default().fg(theme.text).bg(theme.background))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("确认", "Confirm"))
+                .border_style(Style::default().fg(theme.emphasis)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
@@ -0,0 +1,79 @@
+//! 由书名生成的强调色和字符封面，让书架上的书更容易区分
+
+use ratatui::prelude::*;
+
+/// 可选的强调色，避开与背景、选中行相近的黑白灰
+const ACCENT_COLORS: [Color; 12] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+/// 封面图案的行数和（左半边的）列数，右半边与左半边对称
+const COVER_ROWS: usize = 5;
+const COVER_HALF_COLUMNS: usize = 3;
+
+/// 封面占用的显示宽度（每格两个字符宽）和高度
+pub const COVER_WIDTH: u16 = (COVER_HALF_COLUMNS * 2 - 1) as u16 * 2;
+pub const COVER_HEIGHT: u16 = COVER_ROWS as u16;
+
+fn title_hash(title: &str) -> u32 {
+    crc32fast::hash(title.as_bytes())
+}
+
+/// 由书名得到的强调色，同一书名总是得到同一种颜色
+pub fn accent_color(title: &str) -> Color {
+    ACCENT_COLORS[title_hash(title) as usize % ACCENT_COLORS.len()]
+}
+
+/// 由书名生成左右对称的色块封面（类似头像生成器）
+///
+/// # Returns
+///
+/// `COVER_HEIGHT` 行，每行 `COVER_WIDTH` 个字符宽。
+pub fn cover_lines(title: &str) -> Vec<Line<'static>> {
+    let hash = title_hash(title);
+    let style = Style::default().fg(accent_color(title));
+    (0..COVER_ROWS)
+        .map(|row| {
+            let half: Vec<bool> = (0..COVER_HALF_COLUMNS)
+                .map(|column| hash >> (row * COVER_HALF_COLUMNS + column) & 1 == 1)
+                .collect();
+            let cells: String = half
+                .iter()
+                .chain(half.iter().rev().skip(1))
+                .map(|&filled| if filled { "██" } else { "░░" })
+                .collect();
+            Line::styled(cells, style)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_width::UnicodeWidthStr;
+
+    #[test]
+    fn test_cover_is_stable_and_symmetric() {
+        assert_eq!(accent_color("三体"), accent_color("三体"));
+        let cover = cover_lines("三体");
+        assert_eq!(cover, cover_lines("三体"));
+        assert_eq!(cover.len(), COVER_HEIGHT as usize);
+        for line in &cover {
+            let text = line.to_string();
+            assert_eq!(text.width(), COVER_WIDTH as usize);
+            let cells: Vec<char> = text.chars().step_by(2).collect();
+            assert!(cells.iter().eq(cells.iter().rev()));
+        }
+    }
+}
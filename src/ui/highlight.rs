@@ -0,0 +1,141 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::utils::{render_help_info, split_preview_area};
+use crate::app::App;
+use crate::i18n::{tr, trf};
+
+/// 高亮列表中每项预览的最大字符数
+const PREVIEW_CHARS: usize = 30;
+
+/// 渲染当前小说的高亮列表
+/// # 参数
+/// - `f`: 渲染框架
+/// - `app`: 应用状态
+pub fn render_highlight_list(f: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let area = f.area();
+
+    let title = Paragraph::new(tr("高亮", "Highlights"))
+        .style(Style::default().fg(theme.secondary))
+        .alignment(Alignment::Center);
+    f.render_widget(
+        title,
+        Rect {
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: 2,
+        },
+    );
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let (Some(novel), Some(highlights)) = (&app.current_novel, app.get_current_highlights()) else {
+        return;
+    };
+    if highlights.is_empty() {
+        let empty = Paragraph::new(tr("暂无高亮\n\n阅读时按 'v' 选择行，再按 Enter 保存为高亮", "No highlights yet\n\nPress 'v' while reading to select lines, then Enter to highlight them"))
+            .style(Style::default().fg(theme.emphasis))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::ALL).title(tr("提示", "Hint")));
+        f.render_widget(empty, content_area);
+        render_help_info(
+            f,
+            theme,
+            &trf!(
+                "Esc: 返回阅读 | {}: 退出",
+                "Esc: Back to reading | {}: Quit",
+                app.preferences.quit_key.hint()
+            ),
+            area,
+        );
+        return;
+    }
+
+    let lines = novel.lines();
+    let items: Vec<ListItem> = highlights
+        .iter()
+        .enumerate()
+        .map(|(index, highlight)| {
+            let prefix = if Some(index) == app.highlight_list.selected_index {
+                ">> "
+            } else {
+                "   "
+            };
+            let range = if highlight.start == highlight.end {
+                trf!("第 {} 行", "Line {}", highlight.start + 1)
+            } else {
+                trf!(
+                    "第 {}–{} 行",
+                    "Lines {}–{}",
+                    highlight.start + 1,
+                    highlight.end + 1
+                )
+            };
+            let preview: String = lines
+                .get(highlight.start)
+                .map_or("", |line| line.trim())
+                .chars()
+                .take(PREVIEW_CHARS)
+                .collect();
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{}{}  ", prefix, range)),
+                Span::styled(preview, Style::default().fg(theme.dim)),
+            ]))
+            .style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(trf!(
+            "高亮列表 (共{}处)",
+            "Highlights ({} total)",
+            highlights.len()
+        )))
+        .highlight_style(Style::default().bg(theme.selection_bg))
+        .highlight_symbol("");
+
+    let (list_area, detail_area) = split_preview_area(content_area);
+    if let Some(highlight) = app
+        .highlight_list
+        .selected_index
+        .and_then(|index| highlights.get(index))
+    {
+        let text: Vec<Line> = lines
+            .get(highlight.start..=highlight.end.min(lines.len().saturating_sub(1)))
+            .unwrap_or_default()
+            .iter()
+            .map(|line| Line::from(line.trim().to_string()))
+            .collect();
+        let detail = Paragraph::new(text)
+            .style(Style::default().fg(theme.text).bg(theme.highlight_bg))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr("内容", "Content")),
+            );
+        f.render_widget(detail, detail_area);
+    }
+
+    let mut state = ListState::default();
+    state.select(app.highlight_list.selected_index);
+    f.render_stateful_widget(list, list_area, &mut state);
+
+    render_help_info(
+        f,
+        theme,
+        &trf!(
+            "↑/↓: 选择高亮 | Enter: 跳转 | d: 删除 | Esc: 返回阅读 | {}: 退出",
+            "↑/↓: Select highlight | Enter: Jump | d: Delete | Esc: Back to reading | {}: Quit",
+            app.preferences.quit_key.hint()
+        ),
+        area,
+    );
+}
@@ -0,0 +1,55 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+use unicode_width::UnicodeWidthStr;
+
+use crate::app::App;
+use crate::i18n::tr;
+
+/// 按下 `,` 后在右下角列出当前界面可用的组合键
+pub fn render_leader_popup(f: &mut Frame, app: &App) {
+    let area = f.area();
+    let theme = app.theme();
+    let bindings = app.leader_bindings();
+    let title = tr(" , 组合键 ", " , chords ");
+
+    let content_width = bindings
+        .iter()
+        .map(|(_, action)| action.label().width() + 3)
+        .chain([title.width()])
+        .max()
+        .unwrap_or(0) as u16;
+    let width = (content_width + 4).min(area.width);
+    let height = (bindings.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let popup_area = Rect {
+        x: area.x + area.width.saturating_sub(width + 1),
+        y: area.y + area.height.saturating_sub(height + 2),
+        width,
+        height,
+    };
+
+    let lines: Vec<Line> = bindings
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}  ", key),
+                    Style::default()
+                        .fg(theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(action.label()),
+            ])
+        })
+        .collect();
+    let popup = Paragraph::new(lines)
+        .style(Style::default().fg(theme.text).bg(theme.background))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(theme.emphasis)),
+        );
+
+    f.render_widget(Clear, popup_area);
+    f.render_widget(popup, popup_area);
+}
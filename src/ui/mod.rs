@@ -1,9 +1,17 @@
+pub mod annotation;
+pub mod book_detail;
 pub mod bookmark;
 pub mod bookshelf;
 pub mod chapter_list;
+pub mod confirm;
+pub mod cover;
+pub mod highlight;
+pub mod leader;
 pub mod reader;
 pub mod search;
 pub mod settings;
+pub mod setup;
 pub mod sync_status;
+pub mod theme;
 pub mod utils;
 pub use utils::render;
@@ -0,0 +1,490 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::utils::{create_line_with_ranges, render_help_info};
+use crate::app::App;
+use crate::config::config;
+use crate::i18n::{tr, trf};
+use crate::model::matcher::highlight_ranges;
+
+mod vertical;
+mod wrap;
+
+#[cfg(test)]
+pub use wrap::wrap_line;
+pub use wrap::{WrappedRow, wrap_rows};
+
+/// 伪装模式下左侧日志前缀栏的宽度
+const CAMOUFLAGE_GUTTER_WIDTH: u16 = 20;
+
+/// 计算阅读界面中正文文字实际占用的区域（不含边框）
+///
+/// 渲染与翻页计算共用此函数，保证两者对可见区域的理解一致。
+/// 专注模式下不绘制边框和帮助栏，正文可使用更大的区域；
+/// 随后扣除偏好设置中的留白。伪装模式只影响绘制，不改变正文区域。
+pub fn text_area(app: &App, area: Rect) -> Rect {
+    let mut text_area = if app.reader.zen_mode {
+        Rect {
+            x: area.x + 1,
+            y: area.y,
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(1),
+        }
+    } else {
+        Rect {
+            x: area.x + 2,
+            y: area.y + 1,
+            width: area.width.saturating_sub(4),
+            height: area.height.saturating_sub(3),
+        }
+    };
+
+    // 留白过大时至少保留一行一列正文
+    let padding = app.preferences.padding;
+    let left = padding.left.min(text_area.width.saturating_sub(1));
+    let right = padding.right.min(text_area.width.saturating_sub(1 + left));
+    let top = padding.top.min(text_area.height.saturating_sub(1));
+    let bottom = padding.bottom.min(text_area.height.saturating_sub(1 + top));
+    text_area.x += left;
+    text_area.y += top;
+    text_area.width = text_area.width.saturating_sub(left + right);
+    text_area.height = text_area.height.saturating_sub(top + bottom);
+
+    let max_width = config().reader.max_width;
+    if max_width > 0 && text_area.width > max_width && !app.reader.vertical_layout {
+        text_area.x += (text_area.width - max_width) / 2;
+        text_area.width = max_width;
+    }
+    text_area
+}
+
+/// 伪装模式下正文和日志前缀栏的绘制位置
+///
+/// 前缀栏只占用正文两侧的空白：左侧空白不够时正文整体右移，宽度保持不变，
+/// 因此折行和翻页与普通模式完全一致；两侧空白都不够时前缀栏相应变窄。
+///
+/// # Returns
+///
+/// 返回 `(正文绘制区域, 前缀栏区域)`
+fn camouflage_areas(area: Rect, text_area: Rect) -> (Rect, Rect) {
+    let room = area.width.saturating_sub(text_area.width);
+    let gutter_width = CAMOUFLAGE_GUTTER_WIDTH.min(room);
+    let x = text_area.x.max(area.x + gutter_width);
+    let text = Rect { x, ..text_area };
+    let gutter = Rect {
+        x: x - gutter_width,
+        width: gutter_width,
+        ..text_area
+    };
+    (text, gutter)
+}
+
+/// 阅读区排版信息
+///
+/// 渲染和翻页计算共用，横排与竖排的差异都封装在这里：
+/// 横排时"物理行"是屏幕上的一行，竖排时是屏幕上的一列。
+pub struct ReaderLayout {
+    /// 正文区域
+    pub text_area: Rect,
+    /// 是否为竖排（从右到左）
+    pub vertical: bool,
+    /// 横排折行时是否为长单词添加连字符
+    pub hyphenate: bool,
+    /// 横排时段首缩进的全角空格数
+    pub indent: usize,
+}
+
+impl ReaderLayout {
+    pub fn new(app: &App, area: Rect) -> Self {
+        Self {
+            text_area: text_area(app, area),
+            vertical: app.reader.vertical_layout,
+            hyphenate: app.preferences.hyphenate,
+            indent: config().reader.indent,
+        }
+    }
+
+    /// 一页可容纳的物理行数（竖排时为列数）
+    pub fn page_rows(&self) -> usize {
+        if self.vertical {
+            (self.text_area.width / vertical::COLUMN_WIDTH) as usize
+        } else {
+            self.text_area.height as usize
+        }
+    }
+
+    /// 一行正文在当前排版下占用的物理行数（竖排时为列数）
+    pub fn line_rows(&self, line: &str) -> usize {
+        if self.vertical {
+            vertical::count_columns(line, self.text_area.height as usize)
+        } else {
+            self.wrap(line).len()
+        }
+    }
+
+    /// 横排时将一行正文折成屏幕行，非空行先按设置统一段首缩进
+    pub fn wrap(&self, line: &str) -> Vec<String> {
+        self.wrap_rows(line)
+            .into_iter()
+            .map(|row| row.text)
+            .collect()
+    }
+
+    /// 与 [`ReaderLayout::wrap`] 相同，同时给出各屏幕行在原行中的起始字符位置
+    ///
+    /// 缩进部分不对应原文，以缩进开头的屏幕行从原文第一个非空白字符算起，缩进计入 `prefix`。
+    pub fn wrap_rows(&self, line: &str) -> Vec<WrappedRow> {
+        let width = self.text_area.width as usize;
+        if self.indent == 0 || line.trim().is_empty() {
+            return wrap_rows(line, width, self.hyphenate);
+        }
+        let body = line.trim_start();
+        let skipped = line.chars().count() - body.chars().count();
+        let indented = format!("{}{}", "\u{3000}".repeat(self.indent), body);
+        let mut rows = wrap_rows(&indented, width, self.hyphenate);
+        for row in &mut rows {
+            match row.start.checked_sub(self.indent) {
+                Some(start) => row.start = start + skipped,
+                // 首行以缩进开头，缩进之后才是原文
+                None => {
+                    row.prefix = self.indent - row.start;
+                    row.len = row.len.saturating_sub(row.prefix);
+                    row.start = skipped;
+                }
+            }
+        }
+        rows
+    }
+
+    /// 一行正文中页首可以停留的位置数：横排为屏幕行数，竖排只能停在行首
+    pub fn scroll_rows(&self, line: &str) -> usize {
+        if self.vertical {
+            1
+        } else {
+            self.line_rows(line)
+        }
+    }
+
+    /// 行内字符位置 `offset` 所在的屏幕行序号；竖排时阅读位置只精确到行，总是 0
+    pub fn row_of_offset(&self, line: &str, offset: usize) -> usize {
+        if self.vertical || offset == 0 {
+            return 0;
+        }
+        self.wrap_rows(line)
+            .partition_point(|row| row.start <= offset)
+            .saturating_sub(1)
+    }
+
+    /// 第 `row` 个屏幕行在原行中的起始字符位置，超出行数时取最后一行
+    pub fn row_start(&self, line: &str, row: usize) -> usize {
+        if self.vertical || row == 0 {
+            return 0;
+        }
+        let rows = self.wrap_rows(line);
+        rows.get(row).or(rows.last()).map_or(0, |row| row.start)
+    }
+}
+
+/// 正文中一行的背景：选中 > 引导线 > 已保存的高亮
+fn line_background(app: &App, line_num: usize, guide: bool) -> Option<Color> {
+    if app
+        .visual_range()
+        .is_some_and(|(start, end)| (start..=end).contains(&line_num))
+    {
+        return Some(app.theme().visual_bg);
+    }
+    if guide {
+        return Some(app.theme().guide_bg);
+    }
+    app.get_current_highlights()
+        .is_some_and(|highlights| highlights.iter().any(|h| h.contains(line_num)))
+        .then_some(app.theme().highlight_bg)
+}
+
+/// 该行是否有批注（有批注的行加下划线）
+fn has_annotation(app: &App, line_num: usize) -> bool {
+    app.get_current_annotations()
+        .is_some_and(|annotations| annotations.iter().any(|a| a.line == line_num))
+}
+
+/// 根据行号生成稳定的伪日志前缀（时间戳 + 日志级别）
+///
+/// 同一行每次渲染得到相同的前缀，滚动时不会闪烁。
+fn camouflage_prefix(line_num: usize) -> String {
+    const LEVELS: [&str; 4] = ["INFO", "DEBUG", "INFO", "TRACE"];
+    let seconds = 9 * 3600 + line_num * 7 % (15 * 3600);
+    let millis = line_num * 389 % 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03} {:<5}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        millis,
+        LEVELS[line_num % LEVELS.len()]
+    )
+}
+
+/// 按横排方式绘制正文，包括关键词高亮、阅读引导线和伪装前缀
+fn render_horizontal(
+    f: &mut Frame,
+    app: &App,
+    area: Rect,
+    layout: &ReaderLayout,
+    visible: std::ops::Range<usize>,
+) {
+    let theme = app.theme();
+    let (text_area, gutter_area) = if app.reader.camouflage {
+        let (text_area, gutter_area) = camouflage_areas(area, layout.text_area);
+        (text_area, Some(gutter_area))
+    } else {
+        (layout.text_area, None)
+    };
+    let Some(novel) = &app.current_novel else {
+        return;
+    };
+    let spacing = novel.progress.line_spacing;
+    let visible_lines = novel.lines().get(visible.clone()).unwrap_or_default();
+
+    let highlight_term = app.reader.highlight_term.as_deref().unwrap_or("");
+    let top_row = visible_lines.first().map_or(0, |line| {
+        layout.row_of_offset(line, novel.top_offset_in_line())
+    });
+    let guide_index = app
+        .reader
+        .reading_guide
+        .then(|| app.reader.guide_anchor.min(visible.len().saturating_sub(1)));
+    let mut visible_content: Vec<Line> = Vec::new();
+    for (i, line) in visible_lines.iter().enumerate() {
+        if i > 0 {
+            visible_content.extend(std::iter::repeat_n(Line::default(), spacing));
+        }
+        let background = line_background(app, visible.start + i, guide_index == Some(i));
+        let annotated = has_annotation(app, visible.start + i);
+        // 页首可能在首行中间，跳过首行中页首之前的屏幕行
+        let skip = if i == 0 { top_row } else { 0 };
+        // 高亮在整行原文上计算，再分到各屏幕行，跨越折行（含连字符断点）的匹配不会断开
+        let ranges = if highlight_term.is_empty() {
+            Vec::new()
+        } else {
+            highlight_ranges(line, highlight_term, app.reader.highlight_fuzzy)
+        };
+        for row in layout.wrap_rows(line).into_iter().skip(skip) {
+            let row_ranges = row.map_ranges(line, &ranges);
+            let mut rendered = create_line_with_ranges(theme, &row.text, &row_ranges);
+            if let Some(color) = background {
+                rendered = rendered.patch_style(Style::default().bg(color));
+            }
+            if annotated {
+                rendered =
+                    rendered.patch_style(Style::default().add_modifier(Modifier::UNDERLINED));
+            }
+            visible_content.push(rendered);
+        }
+    }
+    let text_color = if app.reader.camouflage {
+        theme.muted
+    } else {
+        theme.text
+    };
+    // 已按 ReaderLayout 预先折行，不再交给 Paragraph 折行，以免与翻页计算不一致
+    let content = Paragraph::new(visible_content).style(Style::default().fg(text_color));
+    f.render_widget(content, text_area);
+
+    if let Some(gutter_area) = gutter_area
+        && !visible_lines.is_empty()
+    {
+        let mut gutter_lines: Vec<Line> = Vec::new();
+        for (i, line) in visible_lines.iter().enumerate() {
+            gutter_lines.push(Line::from(camouflage_prefix(visible.start + i)));
+            let skip = if i == 0 { top_row } else { 0 };
+            let rows = layout.line_rows(line) - 1 - skip + spacing;
+            gutter_lines.extend(std::iter::repeat_n(Line::default(), rows));
+        }
+        f.render_widget(
+            Paragraph::new(gutter_lines).style(Style::default().fg(theme.dim)),
+            gutter_area,
+        );
+    }
+}
+
+pub fn render_reader(f: &mut Frame, app: &App) {
+    let theme = app.theme();
+    if let Some(novel) = &app.current_novel {
+        let area = f.area();
+        let zen_mode = app.reader.zen_mode;
+
+        let layout = ReaderLayout::new(app, area);
+        let text_area = layout.text_area;
+
+        let total_lines = novel.line_count();
+        let spacing = novel.progress.line_spacing;
+        let line_physical_height = spacing + 1;
+
+        let start_line = novel
+            .progress
+            .scroll_offset
+            .min(total_lines.saturating_sub(1));
+        let visible_line_count = layout.page_rows() / line_physical_height;
+        let end_line = (start_line + visible_line_count).min(total_lines);
+
+        // 伪装模式下横排正文像日志输出一样不加边框，前缀栏可能占用边框位置
+        let camouflaged = app.reader.camouflage && !layout.vertical;
+        if !zen_mode && !camouflaged {
+            let content_area = Rect {
+                x: area.x + 1,
+                y: area.y,
+                width: area.width - 2,
+                height: area.height - 1,
+            };
+            f.render_widget(Block::default().borders(Borders::ALL), content_area);
+        }
+
+        if layout.vertical {
+            if start_line < total_lines {
+                let style = Style::default().fg(theme.text);
+                vertical::render_vertical(
+                    f.buffer_mut(),
+                    text_area,
+                    &novel.lines()[start_line..end_line],
+                    spacing,
+                    |i| {
+                        let style = match line_background(app, start_line + i, false) {
+                            Some(color) => style.bg(color),
+                            None => style,
+                        };
+                        if has_annotation(app, start_line + i) {
+                            style.add_modifier(Modifier::UNDERLINED)
+                        } else {
+                            style
+                        }
+                    },
+                );
+            }
+        } else {
+            render_horizontal(f, app, area, &layout, start_line..end_line);
+        }
+
+        if let Some(input) = &app.reader.find_input {
+            let prompt = trf!(
+                "查找: {}▏ │ Enter:确定 Esc:取消",
+                "Find: {}▏ │ Enter:Confirm Esc:Cancel",
+                input
+            );
+            render_help_info(f, theme, &prompt, area);
+            return;
+        }
+        if let Some(prompt) = super::annotation::annotation_prompt(app) {
+            render_help_info(f, theme, &prompt, area);
+            return;
+        }
+        if let Some((start, end)) = app.visual_range() {
+            let prompt = trf!(
+                "选择: 第 {}–{} 行 │ j/k:扩展 Enter:高亮 Esc:取消",
+                "Select: lines {}–{} │ j/k:Extend Enter:Highlight Esc:Cancel",
+                start + 1,
+                end + 1
+            );
+            render_help_info(f, theme, &prompt, area);
+            return;
+        }
+
+        if zen_mode {
+            return;
+        }
+
+        let percent = ((start_line + 1) * 100)
+            .checked_div(total_lines)
+            .unwrap_or(0);
+        let progress_text = format!("{}/{}({}%)", start_line + 1, total_lines, percent);
+        let chapter_text = novel
+            .chapter_progress()
+            .map(|(index, chapter_percent)| {
+                trf!("第{}章·{}% │ ", "Ch.{}·{}% │ ", index + 1, chapter_percent)
+            })
+            .unwrap_or_default();
+        let bookmark_count = novel.progress.bookmarks.len();
+        let bookmark_info = if bookmark_count > 0 {
+            trf!(" 签:{}", " bm:{}", bookmark_count)
+        } else {
+            String::new()
+        };
+
+        let spacing_info = if spacing > 0 {
+            trf!(" 间距:{}", " spacing:{}", spacing)
+        } else {
+            String::new()
+        };
+
+        let overlap_info = if app.preferences.page_overlap != 1 {
+            trf!(" 重叠:{}", " overlap:{}", app.preferences.page_overlap)
+        } else {
+            String::new()
+        };
+        let count_info = app
+            .reader
+            .pending_count
+            .map(|count| trf!(" 计数:{}", " count:{}", count))
+            .unwrap_or_default();
+        let match_info = app
+            .match_position()
+            .map(|(current, total)| trf!(" 匹配:{}/{}", " match:{}/{}", current, total))
+            .unwrap_or_default();
+        let auto_scroll_info = if app.reader.auto_scroll.is_some() {
+            tr(" 自动滚动", " auto-scroll")
+        } else {
+            ""
+        };
+        let spacing_info = format!(
+            "{}{}{}{}{}",
+            spacing_info, overlap_info, count_info, match_info, auto_scroll_info
+        );
+
+        let elapsed_minutes = app.reader.session_elapsed().as_secs() / 60;
+        let clock_text = trf!(
+            "{} 已读{}:{:02}",
+            "{} read {}:{:02}",
+            chrono::Local::now().format("%H:%M"),
+            elapsed_minutes / 60,
+            elapsed_minutes % 60
+        );
+
+        let width = area.width as usize;
+        let quit = app.preferences.quit_key.hint();
+        let help_text = if width >= 100 {
+            trf!(
+                "{} │ {}{}行{}{} │ jk:滚动 hl:翻页 []:章节 /:搜索 f:查找 t:目录 b:书签 M:加签 '1-9:快捷书签 z:专注 e:夜间 Esc:返回 {}:退出",
+                "{} │ {}{} lines{}{} │ jk:Scroll hl:Page []:Chapter /:Search f:Find t:Chapters b:Bookmarks M:Mark '1-9:Quick mark z:Zen e:Night Esc:Back {}:Quit",
+                clock_text,
+                chapter_text,
+                progress_text,
+                bookmark_info,
+                spacing_info,
+                quit
+            )
+        } else if width >= 70 {
+            trf!(
+                "{} │ {}{}行{}{} │ jk:滚动 hl:翻页 []:章节 /:搜 t:目录 b:签 M:加签 z:专注 {}:退",
+                "{} │ {}{} lines{}{} │ jk:Scroll hl:Page []:Ch /:Search t:TOC b:Bm M:Mark z:Zen {}:Quit",
+                clock_text,
+                chapter_text,
+                progress_text,
+                bookmark_info,
+                spacing_info,
+                quit
+            )
+        } else if width >= 50 {
+            trf!(
+                "{} │ {}{}行{} │ jk:滚 hl:翻 []:章 /:搜 t:目录 {}:退",
+                "{} │ {}{}{} │ jk hl []:Ch /:Srch t:TOC {}:Quit",
+                clock_text,
+                chapter_text,
+                progress_text,
+                spacing_info,
+                quit
+            )
+        } else {
+            trf!("{}行{}", "{}{}", progress_text, spacing_info)
+        };
+        render_help_info(f, theme, &help_text, area);
+    }
+}
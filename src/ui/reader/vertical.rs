@@ -0,0 +1,83 @@
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthChar;
+
+/// 竖排时每列占用的终端宽度（一个全角字符）
+pub(super) const COLUMN_WIDTH: u16 = 2;
+
+/// 竖排时需要替换为竖排字形的标点
+fn vertical_form(ch: char) -> char {
+    match ch {
+        '…' => '︙',
+        '—' => '︱',
+        '（' | '(' => '︵',
+        '）' | ')' => '︶',
+        '「' => '﹁',
+        '」' => '﹂',
+        '『' => '﹃',
+        '』' => '﹄',
+        '《' => '︽',
+        '》' => '︾',
+        '【' => '︻',
+        '】' => '︼',
+        _ => ch,
+    }
+}
+
+/// 计算一行正文竖排时占用的列数
+///
+/// # Arguments
+///
+/// * `line` - 要计算的字符串
+/// * `rows` - 每列可容纳的字符数
+///
+/// # Returns
+///
+/// 占用的列数。空字符串或零高度返回 1。
+pub(super) fn count_columns(line: &str, rows: usize) -> usize {
+    let chars = line.chars().filter(|c| c.width().unwrap_or(0) > 0).count();
+    if chars == 0 || rows == 0 {
+        return 1;
+    }
+    chars.div_ceil(rows)
+}
+
+/// 按传统竖排方式（列从右到左，字从上到下）逐格绘制正文
+///
+/// 每个逻辑行从新的一列开始，行间距表现为额外的空列。
+/// `line_style` 根据行在 `lines` 中的序号返回该行的样式（用于高亮）。
+pub(super) fn render_vertical(
+    buf: &mut Buffer,
+    area: Rect,
+    lines: &[String],
+    spacing: usize,
+    line_style: impl Fn(usize) -> Style,
+) {
+    let columns = (area.width / COLUMN_WIDTH) as usize;
+    let rows = area.height as usize;
+    if columns == 0 || rows == 0 {
+        return;
+    }
+
+    let mut column = 0;
+    for (index, line) in lines.iter().enumerate() {
+        let style = line_style(index);
+        let mut row = 0;
+        for ch in line.chars().filter(|c| c.width().unwrap_or(0) > 0) {
+            if row == rows {
+                column += 1;
+                row = 0;
+            }
+            if column >= columns {
+                return;
+            }
+            let x = area.right() - COLUMN_WIDTH * (column as u16 + 1);
+            let y = area.y + row as u16;
+            buf.set_string(x, y, vertical_form(ch).to_string(), style);
+            row += 1;
+        }
+        column += 1 + spacing;
+        if column >= columns {
+            return;
+        }
+    }
+}
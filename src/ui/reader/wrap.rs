@@ -0,0 +1,235 @@
+use std::ops::Range;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 折行后的一个屏幕行
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappedRow {
+    /// 本行第一个原文字符在原行中的字符位置
+    pub start: usize,
+    /// 本行显示的文字
+    pub text: String,
+    /// 文字开头不属于原文的字符数（段首缩进）
+    pub prefix: usize,
+    /// 本行显示的原文字符数（不含缩进和补上的连字符）
+    pub len: usize,
+}
+
+impl WrappedRow {
+    fn new(start: usize, text: String, hyphenated: bool) -> Self {
+        let len = text.chars().count() - usize::from(hyphenated);
+        Self {
+            start,
+            text,
+            prefix: 0,
+            len,
+        }
+    }
+
+    /// 把原行中的字节范围换算为本行文字中的字节范围
+    ///
+    /// 渲染高亮时使用：高亮在整行原文上计算，再按折行位置分到各屏幕行，
+    /// 跨行的匹配在每一行中各高亮一段，补上的连字符和缩进不会被高亮。
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - 折行前的原行
+    /// * `ranges` - 原行中的字节范围，升序且互不重叠
+    pub fn map_ranges(&self, line: &str, ranges: &[Range<usize>]) -> Vec<Range<usize>> {
+        let char_index = |byte: usize| line[..byte].chars().count();
+        let text_byte = |index: usize| {
+            self.text
+                .char_indices()
+                .nth(index)
+                .map_or(self.text.len(), |(byte, _)| byte)
+        };
+        let row_end = self.start + self.len;
+        ranges
+            .iter()
+            .filter_map(|range| {
+                let start = char_index(range.start).max(self.start);
+                let end = char_index(range.end).min(row_end);
+                (start < end).then(|| {
+                    text_byte(start - self.start + self.prefix)
+                        ..text_byte(end - self.start + self.prefix)
+                })
+            })
+            .collect()
+    }
+}
+
+/// 按单词边界将一行正文折成若干屏幕行
+///
+/// 渲染和翻页计算共用此函数，保证两者对折行的理解完全一致。
+/// 英文在空格处断行，中日韩等宽字符可在任意字符间断行；
+/// 单个单词超过整行宽度时强制断开，`hyphenate` 为真时在断开处补连字符。
+///
+/// # Arguments
+///
+/// * `line` - 要折行的字符串
+/// * `width` - 可用宽度（终端列数）
+/// * `hyphenate` - 是否为被强制断开的英文单词添加连字符
+///
+/// # Returns
+///
+/// 折行后的各屏幕行及其在原行中的起始位置，至少包含一行。
+pub fn wrap_rows(line: &str, width: usize, hyphenate: bool) -> Vec<WrappedRow> {
+    if line.is_empty() || width == 0 {
+        return vec![WrappedRow::new(0, line.to_string(), false)];
+    }
+
+    let mut rows = Vec::new();
+    let mut current = String::new();
+    // 当前行第一个字符在原行中的字符位置
+    let mut current_start = 0;
+    let mut current_width = 0;
+    // 当前行中最后一个可断行位置（字节偏移），之后的内容可整体移到下一行
+    let mut break_at: Option<usize> = None;
+
+    for (index, ch) in line.chars().enumerate() {
+        let ch_width = ch.width().unwrap_or(0);
+
+        if current_width + ch_width > width && ch.is_whitespace() {
+            // 行尾溢出的空白直接丢弃，不带到下一行开头
+            rows.push(WrappedRow::new(
+                current_start,
+                current.trim_end().to_string(),
+                false,
+            ));
+            current.clear();
+            current_start = index + 1;
+            current_width = 0;
+            break_at = None;
+            continue;
+        }
+
+        while current_width > 0 && current_width + ch_width > width {
+            let row_start = current_start;
+            match break_at.take() {
+                Some(pos) if pos > 0 => {
+                    let rest = current.split_off(pos);
+                    current_start += current.chars().count();
+                    rows.push(WrappedRow::new(
+                        row_start,
+                        current.trim_end().to_string(),
+                        false,
+                    ));
+                    current = rest;
+                }
+                _ if hyphenate
+                    && width >= 2
+                    && ch.is_ascii_alphanumeric()
+                    && current.ends_with(|c: char| c.is_ascii_alphanumeric()) =>
+                {
+                    let last = current.pop().unwrap_or_default();
+                    current.push('-');
+                    rows.push(WrappedRow::new(
+                        row_start,
+                        std::mem::take(&mut current),
+                        true,
+                    ));
+                    current.push(last);
+                    current_start = index - 1;
+                }
+                _ => {
+                    rows.push(WrappedRow::new(
+                        row_start,
+                        std::mem::take(&mut current),
+                        false,
+                    ));
+                    current_start = index;
+                }
+            }
+            current_width = current.width();
+        }
+
+        current.push(ch);
+        current_width += ch_width;
+        if ch.is_whitespace() || ch_width > 1 {
+            break_at = Some(current.len());
+        }
+    }
+
+    rows.push(WrappedRow::new(current_start, current, false));
+    rows
+}
+
+/// 与 [`wrap_rows`] 相同，只返回各屏幕行的文字
+#[cfg(test)]
+pub fn wrap_line(line: &str, width: usize, hyphenate: bool) -> Vec<String> {
+    wrap_rows(line, width, hyphenate)
+        .into_iter()
+        .map(|row| row.text)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_breaks_at_word_boundaries() {
+        assert_eq!(
+            wrap_line("the quick brown fox", 10, false),
+            vec!["the quick", "brown fox"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_cjk_breaks_anywhere() {
+        assert_eq!(
+            wrap_line("一二三四五", 4, false),
+            vec!["一二", "三四", "五"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_long_word_with_hyphenation() {
+        assert_eq!(
+            wrap_line("abcdefghij", 4, true),
+            vec!["abc-", "def-", "ghij"]
+        );
+        assert_eq!(
+            wrap_line("abcdefghij", 4, false),
+            vec!["abcd", "efgh", "ij"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_rows_record_start_positions() {
+        let starts = |line: &str, width: usize, hyphenate: bool| -> Vec<usize> {
+            wrap_rows(line, width, hyphenate)
+                .iter()
+                .map(|row| row.start)
+                .collect()
+        };
+        assert_eq!(starts("the quick brown fox", 10, false), vec![0, 10]);
+        assert_eq!(starts("一二三四五", 4, false), vec![0, 2, 4]);
+        assert_eq!(starts("abcdefghij", 4, true), vec![0, 3, 6]);
+        assert_eq!(starts("abcdefghij", 4, false), vec![0, 4, 8]);
+        // 行尾溢出的空白被丢弃，下一行从空白之后开始
+        assert_eq!(starts("abcd efgh", 4, false), vec![0, 5]);
+    }
+
+    #[test]
+    fn test_map_ranges_across_hyphenated_rows() {
+        let line = "abcdefghij";
+        let rows = wrap_rows(line, 4, true);
+        // “cde” 跨越第一、二行的连字符断点，连字符本身不高亮
+        let mapped: Vec<_> = rows
+            .iter()
+            .map(|row| row.map_ranges(line, &[0..1, 2..5]))
+            .collect();
+        assert_eq!(mapped, vec![vec![0..1, 2..3], vec![0..2], vec![]]);
+        assert_eq!(&rows[1].text[0..2], "de");
+    }
+
+    #[test]
+    fn test_wrap_rows_never_exceed_width() {
+        let line = "Hello, 世界! A mixed line with 中文 and supercalifragilistic words.";
+        for width in 2..30 {
+            for row in wrap_line(line, width, true) {
+                assert!(row.width() <= width, "{:?} wider than {}", row, width);
+            }
+        }
+    }
+}
@@ -2,59 +2,20 @@ use ratatui::prelude::*;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::*;
 
-use super::utils::render_help_info;
-use crate::app::App;
-
-/// 创建带高亮的文本行
-/// # 参数
-/// - `text`: 原始文本
-/// - `search_term`: 搜索关键词
-/// # 返回
-/// 返回包含高亮显示的Line对象
-fn create_highlighted_line(text: &str, search_term: &str) -> Line<'static> {
-    if search_term.is_empty() {
-        return Line::from(text.to_string());
-    }
-
-    let mut spans = Vec::new();
-    let text_lower = text.to_lowercase();
-    let search_lower = search_term.to_lowercase();
-    let mut last_end = 0;
-
-    while let Some(start) = text_lower[last_end..].find(&search_lower) {
-        let actual_start = last_end + start;
-        let actual_end = actual_start + search_term.len();
-
-        if actual_start > last_end {
-            spans.push(Span::styled(
-                text[last_end..actual_start].to_string(),
-                Style::default().fg(Color::White),
-            ));
-        }
-
-        spans.push(Span::styled(
-            text[actual_start..actual_end].to_string(),
-            Style::default().fg(Color::Black).bg(Color::Yellow),
-        ));
-
-        last_end = actual_end;
-    }
-
-    if last_end < text.len() {
-        spans.push(Span::styled(
-            text[last_end..].to_string(),
-            Style::default().fg(Color::White),
-        ));
-    }
-
-    Line::from(spans)
-}
+use super::utils::{create_highlighted_line, render_help_info};
+use crate::app::{App, SearchScope};
+use crate::i18n::{tr, trf};
 
 pub fn render_search(f: &mut Frame, app: &App) {
+    let theme = app.theme();
     let area = f.area();
 
-    let title = Paragraph::new("搜索模式")
-        .style(Style::default().fg(Color::Yellow))
+    let mut title_text = trf!("搜索模式 [{}]", "Search [{}]", app.search.scope.label());
+    if app.search.fuzzy {
+        title_text.push_str(tr("（模糊：拼音/容错）", " (fuzzy: pinyin/typos)"));
+    }
+    let title = Paragraph::new(title_text)
+        .style(Style::default().fg(theme.emphasis))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -66,10 +27,26 @@ pub fn render_search(f: &mut Frame, app: &App) {
 
     f.render_widget(title, title_area);
 
-    let search_text = format!("搜索: {}", app.search.input);
+    let search_text = trf!("搜索: {}", "Search: {}", app.search.input);
+    let input_title =
+        if app.search.scope == SearchScope::ChapterRange && app.search.chapter_range().is_none() {
+            tr(
+                "输入搜索内容（格式：起始章-结束章 关键词，如 12-30 张三）",
+                "Search text (format: first-last chapter keyword, e.g. 12-30 Alice)",
+            )
+            .to_string()
+        } else if app.search.is_searching() {
+            trf!(
+                "输入搜索内容（搜索中… 已找到 {} 条）",
+                "Search text (searching… {} found)",
+                app.search.results.len()
+            )
+        } else {
+            tr("输入搜索内容", "Search text").to_string()
+        };
     let search_input = Paragraph::new(search_text)
-        .style(Style::default().fg(Color::White))
-        .block(Block::default().borders(Borders::ALL).title("输入搜索内容"));
+        .style(Style::default().fg(theme.text))
+        .block(Block::default().borders(Borders::ALL).title(input_title));
 
     let input_area = Rect {
         x: area.x + 2,
@@ -81,6 +58,13 @@ pub fn render_search(f: &mut Frame, app: &App) {
     f.render_widget(search_input, input_area);
 
     if !app.search.results.is_empty() {
+        let highlight_term = app.search.highlight_term();
+        let chapters = app
+            .current_novel
+            .as_ref()
+            .map(|novel| novel.chapters.as_slice())
+            .unwrap_or_default();
+
         let items: Vec<ListItem> = app
             .search
             .results
@@ -95,18 +79,44 @@ pub fn render_search(f: &mut Frame, app: &App) {
 
                 let line_prefix = format!("{}{}: ", prefix, line_num + 1);
                 let mut line_spans =
-                    vec![Span::styled(line_prefix, Style::default().fg(Color::Cyan))];
-
-                let highlighted_line = create_highlighted_line(content.trim(), &app.search.input);
+                    vec![Span::styled(line_prefix, Style::default().fg(theme.accent))];
+
+                if !chapters.is_empty() {
+                    let chapter = &chapters[App::find_chapter_index(chapters, *line_num)];
+                    line_spans.push(Span::styled(
+                        format!("[{}] ", chapter.title.trim()),
+                        Style::default().fg(theme.special),
+                    ));
+                }
+
+                let highlighted_line = create_highlighted_line(
+                    theme,
+                    content.trim(),
+                    &highlight_term,
+                    app.search.fuzzy,
+                );
                 line_spans.extend(highlighted_line.spans);
 
                 ListItem::new(Line::from(line_spans))
             })
             .collect();
 
+        let results_title = match app.search.selected_index {
+            Some(index) => trf!(
+                "搜索结果 {}/{}",
+                "Results {}/{}",
+                index + 1,
+                app.search.results.len()
+            ),
+            None => trf!(
+                "搜索结果 共{}条",
+                "Results ({} total)",
+                app.search.results.len()
+            ),
+        };
         let results_list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title("搜索结果"))
-            .highlight_style(Style::default().bg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title(results_title))
+            .highlight_style(Style::default().bg(theme.selection_bg))
             .highlight_symbol("");
 
         let results_area = Rect {
@@ -133,6 +143,20 @@ pub fn render_search(f: &mut Frame, app: &App) {
         f.render_stateful_widget(results_list, results_area, &mut state);
     }
 
-    let help_text = "输入搜索内容 | ↑/↓: 选择结果 | Enter: 跳转 | Esc: 返回阅读";
-    render_help_info(f, help_text, area);
+    if let Some(input) = &app.search.goto_input {
+        let prompt = trf!(
+            "跳转到第 {}▏ 条（共 {} 条）│ Enter:跳转 Esc:取消",
+            "Jump to result {}▏ (of {}) │ Enter:Jump Esc:Cancel",
+            input,
+            app.search.results.len()
+        );
+        render_help_info(f, theme, &prompt, area);
+        return;
+    }
+
+    let help_text = tr(
+        "输入搜索内容 | ↑/↓: 选择结果 | Enter: 跳转 | Ctrl+g: 按序号跳转 | Tab: 范围 | Ctrl+f: 模糊 | Ctrl+e: 导出 | Esc: 返回阅读",
+        "Type to search | ↑/↓: Select result | Enter: Jump | Ctrl+g: Jump to number | Tab: Scope | Ctrl+f: Fuzzy | Ctrl+e: Export | Esc: Back to reading",
+    );
+    render_help_info(f, theme, help_text, area);
 }
@@ -1,9 +1,14 @@
 use ratatui::prelude::*;
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::*;
+use unicode_width::UnicodeWidthStr;
 
-use super::utils::render_help_info;
-use crate::app::App;
+use super::theme::Theme;
+use super::utils::{format_timestamp, render_help_info, split_preview_area};
+use crate::app::{App, PerformanceOption, ReaderOption};
+use crate::config::config;
+use crate::i18n::{tr, trf};
+use crate::model::preferences::ThemeName;
 use crate::state::SettingsMode;
 
 pub fn render_settings(f: &mut Frame, app: &App) {
@@ -14,13 +19,21 @@ pub fn render_settings(f: &mut Frame, app: &App) {
         SettingsMode::DeleteNovel => render_delete_novel_menu(f, app, area),
         SettingsMode::DeleteOrphaned => render_delete_orphaned_menu(f, app, area),
         SettingsMode::WebDavConfig => render_webdav_config(f, app, area),
+        SettingsMode::BookmarkTransfer => render_bookmark_transfer(f, app, area),
+        SettingsMode::LibraryDirs => render_library_dirs(f, app, area),
+        SettingsMode::Trash => render_trash(f, app, area),
+        SettingsMode::Appearance => render_appearance(f, app, area),
+        SettingsMode::ReaderOptions => render_reader_options(f, app, area),
+        SettingsMode::Backups => render_backups(f, app, area),
+        SettingsMode::Performance => render_performance(f, app, area),
     }
 }
 
 /// 渲染设置主菜单
 fn render_settings_main_menu(f: &mut Frame, app: &App, area: Rect) {
-    let title = Paragraph::new("设置")
-        .style(Style::default().fg(Color::Magenta))
+    let theme = app.theme();
+    let title = Paragraph::new(tr("设置", "Settings"))
+        .style(Style::default().fg(theme.special))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -32,7 +45,21 @@ fn render_settings_main_menu(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_widget(title, title_area);
 
-    let menu_options = ["删除小说", "清理孤立记录", "WebDAV同步配置"];
+    let language_item = format!("界面语言 / Language: {}", app.preferences.language.label());
+    let menu_options = [
+        tr("删除小说", "Delete novels"),
+        tr("清理孤立记录", "Clean up orphaned records"),
+        tr("WebDAV同步配置", "WebDAV sync"),
+        tr("书签导入导出", "Bookmark import/export"),
+        tr("小说目录", "Novel directories"),
+        tr("回收站", "Trash"),
+        tr("外观", "Appearance"),
+        tr("阅读选项", "Reading options"),
+        tr("进度备份", "Progress backups"),
+        tr("性能", "Performance"),
+        // 两种语言都写出来，看不懂当前语言的人也能找到这一项
+        &language_item,
+    ];
     let items: Vec<ListItem> = menu_options
         .iter()
         .enumerate()
@@ -43,13 +70,17 @@ fn render_settings_main_menu(f: &mut Frame, app: &App, area: Rect) {
                 "   "
             };
             let display_text = format!("{}{}", prefix, option);
-            ListItem::new(display_text).style(Style::default().fg(Color::White))
+            ListItem::new(display_text).style(Style::default().fg(theme.text))
         })
         .collect();
 
     let menu_list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title("选择操作"))
-        .highlight_style(Style::default().bg(Color::DarkGray))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("选择操作", "Choose an action")),
+        )
+        .highlight_style(Style::default().bg(theme.selection_bg))
         .highlight_symbol("");
 
     let list_area = Rect {
@@ -64,14 +95,19 @@ fn render_settings_main_menu(f: &mut Frame, app: &App, area: Rect) {
 
     f.render_stateful_widget(menu_list, list_area, &mut state);
 
-    let help_text = "↑/↓: 选择选项 | Enter: 确认 | Esc: 返回书架 | q: 退出";
-    render_help_info(f, help_text, area);
+    let help_text = trf!(
+        "↑/↓: 选择选项 | Enter: 确认 | e: 导出设置 | i: 导入设置 | Esc: 返回书架 | {}: 退出",
+        "↑/↓: Select | Enter: Open | e: Export settings | i: Import settings | Esc: Back to bookshelf | {}: Quit",
+        app.preferences.quit_key.hint()
+    );
+    render_help_info(f, theme, &help_text, area);
 }
 
 /// 渲染删除小说菜单
 fn render_delete_novel_menu(f: &mut Frame, app: &App, area: Rect) {
-    let title = Paragraph::new("删除小说")
-        .style(Style::default().fg(Color::Red))
+    let theme = app.theme();
+    let title = Paragraph::new(tr("删除小说", "Delete novels"))
+        .style(Style::default().fg(theme.danger))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -84,10 +120,14 @@ fn render_delete_novel_menu(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(title, title_area);
 
     if app.novels.is_empty() {
-        let no_novels = Paragraph::new("没有发现小说文件")
-            .style(Style::default().fg(Color::Yellow))
+        let no_novels = Paragraph::new(tr("没有发现小说文件", "No novel files found"))
+            .style(Style::default().fg(theme.emphasis))
             .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("状态"));
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr("状态", "Status")),
+            );
 
         let content_area = Rect {
             x: area.x + 2,
@@ -113,17 +153,17 @@ fn render_delete_novel_menu(f: &mut Frame, app: &App, area: Rect) {
                     prefix,
                     novel.path.file_stem().unwrap_or_default().to_string_lossy()
                 );
-                ListItem::new(display_text).style(Style::default().fg(Color::White))
+                ListItem::new(display_text).style(Style::default().fg(theme.text))
             })
             .collect();
 
         let novel_list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!("小说列表 (共{}本)", app.novels.len())),
-            )
-            .highlight_style(Style::default().bg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title(trf!(
+                "小说列表 (共{}本)",
+                "Novels ({} total)",
+                app.novels.len()
+            )))
+            .highlight_style(Style::default().bg(theme.selection_bg))
             .highlight_symbol("");
 
         let list_area = Rect {
@@ -140,17 +180,653 @@ fn render_delete_novel_menu(f: &mut Frame, app: &App, area: Rect) {
     }
 
     let help_text = if app.novels.is_empty() {
-        "Esc: 返回设置菜单 | q: 退出"
+        trf!(
+            "Esc: 返回设置菜单 | {}: 退出",
+            "Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
+    } else {
+        trf!(
+            "↑/↓: 选择小说 | D/d: 移到回收站 | Esc: 返回设置菜单 | {}: 退出",
+            "↑/↓: Select novel | D/d: Move to trash | Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
+    };
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染书签导入导出菜单
+fn render_bookmark_transfer(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("书签导入导出", "Bookmark import/export"))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+
+    f.render_widget(title, title_area);
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    if app.novels.is_empty() {
+        let no_novels = Paragraph::new(tr("没有发现小说文件", "No novel files found"))
+            .style(Style::default().fg(theme.emphasis))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr("状态", "Status")),
+            );
+        f.render_widget(no_novels, content_area);
+    } else {
+        let items: Vec<ListItem> = app
+            .novels
+            .iter()
+            .enumerate()
+            .map(|(index, novel)| {
+                let prefix = if Some(index) == app.settings.selected_bookmark_novel_index {
+                    ">> "
+                } else {
+                    "   "
+                };
+                // 当前打开的小说以内存中的进度为准
+                let count = match &app.current_novel {
+                    Some(current) if current.path == novel.path => current.progress.bookmarks.len(),
+                    _ => app.library.get_novel_progress(&novel.path).bookmarks.len(),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{}{}", prefix, novel.title)),
+                    Span::styled(
+                        trf!("  {} 个书签", "  {} bookmarks", count),
+                        Style::default().fg(theme.dim),
+                    ),
+                ]))
+                .style(Style::default().fg(theme.text))
+            })
+            .collect();
+
+        let novel_list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(tr(
+                "小说列表（文件保存在 ~/.fish_reader/exports/）",
+                "Novels (files are saved in ~/.fish_reader/exports/)",
+            )))
+            .highlight_style(Style::default().bg(theme.selection_bg))
+            .highlight_symbol("");
+
+        let mut state = ListState::default();
+        state.select(app.settings.selected_bookmark_novel_index);
+
+        f.render_stateful_widget(novel_list, content_area, &mut state);
+    }
+
+    let help_text = if app.novels.is_empty() {
+        trf!(
+            "Esc: 返回设置菜单 | {}: 退出",
+            "Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
     } else {
-        "↑/↓: 选择小说 | D/d: 删除选中小说 | Esc: 返回设置菜单 | q: 退出"
+        trf!(
+            "↑/↓: 选择小说 | e: 导出为 Markdown 和 JSON | i: 从 JSON 导入 | Esc: 返回设置菜单 | {}: 退出",
+            "↑/↓: Select novel | e: Export as Markdown and JSON | i: Import from JSON | Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
+    };
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染外观页面：主题列表，每项附带该主题的色板
+///
+/// 选中的主题已即时应用到整个界面，作为预览。
+fn render_appearance(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("外观", "Appearance"))
+        .style(Style::default().fg(theme.special))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+
+    f.render_widget(title, title_area);
+
+    let items: Vec<ListItem> = ThemeName::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, &name)| {
+            let prefix = if Some(index) == app.settings.selected_theme_index {
+                ">> "
+            } else {
+                "   "
+            };
+            let palette = Theme::of(name);
+            let label = name.label();
+            let padding = " ".repeat(10usize.saturating_sub(label.width()));
+            let mut spans = vec![Span::raw(format!("{}{}{}", prefix, label, padding))];
+            spans.extend(
+                [
+                    palette.text,
+                    palette.accent,
+                    palette.emphasis,
+                    palette.secondary,
+                    palette.special,
+                    palette.success,
+                    palette.danger,
+                ]
+                .into_iter()
+                .map(|color| Span::styled(" ■", Style::default().fg(color).bg(palette.background))),
+            );
+            if name == app.settings.saved_theme {
+                spans.push(Span::styled(
+                    tr("  (当前)", "  (current)"),
+                    Style::default().fg(theme.dim),
+                ));
+            }
+            ListItem::new(Line::from(spans)).style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let theme_list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("界面主题", "Theme")),
+        )
+        .highlight_style(Style::default().bg(theme.selection_bg))
+        .highlight_symbol("");
+
+    let list_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let mut state = ListState::default();
+    state.select(app.settings.selected_theme_index);
+
+    f.render_stateful_widget(theme_list, list_area, &mut state);
+
+    let help_text = trf!(
+        "↑/↓: 选择主题（即时预览） | Enter: 保存 | Esc: 取消 | {}: 退出",
+        "↑/↓: Select theme (live preview) | Enter: Save | Esc: Cancel | {}: Quit",
+        app.preferences.quit_key.hint()
+    );
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染阅读选项页面：每行一个选项及其当前值
+fn render_reader_options(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("阅读选项", "Reading options"))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+    f.render_widget(title, title_area);
+
+    let mut lines: Vec<Line> = ReaderOption::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, &option)| {
+            let line_style = if app.settings.selected_reader_option == Some(index) {
+                Style::default().fg(theme.inverse).bg(theme.accent)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let label = option.label();
+            let padding = " ".repeat(10usize.saturating_sub(label.width()));
+            let value = app.reader_option_value(option);
+            Line::from(Span::styled(
+                format!("{}{}◀ {} ▶", label, padding, value),
+                line_style,
+            ))
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        tr("行间距按书保存；缩进和最大宽度只对横排生效；阅读时按 s 开启/关闭自动滚动", "Line spacing is saved per book; indent and max width only apply to horizontal text; press s while reading to toggle auto-scroll"),
+        Style::default().fg(theme.muted),
+    )));
+
+    let options = Paragraph::new(Text::from(lines))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("选项", "Options")),
+        )
+        .wrap(Wrap { trim: true });
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 4,
     };
-    render_help_info(f, help_text, area);
+    f.render_widget(options, content_area);
+
+    let help_text = trf!(
+        "↑/↓: 选择选项 | ←/→: 调整（立即生效并保存） | Esc: 返回设置菜单 | {}: 退出",
+        "↑/↓: Select option | ←/→: Adjust (applied and saved immediately) | Esc: Back to settings | {}: Quit",
+        app.preferences.quit_key.hint()
+    );
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染性能页面：每行一个选项及其当前值
+fn render_performance(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("性能", "Performance"))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+    f.render_widget(title, title_area);
+
+    let mut lines: Vec<Line> = PerformanceOption::ALL
+        .iter()
+        .enumerate()
+        .map(|(index, &option)| {
+            let line_style = if app.settings.selected_performance_option == Some(index) {
+                Style::default().fg(theme.inverse).bg(theme.accent)
+            } else {
+                Style::default().fg(theme.text)
+            };
+            let label = option.label();
+            let padding = " ".repeat(10usize.saturating_sub(label.width()));
+            let value = app.performance_option_value(option);
+            Line::from(Span::styled(
+                format!("{}{}◀ {} ▶", label, padding, value),
+                line_style,
+            ))
+        })
+        .collect();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "刷新间隔越大越省电，但同步、搜索等后台任务的进度显示得越慢；\
+         按键和后台任务进行中总会立即重绘，空闲重绘只影响时钟等静止画面",
+        Style::default().fg(theme.muted),
+    )));
+
+    let options = Paragraph::new(Text::from(lines))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("选项", "Options")),
+        )
+        .wrap(Wrap { trim: true });
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 4,
+    };
+    f.render_widget(options, content_area);
+
+    let help_text = trf!(
+        "↑/↓: 选择选项 | ←/→: 调整（立即生效并保存） | Esc: 返回设置菜单 | {}: 退出",
+        "↑/↓: Select option | ←/→: Adjust (applied and saved immediately) | Esc: Back to settings | {}: Quit",
+        app.preferences.quit_key.hint()
+    );
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染小说目录管理界面
+fn render_library_dirs(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("小说目录", "Novel directories"))
+        .style(Style::default().fg(theme.accent))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+
+    f.render_widget(title, title_area);
+
+    let default_dir = App::get_novels_dir();
+    let dirs = std::iter::once(&default_dir).chain(&app.preferences.library_dirs);
+    let items: Vec<ListItem> = dirs
+        .enumerate()
+        .map(|(index, dir)| {
+            let prefix = if Some(index) == app.settings.selected_library_dir_index {
+                ">> "
+            } else {
+                "   "
+            };
+            let label = if index == 0 {
+                tr("  (默认)", "  (default)")
+            } else {
+                ""
+            };
+            let status = if dir.is_dir() {
+                let count = app.novels.iter().filter(|n| &n.root == dir).count();
+                trf!("  {} 本", "  {} books", count)
+            } else {
+                tr("  目录不存在", "  directory missing").to_string()
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{}{}{}", prefix, dir.display(), label)),
+                Span::styled(status, Style::default().fg(theme.dim)),
+            ]))
+            .style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let dir_list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(tr(
+            "扫描的目录（额外目录中的小说按目录名分类）",
+            "Scanned directories (novels in extra directories are grouped by directory name)",
+        )))
+        .highlight_style(Style::default().bg(theme.selection_bg))
+        .highlight_symbol("");
+
+    let list_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let mut state = ListState::default();
+    state.select(app.settings.selected_library_dir_index);
+
+    f.render_stateful_widget(dir_list, list_area, &mut state);
+
+    let help_text = match (&app.settings.novels_dir_input, &app.settings.library_dir_input) {
+        (Some(input), _) => {
+            let mode = if app.settings.novels_dir_move {
+                tr("移动原有小说到新目录", "move existing novels to the new directory")
+            } else {
+                tr("不移动原有小说", "keep existing novels in place")
+            };
+            trf!(
+                "默认目录: {}▏ | Enter: 更换（{}） | Tab: 切换是否移动 | Esc: 取消", "Default directory: {}▏ | Enter: Change ({}) | Tab: Toggle moving | Esc: Cancel",
+                input, mode
+            )
+        }
+        (None, Some(input)) => trf!("新目录: {}▏ | Enter: 添加 | Esc: 取消", "New directory: {}▏ | Enter: Add | Esc: Cancel", input),
+        (None, None) => trf!("↑/↓: 选择目录 | a: 添加目录 | d: 移除目录（不删除文件） | c: 更换默认目录 | Esc: 返回设置菜单 | {}: 退出", "↑/↓: Select directory | a: Add | d: Remove (files are kept) | c: Change default directory | Esc: Back to settings | {}: Quit", app.preferences.quit_key.hint())
+            .to_string(),
+    };
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染回收站
+fn render_trash(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("回收站", "Trash"))
+        .style(Style::default().fg(theme.danger))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+
+    f.render_widget(title, title_area);
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let entries = &app.settings.trash.entries;
+    if entries.is_empty() {
+        let empty = Paragraph::new(tr("回收站是空的", "Trash is empty"))
+            .style(Style::default().fg(theme.success))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr("状态", "Status")),
+            );
+        f.render_widget(empty, content_area);
+    } else {
+        let items: Vec<ListItem> = entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let prefix = if Some(index) == app.settings.selected_trash_index {
+                    ">> "
+                } else {
+                    "   "
+                };
+                let detail = trf!(
+                    "  删除于 {}  {}",
+                    "  deleted {}  {}",
+                    format_timestamp(entry.deleted_at),
+                    entry.original_path.display()
+                );
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{}{}", prefix, entry.title())),
+                    Span::styled(detail, Style::default().fg(theme.dim)),
+                ]))
+                .style(Style::default().fg(theme.text))
+            })
+            .collect();
+
+        let trash_list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(trf!(
+                "已删除的小说 (共{}本，{} 天后自动清除)",
+                "Deleted novels ({} total, purged after {} days)",
+                entries.len(),
+                config().trash_retention_days
+            )))
+            .highlight_style(Style::default().bg(theme.selection_bg))
+            .highlight_symbol("");
+
+        let mut state = ListState::default();
+        state.select(app.settings.selected_trash_index);
+
+        f.render_stateful_widget(trash_list, content_area, &mut state);
+    }
+
+    let help_text = if entries.is_empty() {
+        trf!(
+            "Esc: 返回设置菜单 | {}: 退出",
+            "Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
+    } else {
+        trf!(
+            "↑/↓: 选择小说 | r: 恢复 | d: 彻底删除 | Esc: 返回设置菜单 | {}: 退出",
+            "↑/↓: Select novel | r: Restore | d: Delete permanently | Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
+    };
+    render_help_info(f, theme, &help_text, area);
+}
+
+/// 渲染进度备份页面：左侧为备份列表，右侧预览选中备份中的小说
+fn render_backups(f: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let title = Paragraph::new(tr("进度备份", "Progress backups"))
+        .style(Style::default().fg(theme.special))
+        .alignment(Alignment::Center);
+
+    let title_area = Rect {
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: 2,
+    };
+
+    f.render_widget(title, title_area);
+
+    let content_area = Rect {
+        x: area.x + 2,
+        y: area.y + 2,
+        width: area.width - 4,
+        height: area.height - 3,
+    };
+
+    let backups = &app.settings.backups;
+    if backups.is_empty() {
+        let empty = Paragraph::new(tr(
+            "还没有备份，保存阅读进度时会自动备份",
+            "No backups yet; one is made automatically when reading progress is saved",
+        ))
+        .style(Style::default().fg(theme.success))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("状态", "Status")),
+        );
+        f.render_widget(empty, content_area);
+        render_help_info(
+            f,
+            theme,
+            &trf!(
+                "Esc: 返回设置菜单 | {}: 退出",
+                "Esc: Back to settings | {}: Quit",
+                app.preferences.quit_key.hint()
+            ),
+            area,
+        );
+        return;
+    }
+
+    let (list_area, preview_area) = split_preview_area(content_area);
+    let items: Vec<ListItem> = backups
+        .iter()
+        .enumerate()
+        .map(|(index, backup)| {
+            let prefix = if Some(index) == app.settings.selected_backup_index {
+                ">> "
+            } else {
+                "   "
+            };
+            let detail = match &backup.titles {
+                Some(titles) => trf!("  {} 本", "  {} books", titles.len()),
+                None => tr("  已损坏", "  corrupted").to_string(),
+            };
+            let detail_color = if backup.titles.is_some() {
+                theme.dim
+            } else {
+                theme.danger
+            };
+            ListItem::new(Line::from(vec![
+                Span::raw(format!("{}{}", prefix, format_timestamp(backup.timestamp))),
+                Span::styled(detail, Style::default().fg(detail_color)),
+            ]))
+            .style(Style::default().fg(theme.text))
+        })
+        .collect();
+
+    let backup_list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(trf!(
+            "自动备份 (共{}个，保留 {} 天)",
+            "Automatic backups ({} total, kept for {} days)",
+            backups.len(),
+            config().backup.retention_days
+        )))
+        .highlight_style(Style::default().bg(theme.selection_bg))
+        .highlight_symbol("");
+
+    let mut state = ListState::default();
+    state.select(app.settings.selected_backup_index);
+    f.render_stateful_widget(backup_list, list_area, &mut state);
+
+    if let Some(backup) = app
+        .settings
+        .selected_backup_index
+        .and_then(|index| backups.get(index))
+    {
+        let mut lines = vec![Line::from(Span::styled(
+            trf!(
+                "备份于 {}",
+                "Backed up {}",
+                format_timestamp(backup.timestamp)
+            ),
+            Style::default().fg(theme.emphasis),
+        ))];
+        match &backup.titles {
+            Some(titles) => {
+                let last_read = if backup.last_read == 0 {
+                    tr("无", "never").to_string()
+                } else {
+                    format_timestamp(backup.last_read)
+                };
+                lines.push(Line::from(Span::styled(
+                    trf!(
+                        "{} 本小说，最近阅读 {}",
+                        "{} novels, last read {}",
+                        titles.len(),
+                        last_read
+                    ),
+                    Style::default().fg(theme.muted),
+                )));
+                lines.push(Line::from(""));
+                lines.extend(titles.iter().map(|title| Line::from(title.as_str())));
+            }
+            None => lines.push(Line::from(Span::styled(
+                tr("备份文件无法解析", "The backup file could not be parsed"),
+                Style::default().fg(theme.danger),
+            ))),
+        }
+        let preview = Paragraph::new(lines)
+            .style(Style::default().fg(theme.text))
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(tr("预览", "Preview")),
+            );
+        f.render_widget(preview, preview_area);
+    }
+
+    render_help_info(
+        f,
+        theme,
+        &trf!(
+            "↑/↓: 选择备份 | r/Enter: 恢复 | Esc: 返回设置菜单 | {}: 退出",
+            "↑/↓: Select backup | r/Enter: Restore | Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        ),
+        area,
+    );
 }
 
 /// 渲染删除孤立记录菜单
 fn render_delete_orphaned_menu(f: &mut Frame, app: &App, area: Rect) {
-    let title = Paragraph::new("清理孤立记录")
-        .style(Style::default().fg(Color::Yellow))
+    let theme = app.theme();
+    let title = Paragraph::new(tr("清理孤立记录", "Clean up orphaned records"))
+        .style(Style::default().fg(theme.emphasis))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -163,10 +839,17 @@ fn render_delete_orphaned_menu(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(title, title_area);
 
     if app.settings.orphaned_novels.is_empty() {
-        let no_orphaned = Paragraph::new("没有发现孤立记录\n所有记录都对应有效的小说文件")
-            .style(Style::default().fg(Color::Green))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("状态"));
+        let no_orphaned = Paragraph::new(tr(
+            "没有发现孤立记录\n所有记录都对应有效的小说文件",
+            "No orphaned records\nEvery record belongs to an existing novel file",
+        ))
+        .style(Style::default().fg(theme.success))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("状态", "Status")),
+        );
 
         let content_area = Rect {
             x: area.x + 2,
@@ -194,16 +877,17 @@ fn render_delete_orphaned_menu(f: &mut Frame, app: &App, area: Rect) {
                     novel_info.title,
                     novel_info.path.display()
                 );
-                ListItem::new(display_text).style(Style::default().fg(Color::Yellow))
+                ListItem::new(display_text).style(Style::default().fg(theme.emphasis))
             })
             .collect();
 
         let orphaned_list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(format!(
+            .block(Block::default().borders(Borders::ALL).title(trf!(
                 "孤立记录 (共{}条)",
+                "Orphaned records ({} total)",
                 app.settings.orphaned_novels.len()
             )))
-            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_style(Style::default().bg(theme.selection_bg))
             .highlight_symbol("");
 
         let list_area = Rect {
@@ -219,18 +903,36 @@ fn render_delete_orphaned_menu(f: &mut Frame, app: &App, area: Rect) {
         f.render_stateful_widget(orphaned_list, list_area, &mut state);
     }
 
+    if let Some(input) = &app.settings.relocate_input {
+        let help_text = trf!(
+            "新文件路径: {}▏ | Enter: 关联（保留进度和书签） | Esc: 取消",
+            "New file path: {}▏ | Enter: Relink (keeps progress and bookmarks) | Esc: Cancel",
+            input
+        );
+        render_help_info(f, theme, &help_text, area);
+        return;
+    }
     let help_text = if app.settings.orphaned_novels.is_empty() {
-        "Esc: 返回设置菜单 | q: 退出"
+        trf!(
+            "Esc: 返回设置菜单 | {}: 退出",
+            "Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
     } else {
-        "↑/↓: 选择记录 | D/d: 删除选中记录 | Esc: 返回设置菜单 | q: 退出"
+        trf!(
+            "↑/↓: 选择记录 | r: 关联到新文件 | D/d: 删除选中记录 | Esc: 返回设置菜单 | {}: 退出",
+            "↑/↓: Select record | r: Relink to a new file | D/d: Delete record | Esc: Back to settings | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
     };
-    render_help_info(f, help_text, area);
+    render_help_info(f, theme, &help_text, area);
 }
 
 /// 渲染WebDAV配置界面
 fn render_webdav_config(f: &mut Frame, app: &App, area: Rect) {
-    let title = Paragraph::new("WebDAV 同步配置")
-        .style(Style::default().fg(Color::Cyan))
+    let theme = app.theme();
+    let title = Paragraph::new(tr("WebDAV 同步配置", "WebDAV sync"))
+        .style(Style::default().fg(theme.accent))
         .alignment(Alignment::Center);
 
     let title_area = Rect {
@@ -248,14 +950,14 @@ fn render_webdav_config(f: &mut Frame, app: &App, area: Rect) {
 
     let fields = [
         (
-            "启用同步",
+            tr("启用同步", "Enabled"),
             if temp_config.enabled { "[✓]" } else { "[ ]" },
             0,
         ),
         ("URL", &temp_config.url, 1),
-        ("用户名", &temp_config.username, 2),
+        (tr("用户名", "Username"), &temp_config.username, 2),
         (
-            "密码",
+            tr("密码", "Password"),
             if config_state.show_password {
                 &temp_config.password
             } else {
@@ -263,18 +965,23 @@ fn render_webdav_config(f: &mut Frame, app: &App, area: Rect) {
             },
             3,
         ),
-        ("远程路径", &temp_config.remote_path, 4),
+        (tr("远程路径", "Remote path"), &temp_config.remote_path, 4),
+        (
+            tr("书签合并", "Bookmark merge"),
+            temp_config.bookmark_merge.label(),
+            5,
+        ),
     ];
 
     let mut lines: Vec<Line> = vec![];
     for (label, value, idx) in fields {
         let is_selected = selected == idx;
-        let is_editing = is_selected && edit_mode && idx > 0;
+        let is_editing = is_selected && edit_mode && (1..=4).contains(&idx);
 
         let line_style = if is_selected {
-            Style::default().fg(Color::Black).bg(Color::Cyan)
+            Style::default().fg(theme.inverse).bg(theme.accent)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         };
 
         let display_value = if is_editing {
@@ -289,30 +996,31 @@ fn render_webdav_config(f: &mut Frame, app: &App, area: Rect) {
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        format!(
+        trf!(
             "按 'p' 切换密码显示 (当前: {})",
+            "Press 'p' to toggle password visibility (currently: {})",
             if config_state.show_password {
-                "显示"
+                tr("显示", "shown")
             } else {
-                "隐藏"
+                tr("隐藏", "hidden")
             }
         ),
-        Style::default().fg(Color::Gray),
+        Style::default().fg(theme.muted),
     )));
 
     match &config_state.connection_status {
         Some(Ok(())) => {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
-                "✓ 连接成功",
-                Style::default().fg(Color::Green),
+                tr("✓ 连接成功", "✓ Connected"),
+                Style::default().fg(theme.success),
             )));
         }
         Some(Err(msg)) => {
             lines.push(Line::from(""));
             lines.push(Line::from(Span::styled(
-                format!("✗ 连接失败: {}", msg),
-                Style::default().fg(Color::Red),
+                trf!("✗ 连接失败: {}", "✗ Connection failed: {}", msg),
+                Style::default().fg(theme.danger),
             )));
         }
         None => {}
@@ -320,7 +1028,11 @@ fn render_webdav_config(f: &mut Frame, app: &App, area: Rect) {
 
     let config_text = Text::from(lines);
     let config_paragraph = Paragraph::new(config_text)
-        .block(Block::default().borders(Borders::ALL).title("配置"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("配置", "Configuration")),
+        )
         .alignment(Alignment::Left);
 
     let content_area = Rect {
@@ -332,9 +1044,17 @@ fn render_webdav_config(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(config_paragraph, content_area);
 
     let help_text = if edit_mode {
-        "输入文本 | Enter: 确认 | Esc: 取消编辑"
+        tr(
+            "输入文本 | Enter: 确认 | Esc: 取消编辑",
+            "Type text | Enter: Confirm | Esc: Cancel editing",
+        )
+        .to_string()
     } else {
-        "↑/↓: 选择字段 | Enter: 编辑/切换启用 | S: 保存 | T: 测试连接 | P: 切换密码显示 | Esc: 返回 | q: 退出"
+        trf!(
+            "↑/↓: 选择字段 | Enter: 编辑/切换 | S: 保存 | T: 测试连接 | P: 切换密码显示 | Esc: 返回 | {}: 退出",
+            "↑/↓: Select field | Enter: Edit/toggle | S: Save | T: Test connection | P: Toggle password | Esc: Back | {}: Quit",
+            app.preferences.quit_key.hint()
+        )
     };
-    render_help_info(f, help_text, area);
+    render_help_info(f, theme, &help_text, area);
 }
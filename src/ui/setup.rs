@@ -0,0 +1,127 @@
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::utils::render_help_info;
+use crate::app::{App, SetupStep};
+use crate::config::config;
+use crate::i18n::{tr, trf};
+
+/// 渲染首次启动向导：标题、当前步骤的说明和输入框
+pub fn render_setup(f: &mut Frame, app: &App) {
+    let theme = app.theme();
+    let area = f.area();
+    let step = app.setup.step;
+
+    let title = Paragraph::new(tr("欢迎使用 fish_reader", "Welcome to fish_reader"))
+        .style(Style::default().fg(theme.special))
+        .alignment(Alignment::Center);
+    f.render_widget(
+        title,
+        Rect {
+            x: area.x,
+            y: area.y,
+            width: area.width,
+            height: 2,
+        },
+    );
+
+    let description = match step {
+        SetupStep::NovelsDir => trf!(
+            "书架还是空的，先选择存放小说的目录。书架会扫描其中的 {} 文件，子文件夹作为分类；目录不存在时会自动创建。",
+            "The bookshelf is empty. First choose where your novels live: the bookshelf scans {} files in it and shows subfolders as categories. The directory is created if it does not exist.",
+            config().supported_extensions.join(" / ")
+        ),
+        SetupStep::Import => tr(
+            "可以现在导入小说：输入文件路径（支持以 ~ 开头），文件会被复制到小说目录中。也可以稍后直接把文件放进小说目录，或在书架按 i 导入。",
+            "You can import novels now: enter a file path (~ is allowed) and the file is copied into the novel directory. You can also drop files into the directory later or press i on the bookshelf.",
+        )
+        .to_string(),
+        SetupStep::WebDav => tr(
+            "需要在多台电脑之间同步小说和阅读进度吗？可以现在配置 WebDAV，以后也可以在设置 → WebDAV 同步配置中修改。",
+            "Do you want to sync novels and reading progress between computers? You can set up WebDAV now or later in Settings → WebDAV sync.",
+        )
+        .to_string(),
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            trf!(
+                "第 {}/{} 步：{}",
+                "Step {}/{}: {}",
+                step.number(),
+                SetupStep::COUNT,
+                step.title()
+            ),
+            Style::default()
+                .fg(theme.emphasis)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::default(),
+        Line::from(description),
+        Line::default(),
+    ];
+    match step {
+        SetupStep::NovelsDir | SetupStep::Import => {
+            let label = if step == SetupStep::NovelsDir {
+                tr("小说目录：", "Novel directory: ")
+            } else {
+                tr("文件路径：", "File path: ")
+            };
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(theme.muted)),
+                Span::styled(
+                    format!("{}▏", app.setup.input),
+                    Style::default().fg(theme.accent),
+                ),
+            ]));
+        }
+        SetupStep::WebDav => {
+            let status = if app.webdav_config.is_configured() {
+                tr("当前已配置 WebDAV", "WebDAV is already configured")
+            } else {
+                tr("当前未配置 WebDAV", "WebDAV is not configured yet")
+            };
+            lines.push(Line::from(Span::styled(
+                status,
+                Style::default().fg(theme.muted),
+            )));
+        }
+    }
+
+    let content = Paragraph::new(lines)
+        .style(Style::default().fg(theme.text))
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("首次设置", "First-time setup")),
+        );
+    f.render_widget(
+        content,
+        Rect {
+            x: area.x + 2,
+            y: area.y + 2,
+            width: area.width.saturating_sub(4),
+            height: area.height.saturating_sub(3),
+        },
+    );
+
+    let help_text = match step {
+        SetupStep::NovelsDir => tr(
+            "Enter: 使用这个目录 | Tab: English | Esc: 跳过向导",
+            "Enter: Use this directory | Tab: 中文 | Esc: Skip setup",
+        )
+        .to_string(),
+        SetupStep::Import => tr(
+            "Enter: 导入（留空进入下一步） | Tab: English | Esc: 跳过向导",
+            "Enter: Import (leave empty to continue) | Tab: 中文 | Esc: Skip setup",
+        )
+        .to_string(),
+        SetupStep::WebDav => trf!(
+            "w: 配置 WebDAV | Enter: 完成 | Tab: English | Esc: 跳过向导 | {}: 退出",
+            "w: Set up WebDAV | Enter: Finish | Tab: 中文 | Esc: Skip setup | {}: Quit",
+            app.preferences.quit_key.hint()
+        ),
+    };
+    render_help_info(f, theme, &help_text, area);
+}
@@ -1,5 +1,7 @@
 use ratatui::{prelude::*, style::Color, widgets::Paragraph};
 
+use super::theme::Theme;
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SyncStatus {
     Idle,
@@ -16,12 +18,12 @@ impl SyncStatus {
         }
     }
 
-    pub fn color(&self) -> Color {
+    pub fn color(&self, theme: &Theme) -> Color {
         match self {
-            SyncStatus::Idle => Color::Gray,
-            SyncStatus::InProgress(_) => Color::Yellow,
-            SyncStatus::Success(_) => Color::Green,
-            SyncStatus::Error(_) => Color::Red,
+            SyncStatus::Idle => theme.muted,
+            SyncStatus::InProgress(_) => theme.emphasis,
+            SyncStatus::Success(_) => theme.success,
+            SyncStatus::Error(_) => theme.danger,
         }
     }
 
@@ -32,6 +34,7 @@ impl SyncStatus {
 
 pub struct SyncStatusWidget {
     pub status: SyncStatus,
+    pub theme: &'static Theme,
 }
 
 impl Widget for SyncStatusWidget {
@@ -40,7 +43,7 @@ impl Widget for SyncStatusWidget {
         if text.is_empty() {
             return;
         }
-        let style = Style::default().fg(self.status.color());
+        let style = Style::default().fg(self.status.color(self.theme));
         Paragraph::new(text).style(style).render(area, buf);
     }
 }
@@ -48,6 +51,7 @@ impl Widget for SyncStatusWidget {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::preferences::ThemeName;
 
     #[test]
     fn test_sync_status_text_mapping() {
@@ -62,13 +66,17 @@ mod tests {
 
     #[test]
     fn test_sync_status_color_mapping() {
-        assert_eq!(SyncStatus::Idle.color(), Color::Gray);
+        let theme = Theme::of(ThemeName::Dark);
+        assert_eq!(SyncStatus::Idle.color(theme), Color::Gray);
         assert_eq!(
-            SyncStatus::InProgress("x".to_string()).color(),
+            SyncStatus::InProgress("x".to_string()).color(theme),
             Color::Yellow
         );
-        assert_eq!(SyncStatus::Success("x".to_string()).color(), Color::Green);
-        assert_eq!(SyncStatus::Error("x".to_string()).color(), Color::Red);
+        assert_eq!(
+            SyncStatus::Success("x".to_string()).color(theme),
+            Color::Green
+        );
+        assert_eq!(SyncStatus::Error("x".to_string()).color(theme), Color::Red);
     }
 
     #[test]
@@ -0,0 +1,128 @@
+//! 界面主题：各界面按用途取色，切换主题时整体换一套配色
+
+use ratatui::prelude::*;
+
+use crate::model::preferences::ThemeName;
+
+/// 一套界面配色，字段按用途而不是颜色命名
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    /// 界面背景，`Color::Reset` 表示沿用终端背景
+    pub background: Color,
+    /// 正文和列表项
+    pub text: Color,
+    /// 帮助栏和次要说明
+    pub muted: Color,
+    /// 附加信息、分隔行等弱化文字
+    pub dim: Color,
+    /// 列表选中行的背景
+    pub selection_bg: Color,
+    /// 强调色背景上的文字，如搜索匹配和选中的配置项
+    pub inverse: Color,
+    /// 书架、详情等主要界面的标题
+    pub accent: Color,
+    /// 列表边框、提示状态和当前项
+    pub emphasis: Color,
+    /// 书签、高亮、批注界面的标题
+    pub secondary: Color,
+    /// 设置标题和搜索结果中的匹配
+    pub special: Color,
+    /// 成功提示
+    pub success: Color,
+    /// 错误提示和删除操作
+    pub danger: Color,
+    /// 阅读时已保存高亮的背景
+    pub highlight_bg: Color,
+    /// 选择模式中选中行的背景
+    pub visual_bg: Color,
+    /// 阅读引导线的背景
+    pub guide_bg: Color,
+}
+
+/// 深色主题（默认），沿用终端背景
+const DARK: Theme = Theme {
+    background: Color::Reset,
+    text: Color::White,
+    muted: Color::Gray,
+    dim: Color::DarkGray,
+    selection_bg: Color::DarkGray,
+    inverse: Color::Black,
+    accent: Color::Cyan,
+    emphasis: Color::Yellow,
+    secondary: Color::Blue,
+    special: Color::Magenta,
+    success: Color::Green,
+    danger: Color::Red,
+    highlight_bg: Color::Indexed(58),
+    visual_bg: Color::Indexed(24),
+    guide_bg: Color::Indexed(236),
+};
+
+/// 浅色主题：白底深色文字
+const LIGHT: Theme = Theme {
+    background: Color::Indexed(255),
+    text: Color::Indexed(235),
+    muted: Color::Indexed(242),
+    dim: Color::Indexed(247),
+    selection_bg: Color::Indexed(252),
+    inverse: Color::Indexed(255),
+    accent: Color::Indexed(30),
+    emphasis: Color::Indexed(130),
+    secondary: Color::Indexed(25),
+    special: Color::Indexed(90),
+    success: Color::Indexed(28),
+    danger: Color::Indexed(160),
+    highlight_bg: Color::Indexed(229),
+    visual_bg: Color::Indexed(153),
+    guide_bg: Color::Indexed(254),
+};
+
+/// 护眼主题：米黄底棕色文字
+const SEPIA: Theme = Theme {
+    background: Color::Indexed(230),
+    text: Color::Indexed(94),
+    muted: Color::Indexed(137),
+    dim: Color::Indexed(180),
+    selection_bg: Color::Indexed(223),
+    inverse: Color::Indexed(230),
+    accent: Color::Indexed(30),
+    emphasis: Color::Indexed(166),
+    secondary: Color::Indexed(24),
+    special: Color::Indexed(96),
+    success: Color::Indexed(64),
+    danger: Color::Indexed(124),
+    highlight_bg: Color::Indexed(222),
+    visual_bg: Color::Indexed(152),
+    guide_bg: Color::Indexed(229),
+};
+
+/// 高对比度主题：黑底亮色文字
+const HIGH_CONTRAST: Theme = Theme {
+    background: Color::Black,
+    text: Color::White,
+    muted: Color::White,
+    dim: Color::Gray,
+    selection_bg: Color::Blue,
+    inverse: Color::Black,
+    accent: Color::LightCyan,
+    emphasis: Color::LightYellow,
+    secondary: Color::LightBlue,
+    special: Color::LightMagenta,
+    success: Color::LightGreen,
+    danger: Color::LightRed,
+    highlight_bg: Color::Indexed(94),
+    visual_bg: Color::Blue,
+    guide_bg: Color::Indexed(238),
+};
+
+impl Theme {
+    /// 主题名对应的配色
+    pub fn of(name: ThemeName) -> &'static Theme {
+        match name {
+            ThemeName::Dark => &DARK,
+            ThemeName::Light => &LIGHT,
+            ThemeName::Sepia => &SEPIA,
+            ThemeName::HighContrast => &HIGH_CONTRAST,
+        }
+    }
+}
@@ -1,15 +1,201 @@
 use ratatui::prelude::*;
 use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
 use ratatui::widgets::*;
 
+use super::theme::Theme;
 use crate::app::App;
+use crate::i18n::{tr, trf};
+use crate::model::matcher::highlight_ranges;
 use crate::state::AppState;
+use std::ops::Range;
 
-use super::{bookmark, bookshelf, chapter_list, reader, search, settings, sync_status};
+use super::{
+    annotation, book_detail, bookmark, bookshelf, chapter_list, confirm, highlight, leader, reader,
+    search, settings, setup, sync_status,
+};
 
-pub fn render_help_info(f: &mut Frame, help_text: &str, area: Rect) {
+/// 把 Unix 秒格式化为相对当前时间的描述，如 “刚刚”、“2小时前”、“3天前”
+///
+/// 超过一年的显示日期；时间戳为 0（旧数据）时返回空字符串。
+pub fn format_relative_time(timestamp: u64) -> String {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    relative_time(timestamp, now)
+}
+
+fn relative_time(timestamp: u64, now: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    if timestamp == 0 {
+        return String::new();
+    }
+    let elapsed = now.saturating_sub(timestamp);
+    if elapsed < MINUTE {
+        tr("刚刚", "just now").to_string()
+    } else if elapsed < HOUR {
+        trf!("{}分钟前", "{} min ago", elapsed / MINUTE)
+    } else if elapsed < DAY {
+        trf!("{}小时前", "{} h ago", elapsed / HOUR)
+    } else if elapsed < 2 * DAY {
+        tr("昨天", "yesterday").to_string()
+    } else if elapsed < 30 * DAY {
+        trf!("{}天前", "{} days ago", elapsed / DAY)
+    } else if elapsed < 365 * DAY {
+        trf!("{}个月前", "{} months ago", elapsed / (30 * DAY))
+    } else {
+        chrono::DateTime::from_timestamp(timestamp as i64, 0)
+            .map(|time| {
+                time.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// 把 Unix 秒格式化为本地时间，时间戳为 0（旧数据）时返回空字符串
+pub fn format_timestamp(timestamp: u64) -> String {
+    if timestamp == 0 {
+        return String::new();
+    }
+    chrono::DateTime::from_timestamp(timestamp as i64, 0)
+        .map(|time| {
+            time.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// 创建带高亮的文本行
+/// # 参数
+/// - `theme`: 界面配色
+/// - `text`: 原始文本
+/// - `search_term`: 搜索关键词
+/// - `fuzzy`: 没有普通匹配时是否高亮拼音或容错匹配到的部分
+/// # 返回
+/// 返回包含高亮显示的Line对象
+pub fn create_highlighted_line(
+    theme: &Theme,
+    text: &str,
+    search_term: &str,
+    fuzzy: bool,
+) -> Line<'static> {
+    if search_term.is_empty() {
+        return Line::from(text.to_string());
+    }
+    create_line_with_ranges(theme, text, &highlight_ranges(text, search_term, fuzzy))
+}
+
+/// 按给定的字节范围高亮文本行
+/// # 参数
+/// - `theme`: 界面配色
+/// - `text`: 原始文本
+/// - `ranges`: 需要高亮的字节范围，升序且互不重叠
+/// # 返回
+/// 返回包含高亮显示的Line对象
+pub fn create_line_with_ranges(
+    theme: &Theme,
+    text: &str,
+    ranges: &[Range<usize>],
+) -> Line<'static> {
+    if ranges.is_empty() {
+        return Line::from(text.to_string());
+    }
+
+    let mut spans = Vec::new();
+    let mut last_end = 0;
+
+    for range in ranges {
+        if range.start > last_end {
+            spans.push(Span::styled(
+                text[last_end..range.start].to_string(),
+                Style::default().fg(theme.text),
+            ));
+        }
+
+        spans.push(Span::styled(
+            text[range.clone()].to_string(),
+            Style::default().fg(theme.inverse).bg(theme.emphasis),
+        ));
+
+        last_end = range.end;
+    }
+
+    if last_end < text.len() {
+        spans.push(Span::styled(
+            text[last_end..].to_string(),
+            Style::default().fg(theme.text),
+        ));
+    }
+
+    Line::from(spans)
+}
+
+/// 内容区至少这么宽时预览窗格显示在右侧，否则显示在下方
+const PREVIEW_SIDE_MIN_WIDTH: u16 = 80;
+
+/// 把列表界面的内容区拆分为列表和预览窗格（60% / 40%）
+///
+/// # Returns
+///
+/// `(列表区域, 预览区域)`
+pub fn split_preview_area(area: Rect) -> (Rect, Rect) {
+    let direction = if area.width >= PREVIEW_SIDE_MIN_WIDTH {
+        Direction::Horizontal
+    } else {
+        Direction::Vertical
+    };
+    let panes = Layout::default()
+        .direction(direction)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
+    (panes[0], panes[1])
+}
+
+/// 在带边框列表的右边框上画滚动条，列表能完整显示时不画
+///
+/// # Arguments
+///
+/// * `theme` - 界面配色
+/// * `list_area` - 列表（含边框）的区域
+/// * `row_count` - 列表总行数
+/// * `offset` - 列表顶部显示的行号
+pub fn render_list_scrollbar(
+    f: &mut Frame,
+    theme: &Theme,
+    list_area: Rect,
+    row_count: usize,
+    offset: usize,
+) {
+    let visible_height = list_area.height.saturating_sub(2) as usize;
+    if row_count <= visible_height {
+        return;
+    }
+    let max_offset = row_count - visible_height;
+    let mut state = ScrollbarState::new(max_offset + 1).position(offset.min(max_offset));
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .thumb_style(Style::default().fg(theme.muted))
+        .track_style(Style::default().fg(theme.dim));
+    f.render_stateful_widget(
+        scrollbar,
+        list_area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+}
+
+pub fn render_help_info(f: &mut Frame, theme: &Theme, help_text: &str, area: Rect) {
     let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::Gray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center);
 
     let help_area = Rect {
@@ -22,23 +208,61 @@ pub fn render_help_info(f: &mut Frame, help_text: &str, area: Rect) {
     f.render_widget(help, help_area);
 }
 
-pub fn render_error_message(f: &mut Frame, error_msg: &str, area: Rect) {
-    let error = Paragraph::new(format!("⚠ {}", error_msg))
-        .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
+pub fn render_error_message(f: &mut Frame, theme: &Theme, error_msg: &str, area: Rect) {
+    render_status_message(f, &format!("⚠ {}", error_msg), theme.danger, area);
+}
+
+pub fn render_info_message(f: &mut Frame, theme: &Theme, info_msg: &str, area: Rect) {
+    render_status_message(f, &format!("✓ {}", info_msg), theme.success, area);
+}
+
+fn render_status_message(f: &mut Frame, text: &str, color: Color, area: Rect) {
+    let message = Paragraph::new(text.to_string())
+        .style(Style::default().fg(color).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
 
-    let error_area = Rect {
+    let message_area = Rect {
         x: area.x,
         y: area.height.saturating_sub(2),
         width: area.width,
         height: 1,
     };
 
-    f.render_widget(error, error_area);
+    f.render_widget(message, message_area);
+}
+
+/// 在最底行（帮助栏的位置）绘制命令行：`:` 加上已输入的命令
+pub fn render_command_line(f: &mut Frame, theme: &Theme, input: &str, area: Rect) {
+    let command_area = Rect {
+        x: area.x,
+        y: area.height.saturating_sub(1),
+        width: area.width,
+        height: 1,
+    };
+    let line = Paragraph::new(format!(":{}▏", input))
+        .style(Style::default().fg(theme.text).bg(theme.background));
+    f.render_widget(Clear, command_area);
+    f.render_widget(line, command_area);
+}
+
+/// 将缓冲区中的所有单元格改为暗灰色文字、默认背景
+///
+/// 夜间模式使用，作用于最终画面，与各界面自身的配色无关。
+pub fn dim_buffer(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::DarkGray);
+        cell.set_bg(Color::Reset);
+        cell.modifier = Modifier::empty();
+    }
 }
 
 pub fn render(f: &mut Frame, app: &App) {
     let area = f.area();
+    let theme = app.theme();
+    f.render_widget(
+        Block::default().style(Style::default().bg(theme.background)),
+        area,
+    );
 
     let main_layout = Layout::default()
         .direction(Direction::Vertical)
@@ -53,15 +277,63 @@ pub fn render(f: &mut Frame, app: &App) {
         AppState::Searching => search::render_search(f, app),
         AppState::ChapterList => chapter_list::render_chapter_list(f, app),
         AppState::Settings => settings::render_settings(f, app),
-        AppState::BookmarkList | AppState::BookmarkAdd => bookmark::render_bookmark(f, app),
+        AppState::BookmarkList | AppState::BookmarkAdd | AppState::AllBookmarks => {
+            bookmark::render_bookmark(f, app)
+        }
+        AppState::HighlightList => highlight::render_highlight_list(f, app),
+        AppState::AnnotationList => annotation::render_annotation_list(f, app),
+        AppState::BookDetail => book_detail::render_book_detail(f, app),
+        AppState::Setup => setup::render_setup(f, app),
     }
 
     let sync_widget = sync_status::SyncStatusWidget {
         status: app.sync_status.clone(),
+        theme,
     };
     sync_widget.render(status_area, f.buffer_mut());
 
+    if let Some(dialog) = &app.confirm {
+        confirm::render_confirm(f, theme, dialog);
+    }
+
+    if app.leader_pending {
+        leader::render_leader_popup(f, app);
+    }
+
+    if let Some(input) = &app.command_input {
+        render_command_line(f, theme, input, area);
+    }
+
+    if app.reader.night_mode {
+        dim_buffer(f.buffer_mut());
+    }
+
     if let Some(ref error_msg) = app.error_message {
-        render_error_message(f, error_msg, area);
+        render_error_message(f, theme, error_msg, area);
+    } else if let Some(ref info_msg) = app.info_message {
+        render_info_message(f, theme, info_msg, area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_time() {
+        let now = 1_700_000_000;
+        assert_eq!(relative_time(0, now), "");
+        assert_eq!(relative_time(now - 30, now), "刚刚");
+        assert_eq!(relative_time(now - 5 * 60, now), "5分钟前");
+        assert_eq!(relative_time(now - 2 * 3600 - 59, now), "2小时前");
+        assert_eq!(relative_time(now - 30 * 3600, now), "昨天");
+        assert_eq!(relative_time(now - 3 * 86400, now), "3天前");
+        assert_eq!(relative_time(now - 65 * 86400, now), "2个月前");
+        assert_eq!(
+            relative_time(now - 400 * 86400, now).len(),
+            "2022-10-10".len()
+        );
+        // 时钟回拨时不显示负数
+        assert_eq!(relative_time(now + 100, now), "刚刚");
     }
 }